@@ -0,0 +1,518 @@
+// native Array.prototype methods
+//
+// These work on any object with a length, as the spec's generic
+// algorithms do, reading and writing elements through get_slot and
+// set_slot so that array lengths stay up to date.
+use interp::Environment;
+use intern::{intern, intern_get, intern_to_uint};
+use object::*;
+
+priv fn index(i: uint) -> JsVal { JsNumber(i as f64) }
+
+priv fn has_index(obj: @mut Object, i: uint) -> bool {
+    obj.contains(FieldDesc { name: intern(i.to_str()), hidden: false })
+}
+
+// the indexes below len which obj or its prototypes have, in order.  A
+// sparse array's length can be far more than its number of fields, and
+// then we look through the field names rather than try every index.
+priv fn present_indexes(obj: @mut Object, len: uint) -> ~[uint] {
+    let mut chain = ~[];
+    let mut fields = 0;
+    let mut o = obj;
+    loop {
+        chain.push(o);
+        fields += o.map.fields.len();
+        match o.get_simple(FieldDesc::proto()) {
+            Some(JsObject(parent)) => { o = parent; },
+            _ => { break; }
+        }
+    }
+    if len <= 2 * fields {
+        return do vec::filter(vec::from_fn(len, |i| i)) |i| {
+            has_index(obj, *i)
+        };
+    }
+    let mut present : ~[uint] = ~[];
+    for chain.each |o| {
+        for o.map.visible_names().each |name| {
+            match intern_to_uint(*name) {
+                Some(n) if n < len && n.to_str() == intern_get(*name) => {
+                    // keep them sorted, and each only once
+                    let mut pos = present.len();
+                    while pos > 0 && present[pos-1] > n { pos -= 1; }
+                    if pos == 0 || present[pos-1] != n {
+                        present.insert(pos, n);
+                    }
+                },
+                _ => {}
+            }
+        }
+    }
+    present
+}
+
+priv fn get_length(env: Environment, obj: JsVal) -> Result<uint, JsVal> {
+    match env.tryToUint32(env.get_slot(obj, JsVal::from_str("length"))) {
+        Ok(n) => Ok(n as uint),
+        Err(e) => Err(e)
+    }
+}
+
+priv fn set_length(env: Environment, obj: JsVal, len: uint) {
+    env.set_slot(obj, JsVal::from_str("length"), JsNumber(len as f64));
+}
+
+// this, as an object, or a TypeError
+priv fn this_object(env: Environment, this: JsVal, name: &str) -> Result<@mut Object, JsVal> {
+    match this {
+        JsObject(o) => Ok(o),
+        _ => Err(env.throwError("TypeError", fmt!(
+            "Array.prototype.%s called on null or undefined", name)))
+    }
+}
+
+// a relative index argument (as for slice and splice), clamped to [0, len]
+priv fn relative_index(env: Environment, arg: JsVal, len: uint) -> Result<uint, JsVal> {
+    let rel = match env.tryToInteger(arg) {
+        Ok(n) => n,
+        Err(e) => { return Err(e); }
+    };
+    Ok(if rel < 0f64 {
+        f64::max(rel + (len as f64), 0f64) as uint
+    } else {
+        f64::min(rel, len as f64) as uint
+    })
+}
+
+// copy element `from` to `to`, or delete `to` if `from` is a hole
+priv fn move_element(env: Environment, obj: @mut Object, from: uint, to: uint) {
+    let this = JsObject(obj);
+    if has_index(obj, from) {
+        env.set_slot(this, index(to), env.get_slot(this, index(from)));
+    } else {
+        env.deleteSlot(this, index(to));
+    }
+}
+
+// the callback of forEach-like methods, or a TypeError
+priv fn callback(env: Environment, args: &[JsVal]) -> Result<JsVal, JsVal> {
+    let f = getarg(args, 0);
+    if env.isCallable(f) {
+        Ok(f)
+    } else {
+        Err(env.throwError("TypeError", fmt!("%s is not a function",
+                                             env.toString(f))))
+    }
+}
+
+// compare two strings by UTF-16 code units, as the < operator does
+priv fn utf16_cmp(a: &[u16], b: &[u16]) -> int {
+    let n = uint::min(a.len(), b.len());
+    for uint::range(0, n) |i| {
+        if a[i] != b[i] { return if a[i] < b[i] { -1 } else { 1 }; }
+    }
+    (a.len() as int) - (b.len() as int)
+}
+
+// a stable merge sort with a comparator which may throw.  `after(x, y)`
+// is true if x belongs after y.  On an exception v may be partly sorted,
+// but still holds the same elements.
+priv fn merge_sort(v: &mut ~[JsVal],
+                   after: &fn(JsVal, JsVal) -> Result<bool, JsVal>) -> Result<(), JsVal> {
+    let len = v.len();
+    let mut width = 1u;
+    while width < len {
+        let mut merged : ~[JsVal] = vec::with_capacity(len);
+        let mut lo = 0u;
+        while lo < len {
+            let mid = uint::min(lo + width, len);
+            let hi = uint::min(lo + 2 * width, len);
+            let (mut i, mut j) = (lo, mid);
+            while i < mid && j < hi {
+                match after(v[i], v[j]) {
+                    Err(e) => { return Err(e); },
+                    Ok(true) => { merged.push(v[j]); j += 1; },
+                    Ok(false) => { merged.push(v[i]); i += 1; }
+                }
+            }
+            merged.push_all(v.slice(i, mid));
+            merged.push_all(v.slice(j, hi));
+            lo = hi;
+        }
+        *v = merged;
+        width *= 2;
+    }
+    Ok(())
+}
+
+pub fn add_array_methods(env: Environment, frame: @mut Object,
+                         myArrayCons: @mut Object) {
+    let myArray = env.myArray;
+
+    // ES5 15.4.4.11
+    do env.add_native_func_str(frame, myArray, "sort") |this, args| {
+        let obj = match this {
+            JsObject(o) => o,
+            _ => { return env.throwError("TypeError", "Array.prototype.sort called on non-object"); }
+        };
+        let comparefn = getarg(args, 0);
+        match comparefn {
+            JsUndefined => {},
+            _ if env.isCallable(comparefn) => {},
+            _ => {
+                return env.throwError("TypeError", "The comparison function must be either a function or undefined");
+            }
+        }
+        let len = try_native!(get_length(env, this));
+        // holes sort after undefined, which sorts after everything else
+        let mut values : ~[JsVal] = ~[];
+        let mut undefs = 0u;
+        let indexes = present_indexes(obj, len);
+        for indexes.each |i| {
+            match env.get_slot(this, index(*i)) {
+                JsUndefined => { undefs += 1; },
+                v => values.push(v)
+            }
+        }
+        let present = values.len() + undefs;
+        let result = do merge_sort(&mut values) |x, y| {
+            match comparefn {
+                JsUndefined => match (env.tryToUtf16(x), env.tryToUtf16(y)) {
+                    (Ok(a), Ok(b)) => Ok(utf16_cmp(a, b) > 0),
+                    (Err(e), _) | (_, Err(e)) => Err(e)
+                },
+                _ => match env.interpret_function(comparefn, JsUndefined, ~[x, y]) {
+                    JsThrown(e) => Err(JsThrown(e)),
+                    rv => match env.tryToNumber(rv) {
+                        Ok(n) => Ok(n > 0f64),
+                        Err(e) => Err(e)
+                    }
+                }
+            }
+        };
+        match result {
+            // the array hasn't been touched yet
+            Err(e) => e,
+            Ok(()) => {
+                for values.eachi |i, v| { env.set_slot(this, index(i), *v); }
+                for uint::range(values.len(), present) |i| {
+                    env.set_slot(this, index(i), JsUndefined);
+                }
+                // and the holes go last
+                for indexes.each |i| {
+                    if *i >= present { env.deleteSlot(this, index(*i)); }
+                }
+                this
+            }
+        }
+    };
+
+    // ES5 15.4.4.10
+    do env.add_native_func_str(frame, myArray, "slice") |this, args| {
+        match this_object(env, this, "slice") {
+            Err(e) => e,
+            Ok(obj) => {
+                let len = try_native!(get_length(env, this));
+                let k = try_native!(relative_index(env, getarg(args, 0), len));
+                let end = match getarg(args, 1) {
+                    JsUndefined => len,
+                    e => try_native!(relative_index(env, e, len))
+                };
+                let a = env.arrayCreate([]);
+                for uint::range(k, uint::max(k, end)) |i| {
+                    if has_index(obj, i) {
+                        env.set_slot(a, index(i - k), env.get_slot(this, index(i)));
+                    }
+                }
+                set_length(env, a, uint::max(k, end) - k);
+                a
+            }
+        }
+    };
+    // ES5 15.4.4.12
+    do env.add_native_func_str(frame, myArray, "splice") |this, args| {
+        match this_object(env, this, "splice") {
+            Err(e) => e,
+            Ok(obj) => {
+                let len = try_native!(get_length(env, this));
+                let start = try_native!(relative_index(env, getarg(args, 0), len));
+                // as in browsers, a missing deleteCount removes the rest
+                let count = match args.len() {
+                    0 => 0,
+                    1 => len - start,
+                    _ => f64::min(f64::max(try_native!(env.tryToInteger(args[1])), 0f64),
+                                  (len - start) as f64) as uint
+                };
+                let items = if args.len() > 2 { args.slice(2, args.len()) } else { &[] };
+                let removed = env.arrayCreate([]);
+                for uint::range(0, count) |i| {
+                    if has_index(obj, start + i) {
+                        env.set_slot(removed, index(i), env.get_slot(this, index(start + i)));
+                    }
+                }
+                set_length(env, removed, count);
+                if items.len() < count {
+                    for uint::range(start, len - count) |k| {
+                        move_element(env, obj, k + count, k + items.len());
+                    }
+                    let mut k = len;
+                    while k > len - count + items.len() {
+                        env.deleteSlot(this, index(k - 1));
+                        k -= 1;
+                    }
+                } else if items.len() > count {
+                    let mut k = len - count;
+                    while k > start {
+                        move_element(env, obj, k + count - 1, k + items.len() - 1);
+                        k -= 1;
+                    }
+                }
+                for items.eachi |i, v| {
+                    env.set_slot(this, index(start + i), *v);
+                }
+                set_length(env, this, len - count + items.len());
+                removed
+            }
+        }
+    };
+    // ES5 15.4.4.9
+    do env.add_native_func_str(frame, myArray, "shift") |this, _args| {
+        match this_object(env, this, "shift") {
+            Err(e) => e,
+            Ok(obj) => {
+                let len = try_native!(get_length(env, this));
+                if len == 0 {
+                    set_length(env, this, 0);
+                    JsUndefined
+                } else {
+                    let first = env.get_slot(this, index(0));
+                    for uint::range(1, len) |k| {
+                        move_element(env, obj, k, k - 1);
+                    }
+                    env.deleteSlot(this, index(len - 1));
+                    set_length(env, this, len - 1);
+                    first
+                }
+            }
+        }
+    };
+    // ES5 15.4.4.13
+    do env.add_native_func_str(frame, myArray, "unshift") |this, args| {
+        match this_object(env, this, "unshift") {
+            Err(e) => e,
+            Ok(obj) => {
+                let len = try_native!(get_length(env, this));
+                let mut k = len;
+                while k > 0 {
+                    move_element(env, obj, k - 1, k + args.len() - 1);
+                    k -= 1;
+                }
+                for args.eachi |i, v| {
+                    env.set_slot(this, index(i), *v);
+                }
+                set_length(env, this, len + args.len());
+                JsNumber((len + args.len()) as f64)
+            }
+        }
+    };
+    // ES5 15.4.4.8
+    do env.add_native_func_str(frame, myArray, "reverse") |this, _args| {
+        match this_object(env, this, "reverse") {
+            Err(e) => e,
+            Ok(obj) => {
+                let len = try_native!(get_length(env, this));
+                for uint::range(0, len / 2) |lower| {
+                    let upper = len - lower - 1;
+                    let lowerValue = env.get_slot(this, index(lower));
+                    let upperValue = env.get_slot(this, index(upper));
+                    match (has_index(obj, lower), has_index(obj, upper)) {
+                        (true, true) => {
+                            env.set_slot(this, index(lower), upperValue);
+                            env.set_slot(this, index(upper), lowerValue);
+                        },
+                        (false, true) => {
+                            env.set_slot(this, index(lower), upperValue);
+                            env.deleteSlot(this, index(upper));
+                        },
+                        (true, false) => {
+                            env.deleteSlot(this, index(lower));
+                            env.set_slot(this, index(upper), lowerValue);
+                        },
+                        (false, false) => {}
+                    }
+                }
+                this
+            }
+        }
+    };
+    // ES5 15.4.4.14
+    do env.add_native_func_str(frame, myArray, "indexOf") |this, args| {
+        match this_object(env, this, "indexOf") {
+            Err(e) => e,
+            Ok(obj) => {
+                let len = try_native!(get_length(env, this));
+                let search = getarg(args, 0);
+                let mut k = try_native!(relative_index(env, getarg(args, 1), len));
+                let mut rv = -1f64;
+                while k < len {
+                    if has_index(obj, k) &&
+                        env.strictEquals(env.get_slot(this, index(k)), search) {
+                        rv = k as f64;
+                        break;
+                    }
+                    k += 1;
+                }
+                JsNumber(rv)
+            }
+        }
+    };
+    // ES5 15.4.4.15
+    do env.add_native_func_str(frame, myArray, "lastIndexOf") |this, args| {
+        match this_object(env, this, "lastIndexOf") {
+            Err(e) => e,
+            Ok(obj) => {
+                let len = try_native!(get_length(env, this));
+                let search = getarg(args, 0);
+                // one past the first index to look at
+                let mut k = if args.len() < 2 {
+                    len
+                } else {
+                    let n = try_native!(env.tryToInteger(args[1]));
+                    if n < 0f64 {
+                        f64::max(n + (len as f64) + 1f64, 0f64) as uint
+                    } else {
+                        f64::min(n + 1f64, len as f64) as uint
+                    }
+                };
+                let mut rv = -1f64;
+                while k > 0 {
+                    k -= 1;
+                    if has_index(obj, k) &&
+                        env.strictEquals(env.get_slot(this, index(k)), search) {
+                        rv = k as f64;
+                        break;
+                    }
+                }
+                JsNumber(rv)
+            }
+        }
+    };
+
+    // call f for each present element with (value, index, object) until
+    // it returns false; returns Some(exception) if the callback throws.
+    let each_result: @fn(@mut Object, JsVal, JsVal, &fn(JsVal, JsVal) -> bool) -> Option<JsVal> =
+        |obj, f, thisArg, g| {
+        let this = JsObject(obj);
+        let len = match get_length(env, this) {
+            Ok(n) => n,
+            Err(e) => { return Some(e); }
+        };
+        let mut thrown = None;
+        for uint::range(0, len) |k| {
+            if has_index(obj, k) {
+                let v = env.get_slot(this, index(k));
+                let rv = env.interpret_function(f, thisArg, ~[v, index(k), this]);
+                match rv {
+                    JsThrown(_) => { thrown = Some(rv); break; },
+                    _ => if !g(v, rv) { break; }
+                }
+            }
+        }
+        thrown
+    };
+    // ES5 15.4.4.20
+    do env.add_native_func_str(frame, myArray, "filter") |this, args| {
+        match (this_object(env, this, "filter"), callback(env, args)) {
+            (Err(e), _) | (_, Err(e)) => e,
+            (Ok(obj), Ok(f)) => {
+                let mut kept : ~[JsVal] = ~[];
+                let thrown = do each_result(obj, f, getarg(args, 1)) |v, rv| {
+                    if env.toBoolean(rv) { kept.push(v); }
+                    true
+                };
+                match thrown {
+                    Some(e) => e,
+                    None => env.arrayCreate(kept)
+                }
+            }
+        }
+    };
+    // ES5 15.4.4.17
+    do env.add_native_func_str(frame, myArray, "some") |this, args| {
+        match (this_object(env, this, "some"), callback(env, args)) {
+            (Err(e), _) | (_, Err(e)) => e,
+            (Ok(obj), Ok(f)) => {
+                let mut found = false;
+                let thrown = do each_result(obj, f, getarg(args, 1)) |_v, rv| {
+                    found = env.toBoolean(rv);
+                    !found
+                };
+                match thrown {
+                    Some(e) => e,
+                    None => JsBool(found)
+                }
+            }
+        }
+    };
+    // ES5 15.4.4.16
+    do env.add_native_func_str(frame, myArray, "every") |this, args| {
+        match (this_object(env, this, "every"), callback(env, args)) {
+            (Err(e), _) | (_, Err(e)) => e,
+            (Ok(obj), Ok(f)) => {
+                let mut all = true;
+                let thrown = do each_result(obj, f, getarg(args, 1)) |_v, rv| {
+                    all = env.toBoolean(rv);
+                    all
+                };
+                match thrown {
+                    Some(e) => e,
+                    None => JsBool(all)
+                }
+            }
+        }
+    };
+
+    // ES5 15.4.4.21 and 15.4.4.22
+    let reduce: @fn(JsVal, &[JsVal], bool) -> JsVal = |this, args, right| {
+        match (this_object(env, this, if right { "reduceRight" } else { "reduce" }),
+               callback(env, args)) {
+            (Err(e), _) | (_, Err(e)) => e,
+            (Ok(obj), Ok(f)) => {
+                let len = try_native!(get_length(env, this));
+                let present = present_indexes(obj, len);
+                let order = if right { vec::reversed(present) } else { present };
+                let (mut acc, skip) = if args.len() > 1 {
+                    (args[1], 0)
+                } else if order.is_empty() {
+                    return env.throwError("TypeError", "Reduce of empty array with no initial value");
+                } else {
+                    (env.get_slot(this, index(order[0])), 1)
+                };
+                for order.slice(skip, order.len()).each |k| {
+                    // the callback may delete elements we haven't visited
+                    if !has_index(obj, *k) { loop; }
+                    let v = env.get_slot(this, index(*k));
+                    acc = env.interpret_function(f, JsUndefined,
+                                                 ~[acc, v, index(*k), this]);
+                    match acc {
+                        JsThrown(_) => { return acc; },
+                        _ => {}
+                    }
+                }
+                acc
+            }
+        }
+    };
+    do env.add_native_func_str(frame, myArray, "reduce") |this, args| {
+        reduce(this, args, false)
+    };
+    do env.add_native_func_str(frame, myArray, "reduceRight") |this, args| {
+        reduce(this, args, true)
+    };
+
+    // ES5 15.4.3.2
+    do env.add_native_func_str(frame, myArrayCons, "isArray") |_this, args| {
+        JsBool(env.isArray(getarg(args, 0)))
+    };
+}
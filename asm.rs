@@ -0,0 +1,416 @@
+// a bytecode assembler, which reads the listings disasm.rs writes.
+//
+// A listing is a series of function blocks followed by the literal
+// pool:
+//
+//     function 0 "name" nargs 0 max_stack 1
+//             push_literal 0
+//             jmp_unless done
+//     done:
+//             return
+//     literals
+//             "a string"
+//
+// Comments run from ';' to the end of the line.  A jump's operand can
+// be a label defined in the same function or a pc, and a bare number
+// in a function is a word of bytecode by itself.
+use core::hashmap::HashMap;
+
+use function::Function;
+use module::Module;
+use numconv;
+use object::{JsVal,JsNumber,JsString,JsBool,JsNull,JsUndefined};
+use op::*;
+
+pub struct AsmError {
+    line: uint,
+    message: ~str
+}
+
+impl AsmError {
+    pub fn to_str(&self) -> ~str {
+        fmt!("line %u: %s", self.line, self.message)
+    }
+}
+
+priv enum AsmToken {
+    AWord(~str),
+    AString(~[u16])
+}
+
+priv fn is_space(c: u16) -> bool {
+    c == (' ' as u16) || c == ('\t' as u16) || c == ('\r' as u16)
+}
+
+priv fn hex_digit(c: u16) -> Option<u16> {
+    let c = c as char;
+    if c >= '0' && c <= '9' { Some((c as u16) - ('0' as u16)) }
+    else if c >= 'a' && c <= 'f' { Some((c as u16) - ('a' as u16) + 10) }
+    else if c >= 'A' && c <= 'F' { Some((c as u16) - ('A' as u16) + 10) }
+    else { None }
+}
+
+// split a line into words and JSON-quoted strings, dropping any comment
+priv fn tokenize_line(line: &[u16]) -> Result<~[AsmToken], ~str> {
+    let mut tokens = ~[];
+    let mut i = 0;
+    while i < line.len() {
+        let c = line[i];
+        if is_space(c) {
+            i += 1;
+        } else if c == (';' as u16) {
+            break;
+        } else if c == ('"' as u16) {
+            let mut s : ~[u16] = ~[];
+            i += 1;
+            loop {
+                if i >= line.len() { return Err(~"unterminated string"); }
+                let c = line[i];
+                i += 1;
+                if c == ('"' as u16) { break; }
+                if c != ('\\' as u16) { s.push(c); loop; }
+                if i >= line.len() { return Err(~"unterminated string"); }
+                let e = line[i];
+                i += 1;
+                s.push(match e as char {
+                    '"' => '"' as u16,
+                    '\\' => '\\' as u16,
+                    '/' => '/' as u16,
+                    'b' => 0x08,
+                    'f' => 0x0C,
+                    'n' => 0x0A,
+                    'r' => 0x0D,
+                    't' => 0x09,
+                    'u' if i + 4 <= line.len() => {
+                        let mut n = 0u16;
+                        for line.slice(i, i + 4).each |h| {
+                            match hex_digit(*h) {
+                                Some(d) => { n = n * 16 + d; },
+                                None => { return Err(~"bad \\u escape"); }
+                            }
+                        }
+                        i += 4;
+                        n
+                    },
+                    _ => { return Err(~"bad escape in string"); }
+                });
+            }
+            tokens.push(AString(s));
+        } else {
+            let start = i;
+            while i < line.len() && !is_space(line[i]) &&
+                line[i] != (';' as u16) && line[i] != ('"' as u16) {
+                i += 1;
+            }
+            tokens.push(AWord(str::from_utf16(line.slice(start, i))));
+        }
+    }
+    Ok(tokens)
+}
+
+priv fn lookup_op(name: &str) -> Option<Op> {
+    let mut val = 0;
+    while is_op(val) {
+        let op = Op::new_from_uint(val);
+        if name == op.name() { return Some(op); }
+        val += 1;
+    }
+    None
+}
+
+priv fn parse_literal(token: &AsmToken) -> Result<JsVal, ~str> {
+    match *token {
+        // not through ~str, which can't hold an unpaired surrogate
+        AString(ref s) => Ok(JsString(at_vec::to_managed(*s))),
+        AWord(~"true") => Ok(JsBool(true)),
+        AWord(~"false") => Ok(JsBool(false)),
+        AWord(~"null") => Ok(JsNull),
+        AWord(~"undefined") => Ok(JsUndefined),
+        AWord(~"NaN") => Ok(JsNumber(f64::NaN)),
+        AWord(ref w) => {
+            let n = numconv::string_to_number(str::to_utf16(*w));
+            if n.is_NaN() { Err(fmt!("bad literal %s", *w)) } else { Ok(JsNumber(n)) }
+        }
+    }
+}
+
+priv fn parse_uint(token: &AsmToken) -> Result<uint, ~str> {
+    match *token {
+        AWord(ref w) => match uint::from_str(*w) {
+            Some(n) => Ok(n),
+            None => Err(fmt!("expected a number, not %s", *w))
+        },
+        AString(_) => Err(~"expected a number, not a string")
+    }
+}
+
+// a function we're in the middle of
+priv struct Func {
+    name: Option<@[u16]>,
+    nargs: uint,
+    max_stack: uint,
+    bytecode: ~[uint],
+    labels: HashMap<~str, uint>,
+    // (operand pc, label, line) for each jump to a label
+    fixups: ~[(uint, ~str, uint)]
+}
+
+// resolve the jumps to labels, now that we've seen them all
+priv fn finish(f: Func, id: uint) -> Result<@Function, AsmError> {
+    let Func { name: name, nargs: nargs, max_stack: max_stack,
+               bytecode: bytecode, labels: labels, fixups: fixups } = f;
+    let mut bytecode = bytecode;
+    for fixups.each |fixup| {
+        let (pc, label, line) = copy *fixup;
+        match labels.find(&label) {
+            Some(&target) => { bytecode[pc] = target; },
+            None => {
+                return Err(AsmError { line: line,
+                                      message: fmt!("undefined label %s", label) });
+            }
+        }
+    }
+    Ok(@Function {
+        name: name,
+        id: id,
+        nargs: nargs,
+        max_stack: max_stack,
+        source: None,
+        bytecode: bytecode
+    })
+}
+
+// the value after the given keyword in a function header
+priv fn header_field(tokens: &[AsmToken], i: uint,
+                    field: &str) -> Result<uint, ~str> {
+    if i + 1 >= tokens.len() { return Err(fmt!("expected %s", field)); }
+    match tokens[i] {
+        AWord(ref w) if field == *w => parse_uint(&tokens[i + 1]),
+        _ => Err(fmt!("expected %s", field))
+    }
+}
+
+// the function header: function <id> ["name"] nargs <n> max_stack <n>
+priv fn parse_header(tokens: &[AsmToken], id: uint) -> Result<Func, ~str> {
+    match header_field(tokens, 0, "function") {
+        Ok(n) if n == id => {},
+        _ => { return Err(fmt!("expected function %u", id)); }
+    }
+    let mut i = 2;
+    let name = if i < tokens.len() {
+        match tokens[i] {
+            AString(ref s) => { i += 1; Some(at_vec::to_managed(*s)) },
+            _ => None
+        }
+    } else {
+        None
+    };
+    let nargs = match header_field(tokens, i, "nargs") {
+        Ok(n) => n,
+        Err(e) => { return Err(e); }
+    };
+    let max_stack = match header_field(tokens, i + 2, "max_stack") {
+        Ok(n) => n,
+        Err(e) => { return Err(e); }
+    };
+    if i + 4 < tokens.len() { return Err(~"unexpected text after the header"); }
+    Ok(Func {
+        name: name,
+        nargs: nargs,
+        max_stack: max_stack,
+        bytecode: ~[],
+        labels: HashMap::new(),
+        fixups: ~[]
+    })
+}
+
+// parse a listing, and encode it as Module::new_from_bytes decodes it
+pub fn assemble(text: &str) -> Result<Module, AsmError> {
+    let mut functions : ~[@Function] = ~[];
+    let mut literals : ~[JsVal] = ~[];
+    let mut current : Option<Func> = None;
+    let mut in_literals = false;
+    let mut line_no = 0;
+    for str::each_line_any(text) |line| {
+        line_no += 1;
+        let err = |message: ~str| AsmError { line: line_no, message: message };
+        let tokens = match tokenize_line(str::to_utf16(line)) {
+            Ok(t) => t,
+            Err(e) => { return Err(err(e)); }
+        };
+        if tokens.is_empty() { loop; }
+        // a new section ends the current function
+        let header = match tokens[0] {
+            AWord(~"function") | AWord(~"literals") => true,
+            _ => false
+        };
+        if header {
+            match util::replace(&mut current, None) {
+                Some(f) => match finish(f, functions.len()) {
+                    Ok(done) => functions.push(done),
+                    Err(e) => { return Err(e); }
+                },
+                None => {}
+            }
+            if in_literals { return Err(err(~"literals must come last")); }
+            match tokens[0] {
+                AWord(~"function") => match parse_header(tokens, functions.len()) {
+                    Ok(f) => { current = Some(f); },
+                    Err(e) => { return Err(err(e)); }
+                },
+                _ => {
+                    if tokens.len() > 1 { return Err(err(~"unexpected text after literals")); }
+                    in_literals = true;
+                }
+            }
+            loop;
+        }
+        if in_literals {
+            if tokens.len() > 1 { return Err(err(~"one literal per line")); }
+            match parse_literal(&tokens[0]) {
+                Ok(v) => literals.push(v),
+                Err(e) => { return Err(err(e)); }
+            }
+            loop;
+        }
+        let f = match current {
+            Some(ref mut f) => f,
+            None => { return Err(err(~"expected a function header")); }
+        };
+        let word = match tokens[0] {
+            AWord(ref w) => copy *w,
+            AString(_) => { return Err(err(~"unexpected string")); }
+        };
+        if word.ends_with(":") {
+            // a label
+            let label = word.slice(0, word.len() - 1).to_owned();
+            if tokens.len() > 1 || label.is_empty() {
+                return Err(err(~"a label goes on a line by itself"));
+            }
+            if f.labels.contains_key(&label) {
+                return Err(err(fmt!("label %s defined twice", label)));
+            }
+            let pc = f.bytecode.len();
+            f.labels.insert(label, pc);
+            loop;
+        }
+        match uint::from_str(word) {
+            Some(w) if tokens.len() == 1 => {
+                // a raw word of bytecode
+                f.bytecode.push(w);
+                loop;
+            },
+            _ => {}
+        }
+        let op = match lookup_op(word) {
+            Some(op) => op,
+            None => { return Err(err(fmt!("unknown instruction %s", word))); }
+        };
+        if tokens.len() != 1 + op.args() {
+            return Err(err(fmt!("%s takes %u operand(s)", word, op.args())));
+        }
+        f.bytecode.push(op as uint);
+        if op.args() > 0 {
+            match (op, &tokens[1]) {
+                (Op_jmp, &AWord(ref l)) | (Op_jmp_unless, &AWord(ref l))
+                    if uint::from_str(*l).is_none() => {
+                    let pc = f.bytecode.len();
+                    f.fixups.push((pc, copy *l, line_no));
+                    f.bytecode.push(0);
+                },
+                (_, t) => match parse_uint(t) {
+                    Ok(n) => f.bytecode.push(n),
+                    Err(e) => { return Err(err(e)); }
+                }
+            }
+        }
+    }
+    match util::replace(&mut current, None) {
+        Some(f) => match finish(f, functions.len()) {
+            Ok(done) => functions.push(done),
+            Err(e) => { return Err(e); }
+        },
+        None => {}
+    }
+    let module = Module { functions: functions, literals: literals, source: None };
+    match Module::new_from_bytes(module.to_bytes()) {
+        Ok(m) => Ok(m),
+        Err(e) => Err(AsmError { line: line_no, message: e.to_str() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use disasm::disassemble;
+    use module::Module;
+    use object::JsString;
+
+    fn error(text: &str) -> ~str {
+        match assemble(text) {
+            Ok(_) => ~"ok",
+            Err(e) => e.to_str()
+        }
+    }
+
+    #[test]
+    fn test_assemble() {
+        let text = "; the answer\n" +
+            "function 0 nargs 0 max_stack 1\n" +
+            "    push_literal 0\n" +
+            "    jmp_unless else ; to else\n" +
+            "    push_literal 1\n" +
+            "    return\n" +
+            "else:\n" +
+            "    push_literal 2\n" +
+            "    return\n" +
+            "literals\n" +
+            "    true\n" +
+            "    \"semi;colon \\u0041\\n\"\n" +
+            "    -0\n";
+        let module = assemble(text).unwrap();
+        assert_eq!(module.to_bytes(),
+                   ~[0, 84, 83, 66, 67, 1,
+                     1, 0, 1, 0, 10, 1, 0, 13, 7, 1, 1, 11, 1, 2, 11,
+                     3, 2, 1, 13, 115, 101, 109, 105, 59, 99, 111, 108, 111,
+                     110, 32, 65, 10, 0, 2, 45, 48]);
+        // the disassembler's listing reads back as the same module
+        let listing = disassemble(&module);
+        assert_eq!(disassemble(&assemble(listing).unwrap()), listing);
+        let startup = Module::new_startup_module();
+        assert_eq!(assemble(disassemble(&startup)).unwrap().to_bytes(),
+                   startup.to_bytes());
+
+        assert_eq!(error("    return\n"), ~"line 1: expected a function header");
+        assert_eq!(error("function 1 nargs 0 max_stack 0\n"),
+                   ~"line 1: expected function 0");
+        assert_eq!(error("function 0 nargs 0\n"), ~"line 1: expected max_stack");
+        assert_eq!(error("function 0 nargs 0 max_stack 0\n    frob\n"),
+                   ~"line 2: unknown instruction frob");
+        assert_eq!(error("function 0 nargs 0 max_stack 0\n    jmp\n"),
+                   ~"line 2: jmp takes 1 operand(s)");
+        assert_eq!(error("function 0 nargs 0 max_stack 0\n    jmp nowhere\n"),
+                   ~"line 2: undefined label nowhere");
+        assert_eq!(error("function 0 nargs 0 max_stack 0\nliterals\n    bogus\n"),
+                   ~"line 3: bad literal bogus");
+        assert_eq!(error("literals\n    \"open\n"), ~"line 2: unterminated string");
+    }
+
+    #[test]
+    fn test_unpaired_surrogates() {
+        // strings and names are UTF-16, and needn't be valid Unicode
+        let text = "function 0 \"f\\ud800\" nargs 0 max_stack 1\n" +
+            "    push_literal 0\n" +
+            "    return\n" +
+            "literals\n" +
+            "    \"\\udc00x\"\n";
+        let module = assemble(text).unwrap();
+        assert_eq!(module.functions[0].name, Some(@['f' as u16, 0xD800]));
+        match module.literals[0] {
+            JsString(s) => assert_eq!(s, @[0xDC00, 'x' as u16]),
+            _ => fail!()
+        }
+        let listing = disassemble(&module);
+        assert_eq!(disassemble(&assemble(listing).unwrap()), listing);
+    }
+}
@@ -0,0 +1,644 @@
+// the TurtleScript bytecode compiler, ported from bcompile.js
+//
+// This walks the tree from parse.rs and writes the same modules, byte
+// for byte, as the self-hosted compiler in startup.rs; see
+// Module::decode for the format.  It's much faster, since it doesn't
+// run through the interpreter.
+use numconv;
+use op::*;
+use parse::*;
+use tokenize::SyntaxError;
+
+// the names every module can use without declaring them.  This must
+// agree with the list in startup.rs, which adds Date, Map, Set,
+// require, module and exports by hand to the TOP_LEVEL of bcompile.js.
+pub static TOP_LEVEL: &'static str = "isFinite parseInt parseFloat isNaN Boolean String Function Math Number JSON RegExp Date Map Set SyntaxError console arguments now define document require module exports";
+
+// a bytecode word, or a jump target to fill in when encoding
+priv enum Item {
+    IWord(uint),
+    ILabel(uint)
+}
+
+priv struct Func {
+    nargs: uint,
+    max_stack: uint,
+    name: Option<~[u16]>,
+    bytecode: ~[Item],
+    stack_depth: int,
+    loop_labels: ~[uint],
+    // can control reach the end of the code emitted so far?
+    can_fall_off: bool,
+    // where the function literal is in the source
+    span: Option<(uint, uint)>
+}
+
+priv struct State {
+    functions: ~[Func],
+    literals: ~[Value],
+    labels: ~[uint],
+    current: uint, // the function being compiled
+    scope: uint,   // how many functions deep we are
+    error: Option<SyntaxError>
+}
+
+priv fn utf16(s: &str) -> ~[u16] { str::to_utf16(s) }
+
+impl State {
+    fn new() -> State {
+        State {
+            functions: ~[],
+            literals: ~[],
+            labels: ~[],
+            current: 0,
+            scope: 0,
+            error: None
+        }
+    }
+
+    // the index of a literal; NaN is one literal, and so are 0 and -0
+    fn literal(&mut self, v: Value) -> uint {
+        for self.literals.eachi |i, l| {
+            let same = match (l, &v) {
+                (&VNumber(a), &VNumber(b)) => a == b || (a.is_NaN() && b.is_NaN()),
+                _ => *l == v
+            };
+            if same { return i; }
+        }
+        self.literals.push(v);
+        self.literals.len() - 1
+    }
+
+    fn str_literal(&mut self, s: &str) -> uint {
+        self.literal(VString(utf16(s)))
+    }
+
+    fn new_function(&mut self, nargs: uint) -> uint {
+        self.functions.push(Func {
+            nargs: nargs,
+            max_stack: 0,
+            name: None,
+            bytecode: ~[],
+            stack_depth: 0,
+            loop_labels: ~[],
+            can_fall_off: false,
+            span: None
+        });
+        self.functions.len() - 1
+    }
+
+    fn emit_item(&mut self, op: Op, arg: Option<Item>) {
+        let f = &mut self.functions[self.current];
+        f.bytecode.push(IWord(op as uint));
+        let args = match arg {
+            Some(a) => {
+                let v = match a { IWord(w) => w as int, ILabel(_) => 0 };
+                f.bytecode.push(a);
+                ~[v]
+            },
+            None => ~[]
+        };
+        f.stack_depth -= op.stackpop(args) as int;
+        f.stack_depth += op.stackpush() as int;
+        if f.stack_depth > f.max_stack as int {
+            f.max_stack = f.stack_depth as uint;
+        }
+        f.can_fall_off = true;
+    }
+
+    fn emit(&mut self, op: Op) { self.emit_item(op, None); }
+    fn emit_arg(&mut self, op: Op, arg: uint) { self.emit_item(op, Some(IWord(arg))); }
+    fn emit_jump(&mut self, op: Op, label: uint) { self.emit_item(op, Some(ILabel(label))); }
+
+    fn new_label(&mut self) -> uint {
+        self.labels.push(0);
+        self.labels.len() - 1
+    }
+
+    fn set_label(&mut self, label: uint) {
+        self.labels[label] = self.functions[self.current].bytecode.len();
+    }
+
+    // the frame a name was declared in
+    fn push_scope(&mut self, scope: @mut Scope) {
+        self.emit(Op_push_frame);
+        // the parser doesn't unwind its scope after a syntax error, so
+        // levels can be higher than our depth; bcompile.js then emits
+        // nothing here.
+        let mut depth = self.scope as int - scope.level as int;
+        while depth > 0 {
+            let proto = self.str_literal("__proto__");
+            self.emit_arg(Op_get_slot_direct, proto);
+            depth -= 1;
+        }
+    }
+
+    fn stmts(&mut self, list: &[@mut Node]) {
+        for list.each |t| { self.stmt(*t); }
+    }
+
+    fn stmt(&mut self, t: @mut Node) {
+        if t.arity() == Some(ABinary) && is_assignment(t) {
+            self.assign(t, true);
+            return;
+        }
+        self.expr(t);
+        if t.arity() != Some(AStatement) {
+            self.emit(Op_pop);
+        }
+    }
+
+    fn expr(&mut self, t: @mut Node) {
+        match t.arity() {
+            Some(AName) => {
+                self.push_scope(t.scope().get());
+                let name = self.literal(t.value());
+                self.emit_arg(Op_get_slot_direct, name);
+            },
+            Some(ALiteral) => match t.value() {
+                VObject | VArray => {
+                    self.emit(Op_push_frame);
+                    let cons = self.str_literal(
+                        if t.value() == VArray { "Array" } else { "Object" });
+                    self.emit_arg(Op_get_slot_direct, cons);
+                },
+                v => {
+                    let l = self.literal(v);
+                    self.emit_arg(Op_push_literal, l);
+                }
+            },
+            Some(AThis) => {
+                self.emit(Op_push_frame);
+                let this = self.str_literal("this");
+                self.emit_arg(Op_get_slot_direct, this);
+            },
+            Some(AUnary) => self.unary(t),
+            Some(ABinary) => self.binary(t),
+            Some(ATernary) => self.ternary(t),
+            Some(AStatement) => self.statement(t),
+            Some(AFunction) => self.function(t),
+            _ => fail!(~"no arity")
+        }
+    }
+
+    fn unary(&mut self, t: @mut Node) {
+        if t.value_is("[") {
+            self.emit(Op_new_array);
+            for t.first().list().eachi |i, e| {
+                self.emit(Op_dup);
+                self.expr(*e);
+                let index = self.literal(VNumber(i as f64));
+                self.emit_arg(Op_set_slot_direct, index);
+            }
+        } else if t.value_is("{") {
+            self.emit(Op_new_object);
+            for t.first().list().each |e| {
+                self.emit(Op_dup);
+                let key = e.key();
+                if e.arity() == Some(AFunction) {
+                    let mut name = match key {
+                        VString(ref s) => copy *s,
+                        VNumber(n) => utf16(numconv::number_to_str(n)),
+                        _ => fail!(~"bad key")
+                    };
+                    name.push(':' as u16);
+                    e.extra_name = Some(name);
+                }
+                self.expr(*e);
+                let k = self.literal(key);
+                self.emit_arg(Op_set_slot_direct, k);
+            }
+        } else {
+            self.expr(t.first().node());
+            self.emit(if t.value_is("!") {
+                Op_un_not
+            } else if t.value_is("-") {
+                Op_un_minus
+            } else if t.value_is("~") {
+                Op_un_bitnot
+            } else if t.value_is("void") {
+                Op_un_void
+            } else {
+                Op_un_typeof
+            });
+        }
+    }
+
+    fn binary(&mut self, t: @mut Node) {
+        if is_assignment(t) {
+            self.assign(t, false);
+        } else if t.value_is("||") || t.value_is("&&") {
+            let merge = self.new_label();
+            self.expr(t.first().node());
+            self.emit(Op_dup);
+            if t.value_is("||") { self.emit(Op_un_not); }
+            self.emit_jump(Op_jmp_unless, merge);
+            self.emit(Op_pop);
+            self.expr(t.second().node());
+            self.set_label(merge);
+        } else if t.value_is(".") {
+            self.expr(t.first().node());
+            let name = self.literal(t.second().node().value());
+            self.emit_arg(Op_get_slot_direct, name);
+        } else if t.value_is("[") {
+            self.expr(t.first().node());
+            self.expr(t.second().node());
+            self.emit(Op_get_slot_indirect);
+        } else if t.value_is("(") {
+            // a function call; 'this' is passed through
+            let args = t.second().list();
+            self.expr(t.first().node());
+            self.emit(Op_push_frame);
+            let this = self.str_literal("this");
+            self.emit_arg(Op_get_slot_direct, this);
+            for args.each |e| { self.expr(*e); }
+            self.emit_arg(Op_invoke, args.len());
+        } else {
+            // a === b, and so on
+            let op = if t.value_is("===") || t.value_is("!==") {
+                Op_bi_eq
+            } else if t.value_is("==") || t.value_is("!=") {
+                Op_bi_loose_eq
+            } else if t.value_is("<") {
+                Op_bi_lt
+            } else if t.value_is("<=") {
+                Op_bi_lte
+            } else if t.value_is(">") {
+                Op_bi_gt
+            } else if t.value_is(">=") {
+                Op_bi_gte
+            } else if t.value_is("in") {
+                Op_bi_in
+            } else if t.value_is("instanceof") {
+                Op_bi_instanceof
+            } else {
+                arith_op(t)
+            };
+            self.expr(t.first().node());
+            self.expr(t.second().node());
+            self.emit(op);
+            if t.value_is("!==") || t.value_is("!=") { self.emit(Op_un_not); }
+        }
+    }
+
+    // a = b, a += b, ...; as a statement we needn't leave the value on
+    // the stack.
+    fn assign(&mut self, t: @mut Node, is_stmt: bool) {
+        let mode = if t.value_is("=") { None } else { Some(arith_op(t)) };
+        let target = t.first().node();
+        let value = t.second().node();
+        if target.arity() == Some(AName) {
+            let name = target.str_value();
+            self.push_scope(target.scope().get());
+            if mode.is_some() {
+                self.emit(Op_dup);
+                let l = self.literal(VString(copy name));
+                self.emit_arg(Op_get_slot_direct, l);
+            }
+            if value.arity() == Some(AFunction) {
+                value.extra_name = Some(copy name);
+            }
+            self.expr(value);
+            match mode { Some(op) => self.emit(op), None => {} }
+            if !is_stmt { self.emit(Op_over); }
+            let l = self.literal(VString(name));
+            self.emit_arg(Op_set_slot_direct, l);
+        } else if target.value_is(".") {
+            let prop = target.second().node().str_value();
+            self.expr(target.first().node());
+            if mode.is_some() {
+                self.emit(Op_dup);
+                let l = self.literal(VString(copy prop));
+                self.emit_arg(Op_get_slot_direct, l);
+            }
+            if value.arity() == Some(AFunction) {
+                value.extra_name = Some(utf16(".") + prop);
+            }
+            self.expr(value);
+            match mode { Some(op) => self.emit(op), None => {} }
+            if !is_stmt { self.emit(Op_over); }
+            let l = self.literal(VString(prop));
+            self.emit_arg(Op_set_slot_direct, l);
+        } else {
+            // target[index]
+            self.expr(target.first().node());
+            self.expr(target.second().node());
+            if mode.is_some() {
+                self.emit(Op_2dup);
+                self.emit(Op_get_slot_indirect);
+            }
+            self.expr(value);
+            match mode { Some(op) => self.emit(op), None => {} }
+            if !is_stmt { self.emit(Op_over2); }
+            self.emit(Op_set_slot_indirect);
+        }
+    }
+
+    fn ternary(&mut self, t: @mut Node) {
+        if t.value_is("?") {
+            let false_label = self.new_label();
+            let merge = self.new_label();
+            self.expr(t.first().node());
+            self.emit_jump(Op_jmp_unless, false_label);
+            let depth = self.functions[self.current].stack_depth;
+            self.expr(t.second().node());
+            self.emit_jump(Op_jmp, merge);
+            self.functions[self.current].stack_depth = depth;
+            self.set_label(false_label);
+            self.expr(t.third().node());
+            self.set_label(merge);
+        } else {
+            // a method call: object.name(args) or object[expr](args)
+            let args = t.third().list();
+            self.expr(t.first().node());
+            self.emit(Op_dup);
+            let name = t.second().node();
+            match (name.arity(), name.value()) {
+                (Some(ALiteral), VString(s)) => {
+                    let l = self.literal(VString(s));
+                    self.emit_arg(Op_get_slot_direct_check, l);
+                },
+                _ => {
+                    self.expr(name);
+                    self.emit(Op_get_slot_indirect);
+                }
+            }
+            self.emit(Op_swap);
+            for args.each |e| { self.expr(*e); }
+            self.emit_arg(Op_invoke, args.len());
+        }
+    }
+
+    fn statement(&mut self, t: @mut Node) {
+        if t.value_is("block") {
+            self.stmts(*t.first().list());
+        } else if t.value_is("var") {
+            // var x; is x = undefined;
+            let n = t.first().node();
+            self.push_scope(n.scope().get());
+            let undef = self.literal(VUndefined);
+            self.emit_arg(Op_push_literal, undef);
+            let name = self.literal(n.value());
+            self.emit_arg(Op_set_slot_direct, name);
+        } else if t.value_is("if") {
+            let false_label = self.new_label();
+            self.expr(t.first().node());
+            self.emit_jump(Op_jmp_unless, false_label);
+            self.stmt(t.second().node());
+            match t.third() {
+                CNode(otherwise) => {
+                    let merge = self.new_label();
+                    self.emit_jump(Op_jmp, merge);
+                    self.set_label(false_label);
+                    self.stmt(otherwise);
+                    self.set_label(merge);
+                },
+                _ => self.set_label(false_label)
+            }
+        } else if t.value_is("return") {
+            match t.first() {
+                CNode(e) => self.expr(e),
+                _ => {
+                    let undef = self.literal(VUndefined);
+                    self.emit_arg(Op_push_literal, undef);
+                }
+            }
+            self.emit(Op_return);
+            self.functions[self.current].can_fall_off = false;
+        } else if t.value_is("break") {
+            let target = match self.functions[self.current].loop_labels.last_opt() {
+                Some(&l) => Some(l),
+                None => None
+            };
+            match target {
+                Some(l) => self.emit_jump(Op_jmp, l),
+                // bcompile.js crashes on this
+                None => if self.error.is_none() {
+                    self.error = Some(SyntaxError::new(
+                        "Illegal break statement.", t.from(), t.to()));
+                }
+            }
+        } else if t.value_is("while") {
+            let start = self.new_label();
+            let test = self.new_label();
+            let end = self.new_label();
+            self.functions[self.current].loop_labels.push(end);
+            self.emit_jump(Op_jmp, test);
+            self.set_label(start);
+            self.stmt(t.second().node());
+            self.set_label(test);
+            self.expr(t.first().node());
+            self.emit(Op_un_not);
+            self.emit_jump(Op_jmp_unless, start);
+            self.set_label(end);
+            self.functions[self.current].loop_labels.pop();
+        } else {
+            fail!(~"unknown statement");
+        }
+    }
+
+    fn function(&mut self, t: @mut Node) {
+        match t.name() {
+            // function f() {...} is f = function() {...}
+            Some(name) => {
+                self.push_scope(t.scope().get());
+                self.function_body(t, Some(copy name));
+                self.emit(Op_over);
+                let l = self.literal(VString(name));
+                self.emit_arg(Op_set_slot_direct, l);
+            },
+            None => self.function_body(t, t.extra_name())
+        }
+    }
+
+    fn function_body(&mut self, t: @mut Node, name: Option<~[u16]>) {
+        let params = t.first().list();
+        let outer = self.current;
+        let id = self.new_function(params.len());
+        self.current = id;
+        self.functions[id].name = name;
+        self.functions[id].span = Some((t.from(), t.end()));
+        self.scope += 1;
+        // copy the arguments into named slots
+        self.emit(Op_push_frame);
+        let arguments = self.str_literal("arguments");
+        self.emit_arg(Op_get_slot_direct, arguments);
+        for params.eachi |i, p| {
+            self.emit(Op_dup);
+            let index = self.literal(VNumber(i as f64));
+            self.emit_arg(Op_get_slot_direct, index);
+            self.emit(Op_push_frame);
+            self.emit(Op_swap);
+            let name = self.literal(p.value());
+            self.emit_arg(Op_set_slot_direct, name);
+        }
+        self.emit(Op_pop);
+        self.stmts(*t.second().list());
+        if self.functions[id].can_fall_off {
+            self.emit_return_undefined();
+        }
+        self.current = outer;
+        self.scope -= 1;
+        self.emit_arg(Op_new_function, id);
+    }
+
+    fn emit_return_undefined(&mut self) {
+        let undef = self.literal(VUndefined);
+        self.emit_arg(Op_push_literal, undef);
+        self.emit(Op_return);
+        self.functions[self.current].can_fall_off = false;
+    }
+
+    fn encode(&self) -> ~[u8] {
+        let mut out : ~[u8] = ~[];
+        encode_uint(&mut out, self.functions.len());
+        for self.functions.each |f| {
+            encode_uint(&mut out, f.nargs);
+            encode_uint(&mut out, f.max_stack);
+            match f.name {
+                Some(ref n) => encode_str(&mut out, *n),
+                None => encode_str(&mut out, [])
+            }
+            encode_uint(&mut out, f.bytecode.len());
+            for f.bytecode.each |item| {
+                encode_uint(&mut out, match *item {
+                    IWord(w) => w,
+                    ILabel(l) => self.labels[l]
+                });
+            }
+        }
+        encode_uint(&mut out, self.literals.len());
+        for self.literals.each |l| {
+            match *l {
+                VNumber(n) => {
+                    encode_uint(&mut out, 0);
+                    encode_str(&mut out, utf16(numconv::number_to_str(n)));
+                },
+                VString(ref s) => {
+                    encode_uint(&mut out, 1);
+                    encode_str(&mut out, *s);
+                },
+                VBool(true) => encode_uint(&mut out, 2),
+                VBool(false) => encode_uint(&mut out, 3),
+                VNull => encode_uint(&mut out, 4),
+                VUndefined => encode_uint(&mut out, 5),
+                VObject | VArray => fail!(~"not a literal")
+            }
+        }
+        out
+    }
+}
+
+// the operators with an assigning form, and what they do
+static ARITH_OPS: &'static [(&'static str, Op)] = &[
+    ("+", Op_bi_add), ("-", Op_bi_sub), ("*", Op_bi_mul), ("/", Op_bi_div),
+    ("%", Op_bi_mod), ("&", Op_bi_bitand), ("|", Op_bi_bitor),
+    ("^", Op_bi_bitxor), ("<<", Op_bi_lsh), (">>", Op_bi_rsh),
+    (">>>", Op_bi_ursh)
+];
+
+priv fn is_assignment(t: &Node) -> bool {
+    t.value_is("=") || ARITH_OPS.any(|&(name, _)| t.value_is(fmt!("%s=", name)))
+}
+
+// the arithmetic of +, +=, and so on
+priv fn arith_op(t: &Node) -> Op {
+    for ARITH_OPS.each |&(name, op)| {
+        if t.value_is(name) || t.value_is(fmt!("%s=", name)) { return op; }
+    }
+    fail!(~"unknown operator")
+}
+
+// the inverse of Reader::decode_uint
+priv fn encode_uint(out: &mut ~[u8], v: uint) {
+    if v < 128 {
+        out.push(v as u8);
+    } else {
+        out.push(((v % 128) + 128) as u8);
+        encode_uint(out, v / 128);
+    }
+}
+
+priv fn encode_str(out: &mut ~[u8], s: &[u16]) {
+    encode_uint(out, s.len());
+    for s.each |c| { encode_uint(out, *c as uint); }
+}
+
+// a module's bytes, and the (start, end) in the source of each of its
+// functions after the first, for Module::new_from_source.  The
+// self-hosted compiler numbers functions the same way.
+pub type Output = (~[u8], ~[(uint, uint)]);
+
+// compile a parsed program into a module
+priv fn bcompile(tree: &[@mut Node]) -> Result<Output, SyntaxError> {
+    let mut state = State::new();
+    state.new_function(0);
+    state.stmts(tree);
+    if state.functions[0].can_fall_off {
+        state.emit_return_undefined();
+    }
+    match state.error {
+        Some(e) => Err(e),
+        None => {
+            let spans = state.functions.tail().map(|f| f.span.get());
+            Ok((state.encode(), spans))
+        }
+    }
+}
+
+// a compiler for one interpreter; like the self-hosted one it keeps
+// its parser (and the REPL's scope) from one source to the next.
+pub struct Compiler {
+    priv parser: Parser,
+    priv repl_state: Option<@mut Scope>
+}
+
+impl Compiler {
+    pub fn new() -> Compiler {
+        Compiler { parser: Parser::new(), repl_state: None }
+    }
+
+    // compile a script to module bytes
+    pub fn compile(&mut self, source: &str) -> Result<Output, SyntaxError> {
+        let source = if source.is_empty() { "{ return 1+2; }" } else { source };
+        match self.parser.parse(str::to_utf16(source), utf16(TOP_LEVEL)) {
+            Ok(tree) => bcompile(tree),
+            Err(e) => Err(e)
+        }
+    }
+
+    // compile a REPL line: an expression returns its value
+    pub fn compile_repl(&mut self, source: &str) -> Result<Output, SyntaxError> {
+        match self.parser.parse_repl(self.repl_state, str::to_utf16(source),
+                                     utf16(TOP_LEVEL)) {
+            Ok((state, tree)) => {
+                self.repl_state = Some(state);
+                bcompile(tree)
+            },
+            Err(e) => Err(e)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compile() {
+        let mut c = Compiler::new();
+        // one function: push_literal 0; return; and the literal 3
+        assert_eq!(c.compile("{ return 3; }").get(),
+                   (~[1, 0, 1, 0, 3, 1, 0, 11, 1, 0, 1, 51], ~[]));
+        // where each function after the first is
+        let (_, spans) = c.compile("var f = function() { return function(x) {}; }, g = function() {};").get();
+        assert_eq!(spans, ~[(8, 45), (28, 42), (51, 64)]);
+        assert_eq!(c.compile("var x = ;").get_err().to_str(), ~"Undefined. [8-9]");
+        assert_eq!(c.compile("{ break; }").get_err().to_str(),
+                   ~"Illegal break statement. [2-7]");
+        // the REPL keeps its variables
+        assert!(c.compile_repl("var x = 1;").is_ok());
+        assert!(c.compile_repl("x + 1").is_ok());
+        assert_eq!(c.compile_repl("y").get_err().to_str(), ~"Undefined. [0-1]");
+    }
+}
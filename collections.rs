@@ -0,0 +1,397 @@
+// Map and Set (ES2015 sections 23.1 and 23.2)
+//
+// Keys are compared with SameValueZero: primitives by value (NaN equals
+// itself, -0 equals +0), objects by identity.  Entries are kept in
+// insertion order; deleted entries leave a hole so that a forEach in
+// progress sees the right elements.  Once holes make up more than half
+// the entries, and no forEach is running, they're squeezed out.
+//
+// Without iterators, keys(), values() and entries() return arrays.
+use core::hashmap::HashMap;
+
+use interp::Environment;
+use intern::intern;
+use object::*;
+
+#[deriving(Eq, IterBytes)]
+priv enum Key {
+    KUndefined,
+    KNull,
+    KBool(bool),
+    KNumber(u64),
+    KString(~[u16]),
+    KObject(uint)
+}
+
+priv fn key_of(v: JsVal) -> Key {
+    match v {
+        JsUndefined => KUndefined,
+        JsNull => KNull,
+        JsBool(b) => KBool(b),
+        JsNumber(n) => KNumber(if n.is_NaN() {
+            0x7FF8000000000000u64
+        } else if n == 0f64 {
+            0u64 // -0 and +0
+        } else {
+            unsafe { cast::transmute(n) }
+        }),
+        JsString(s) => KString(s.to_owned()),
+        JsObject(o) => KObject(ptr::to_unsafe_ptr(&*o) as uint),
+        _ => fail!(fmt!("%? can't be a collection key", v))
+    }
+}
+
+pub struct Collection {
+    is_set: bool,
+    priv entries: ~[Option<(JsVal, JsVal)>],
+    priv index: HashMap<Key, uint>,
+    priv size: uint,
+    // how many forEach calls are walking the entries
+    priv iterators: uint
+}
+
+impl Collection {
+    pub fn new(is_set: bool) -> Collection {
+        Collection {
+            is_set: is_set,
+            entries: ~[],
+            index: HashMap::new(),
+            size: 0,
+            iterators: 0
+        }
+    }
+
+    pub fn len(&self) -> uint { self.size }
+
+    pub fn get(&self, key: JsVal) -> Option<JsVal> {
+        match self.index.find(&key_of(key)) {
+            Some(&i) => self.entries[i].map(|&(_, v)| v),
+            None => None
+        }
+    }
+
+    pub fn has(&self, key: JsVal) -> bool {
+        self.index.contains_key(&key_of(key))
+    }
+
+    pub fn set(&mut self, key: JsVal, value: JsVal) {
+        // store -0 as +0
+        let key = match key { JsNumber(n) if n == 0f64 => JsNumber(0f64), _ => key };
+        let k = key_of(key);
+        let existing = match self.index.find(&k) {
+            Some(&i) => Some(i),
+            None => None
+        };
+        match existing {
+            Some(i) => { self.entries[i] = Some((key, value)); return; },
+            None => {}
+        }
+        self.index.insert(k, self.entries.len());
+        self.entries.push(Some((key, value)));
+        self.size += 1;
+    }
+
+    pub fn delete(&mut self, key: JsVal) -> bool {
+        match self.index.pop(&key_of(key)) {
+            Some(i) => {
+                self.entries[i] = None;
+                self.size -= 1;
+                self.compact();
+                true
+            },
+            None => false
+        }
+    }
+
+    pub fn clear(&mut self) {
+        for uint::range(0, self.entries.len()) |i| { self.entries[i] = None; }
+        self.index.clear();
+        self.size = 0;
+        self.compact();
+    }
+
+    // drop the holes left by deleted entries, if there are enough of
+    // them, and renumber the index to match
+    priv fn compact(&mut self) {
+        let holes = self.entries.len() - self.size;
+        if self.iterators > 0 || holes * 2 <= self.entries.len() {
+            return;
+        }
+        let old = util::replace(&mut self.entries, ~[]);
+        for old.each |e| {
+            match *e {
+                Some((k, v)) => {
+                    self.index.insert(key_of(k), self.entries.len());
+                    self.entries.push(Some((k, v)));
+                },
+                None => {}
+            }
+        }
+    }
+
+    // positions don't change between these, so a walk over the
+    // entries can't miss one or see one twice
+    pub fn begin_iteration(&mut self) { self.iterators += 1; }
+    pub fn end_iteration(&mut self) {
+        self.iterators -= 1;
+        self.compact();
+    }
+
+    // the entry at position i in insertion order, if it hasn't been
+    // deleted; entries may be added while iterating.
+    pub fn entry(&self, i: uint) -> Option<(JsVal, JsVal)> {
+        if i < self.entries.len() { self.entries[i] } else { None }
+    }
+
+    pub fn capacity(&self) -> uint { self.entries.len() }
+}
+
+// bindings
+
+pub fn get_collection(env: Environment, val: JsVal) -> Option<@mut Collection> {
+    match val {
+        JsObject(obj) => match obj.get_simple(env.fdValue) {
+            Some(JsCollection(c)) => Some(c),
+            _ => None
+        },
+        _ => None
+    }
+}
+
+priv fn update_size(env: Environment, this: JsVal, c: @mut Collection) {
+    env.set_slot(this, JsVal::from_str("size"), JsNumber(c.len() as f64));
+}
+
+// a Map or Set constructor
+priv fn make_cons(env: Environment, frame: @mut Object, name: &'static str,
+                  proto: @mut Object, is_set: bool) -> @mut Object {
+    let cons = Object::create(env.root_map, env.myFunction);
+    cons.set(FieldDesc { name: intern("prototype"), hidden: false },
+             JsObject(proto));
+    cons.set(env.fdParentFrame, JsObject(frame));
+    cons.set(env.fdValue, JsNativeFunction(|this, args| {
+        // only Function.prototype.New passes a fresh object
+        let constructing = match this {
+            JsObject(obj) => get_collection(env, this).is_none() &&
+                match obj.get_simple(env.fdProto) {
+                    Some(JsObject(p)) => ptr::ref_eq(p, proto),
+                    _ => false
+                },
+            _ => false
+        };
+        if !constructing {
+            return env.throwError("TypeError", fmt!(
+                "Constructor %s requires 'new'", name));
+        }
+        let c = @mut Collection::new(is_set);
+        match this {
+            JsObject(obj) => obj.set(env.fdValue, JsCollection(c)),
+            _ => fail!()
+        }
+        // an array of values, or of [key, value] pairs
+        match getarg(args, 0) {
+            JsUndefined | JsNull => {},
+            init if env.isArray(init) => {
+                for env.arrayEach(init) |v| {
+                    if is_set {
+                        c.set(v, v);
+                    } else {
+                        c.set(env.get_slot(v, JsNumber(0f64)),
+                              env.get_slot(v, JsNumber(1f64)));
+                    }
+                }
+            },
+            _ => {
+                return env.throwError("TypeError", fmt!(
+                    "%s can only be initialized from an array", name));
+            }
+        }
+        update_size(env, this, c);
+        this
+    }));
+    cons
+}
+
+// the receiver of a Map or Set method, or a TypeError
+priv fn receiver(env: Environment, this: JsVal, is_set: bool,
+                 method: &str) -> Result<@mut Collection, JsVal> {
+    match get_collection(env, this) {
+        Some(c) if c.is_set == is_set => Ok(c),
+        _ => Err(env.throwError("TypeError", fmt!(
+            "Method %s.prototype.%s called on incompatible receiver",
+            if is_set { "Set" } else { "Map" }, method)))
+    }
+}
+
+// the methods Map and Set share
+priv fn add_common(env: Environment, frame: @mut Object, proto: @mut Object,
+                   is_set: bool) {
+    do env.add_native_func_str(frame, proto, "has") |this, args| {
+        match receiver(env, this, is_set, "has") {
+            Err(e) => e,
+            Ok(c) => JsBool(c.has(getarg(args, 0)))
+        }
+    };
+    do env.add_native_func_str(frame, proto, "delete") |this, args| {
+        match receiver(env, this, is_set, "delete") {
+            Err(e) => e,
+            Ok(c) => {
+                let rv = c.delete(getarg(args, 0));
+                update_size(env, this, c);
+                JsBool(rv)
+            }
+        }
+    };
+    do env.add_native_func_str(frame, proto, "clear") |this, _args| {
+        match receiver(env, this, is_set, "clear") {
+            Err(e) => e,
+            Ok(c) => {
+                c.clear();
+                update_size(env, this, c);
+                JsUndefined
+            }
+        }
+    };
+    do env.add_native_func_str(frame, proto, "forEach") |this, args| {
+        match receiver(env, this, is_set, "forEach") {
+            Err(e) => e,
+            Ok(c) => {
+                let f = getarg(args, 0);
+                if !env.isCallable(f) {
+                    return env.throwError("TypeError", fmt!(
+                        "%s is not a function", env.toString(f)));
+                }
+                let mut i = 0u;
+                let mut rv = JsUndefined;
+                c.begin_iteration();
+                // entries added by the callback are visited too
+                while i < c.capacity() {
+                    match c.entry(i) {
+                        Some((k, v)) => {
+                            match env.interpret_function(
+                                f, getarg(args, 1), ~[v, k, this]) {
+                                JsThrown(ex) => { rv = JsThrown(ex); break; },
+                                _ => {}
+                            }
+                        },
+                        None => {}
+                    }
+                    i += 1;
+                }
+                c.end_iteration();
+                rv
+            }
+        }
+    };
+    let snapshot: @fn(&str, @fn(JsVal, JsVal) -> JsVal) = |name, f| {
+        let label = name.to_owned();
+        do env.add_native_func_str(frame, proto, name) |this, _args| {
+            match receiver(env, this, is_set, label) {
+                Err(e) => e,
+                Ok(c) => {
+                    let mut elements : ~[JsVal] = ~[];
+                    for uint::range(0, c.capacity()) |i| {
+                        match c.entry(i) {
+                            Some((k, v)) => elements.push(f(k, v)),
+                            None => {}
+                        }
+                    }
+                    env.arrayCreate(elements)
+                }
+            }
+        };
+    };
+    snapshot("keys", |k, _v| k);
+    snapshot("values", |_k, v| v);
+    snapshot("entries", |k, v| env.arrayCreate([k, v]));
+}
+
+pub fn make_map_cons(env: Environment, frame: @mut Object) -> @mut Object {
+    let myMap = Object::create(env.root_map, env.myObject);
+    let cons = make_cons(env, frame, "Map", myMap, false);
+    add_common(env, frame, myMap, false);
+    do env.add_native_func_str(frame, myMap, "get") |this, args| {
+        match receiver(env, this, false, "get") {
+            Err(e) => e,
+            Ok(c) => c.get(getarg(args, 0)).get_or_default(JsUndefined)
+        }
+    };
+    do env.add_native_func_str(frame, myMap, "set") |this, args| {
+        match receiver(env, this, false, "set") {
+            Err(e) => e,
+            Ok(c) => {
+                c.set(getarg(args, 0), getarg(args, 1));
+                update_size(env, this, c);
+                this
+            }
+        }
+    };
+    cons
+}
+
+pub fn make_set_cons(env: Environment, frame: @mut Object) -> @mut Object {
+    let mySet = Object::create(env.root_map, env.myObject);
+    let cons = make_cons(env, frame, "Set", mySet, true);
+    add_common(env, frame, mySet, true);
+    do env.add_native_func_str(frame, mySet, "add") |this, args| {
+        match receiver(env, this, true, "add") {
+            Err(e) => e,
+            Ok(c) => {
+                let v = getarg(args, 0);
+                c.set(v, v);
+                update_size(env, this, c);
+                this
+            }
+        }
+    };
+    cons
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use object::*;
+
+    #[test]
+    fn test_same_value_zero() {
+        let mut c = Collection::new(false);
+        c.set(JsNumber(f64::NaN), JsVal::from_str("nan"));
+        c.set(JsNumber(-0f64), JsVal::from_str("zero"));
+        c.set(JsVal::from_str("1"), JsBool(true));
+        assert_eq!(c.len(), 3);
+        assert_eq!(c.get(JsNumber(f64::NaN)).get().to_str(), ~"nan");
+        assert_eq!(c.get(JsNumber(0f64)).get().to_str(), ~"zero");
+        assert!(c.get(JsNumber(1f64)).is_none());
+        // overwriting keeps the original position
+        c.set(JsNumber(f64::NaN), JsNull);
+        assert_eq!(c.entry(0).get().second().to_str(), ~"null");
+        assert!(c.delete(JsNumber(0f64)));
+        assert!(!c.delete(JsNumber(0f64)));
+        assert!(c.entry(1).is_none());
+        assert_eq!(c.len(), 2);
+        c.clear();
+        assert_eq!(c.len(), 0);
+        assert!(!c.has(JsVal::from_str("1")));
+    }
+
+    #[test]
+    fn test_compact() {
+        let mut c = Collection::new(true);
+        for uint::range(0, 10) |i| { c.set(JsNumber(i as f64), JsNull); }
+        for uint::range(0, 5) |i| { c.delete(JsNumber(i as f64)); }
+        // five holes in ten aren't enough
+        assert_eq!(c.capacity(), 10);
+        c.delete(JsNumber(5f64));
+        assert_eq!(c.capacity(), 4);
+        assert_eq!(c.entry(0).get().first().to_str(), ~"6");
+        assert!(c.has(JsNumber(9f64)) && !c.has(JsNumber(5f64)));
+        c.delete(JsNumber(6f64));
+        assert_eq!(c.get(JsNumber(7f64)).get().to_str(), ~"null");
+        // but not while something is walking the entries
+        c.begin_iteration();
+        c.clear();
+        assert_eq!(c.capacity(), 4);
+        c.end_iteration();
+        assert_eq!(c.capacity(), 0);
+    }
+}
@@ -0,0 +1,312 @@
+// conformance tests for the abstract operations (ES5 section 9) as the
+// bytecode operators use them: every combination of operand types
+// either computes the spec result or throws a TypeError.
+use function::Function;
+use interp::Interpreter;
+use module::Module;
+use object::*;
+use op::*;
+
+// one operand of each interesting kind, with the source which made it
+fn operands(i: &Interpreter) -> ~[(~str, JsVal)] {
+    let sources : ~[&str] = ~[
+        "undefined", "null", "true", "false",
+        "0", "-0", "1", "0/0", "1/0",
+        "''", "'1'", "'abc'", "' 0x10 '",
+        "({})", "[]", "[2]", "(function() {})",
+        "({valueOf: function() { return 3; }})",
+        "({valueOf: function() { return {}; }, toString: function() { return '4'; }})",
+        "({valueOf: function() { throw 'boom'; }})",
+        "({valueOf: null, toString: null})"
+    ];
+    let mut rv = ~[];
+    for sources.each |src| {
+        rv.push((src.to_owned(), i.repl(*src)));
+    }
+    // internal values, which user code should never see
+    let code = i.env.get_slot_fd(i.repl("(function() {})"), i.env.fdValue);
+    rv.push((~"<function code>", code));
+    rv.push((~"<native function>", JsNativeFunction(|_, _| JsUndefined)));
+    rv
+}
+
+fn is_internal(val: JsVal) -> bool {
+    match val {
+        JsUndefined | JsNull | JsBool(_) | JsNumber(_) | JsString(_) |
+        JsObject(_) => false,
+        _ => true
+    }
+}
+
+// run bytecode which pushes no more values than it has literals
+fn run(i: &Interpreter, frame: @mut Object, bytecode: ~[uint],
+       literals: &[JsVal]) -> JsVal {
+    let module = @Module {
+        functions: ~[@Function {
+            name: None, id: 0, nargs: 0, max_stack: literals.len(),
+            source: None, bytecode: bytecode
+        }],
+        literals: literals.to_owned(),
+        source: None
+    };
+    i.env.interpret(module, 0, Some(frame))
+}
+
+// run an operator on literal operands
+fn apply(i: &Interpreter, frame: @mut Object, op: Op, args: &[JsVal]) -> JsVal {
+    let mut bytecode : ~[uint] = ~[];
+    for uint::range(0, args.len()) |n| {
+        bytecode.push(Op_push_literal as uint);
+        bytecode.push(n);
+    }
+    bytecode.push(op as uint);
+    bytecode.push(Op_return as uint);
+    run(i, frame, bytecode, args)
+}
+
+fn describe(i: &Interpreter, rv: JsVal) -> ~str {
+    match rv {
+        JsThrown(@JsString(s)) => ~"throws " + str::from_utf16(s),
+        JsThrown(@ex) => ~"throws " + i.env.get_slot(ex, JsVal::from_str("name")).to_str(),
+        JsString(s) => fmt!("'%s'", str::from_utf16(s)),
+        _ => rv.to_str()
+    }
+}
+
+fn unary_ops() -> ~[Op] {
+    ~[Op_un_not, Op_un_minus, Op_un_bitnot, Op_un_void, Op_un_typeof]
+}
+fn binary_ops() -> ~[Op] {
+    ~[Op_bi_eq, Op_bi_loose_eq, Op_bi_gt, Op_bi_gte, Op_bi_lt, Op_bi_lte,
+      Op_bi_add, Op_bi_sub, Op_bi_mul, Op_bi_div, Op_bi_mod,
+      Op_bi_bitand, Op_bi_bitor, Op_bi_bitxor, Op_bi_lsh, Op_bi_rsh, Op_bi_ursh,
+      Op_bi_in, Op_bi_instanceof]
+}
+
+// the operators which have to convert every operand.  (instanceof
+// doesn't convert its left operand, and its right one must be a
+// function.)
+fn converts(op: Op) -> bool {
+    match op {
+        Op_un_not | Op_un_void | Op_bi_eq | Op_bi_loose_eq |
+        Op_bi_instanceof => false,
+        _ => true
+    }
+}
+
+#[test]
+fn test_operators_are_total() {
+    let i = Interpreter::new();
+    let frame = i.env.make_top_level_frame(JsNull, ~[]);
+    let vals = operands(&i);
+    let check = |op: Op, desc: ~str, args: &[JsVal]| {
+        let rv = apply(&i, frame, op, args);
+        let ok = match rv {
+            JsUndefined => op as uint == Op_un_void as uint,
+            JsBool(_) | JsNumber(_) | JsString(_) => {
+                // internal values can't be converted
+                !(converts(op) && vec::any(args, |a| is_internal(*a)))
+            },
+            JsThrown(_) => {
+                let d = describe(&i, rv);
+                d == ~"throws TypeError" || d == ~"throws boom"
+            },
+            _ => false
+        };
+        if !ok {
+            fail!(fmt!("%? on %s gave %s", op, desc, describe(&i, rv)));
+        }
+    };
+    for unary_ops().each |op| {
+        for vals.each |&(ref name, v)| {
+            check(*op, copy *name, [v]);
+        }
+    }
+    for binary_ops().each |op| {
+        for vals.each |&(ref lname, l)| {
+            for vals.each |&(ref rname, r)| {
+                check(*op, fmt!("%s, %s", *lname, *rname), [l, r]);
+            }
+        }
+    }
+}
+
+// the property operators and invoke: undefined and null have no
+// properties, and only functions can be called, but every combination
+// either works or throws a TypeError
+#[test]
+fn test_slots_are_total() {
+    let i = Interpreter::new();
+    let frame = i.env.make_top_level_frame(JsNull, ~[]);
+    let vals = operands(&i);
+    let one = JsNumber(1f64);
+    let push = Op_push_literal as uint;
+    let check = |what: &str, desc: &str, obj: JsVal, rv: JsVal| {
+        let ok = match rv {
+            // converting the property name may throw as well
+            JsThrown(_) => {
+                let d = describe(&i, rv);
+                d == ~"throws TypeError" || d == ~"throws boom"
+            },
+            _ => match obj {
+                JsUndefined | JsNull => false,
+                _ => !is_internal(rv)
+            }
+        };
+        if !ok {
+            fail!(fmt!("%s on %s gave %s", what, desc, describe(&i, rv)));
+        }
+    };
+    for vals.each |&(ref oname, obj)| {
+        if is_internal(obj) { loop; }
+        for ["x", "length", "0"].each |name| {
+            let n = JsVal::from_str(*name);
+            let desc = fmt!("%s, '%s'", *oname, *name);
+            check("get_slot_direct", desc, obj, run(&i, frame, ~[
+                push, 0, Op_get_slot_direct as uint, 1, Op_return as uint
+            ], [obj, n]));
+            check("set_slot_direct", desc, obj, run(&i, frame, ~[
+                push, 0, push, 1, Op_set_slot_direct as uint, 2,
+                push, 1, Op_return as uint
+            ], [obj, one, n]));
+        }
+        for vals.each |&(ref nname, n)| {
+            if is_internal(n) { loop; }
+            let desc = fmt!("%s, %s", *oname, *nname);
+            check("get_slot_indirect", desc, obj, run(&i, frame, ~[
+                push, 0, push, 1, Op_get_slot_indirect as uint, Op_return as uint
+            ], [obj, n]));
+            check("set_slot_indirect", desc, obj, run(&i, frame, ~[
+                push, 0, push, 1, push, 2, Op_set_slot_indirect as uint,
+                push, 2, Op_return as uint
+            ], [obj, n, one]));
+        }
+        let rv = run(&i, frame, ~[
+            push, 0, push, 1, Op_invoke as uint, 0, Op_return as uint
+        ], [obj, JsUndefined]);
+        let ok = if i.env.isCallable(obj) {
+            !is_internal(rv)
+        } else {
+            describe(&i, rv) == ~"throws TypeError"
+        };
+        if !ok {
+            fail!(fmt!("invoke on %s gave %s", *oname, describe(&i, rv)));
+        }
+    }
+}
+
+#[test]
+fn test_operator_results() {
+    let i = Interpreter::new();
+    let frame = i.env.make_top_level_frame(JsNull, ~[]);
+    let nullproto = "({valueOf: null, toString: null})";
+    let three = "({valueOf: function() { return 3; }})";
+    let four = "({valueOf: function() { return {}; }, toString: function() { return '4'; }})";
+    let boom = "({valueOf: function() { throw 'boom'; }})";
+    let cases : ~[(Op, ~[&str], &str)] = ~[
+        // ToNumber and ToPrimitive, through the arithmetic operators
+        (Op_un_minus, ~["'5'"], "-5"),
+        (Op_un_minus, ~["' 0x10 '"], "-16"),
+        (Op_un_minus, ~["'abc'"], "NaN"),
+        (Op_un_minus, ~["undefined"], "NaN"),
+        (Op_un_minus, ~["null"], "0"),
+        (Op_un_minus, ~["[]"], "0"),
+        (Op_un_minus, ~["[2]"], "-2"),
+        (Op_un_minus, ~[three], "-3"),
+        (Op_un_minus, ~[four], "-4"),
+        (Op_un_minus, ~[boom], "throws boom"),
+        (Op_un_minus, ~[nullproto], "throws TypeError"),
+        (Op_bi_sub, ~["true", "null"], "1"),
+        (Op_bi_sub, ~[three, four], "-1"),
+        (Op_bi_mul, ~["'3'", "'4'"], "12"),
+        (Op_bi_div, ~["1", "-0"], "-Infinity"),
+        (Op_bi_mod, ~["'6'", "4"], "2"),
+        // + prefers strings, converting with no hint
+        (Op_bi_add, ~["undefined", "null"], "NaN"),
+        (Op_bi_add, ~["true", "true"], "2"),
+        (Op_bi_add, ~["''", "null"], "'null'"),
+        (Op_bi_add, ~["'1'", "1"], "'11'"),
+        (Op_bi_add, ~["1", "'1'"], "'11'"),
+        (Op_bi_add, ~["[]", "[]"], "''"),
+        (Op_bi_add, ~["[2]", "1"], "'21'"),
+        (Op_bi_add, ~[three, "1"], "4"),
+        (Op_bi_add, ~[four, "1"], "'41'"),
+        (Op_bi_add, ~["1", boom], "throws boom"),
+        (Op_bi_add, ~[nullproto, "''"], "throws TypeError"),
+        // ToInt32 and ToUint32
+        (Op_un_bitnot, ~["'5'"], "-6"),
+        (Op_bi_bitand, ~["'12'", "10"], "8"),
+        (Op_bi_bitor, ~["1/0", "0"], "0"),
+        (Op_bi_bitor, ~["'abc'", "0"], "0"),
+        (Op_bi_bitxor, ~["true", "1"], "0"),
+        (Op_bi_lsh, ~["[5]", "1"], "10"),
+        (Op_bi_rsh, ~["-8", "'1'"], "-4"),
+        (Op_bi_ursh, ~["-1", "null"], "4294967295"),
+        // the abstract relational comparison
+        (Op_bi_lt, ~["'a'", "'b'"], "true"),
+        (Op_bi_lt, ~["'B'", "'a'"], "true"),
+        (Op_bi_lt, ~["'10'", "'9'"], "true"),
+        (Op_bi_lt, ~["'10'", "9"], "false"),
+        (Op_bi_lt, ~["null", "1"], "true"),
+        (Op_bi_lt, ~["undefined", "1"], "false"),
+        (Op_bi_lt, ~["[]", "1"], "true"),
+        (Op_bi_gt, ~["[2]", "1"], "true"),
+        (Op_bi_gt, ~[three, "'2'"], "true"),
+        (Op_bi_gt, ~["({})", "({})"], "false"),
+        (Op_bi_gte, ~["null", "0"], "true"),
+        (Op_bi_gte, ~["undefined", "undefined"], "false"),
+        (Op_bi_lte, ~["0/0", "0/0"], "false"),
+        (Op_bi_lte, ~["'b'", "'b'"], "true"),
+        (Op_bi_lte, ~[boom, "1"], "throws boom"),
+        // equality
+        (Op_bi_eq, ~["'1'", "1"], "false"),
+        (Op_bi_eq, ~["0", "-0"], "true"),
+        (Op_bi_eq, ~["0/0", "0/0"], "false"),
+        (Op_bi_loose_eq, ~["null", "undefined"], "true"),
+        (Op_bi_loose_eq, ~["null", "0"], "false"),
+        (Op_bi_loose_eq, ~["''", "0"], "true"),
+        (Op_bi_loose_eq, ~["'0'", "false"], "true"),
+        (Op_bi_loose_eq, ~["[]", "false"], "true"),
+        (Op_bi_loose_eq, ~["[2]", "'2'"], "true"),
+        (Op_bi_loose_eq, ~[three, "3"], "true"),
+        (Op_bi_loose_eq, ~[nullproto, "null"], "false"),
+        (Op_bi_loose_eq, ~[nullproto, "'x'"], "throws TypeError"),
+        (Op_bi_loose_eq, ~[boom, "1"], "throws boom"),
+        // the rest
+        (Op_un_not, ~["''"], "true"),
+        (Op_un_not, ~["[]"], "false"),
+        (Op_un_not, ~["0/0"], "true"),
+        (Op_un_typeof, ~["null"], "'object'"),
+        (Op_un_typeof, ~["[]"], "'object'"),
+        (Op_un_typeof, ~["(function() {})"], "'function'"),
+        (Op_un_typeof, ~["''"], "'string'"),
+        (Op_un_void, ~["1"], "undefined"),
+        (Op_bi_in, ~["'length'", "[]"], "true"),
+        (Op_bi_in, ~["'a'", "'abc'"], "throws TypeError"),
+        (Op_bi_instanceof, ~["[]", "Array"], "true"),
+        (Op_bi_instanceof, ~["1", "Array"], "false"),
+        (Op_bi_instanceof, ~["[]", "({})"], "throws TypeError")
+    ];
+    for cases.each |&(op, ref srcs, expected)| {
+        let args = srcs.map(|src| i.repl(*src));
+        let rv = describe(&i, apply(&i, frame, op, args));
+        if rv != expected.to_owned() {
+            fail!(fmt!("%? on %? gave %s, expected %s", op, srcs, rv, expected));
+        }
+    }
+}
+
+#[test]
+fn test_operand_order() {
+    // comparisons convert their operands left to right
+    let i = Interpreter::new();
+    let frame = i.env.make_top_level_frame(JsNull, ~[]);
+    i.repl("var log = ''; var a = {valueOf: function() { log += 'a'; return 1; }}; " +
+           "var b = {valueOf: function() { log += 'b'; return 2; }};");
+    let ops = ~[Op_bi_lt, Op_bi_lte, Op_bi_gt, Op_bi_gte, Op_bi_sub, Op_bi_add];
+    for ops.each |op| {
+        i.repl("log = '';");
+        apply(&i, frame, *op, [i.repl("a"), i.repl("b")]);
+        assert_eq!(i.repl("log").to_str(), ~"ab");
+    }
+}
@@ -0,0 +1,337 @@
+// the console object, and a structural printer for JavaScript values
+// (in the style of node's util.inspect) which the REPL shares.
+use core::hashmap::HashMap;
+
+use interp::Environment;
+use intern::{intern, intern_get};
+use object::*;
+use collections;
+use date;
+use numconv;
+use regexp;
+
+// how deeply nested objects are printed before eliding them
+pub static DEFAULT_DEPTH: uint = 2;
+
+// how the REPL (or an embedder) prints result values
+pub struct InspectOptions {
+    // print values structurally, or just convert them to strings
+    structured: bool,
+    depth: uint
+}
+impl InspectOptions {
+    pub fn new() -> InspectOptions {
+        InspectOptions { structured: true, depth: DEFAULT_DEPTH }
+    }
+}
+
+priv fn is_identifier(s: &str) -> bool {
+    if s.is_empty() { return false; }
+    for s.each_chari |i, c| {
+        let ok = (c >= 'a' && c <= 'z') || (c >= 'A' && c <= 'Z') ||
+            c == '_' || c == '$' || (i > 0 && c >= '0' && c <= '9');
+        if !ok { return false; }
+    }
+    true
+}
+
+// a single-quoted string literal
+pub fn quote(s: &str) -> ~str {
+    let mut out = ~"'";
+    for s.each_char |c| {
+        match c {
+            '\'' => out.push_str("\\'"),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            _ => out.push_char(c)
+        }
+    }
+    out.push_char('\'');
+    out
+}
+
+// the name property which Op_new_function sets
+priv fn function_name(env: Environment, val: JsVal) -> Option<~str> {
+    match env.get_slot(val, JsVal::from_str("name")) {
+        JsString(s) if !s.is_empty() => Some(str::from_utf16(s)),
+        _ => None
+    }
+}
+
+priv struct Inspector {
+    env: Environment,
+    // the objects we're inside of, for cycle detection
+    stack: ~[@mut Object]
+}
+
+impl Inspector {
+    fn inspect(&mut self, val: JsVal, depth: uint, nested: bool) -> ~str {
+        match val {
+            JsString(s) if nested => quote(str::from_utf16(s)),
+            JsNumber(n) if numconv::is_negative_zero(n) => ~"-0",
+            JsObject(obj) => self.inspect_object(obj, depth),
+            _ => val.to_str()
+        }
+    }
+
+    fn inspect_object(&mut self, obj: @mut Object, depth: uint) -> ~str {
+        let env = self.env;
+        let val = JsObject(obj);
+        if env.isCallable(val) {
+            return match function_name(env, val) {
+                Some(name) => fmt!("[Function: %s]", name),
+                None => ~"[Function]"
+            };
+        }
+        match date::time_value(val) {
+            Some(t) if t.is_finite() => { return date::to_iso_string(t); },
+            Some(_) => { return ~"Invalid Date"; },
+            None => {}
+        }
+        match regexp::get_regex(env, val) {
+            Some(_) => { return env.toString(val); },
+            None => {}
+        }
+        for self.stack.each |o| {
+            if ptr::ref_eq(*o, obj) { return ~"[Circular]"; }
+        }
+        let is_array = env.isArray(val);
+        let collection = collections::get_collection(env, val);
+        if depth == 0 {
+            return match collection {
+                Some(c) if c.is_set => ~"[Set]",
+                Some(_) => ~"[Map]",
+                None if is_array => ~"[Array]",
+                None => ~"[Object]"
+            };
+        }
+        self.stack.push(obj);
+        let mut parts : ~[~str] = ~[];
+        // collection entries come first
+        match collection {
+            Some(c) => {
+                for uint::range(0, c.capacity()) |i| {
+                    match c.entry(i) {
+                        Some((_, v)) if c.is_set => {
+                            parts.push(self.inspect(v, depth - 1, true));
+                        },
+                        Some((k, v)) => {
+                            let ks = self.inspect(k, depth - 1, true);
+                            parts.push(fmt!("%s => %s", ks,
+                                            self.inspect(v, depth - 1, true)));
+                        },
+                        None => {}
+                    }
+                }
+            },
+            None => {}
+        }
+        // then array elements, with runs of holes collapsed
+        let len = if is_array { obj.get(env.fdLength).to_uint().get_or_default(0) } else { 0 };
+        let mut holes = 0u;
+        let flush_holes = |parts: &mut ~[~str], holes: &mut uint| {
+            if *holes > 0 {
+                parts.push(fmt!("<%u empty item%s>", *holes,
+                                if *holes == 1 { "" } else { "s" }));
+                *holes = 0;
+            }
+        };
+        for uint::range(0, len) |i| {
+            let desc = FieldDesc { name: intern(i.to_str()), hidden: false };
+            match obj.get_simple(desc) {
+                Some(v) => {
+                    flush_holes(&mut parts, &mut holes);
+                    parts.push(self.inspect(v, depth - 1, true));
+                },
+                None => { holes += 1; }
+            }
+        }
+        flush_holes(&mut parts, &mut holes);
+        // and finally the remaining properties
+        for env.ownKeys(obj).each |name| {
+            let key = intern_get(*name);
+            if is_array && match uint::from_str(key) { Some(n) => n < len, None => false } {
+                loop;
+            }
+            if is_array && key == ~"length" { loop; }
+            if collection.is_some() && key == ~"size" { loop; }
+            let v = obj.get(FieldDesc { name: *name, hidden: false });
+            let k = if is_identifier(key) { key } else { quote(key) };
+            parts.push(fmt!("%s: %s", k, self.inspect(v, depth - 1, true)));
+        }
+        self.stack.pop();
+        let (open, close) = if is_array { ("[", "]") } else { ("{", "}") };
+        let prefix = match collection {
+            Some(c) => fmt!("%s(%u) ", if c.is_set { "Set" } else { "Map" }, c.len()),
+            None => ~""
+        };
+        if parts.is_empty() {
+            fmt!("%s%s%s", prefix, open, close)
+        } else {
+            fmt!("%s%s %s %s", prefix, open, str::connect(parts, ", "), close)
+        }
+    }
+}
+
+// the structural representation of a value, as console.dir and the
+// REPL print it.
+pub fn inspect(env: Environment, val: JsVal, depth: uint) -> ~str {
+    let mut i = Inspector { env: env, stack: ~[] };
+    i.inspect(val, depth, true)
+}
+
+// print a value according to the given options
+pub fn inspect_with(env: Environment, val: JsVal, opts: &InspectOptions) -> ~str {
+    if opts.structured {
+        inspect(env, val, opts.depth)
+    } else {
+        env.toString(val)
+    }
+}
+
+priv fn inspect_arg(env: Environment, val: JsVal) -> ~str {
+    let mut i = Inspector { env: env, stack: ~[] };
+    i.inspect(val, DEFAULT_DEPTH, false)
+}
+
+// console.log's argument formatting: printf-style specifiers in a
+// leading string, then the rest of the arguments separated by spaces.
+pub fn format_args(env: Environment, args: &[JsVal]) -> ~str {
+    let mut parts : ~[~str] = ~[];
+    let mut next = 0u;
+    match if args.is_empty() { JsUndefined } else { args[0] } {
+        JsString(fmt) => {
+            next = 1;
+            let fmt = str::from_utf16(fmt);
+            let chars = str::to_chars(fmt);
+            let mut out = ~"";
+            let mut i = 0u;
+            while i < chars.len() {
+                if chars[i] != '%' || i + 1 >= chars.len() {
+                    out.push_char(chars[i]);
+                    i += 1;
+                    loop;
+                }
+                let spec = chars[i + 1];
+                if spec == '%' {
+                    out.push_char('%');
+                } else if next >= args.len() ||
+                    !(spec == 's' || spec == 'd' || spec == 'i' ||
+                      spec == 'f' || spec == 'o' || spec == 'O') {
+                    // leave unknown or unsatisfied specifiers alone
+                    out.push_char('%');
+                    out.push_char(spec);
+                } else {
+                    let arg = args[next];
+                    next += 1;
+                    out.push_str(match spec {
+                        's' => match arg {
+                            JsObject(_) => inspect(env, arg, DEFAULT_DEPTH),
+                            _ => arg.to_str()
+                        },
+                        // objects aren't converted, so no user code runs
+                        'd' | 'i' => match arg {
+                            JsObject(_) => ~"NaN",
+                            _ => match env.tryToInteger(arg) {
+                                Ok(n) => JsNumber(n).to_str(),
+                                Err(_) => ~"NaN"
+                            }
+                        },
+                        'f' => match arg {
+                            JsObject(_) => ~"NaN",
+                            _ => match env.tryToNumber(arg) {
+                                Ok(n) => JsNumber(n).to_str(),
+                                Err(_) => ~"NaN"
+                            }
+                        },
+                        _ => inspect(env, arg, DEFAULT_DEPTH)
+                    });
+                }
+                i += 2;
+            }
+            parts.push(out);
+        },
+        _ => {}
+    }
+    for uint::range(next, args.len()) |i| {
+        parts.push(inspect_arg(env, args[i]));
+    }
+    str::connect(parts, " ")
+}
+
+priv fn print(is_error: bool, s: &str) {
+    if is_error {
+        io::stderr().write_line(s);
+    } else {
+        io::stdout().write_line(s);
+    }
+}
+
+pub fn make_console(env: Environment, frame: @mut Object) -> @mut Object {
+    let myConsole = Object::create(env.root_map, env.myObject);
+    let add_printer: @fn(&str, bool) = |name, is_error| {
+        do env.add_native_func_str(frame, myConsole, name) |_this, args| {
+            print(is_error, format_args(env, args));
+            JsUndefined
+        };
+    };
+    add_printer("log", false);
+    add_printer("info", false);
+    add_printer("debug", false);
+    add_printer("warn", true);
+    add_printer("error", true);
+    do env.add_native_func_str(frame, myConsole, "dir") |_this, args| {
+        print(false, inspect(env, getarg(args, 0), DEFAULT_DEPTH));
+        JsUndefined
+    };
+    do env.add_native_func_str(frame, myConsole, "assert") |_this, args| {
+        if !env.toBoolean(getarg(args, 0)) {
+            if args.len() > 1 {
+                print(true, ~"Assertion failed: " +
+                      format_args(env, args.slice(1, args.len())));
+            } else {
+                print(true, "Assertion failed");
+            }
+        }
+        JsUndefined
+    };
+    do env.add_native_func_str(frame, myConsole, "trace") |_this, args| {
+        let msg = format_args(env, args);
+        print(true, if msg.is_empty() { ~"Trace" } else { ~"Trace: " + msg });
+        for env.stackTrace().each |line| {
+            print(true, ~"    at " + *line);
+        }
+        JsUndefined
+    };
+    // timers, by label
+    let timers : @mut HashMap<~str, f64> = @mut HashMap::new();
+    let label: @fn(&[JsVal]) -> ~str = |args| {
+        match getarg(args, 0) {
+            JsUndefined => ~"default",
+            l => env.toString(l)
+        }
+    };
+    do env.add_native_func_str(frame, myConsole, "time") |_this, args| {
+        let l = label(args);
+        if timers.contains_key(&l) {
+            print(true, fmt!("Timer '%s' already exists", l));
+        } else {
+            timers.insert(l, (env.clock.now)());
+        }
+        JsUndefined
+    };
+    do env.add_native_func_str(frame, myConsole, "timeEnd") |_this, args| {
+        let l = label(args);
+        match timers.pop(&l) {
+            Some(start) => {
+                let elapsed = (env.clock.now)() - start;
+                print(false, fmt!("%s: %sms", l, JsNumber(elapsed).to_str()));
+            },
+            None => print(true, fmt!("Timer '%s' does not exist", l))
+        }
+        JsUndefined
+    };
+    myConsole
+}
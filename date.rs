@@ -0,0 +1,656 @@
+// Date objects (ES5 section 15.9)
+//
+// Time values are milliseconds since the epoch, UTC, stored as an f64 in
+// a hidden field of the date object.  The wall clock and the local time
+// zone come from the Environment's Clock, so tests can freeze time.
+use std::time;
+
+use interp::Environment;
+use intern::intern;
+use object::*;
+
+pub struct Clock {
+    // the current time value
+    now: @fn() -> f64,
+    // local time minus UTC, in milliseconds, at the given time value
+    local_offset: @fn(f64) -> f64
+}
+
+impl Clock {
+    pub fn system() -> Clock {
+        Clock {
+            now: || {
+                let ts = time::get_time();
+                (ts.sec as f64) * 1000f64 + ((ts.nsec / 1000000) as f64)
+            },
+            local_offset: |t| {
+                if !t.is_finite() || f64::abs(t) > MAX_TIME {
+                    0f64
+                } else {
+                    let secs = f64::floor(t / 1000f64) as i64;
+                    let tm = time::at(time::Timespec::new(secs, 0));
+                    (tm.tm_gmtoff as f64) * MS_PER_SECOND
+                }
+            }
+        }
+    }
+
+    // a clock stopped at the given time value, in a time zone which is
+    // a constant number of minutes ahead of UTC.
+    pub fn fixed(now: f64, offset_minutes: f64) -> Clock {
+        Clock {
+            now: || now,
+            local_offset: |_t| offset_minutes * MS_PER_MINUTE
+        }
+    }
+}
+
+priv static MS_PER_SECOND: f64 = 1000f64;
+priv static MS_PER_MINUTE: f64 = 60000f64;
+priv static MS_PER_HOUR: f64 = 3600000f64;
+priv static MS_PER_DAY: f64 = 86400000f64;
+priv static MAX_TIME: f64 = 8.64e15;
+
+priv static DAY_NAMES: [&'static str, ..7] =
+    ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+priv static MONTH_NAMES: [&'static str, ..12] =
+    ["Jan", "Feb", "Mar", "Apr", "May", "Jun",
+     "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"];
+// days before the start of each month, in a common year
+priv static MONTH_START: [f64, ..13] =
+    [0f64, 31f64, 59f64, 90f64, 120f64, 151f64, 181f64,
+     212f64, 243f64, 273f64, 304f64, 334f64, 365f64];
+
+// the time value arithmetic of ES5 15.9.1
+
+priv fn pmod(a: f64, b: f64) -> f64 {
+    let r = a % b;
+    if r < 0f64 { r + b } else { r }
+}
+
+priv fn integer(x: f64) -> f64 {
+    if x < 0f64 { -f64::floor(-x) } else { f64::floor(x) }
+}
+
+priv fn day(t: f64) -> f64 { f64::floor(t / MS_PER_DAY) }
+
+priv fn day_from_year(y: f64) -> f64 {
+    365f64 * (y - 1970f64) + f64::floor((y - 1969f64) / 4f64)
+        - f64::floor((y - 1901f64) / 100f64)
+        + f64::floor((y - 1601f64) / 400f64)
+}
+
+priv fn is_leap(y: f64) -> bool {
+    (y % 4f64 == 0f64) && (y % 100f64 != 0f64 || y % 400f64 == 0f64)
+}
+
+priv fn year_from_time(t: f64) -> f64 {
+    let d = day(t);
+    let mut y = f64::floor(d / 365.2425f64) + 1970f64;
+    while day_from_year(y) > d { y -= 1f64; }
+    while day_from_year(y + 1f64) <= d { y += 1f64; }
+    y
+}
+
+// the first day of the given month (0-11) of the given year
+priv fn month_start(y: f64, m: uint) -> f64 {
+    MONTH_START[m] + if m >= 2 && is_leap(y) { 1f64 } else { 0f64 }
+}
+
+priv fn month_from_time(t: f64) -> f64 {
+    let y = year_from_time(t);
+    let d = day(t) - day_from_year(y);
+    let mut m = 0u;
+    while m < 11 && d >= month_start(y, m + 1) { m += 1; }
+    m as f64
+}
+
+priv fn date_from_time(t: f64) -> f64 {
+    let y = year_from_time(t);
+    let d = day(t) - day_from_year(y);
+    d - month_start(y, month_from_time(t) as uint) + 1f64
+}
+
+priv fn week_day(t: f64) -> f64 { pmod(day(t) + 4f64, 7f64) }
+
+priv fn hour_from_time(t: f64) -> f64 {
+    pmod(f64::floor(t / MS_PER_HOUR), 24f64)
+}
+priv fn min_from_time(t: f64) -> f64 {
+    pmod(f64::floor(t / MS_PER_MINUTE), 60f64)
+}
+priv fn sec_from_time(t: f64) -> f64 {
+    pmod(f64::floor(t / MS_PER_SECOND), 60f64)
+}
+priv fn ms_from_time(t: f64) -> f64 { pmod(t, MS_PER_SECOND) }
+
+pub fn make_time(h: f64, m: f64, s: f64, ms: f64) -> f64 {
+    if !h.is_finite() || !m.is_finite() || !s.is_finite() || !ms.is_finite() {
+        return f64::NaN;
+    }
+    integer(h) * MS_PER_HOUR + integer(m) * MS_PER_MINUTE +
+        integer(s) * MS_PER_SECOND + integer(ms)
+}
+
+pub fn make_day(year: f64, month: f64, date: f64) -> f64 {
+    if !year.is_finite() || !month.is_finite() || !date.is_finite() {
+        return f64::NaN;
+    }
+    let ym = integer(year) + f64::floor(integer(month) / 12f64);
+    if f64::abs(ym) > 400000f64 { return f64::NaN; }
+    let mn = pmod(integer(month), 12f64) as uint;
+    day_from_year(ym) + month_start(ym, mn) + integer(date) - 1f64
+}
+
+pub fn make_date(day: f64, time: f64) -> f64 {
+    if !day.is_finite() || !time.is_finite() { return f64::NaN; }
+    day * MS_PER_DAY + time
+}
+
+pub fn time_clip(t: f64) -> f64 {
+    if !t.is_finite() || f64::abs(t) > MAX_TIME { return f64::NaN; }
+    integer(t) + 0f64 // convert -0 to +0
+}
+
+priv fn local_time(env: Environment, t: f64) -> f64 {
+    t + (env.clock.local_offset)(t)
+}
+
+priv fn utc(env: Environment, t: f64) -> f64 {
+    let offset = env.clock.local_offset;
+    t - offset(t - offset(t))
+}
+
+// the fields of a time value: year, month, date, hours, minutes,
+// seconds, milliseconds.
+priv fn fields(t: f64) -> ~[f64] {
+    ~[year_from_time(t), month_from_time(t), date_from_time(t),
+      hour_from_time(t), min_from_time(t), sec_from_time(t),
+      ms_from_time(t)]
+}
+
+priv fn from_fields(f: &[f64]) -> f64 {
+    make_date(make_day(f[0], f[1], f[2]), make_time(f[3], f[4], f[5], f[6]))
+}
+
+// string conversions
+
+priv fn pad(n: f64, width: uint) -> ~str {
+    let s = (n as int).to_str();
+    str::from_chars(vec::from_elem(width - uint::min(width, s.len()), '0')) + s
+}
+
+priv fn year_str(y: f64) -> ~str {
+    if y < 0f64 { ~"-" + pad(-y, 4) } else { pad(y, 4) }
+}
+
+priv fn offset_str(offset: f64) -> ~str {
+    let minutes = f64::abs(offset) / MS_PER_MINUTE;
+    fmt!("GMT%s%s%s", if offset < 0f64 { "-" } else { "+" },
+         pad(f64::floor(minutes / 60f64), 2), pad(minutes % 60f64, 2))
+}
+
+priv fn date_str(t: f64) -> ~str {
+    fmt!("%s %s %s %s", DAY_NAMES[week_day(t) as uint],
+         MONTH_NAMES[month_from_time(t) as uint],
+         pad(date_from_time(t), 2), year_str(year_from_time(t)))
+}
+
+priv fn time_str(t: f64) -> ~str {
+    fmt!("%s:%s:%s", pad(hour_from_time(t), 2), pad(min_from_time(t), 2),
+         pad(sec_from_time(t), 2))
+}
+
+pub fn to_string(env: Environment, t: f64) -> ~str {
+    if t.is_NaN() { return ~"Invalid Date"; }
+    let lt = local_time(env, t);
+    fmt!("%s %s %s", date_str(lt), time_str(lt), offset_str(lt - t))
+}
+
+pub fn to_utc_string(t: f64) -> ~str {
+    if t.is_NaN() { return ~"Invalid Date"; }
+    fmt!("%s, %s %s %s %s GMT", DAY_NAMES[week_day(t) as uint],
+         pad(date_from_time(t), 2), MONTH_NAMES[month_from_time(t) as uint],
+         year_str(year_from_time(t)), time_str(t))
+}
+
+// the format of ES5 15.9.1.15; the caller checks that t is finite.
+pub fn to_iso_string(t: f64) -> ~str {
+    let y = year_from_time(t);
+    let year = if y >= 0f64 && y <= 9999f64 {
+        pad(y, 4)
+    } else {
+        (if y < 0f64 { ~"-" } else { ~"+" }) + pad(f64::abs(y), 6)
+    };
+    fmt!("%s-%s-%sT%s.%sZ", year, pad(month_from_time(t) + 1f64, 2),
+         pad(date_from_time(t), 2), time_str(t), pad(ms_from_time(t), 3))
+}
+
+// parsing
+
+priv struct Scanner {
+    s: ~[u16],
+    pos: uint
+}
+
+impl Scanner {
+    fn peek(&self) -> Option<char> {
+        if self.pos < self.s.len() { Some(self.s[self.pos] as char) } else { None }
+    }
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) { self.pos += 1; true } else { false }
+    }
+    // exactly n decimal digits
+    fn digits(&mut self, n: uint) -> Option<f64> {
+        let mut v = 0f64;
+        for n.times {
+            match self.peek() {
+                Some(c @ '0'..'9') => {
+                    v = v * 10f64 + ((c as uint) - ('0' as uint)) as f64;
+                    self.pos += 1;
+                },
+                _ => { return None; }
+            }
+        }
+        Some(v)
+    }
+    // one or more decimal digits
+    fn number(&mut self) -> Option<f64> {
+        let start = self.pos;
+        let mut v = 0f64;
+        loop {
+            match self.peek() {
+                Some(c @ '0'..'9') => {
+                    v = v * 10f64 + ((c as uint) - ('0' as uint)) as f64;
+                    self.pos += 1;
+                },
+                _ => break
+            }
+        }
+        if self.pos == start { None } else { Some(v) }
+    }
+    fn word(&mut self) -> ~str {
+        let start = self.pos;
+        loop {
+            match self.peek() {
+                Some(c) if (c >= 'a' && c <= 'z') || (c >= 'A' && c <= 'Z') => {
+                    self.pos += 1;
+                },
+                _ => break
+            }
+        }
+        str::from_utf16(self.s.slice(start, self.pos))
+    }
+    fn spaces(&mut self) {
+        while self.eat(' ') {}
+    }
+    fn at_end(&self) -> bool { self.pos == self.s.len() }
+}
+
+// the ES5 date time string format: YYYY[-MM[-DD]][THH:mm[:ss[.sss]]][Z|(+|-)HH:mm]
+// with optional six-digit signed years.  As in ES2016, date-only forms
+// are UTC and date-time forms without an offset are local time.
+priv fn parse_iso(env: Environment, s: &[u16]) -> Option<f64> {
+    let mut p = Scanner { s: s.to_owned(), pos: 0 };
+    let year = match p.peek() {
+        Some('+') | Some('-') => {
+            let sign = if p.eat('-') { -1f64 } else { p.pos += 1; 1f64 };
+            match p.digits(6) {
+                Some(y) if !(y == 0f64 && sign < 0f64) => sign * y,
+                _ => { return None; }
+            }
+        },
+        _ => match p.digits(4) { Some(y) => y, None => { return None; } }
+    };
+    let mut f = ~[year, 0f64, 1f64, 0f64, 0f64, 0f64, 0f64];
+    if p.eat('-') {
+        f[1] = match p.digits(2) { Some(m) => m - 1f64, None => { return None; } };
+        if p.eat('-') {
+            f[2] = match p.digits(2) { Some(d) => d, None => { return None; } };
+        }
+    }
+    let mut local = false;
+    let mut offset = 0f64;
+    if p.eat('T') {
+        local = true;
+        f[3] = match p.digits(2) { Some(h) => h, None => { return None; } };
+        if !p.eat(':') { return None; }
+        f[4] = match p.digits(2) { Some(m) => m, None => { return None; } };
+        if p.eat(':') {
+            f[5] = match p.digits(2) { Some(s) => s, None => { return None; } };
+            if p.eat('.') {
+                // milliseconds; further digits are ignored
+                let start = p.pos;
+                let frac = match p.number() { Some(n) => n, None => { return None; } };
+                let n = p.pos - start;
+                f[6] = if n <= 3 {
+                    frac * f64::pow(10f64, (3 - n) as f64)
+                } else {
+                    f64::floor(frac / f64::pow(10f64, (n - 3) as f64))
+                };
+            }
+        }
+        match p.peek() {
+            Some('Z') => { p.pos += 1; local = false; },
+            Some('+') | Some('-') => {
+                let sign = if p.eat('-') { -1f64 } else { p.pos += 1; 1f64 };
+                let h = match p.digits(2) { Some(h) => h, None => { return None; } };
+                if !p.eat(':') { return None; }
+                let m = match p.digits(2) { Some(m) => m, None => { return None; } };
+                if h > 23f64 || m > 59f64 { return None; }
+                offset = sign * (h * MS_PER_HOUR + m * MS_PER_MINUTE);
+                local = false;
+            },
+            _ => {}
+        }
+    }
+    if !p.at_end() { return None; }
+    // range checks; 24:00 is allowed as the end of a day
+    if f[1] < 0f64 || f[1] > 11f64 || f[2] < 1f64 ||
+        f[2] > month_start(year, (f[1] as uint) + 1) - month_start(year, f[1] as uint) ||
+        f[3] > 24f64 || f[4] > 59f64 || f[5] > 59f64 ||
+        (f[3] == 24f64 && (f[4] != 0f64 || f[5] != 0f64 || f[6] != 0f64)) {
+        return None;
+    }
+    let t = from_fields(f);
+    Some(if local { utc(env, t) } else { t - offset })
+}
+
+// the formats produced by toString and toUTCString:
+//   Tue Oct 13 2026 14:03:05 GMT+0200
+//   Tue, 13 Oct 2026 12:03:05 GMT
+priv fn parse_string(s: &[u16]) -> Option<f64> {
+    let mut p = Scanner { s: s.to_owned(), pos: 0 };
+    let month_of = |w: &str| vec::position(MONTH_NAMES, |m| *m == w);
+    p.word(); // the day of the week
+    p.eat(',');
+    p.spaces();
+    let (month, date) = match p.number() {
+        Some(d) => {
+            p.spaces();
+            match month_of(p.word()) { Some(m) => (m, d), None => { return None; } }
+        },
+        None => {
+            let m = match month_of(p.word()) { Some(m) => m, None => { return None; } };
+            p.spaces();
+            match p.number() { Some(d) => (m, d), None => { return None; } }
+        }
+    };
+    p.spaces();
+    let sign = if p.eat('-') { -1f64 } else { 1f64 };
+    let year = match p.number() { Some(y) => sign * y, None => { return None; } };
+    p.spaces();
+    let mut f = ~[year, month as f64, date, 0f64, 0f64, 0f64, 0f64];
+    for uint::range(3, 6) |i| {
+        if i > 3 && !p.eat(':') { return None; }
+        f[i] = match p.digits(2) { Some(n) => n, None => { return None; } };
+    }
+    p.spaces();
+    if p.word() != ~"GMT" { return None; }
+    let offset = match p.peek() {
+        Some('+') | Some('-') => {
+            let sign = if p.eat('-') { -1f64 } else { p.pos += 1; 1f64 };
+            let h = match p.digits(2) { Some(h) => h, None => { return None; } };
+            let m = match p.digits(2) { Some(m) => m, None => { return None; } };
+            sign * (h * MS_PER_HOUR + m * MS_PER_MINUTE)
+        },
+        _ => 0f64
+    };
+    if !p.at_end() { return None; }
+    Some(from_fields(f) - offset)
+}
+
+// Date.parse
+pub fn parse(env: Environment, s: &[u16]) -> f64 {
+    let s = str::to_utf16(str::from_utf16(s).trim());
+    let t = match parse_iso(env, s) {
+        Some(t) => t,
+        None => match parse_string(s) {
+            Some(t) => t,
+            None => f64::NaN
+        }
+    };
+    time_clip(t)
+}
+
+// JavaScript bindings
+
+priv fn fdDate() -> FieldDesc { FieldDesc { name: intern("date"), hidden: true } }
+
+// the time value of a date object
+pub fn time_value(val: JsVal) -> Option<f64> {
+    match val {
+        JsObject(obj) => match obj.get_simple(fdDate()) {
+            Some(JsNumber(t)) => Some(t),
+            _ => None
+        },
+        _ => None
+    }
+}
+
+pub fn is_date(val: JsVal) -> bool { time_value(val).is_some() }
+
+priv fn set_time_value(val: JsVal, t: f64) -> JsVal {
+    match val {
+        JsObject(obj) => obj.set(fdDate(), JsNumber(t)),
+        _ => fail!()
+    }
+    JsNumber(t)
+}
+
+priv fn incompatible(env: Environment, name: &str) -> JsVal {
+    env.throwError("TypeError", fmt!("Date.prototype.%s called on incompatible receiver", name))
+}
+
+// Date.UTC and new Date(y, m, ...): the time value of the given fields
+priv fn time_from_args(env: Environment, args: &[JsVal]) -> Result<f64, JsVal> {
+    let mut f = ~[f64::NaN, f64::NaN, 1f64, 0f64, 0f64, 0f64, 0f64];
+    for uint::range(0, uint::min(args.len(), 7)) |i| {
+        match env.tryToNumber(args[i]) {
+            Ok(n) => { f[i] = n; },
+            Err(e) => { return Err(e); }
+        }
+    }
+    if !f[0].is_NaN() && integer(f[0]) >= 0f64 && integer(f[0]) <= 99f64 {
+        f[0] = 1900f64 + integer(f[0]);
+    }
+    Ok(from_fields(f))
+}
+
+pub fn make_date_cons(env: Environment, frame: @mut Object) -> @mut Object {
+    let myDate = Object::create(env.root_map, env.myObject);
+    let cons = Object::create(env.root_map, env.myFunction);
+    cons.set(FieldDesc { name: intern("prototype"), hidden: false },
+             JsObject(myDate));
+    cons.set(env.fdParentFrame, JsObject(frame));
+    cons.set(env.fdValue, JsNativeFunction(|this, args| {
+        // Function.prototype.New hands us a fresh object; anything else
+        // is a plain call, which returns the current time as a string.
+        let constructing = match this {
+            JsObject(obj) => !is_date(this) && match obj.get_simple(env.fdProto) {
+                Some(JsObject(p)) => ptr::ref_eq(p, myDate),
+                _ => false
+            },
+            _ => false
+        };
+        if !constructing {
+            return JsVal::from_str(to_string(env, (env.clock.now)()));
+        }
+        let t = match args.len() {
+            0 => (env.clock.now)(),
+            1 => match (args[0], time_value(args[0])) {
+                (_, Some(t)) => t,
+                (JsString(s), _) => parse(env, s),
+                (v, _) => time_clip(try_native!(env.tryToNumber(v)))
+            },
+            _ => time_clip(utc(env, try_native!(time_from_args(env, args))))
+        };
+        set_time_value(this, t);
+        this
+    }));
+
+    do env.add_native_func_str(frame, cons, "now") |_this, _args| {
+        JsNumber((env.clock.now)())
+    };
+    do env.add_native_func_str(frame, cons, "parse") |_this, args| {
+        JsNumber(parse(env, try_native!(env.tryToUtf16(getarg(args, 0)))))
+    };
+    do env.add_native_func_str(frame, cons, "UTC") |_this, args| {
+        JsNumber(time_clip(try_native!(time_from_args(env, args))))
+    };
+
+    // getters
+    let add_getter: @fn(&str, bool, @fn(f64) -> f64) = |name, local, f| {
+        let label = name.to_owned();
+        do env.add_native_func_str(frame, myDate, name) |this, _args| {
+            match time_value(this) {
+                None => incompatible(env, label),
+                Some(t) if t.is_NaN() => JsNumber(t),
+                Some(t) => JsNumber(f(if local { local_time(env, t) } else { t }))
+            }
+        };
+    };
+    add_getter("getTime", false, |t| t);
+    add_getter("valueOf", false, |t| t);
+    add_getter("getFullYear", true, |t| year_from_time(t));
+    add_getter("getUTCFullYear", false, |t| year_from_time(t));
+    add_getter("getMonth", true, |t| month_from_time(t));
+    add_getter("getUTCMonth", false, |t| month_from_time(t));
+    add_getter("getDate", true, |t| date_from_time(t));
+    add_getter("getUTCDate", false, |t| date_from_time(t));
+    add_getter("getDay", true, |t| week_day(t));
+    add_getter("getUTCDay", false, |t| week_day(t));
+    add_getter("getHours", true, |t| hour_from_time(t));
+    add_getter("getUTCHours", false, |t| hour_from_time(t));
+    add_getter("getMinutes", true, |t| min_from_time(t));
+    add_getter("getUTCMinutes", false, |t| min_from_time(t));
+    add_getter("getSeconds", true, |t| sec_from_time(t));
+    add_getter("getUTCSeconds", false, |t| sec_from_time(t));
+    add_getter("getMilliseconds", true, |t| ms_from_time(t));
+    add_getter("getUTCMilliseconds", false, |t| ms_from_time(t));
+    do env.add_native_func_str(frame, myDate, "getTimezoneOffset") |this, _args| {
+        match time_value(this) {
+            None => incompatible(env, "getTimezoneOffset"),
+            Some(t) if t.is_NaN() => JsNumber(t),
+            Some(t) => JsNumber((t - local_time(env, t)) / MS_PER_MINUTE)
+        }
+    };
+
+    // setters: each replaces up to `count` fields, starting at `first`
+    // (an index into the result of fields()), with its arguments.
+    let add_setter: @fn(&str, bool, uint, uint) = |name, local, first, count| {
+        let label = name.to_owned();
+        do env.add_native_func_str(frame, myDate, name) |this, args| {
+            match time_value(this) {
+                None => incompatible(env, label),
+                Some(t) => {
+                    // only setFullYear can revive an invalid date
+                    let t = if t.is_NaN() && first == 0 {
+                        0f64
+                    } else if local {
+                        local_time(env, t)
+                    } else {
+                        t
+                    };
+                    let mut f = fields(t);
+                    for uint::range(0, count) |i| {
+                        if i == 0 || i < args.len() {
+                            f[first + i] = try_native!(env.tryToNumber(getarg(args, i)));
+                        }
+                    }
+                    let mut nt = from_fields(f);
+                    if t.is_NaN() { nt = f64::NaN; }
+                    if local { nt = utc(env, nt); }
+                    set_time_value(this, time_clip(nt))
+                }
+            }
+        };
+    };
+    add_setter("setMilliseconds", true, 6, 1);
+    add_setter("setUTCMilliseconds", false, 6, 1);
+    add_setter("setSeconds", true, 5, 2);
+    add_setter("setUTCSeconds", false, 5, 2);
+    add_setter("setMinutes", true, 4, 3);
+    add_setter("setUTCMinutes", false, 4, 3);
+    add_setter("setHours", true, 3, 4);
+    add_setter("setUTCHours", false, 3, 4);
+    add_setter("setDate", true, 2, 1);
+    add_setter("setUTCDate", false, 2, 1);
+    add_setter("setMonth", true, 1, 2);
+    add_setter("setUTCMonth", false, 1, 2);
+    add_setter("setFullYear", true, 0, 3);
+    add_setter("setUTCFullYear", false, 0, 3);
+    do env.add_native_func_str(frame, myDate, "setTime") |this, args| {
+        match time_value(this) {
+            None => incompatible(env, "setTime"),
+            Some(_) => set_time_value(this, time_clip(try_native!(env.tryToNumber(getarg(args, 0)))))
+        }
+    };
+
+    // string conversions
+    let add_formatter: @fn(&str, @fn(f64) -> ~str) = |name, f| {
+        let label = name.to_owned();
+        do env.add_native_func_str(frame, myDate, name) |this, _args| {
+            match time_value(this) {
+                None => incompatible(env, label),
+                Some(t) if t.is_NaN() => JsVal::from_str("Invalid Date"),
+                Some(t) => JsVal::from_str(f(t))
+            }
+        };
+    };
+    add_formatter("toString", |t| to_string(env, t));
+    add_formatter("toLocaleString", |t| to_string(env, t));
+    add_formatter("toUTCString", |t| to_utc_string(t));
+    add_formatter("toDateString", |t| date_str(local_time(env, t)));
+    add_formatter("toLocaleDateString", |t| date_str(local_time(env, t)));
+    add_formatter("toTimeString", |t| {
+        let lt = local_time(env, t);
+        fmt!("%s %s", time_str(lt), offset_str(lt - t))
+    });
+    add_formatter("toLocaleTimeString", |t| time_str(local_time(env, t)));
+    do env.add_native_func_str(frame, myDate, "toISOString") |this, _args| {
+        match time_value(this) {
+            None => incompatible(env, "toISOString"),
+            Some(t) if t.is_NaN() => env.throwError("RangeError", "Invalid time value"),
+            Some(t) => JsVal::from_str(to_iso_string(t))
+        }
+    };
+    do env.add_native_func_str(frame, myDate, "toJSON") |this, _args| {
+        match time_value(this) {
+            Some(t) if t.is_NaN() => JsNull,
+            Some(t) => JsVal::from_str(to_iso_string(t)),
+            None => {
+                let toISOString = env.get_slot(this, JsVal::from_str("toISOString"));
+                if env.isCallable(toISOString) {
+                    env.interpret_function(toISOString, this, ~[])
+                } else {
+                    env.throwError("TypeError", "toISOString is not a function")
+                }
+            }
+        }
+    };
+    cons
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_day_arithmetic() {
+        assert_eq!(make_day(1970f64, 0f64, 1f64), 0f64);
+        assert_eq!(make_day(2000f64, 2f64, 1f64), 11017f64);
+        // months outside 0-11 carry into the year
+        assert_eq!(make_day(1999f64, 14f64, 1f64), 11017f64);
+        assert_eq!(make_day(1969f64, 11f64, 31f64), -1f64);
+        let t = make_date(make_day(2024f64, 1f64, 29f64),
+                          make_time(23f64, 59f64, 59f64, 999f64));
+        assert_eq!(to_iso_string(t), ~"2024-02-29T23:59:59.999Z");
+        assert_eq!(to_utc_string(t), ~"Thu, 29 Feb 2024 23:59:59 GMT");
+        assert_eq!(to_iso_string(-1f64), ~"1969-12-31T23:59:59.999Z");
+        assert_eq!(to_iso_string(-62198755200000f64), ~"-000001-01-01T00:00:00.000Z");
+        assert_eq!(to_iso_string(8.64e15), ~"+275760-09-13T00:00:00.000Z");
+        assert!(time_clip(8.64e15 + 1f64).is_NaN());
+        assert!(make_time(f64::NaN, 0f64, 0f64, 0f64).is_NaN());
+    }
+}
@@ -0,0 +1,186 @@
+// a bytecode disassembler, for looking at what the compiler made of
+// something.  Each instruction is listed with a comment giving its pc,
+// the depth of the stack before it (or '-' if no path reaches it) and
+// the literal or function its operand refers to.
+use function::Function;
+use json;
+use module::Module;
+use numconv;
+use object::{JsVal,JsNumber,JsString,JsBool,JsNull,JsUndefined};
+use op::*;
+
+priv fn quote(s: &[u16]) -> ~str {
+    let mut out : ~[u16] = ~[];
+    json::quote(&mut out, s);
+    str::from_utf16(out)
+}
+
+// a literal as it appears in a listing
+pub fn literal_str(val: JsVal) -> ~str {
+    match val {
+        JsNumber(n) if numconv::is_negative_zero(n) => ~"-0",
+        JsNumber(n) => numconv::number_to_str(n),
+        JsString(s) => quote(s),
+        JsBool(true) => ~"true",
+        JsBool(false) => ~"false",
+        JsNull => ~"null",
+        JsUndefined => ~"undefined",
+        _ => fail!(~"not a literal")
+    }
+}
+
+// the depth of the stack before each instruction we can reach without
+// the stack going wrong
+priv fn stack_depths(f: &Function) -> ~[Option<uint>] {
+    let bytecode : &[uint] = f.bytecode;
+    let len = bytecode.len();
+    let mut depths = vec::from_elem(len, None);
+    let mut work : ~[(uint, uint)] = ~[(0, 0)];
+    while !work.is_empty() {
+        let (pc, depth) = work.pop();
+        if pc >= len || depths[pc].is_some() || !is_op(bytecode[pc]) { loop; }
+        let op = Op::new_from_uint(bytecode[pc]);
+        let next = pc + 1 + op.args();
+        if next > len { loop; }
+        depths[pc] = Some(depth);
+        let arg = if op.args() > 0 { bytecode[pc + 1] } else { 0 };
+        let pop = match op {
+            Op_invoke if arg < depth => arg + 2,
+            Op_invoke => depth + 1, // more than there are
+            _ => op.stackpop([])
+        };
+        if pop > depth { loop; }
+        let depth = depth - pop + op.stackpush();
+        match op {
+            Op_return => {},
+            Op_jmp => work.push((arg, depth)),
+            Op_jmp_unless => {
+                work.push((arg, depth));
+                work.push((next, depth));
+            },
+            _ => work.push((next, depth))
+        }
+    }
+    depths
+}
+
+priv fn disassemble_function(module: &Module, f: &Function, out: &mut ~str) {
+    let bytecode : &[uint] = f.bytecode;
+    let len = bytecode.len();
+    let depths = stack_depths(f);
+    // find the instructions, and label those we jump to
+    let mut starts = vec::from_elem(len, false);
+    let mut labels = vec::from_elem(len, false);
+    let mut pc = 0;
+    while pc < len && is_op(bytecode[pc]) {
+        starts[pc] = true;
+        let op = Op::new_from_uint(bytecode[pc]);
+        if pc + 1 + op.args() > len { break; }
+        match op {
+            Op_jmp | Op_jmp_unless if bytecode[pc + 1] < len => {
+                labels[bytecode[pc + 1]] = true;
+            },
+            _ => {}
+        }
+        pc += 1 + op.args();
+    }
+    pc = 0;
+    while pc < len {
+        let depth = match depths[pc] {
+            Some(d) => d.to_str(),
+            None => ~"-"
+        };
+        if !starts[pc] || pc + 1 + Op::new_from_uint(bytecode[pc]).args() > len {
+            // not something we can decode; list the words themselves
+            out.push_str(fmt!("\t%u\t; %u [%s]\n", bytecode[pc], pc, depth));
+            pc += 1;
+            loop;
+        }
+        if labels[pc] { out.push_str(fmt!("L%u:\n", pc)); }
+        let op = Op::new_from_uint(bytecode[pc]);
+        let arg = if op.args() > 0 { bytecode[pc + 1] } else { 0 };
+        let operand = match op {
+            Op_jmp | Op_jmp_unless if arg < len && starts[arg] => fmt!(" L%u", arg),
+            _ if op.args() > 0 => fmt!(" %u", arg),
+            _ => ~""
+        };
+        let note = match op {
+            Op_push_literal | Op_get_slot_direct |
+            Op_get_slot_direct_check | Op_set_slot_direct
+                if arg < module.literals.len() =>
+                ~" " + literal_str(module.literals[arg]),
+            Op_new_function if arg < module.functions.len() =>
+                match module.functions[arg].name {
+                    // escaped as in a literal, but without the quotes
+                    Some(name) => {
+                        let q = quote(name);
+                        ~" " + q.slice(1, q.len() - 1)
+                    },
+                    None => ~""
+                },
+            _ => ~""
+        };
+        out.push_str(fmt!("\t%s%s\t; %u [%s]%s\n",
+                          op.name(), operand, pc, depth, note));
+        pc += 1 + op.args();
+    }
+}
+
+// a listing of the whole module: each function, then the literals
+pub fn disassemble(module: &Module) -> ~str {
+    let mut out = ~"";
+    for module.functions.eachi |id, f| {
+        out.push_str(fmt!("function %u", id));
+        match f.name {
+            Some(name) => out.push_str(~" " + quote(name)),
+            None => {}
+        }
+        out.push_str(fmt!(" nargs %u max_stack %u\n", f.nargs, f.max_stack));
+        disassemble_function(module, *f, &mut out);
+    }
+    out.push_str("literals\n");
+    for module.literals.eachi |i, l| {
+        out.push_str(fmt!("\t%s\t; %u\n", literal_str(*l), i));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use function::Function;
+    use module::Module;
+    use object::{JsVal,JsNumber,JsBool};
+
+    #[test]
+    fn test_disassemble() {
+        let module = Module {
+            functions: ~[
+                @Function { name: None, id: 0, nargs: 0, max_stack: 1,
+                            source: None,
+                            bytecode: ~[4, 1, 13, 7, 1, 1, 11, 1, 2, 11] },
+                @Function { name: Some(@['f' as u16]), id: 1, nargs: 1, max_stack: 1,
+                            source: None, bytecode: ~[1, 0, 11, 14, 99] }
+            ],
+            literals: ~[JsNumber(-0f64), JsVal::from_str(~"a\n"), JsBool(true)],
+            source: None
+        };
+        assert_eq!(disassemble(&module), ~"function 0 nargs 0 max_stack 1\n" +
+                   "\tnew_function 1\t; 0 [0] f\n" +
+                   "\tjmp_unless L7\t; 2 [1]\n" +
+                   "\tpush_literal 1\t; 4 [0] \"a\\n\"\n" +
+                   "\treturn\t; 6 [1]\n" +
+                   "L7:\n" +
+                   "\tpush_literal 2\t; 7 [0] true\n" +
+                   "\treturn\t; 9 [1]\n" +
+                   "function 1 \"f\" nargs 1 max_stack 1\n" +
+                   "\tpush_literal 0\t; 0 [0] -0\n" +
+                   "\treturn\t; 2 [1]\n" +
+                   "\tpop\t; 3 [-]\n" +
+                   "\t99\t; 4 [-]\n" +
+                   "literals\n" +
+                   "\t-0\t; 0\n" +
+                   "\t\"a\\n\"\t; 1\n" +
+                   "\ttrue\t; 2\n");
+    }
+}
@@ -1,9 +1,13 @@
 // function type.
 
 pub struct Function {
-    name: Option<~str>,
+    // UTF-16, since JavaScript names needn't be valid Unicode
+    name: Option<@[u16]>,
     id: uint,
     nargs: uint,
     max_stack: uint,
+    // the (start, end) of the function's text in its module's source,
+    // in UTF-16 code units
+    source: Option<(uint, uint)>,
     bytecode: ~[uint]
 }
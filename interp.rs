@@ -1,8 +1,26 @@
 use op::*;
 use function::Function;
-use module::Module;
+use module::{Module, BadModule, BadSpans};
 use object::*;
-use intern::{intern,intern_to_uint};
+use intern::{IString,intern,intern_get,intern_to_uint};
+use numconv;
+use json;
+use regexp;
+use date;
+use array;
+use collections;
+use console;
+use bcompile;
+use verify;
+use loader;
+use tokenize::SyntaxError;
+use date::Clock;
+
+// unwrap the Ok value of a fallible abstract operation, or return its
+// Err (a thrown exception)
+macro_rules! try_js(
+    ($e:expr) => (match $e { Ok(v) => v, Err(e) => { return Err(e); } })
+)
 
 struct State {
     // main interpreter state.
@@ -40,6 +58,11 @@ struct Environment {
     myTrue: @mut Object,
     myFalse: @mut Object,
     myMath: @mut Object,
+    // the wall clock and local time zone, for Date and now()
+    clock: @mut Clock,
+    // the state which invoked the running native function, if any, so
+    // natives like console.trace can walk the call chain.
+    native_caller: @mut *State,
     // usefull field descriptors
     fdProto: FieldDesc,
     fdType: FieldDesc,
@@ -102,6 +125,8 @@ impl Environment {
             myTrue: myTrue,
             myFalse: myFalse,
             myMath: myMath,
+            clock: @mut Clock::system(),
+            native_caller: @mut ptr::null(),
             fdProto: fdProto,
             fdType: fdType,
             fdValue: fdValue,
@@ -118,6 +143,11 @@ impl Environment {
         let my_func = Object::create(self.root_map, self.myFunction);
         my_func.set(self.fdParentFrame, JsObject(frame));
         my_func.set(self.fdValue, JsNativeFunction(f));
+        // named for its property, as Op_new_function names functions
+        if !desc.hidden {
+            my_func.set(FieldDesc { name: intern("name"), hidden: false },
+                        JsVal::from_str(intern_get(desc.name)));
+        }
         obj.set(desc, JsObject(my_func));
         my_func
     }
@@ -153,7 +183,7 @@ impl Environment {
         };
 
         let myObjectCons = mkConstructor("Object", self.myObject);
-        mkConstructor("Array", self.myArray);
+        let myArrayCons = mkConstructor("Array", self.myArray);
         mkConstructor("Function", self.myFunction);
         let myBooleanCons = mkConstructor("Boolean", self.myBoolean);
         let myStringCons = mkConstructor("String", self.myString);
@@ -161,11 +191,17 @@ impl Environment {
 
         frame.set(FieldDesc { name: intern("Math"), hidden: false },
                   JsObject(self.myMath));
-
-        // helper function
-        let getarg: @fn(&[JsVal], uint)->JsVal = |args, i| {
-            if args.len() > i { args[i] } else { JsUndefined }
-        };
+        frame.set(FieldDesc { name: intern("JSON"), hidden: false },
+                  JsObject(json::make_json(self, frame)));
+        frame.set(FieldDesc { name: intern("RegExp"), hidden: false },
+                  JsObject(regexp::make_regexp(self, frame)));
+        frame.set(FieldDesc { name: intern("Date"), hidden: false },
+                  JsObject(date::make_date_cons(self, frame)));
+        array::add_array_methods(self, frame, myArrayCons);
+        frame.set(FieldDesc { name: intern("Map"), hidden: false },
+                  JsObject(collections::make_map_cons(self, frame)));
+        frame.set(FieldDesc { name: intern("Set"), hidden: false },
+                  JsObject(collections::make_set_cons(self, frame)));
 
         // Boolean called as function
         myBooleanCons.set(self.fdParentFrame, JsObject(frame));
@@ -173,17 +209,10 @@ impl Environment {
             JsBool(self.toBoolean(getarg(args, 0)))
         }));
 
-        // support for console.log
-        let myConsole = Object::create(self.root_map, self.myObject);
         frame.set(FieldDesc { name: intern("console"), hidden: false },
-                  JsObject(myConsole));
+                  JsObject(console::make_console(self, frame)));
 
         // native functions
-        do self.add_native_func_str(frame, myConsole, "log") |_this, args| {
-            let sargs = do vec::map_consume(args) |val| { val.to_str() };
-            io::println(str::connect(sargs, " "));
-            JsUndefined
-        };
         let opts = do self.add_native_func_str(frame, self.myObject, "toString")
             |this, _args| {
             let _o = self.toObject(this);
@@ -207,7 +236,7 @@ impl Environment {
         };
         do self.add_native_func(frame, self.myObject, self.fdDefaultValue)
             |this, args| {
-            let isDate = false; // XXX fix when we support date objects
+            let isDate = date::is_date(this);
             let rawhint = match getarg(args, 0) {
                 JsString(utf16) => Some(str::from_utf16(utf16)),
                 _ => None
@@ -242,14 +271,15 @@ impl Environment {
                 }
             }
             match rv {
-                None => fail!("TypeError"), // XXX throw
+                None => self.throwError("TypeError",
+                                        "Cannot convert object to primitive value"),
                 Some(rv3) => rv3
             }
         };
         do self.add_native_func_str(frame, self.myObject, "hasOwnProperty")
             |this, args| {
             let prop = FieldDesc {
-                name: intern(self.toString(getarg(args, 0))),
+                name: intern(try_native!(self.tryToString(getarg(args, 0)))),
                 hidden: false
             };
             let rv = match(this) {
@@ -291,52 +321,27 @@ impl Environment {
             }
         };
         do self.add_native_func_str(frame, frame, "isNaN") |_this, args| {
-            JsBool(self.toNumber(getarg(args, 0)).is_NaN())
+            JsBool(try_native!(self.tryToNumber(getarg(args, 0))).is_NaN())
         };
         do self.add_native_func_str(frame, frame, "isFinite") |_this, args| {
-            JsBool(self.toNumber(getarg(args, 0)).is_finite())
+            JsBool(try_native!(self.tryToNumber(getarg(args, 0))).is_finite())
         };
         do self.add_native_func_str(frame, frame, "parseInt") |_this, args| {
-            let number = getarg(args, 0);
-            let radix = match getarg(args, 1) {
-                // falsy values become radix 10
-                JsBool(false) | JsString([]) | JsObject(_) |
-                JsUndefined | JsNull => 10u,
-                r => match self.toNumber(r) {
-                    n if !n.is_finite() => 10u,
-                    n if n<2f64 || n>=37f64 => 0, // aka bail
-                    n => n as uint
-                }
-            };
-            let rv = match number {
-                JsString(utf16) if radix!=0 => {
-                    // XXX what about numbers larger than 2^32?
-                    // XXX parseInt(' 10z ', 16) = 16, so we seem to trim
-                    //     non-digit chars from the right.
-                    let s = str::from_utf16(utf16);
-                    match int::from_str_radix(s.trim(), radix) {
-                        Some(n) => n as f64,
-                        None => f64::NaN
-                    }
-                },
-                JsNumber(n) if radix!=0 => {
-                    // this is weird, but seems to match EcmaScript
-                    match int::from_str_radix(n.to_str(), radix) {
-                        Some(n) => n as f64,
-                        None => f64::NaN
-                    }
-                },
-                _ => f64::NaN
-            };
-            JsNumber(rv)
+            let s = try_native!(self.tryToUtf16(getarg(args, 0)));
+            let radix = try_native!(self.tryToInt32(getarg(args, 1)));
+            JsNumber(numconv::parse_int(s, radix))
+        };
+        do self.add_native_func_str(frame, frame, "parseFloat") |_this, args| {
+            let s = try_native!(self.tryToUtf16(getarg(args, 0)));
+            JsNumber(numconv::parse_float(s))
         };
         do self.add_native_func_str(frame, frame, "now")
             |_this, _args| {
-            fail!("now() unimplemented");
+            JsNumber((self.clock.now)())
         };
         do self.add_native_func_str(frame, self.myString, "charAt")
             |this, args| {
-            let idx = match self.toNumber(getarg(args, 0)) {
+            let idx = match try_native!(self.tryToNumber(getarg(args, 0))) {
                 n if n.is_NaN() => 0i, // strange
                 n => n as int
             };
@@ -355,7 +360,7 @@ impl Environment {
         };
         do self.add_native_func_str(frame, self.myString, "charCodeAt")
             |this, args| {
-            let idx = match self.toNumber(getarg(args, 0)) {
+            let idx = match try_native!(self.tryToNumber(getarg(args, 0))) {
                 n if n.is_NaN() => 0i, // strange
                 n => n as int
             };
@@ -392,27 +397,75 @@ impl Environment {
         };
         do self.add_native_func_str(frame, self.myMath, "floor")
             |_this, args| {
-            JsNumber(self.toNumber(getarg(args, 0)).floor())
+            JsNumber(try_native!(self.tryToNumber(getarg(args, 0))).floor())
         };
         do self.add_native_func_str(frame, self.myNumber, "toString")
             |this, args| {
-            let n = match this {
-                JsNumber(n) => n,
-                _ => fail!("TypeError: Number.prototype.toString is not generic")
-            };
-            let radix = match getarg(args, 0) {
-                JsUndefined => 10u,
-                JsNumber(n) if n >= 2f64 && n <= 36f64 => n as uint,
-                // XXX should throw
-                _ => fail!("RangeError: toString() radix argument must be between 2 and 36")
-            };
-            let s = match n {
-                f64::infinity => ~"Infinity",
-                f64::neg_infinity => ~"-Infinity",
-                _ if n.is_NaN() => ~"NaN",
-                _ => f64::to_str_radix(n, radix)
-            };
-            JsVal::from_str(s)
+            match this {
+                JsNumber(n) => {
+                    let radix = match getarg(args, 0) {
+                        JsUndefined => 10f64,
+                        r => try_native!(self.tryToInteger(r))
+                    };
+                    if radix < 2f64 || radix > 36f64 {
+                        self.throwError("RangeError", "toString() radix argument must be between 2 and 36")
+                    } else {
+                        JsVal::from_str(numconv::number_to_str_radix(n, radix as uint))
+                    }
+                },
+                _ => self.throwError("TypeError", "Number.prototype.toString is not generic")
+            }
+        };
+        do self.add_native_func_str(frame, self.myNumber, "toFixed")
+            |this, args| {
+            match this {
+                JsNumber(n) => {
+                    let f = try_native!(self.tryToInteger(getarg(args, 0)));
+                    if f < 0f64 || f > 100f64 {
+                        self.throwError("RangeError", "toFixed() digits argument must be between 0 and 100")
+                    } else {
+                        JsVal::from_str(numconv::to_fixed(n, f as uint))
+                    }
+                },
+                _ => self.throwError("TypeError", "Number.prototype.toFixed is not generic")
+            }
+        };
+        do self.add_native_func_str(frame, self.myNumber, "toExponential")
+            |this, args| {
+            match this {
+                JsNumber(n) => {
+                    let fd = getarg(args, 0);
+                    let f = try_native!(self.tryToInteger(fd));
+                    if !n.is_finite() {
+                        JsVal::from_str(numconv::number_to_str(n))
+                    } else if f < 0f64 || f > 100f64 {
+                        self.throwError("RangeError", "toExponential() argument must be between 0 and 100")
+                    } else {
+                        JsVal::from_str(numconv::to_exponential(n, match fd {
+                            JsUndefined => None,
+                            _ => Some(f as uint)
+                        }))
+                    }
+                },
+                _ => self.throwError("TypeError", "Number.prototype.toExponential is not generic")
+            }
+        };
+        do self.add_native_func_str(frame, self.myNumber, "toPrecision")
+            |this, args| {
+            match (this, getarg(args, 0)) {
+                (JsNumber(n), JsUndefined) => JsVal::from_str(numconv::number_to_str(n)),
+                (JsNumber(n), precision) => {
+                    let p = try_native!(self.tryToInteger(precision));
+                    if !n.is_finite() {
+                        JsVal::from_str(numconv::number_to_str(n))
+                    } else if p < 1f64 || p > 100f64 {
+                        self.throwError("RangeError", "toPrecision() argument must be between 1 and 100")
+                    } else {
+                        JsVal::from_str(numconv::to_precision(n, p as uint))
+                    }
+                },
+                _ => self.throwError("TypeError", "Number.prototype.toPrecision is not generic")
+            }
         };
         do self.add_native_func_str(frame, self.myNumber, "valueOf")
             |this, _args| {
@@ -491,16 +544,267 @@ impl Environment {
         frame
     }
 
-    fn isCallable(&self, val: JsVal) -> bool {
+    /* the startup module defines some methods in TurtleScript; these
+       native versions replace them once it has run. */
+    pub fn replace_startup_natives(self, frame: @mut Object) {
+        do self.add_native_func_str(frame, self.myFunction, "toString")
+            |this, _args| {
+            match this {
+                JsObject(obj) => match obj.get(self.fdValue) {
+                    JsFunctionCode(f) => match f.module.function_source(f.function) {
+                        Some(src) => JsString(src),
+                        None => self.nativeSource(this)
+                    },
+                    JsNativeFunction(_) | JsBoundFunction(_) => self.nativeSource(this),
+                    _ => self.throwError("TypeError",
+                                         "Function.prototype.toString called on incompatible receiver")
+                },
+                _ => self.throwError("TypeError",
+                                     "Function.prototype.toString called on incompatible receiver")
+            }
+        };
+        do self.add_native_func_str(frame, self.myFunction, "bind")
+            |this, args| {
+            let target = match this {
+                JsObject(obj) if self.isCallable(this) => obj,
+                _ => {
+                    return self.throwError("TypeError",
+                                           "Bind must be called on a function");
+                }
+            };
+            let bound_args = if args.len() > 1 {
+                args.slice(1, args.len()).to_owned()
+            } else {
+                ~[]
+            };
+            let length = match target.get(self.fdLength) {
+                JsNumber(n) if n > (bound_args.len() as f64) =>
+                    n - (bound_args.len() as f64),
+                _ => 0f64
+            };
+            let f = Object::create(self.root_map, self.myFunction);
+            f.set(self.fdParentFrame, JsObject(frame));
+            f.set(self.fdValue, JsBoundFunction(@BoundFunction {
+                target: target,
+                this: getarg(args, 0),
+                args: bound_args
+            }));
+            f.set(FieldDesc { name: intern("name"), hidden: false },
+                  JsVal::from_str(~"bound " + self.functionName(this)));
+            f.set(self.fdLength, JsNumber(length));
+            JsObject(f)
+        };
+        do self.add_native_func_str(frame, self.myFunction, "New")
+            |this, args| {
+            // a bound function constructs its target, ignoring its bound this
+            let (cons, args) = self.unbind(this, args);
+            if !self.isCallable(cons) {
+                return self.throwError("TypeError", fmt!(
+                    "%s is not a constructor", self.toString(cons)));
+            }
+            let proto = match self.get_slot(cons, JsVal::from_str("prototype")) {
+                JsObject(p) => p,
+                _ => self.myObject
+            };
+            let object = JsObject(Object::create(self.root_map, proto));
+            match self.interpret_function(cons, object, args) {
+                JsThrown(ex) => JsThrown(ex),
+                JsObject(o) => JsObject(o),
+                _ => object
+            }
+        };
+        do self.add_native_func_str(frame, self.myFunction, "hasInstance")
+            |this, args| {
+            match self.instanceOf(getarg(args, 0), this) {
+                Ok(b) => JsBool(b),
+                Err(e) => e
+            }
+        };
+    }
+
+    // the in operator (ES5 11.8.7)
+    pub fn hasProperty(&self, obj: JsVal, name: JsVal) -> Result<bool, JsVal> {
+        match obj {
+            JsObject(o) => Ok(o.contains(FieldDesc {
+                name: intern(try_js!(self.tryToString(name))), hidden: false
+            })),
+            _ => Err(self.throwError("TypeError", fmt!(
+                "Cannot use 'in' operator to search for '%s' in %s",
+                self.toString(name), self.toString(obj))))
+        }
+    }
+
+    // the instanceof operator (ES5 11.8.6 and 15.3.5.3): look for the
+    // constructor's prototype on the value's __proto__ chain
+    pub fn instanceOf(&self, val: JsVal, cons: JsVal) -> Result<bool, JsVal> {
+        // the built-in constructors are functions, but not callable ones
+        let is_function = match cons {
+            JsObject(obj) => match obj.get(self.fdType) {
+                JsString(ty) => str::from_utf16(ty) == ~"function",
+                _ => false
+            },
+            _ => false
+        };
+        if !is_function {
+            return Err(self.throwError("TypeError",
+                                       "Right-hand side of 'instanceof' is not callable"));
+        }
+        let (cons, _) = self.unbind(cons, ~[]);
+        let proto = match self.get_slot(cons, JsVal::from_str("prototype")) {
+            JsObject(p) => p,
+            _ => {
+                return Err(self.throwError("TypeError",
+                                           "Function has non-object prototype in instanceof check"));
+            }
+        };
+        let mut v = val;
+        loop {
+            v = match v {
+                JsObject(obj) => obj.get(self.fdProto),
+                _ => { return Ok(false); }
+            };
+            match v {
+                JsObject(p) if ptr::ref_eq(p, proto) => { return Ok(true); },
+                _ => {}
+            }
+        }
+    }
+
+    // the function a (possibly bound) function ultimately calls, and
+    // the arguments it passes
+    fn unbind(&self, f: JsVal, args: ~[JsVal]) -> (JsVal, ~[JsVal]) {
+        match self.get_slot_fd(f, self.fdValue) {
+            JsBoundFunction(b) => self.unbind(JsObject(b.target), b.args + args),
+            _ => (f, args)
+        }
+    }
+
+    fn functionName(&self, f: JsVal) -> ~str {
+        let name = match self.get_slot(f, JsVal::from_str("name")) {
+            JsString(s) => str::from_utf16(s),
+            _ => ~""
+        };
+        // methods in the startup module are named '.method'
+        if name.starts_with(".") { name.slice(1, name.len()).to_owned() } else { name }
+    }
+
+    // the text we show for functions whose source we don't have
+    fn nativeSource(&self, f: JsVal) -> JsVal {
+        JsVal::from_str(fmt!("function %s() { [native code] }", self.functionName(f)))
+    }
+
+    // the === operator
+    pub fn strictEquals(&self, left: JsVal, right: JsVal) -> bool {
+        match (left, right) {
+            (JsNumber(l), JsNumber(r)) => (l == r),
+            (JsString(l), JsString(r)) => (l == r),
+            (JsBool(l), JsBool(r)) => (l == r),
+            (JsObject(l), JsObject(r)) => ptr::ref_eq(l, r),
+            (JsNull, JsNull) | (JsUndefined, JsUndefined) => true,
+            // internal values compare by identity
+            (JsFunctionCode(l), JsFunctionCode(r)) => ptr::ref_eq(l, r),
+            (JsBoundFunction(l), JsBoundFunction(r)) => ptr::ref_eq(l, r),
+            (JsRegExp(l), JsRegExp(r)) => ptr::ref_eq(l, r),
+            (JsCollection(l), JsCollection(r)) => ptr::ref_eq(l, r),
+            // different types, or native closures and thrown values,
+            // which have no identity we can compare
+            _ => false
+        }
+    }
+
+    // the == operator (ES5 11.9.3)
+    pub fn looseEquals(&self, left: JsVal, right: JsVal) -> Result<bool, JsVal> {
+        match (left, right) {
+            (JsNull, JsUndefined) | (JsUndefined, JsNull) => Ok(true),
+            (JsNumber(_), JsString(_)) | (JsString(_), JsNumber(_)) =>
+                Ok(try_js!(self.tryToNumber(left)) == try_js!(self.tryToNumber(right))),
+            (JsBool(_), JsBool(_)) => Ok(self.strictEquals(left, right)),
+            (JsBool(_), _) => self.looseEquals(JsNumber(try_js!(self.tryToNumber(left))), right),
+            (_, JsBool(_)) => self.looseEquals(left, JsNumber(try_js!(self.tryToNumber(right)))),
+            (JsObject(_), JsNumber(_)) | (JsObject(_), JsString(_)) =>
+                self.looseEquals(try_js!(self.toPrimitive(left, "")), right),
+            (JsNumber(_), JsObject(_)) | (JsString(_), JsObject(_)) =>
+                self.looseEquals(left, try_js!(self.toPrimitive(right, ""))),
+            _ => Ok(self.strictEquals(left, right))
+        }
+    }
+
+    // a description of each active function, innermost first, starting
+    // from the caller of the running native function.
+    pub fn stackTrace(&self) -> ~[~str] {
+        let mut lines : ~[~str] = ~[];
+        let mut p = *self.native_caller;
+        while !p.is_null() {
+            let state : &State = unsafe { &*p };
+            p = match state.parent {
+                Some(ref parent) => {
+                    let name = match state.function.name {
+                        Some(n) => str::from_utf16(n),
+                        None => ~"<anonymous>"
+                    };
+                    lines.push(fmt!("%s (function %u, pc %u)", name,
+                                    state.function.id, state.pc));
+                    ptr::to_unsafe_ptr(&**parent)
+                },
+                // the sentinel at the top of the chain
+                None => ptr::null()
+            };
+        }
+        lines
+    }
+
+    pub fn isArray(&self, val: JsVal) -> bool {
+        match val {
+            JsObject(obj) => match obj.get(self.fdType) {
+                JsString(utf16) => "array" == str::from_utf16(utf16),
+                _ => false
+            },
+            _ => false
+        }
+    }
+
+    // the own user-visible property names of an object, with array
+    // indices first, in ascending order, followed by the remaining names
+    // in insertion order (as browsers enumerate them).
+    pub fn ownKeys(&self, obj: @mut Object) -> ~[IString] {
+        let mut indices : ~[(uint, IString)] = ~[];
+        let mut others : ~[IString] = ~[];
+        for obj.map.visible_names().each |name| {
+            match intern_to_uint(*name) {
+                Some(n) if n.to_str() == intern_get(*name) => {
+                    let mut pos = indices.len();
+                    while pos > 0 && indices[pos-1].first() > n { pos -= 1; }
+                    indices.insert(pos, (n, *name));
+                },
+                _ => others.push(*name)
+            }
+        }
+        let mut keys = vec::map(indices, |&(_, name)| name);
+        keys.push_all(others);
+        keys
+    }
+
+    pub fn isCallable(&self, val: JsVal) -> bool {
         match val {
             JsObject(_) => match self.get_slot_fd(val, self.fdValue) {
-                JsNativeFunction(_) | JsFunctionCode(_) => true,
+                JsNativeFunction(_) | JsFunctionCode(_) | JsBoundFunction(_) => true,
                 _ => false
             },
             _ => false
         }
     }
 
+    // create an exception object with the given name and message,
+    // ready to be returned from a native function.
+    pub fn throwError(&self, name: &str, message: &str) -> JsVal {
+        let err = Object::create(self.root_map, self.myObject);
+        err.set(FieldDesc { name: intern("name"), hidden: false },
+                JsVal::from_str(name));
+        err.set(FieldDesc { name: intern("message"), hidden: false },
+                JsVal::from_str(message));
+        JsThrown(@JsObject(err))
+    }
+
     fn toObject(&self, val: JsVal) -> @mut Object {
         match val {
             JsUndefined | JsNull => fail!("TypeError"), // xxx throw
@@ -510,15 +814,126 @@ impl Environment {
         }
     }
 
-    priv fn toPrimitive(&self, val: @mut Object, hint: &str) -> JsVal {
-        let funcDefaultValue = val.get(self.fdDefaultValue);
-        self.interpret_function(funcDefaultValue, JsObject(val),
-                                ~[JsVal::from_str(hint)])
+    /* the abstract operations of ES5 section 9.  Those which can run
+       user code (or meet one of our internal values) come in a
+       fallible form, which the bytecode operators and natives use; an
+       Err holds a thrown exception.  Natives return it, as try_native!
+       does. */
+
+    priv fn internalValueError(&self, val: JsVal) -> JsVal {
+        self.throwError("TypeError", fmt!("Cannot convert %s to a javascript value",
+                                          val.to_str()))
     }
-    pub fn toString(&self, val: JsVal) -> ~str {
+
+    // ToPrimitive (9.1); the hint is "Number", "String", or "" for none
+    pub fn toPrimitive(&self, val: JsVal, hint: &str) -> Result<JsVal, JsVal> {
         match val {
-            JsObject(obj) => self.toString(self.toPrimitive(obj, "String")),
-            _ => val.to_str()
+            JsObject(obj) => {
+                let funcDefaultValue = obj.get(self.fdDefaultValue);
+                match self.interpret_function(funcDefaultValue, val,
+                                              ~[JsVal::from_str(hint)]) {
+                    JsThrown(ex) => Err(JsThrown(ex)),
+                    JsObject(_) => Err(self.throwError(
+                        "TypeError", "Cannot convert object to primitive value")),
+                    prim => Ok(prim)
+                }
+            },
+            JsUndefined | JsNull | JsBool(_) | JsNumber(_) | JsString(_) => Ok(val),
+            _ => Err(self.internalValueError(val))
+        }
+    }
+    // ToNumber (9.3)
+    pub fn tryToNumber(&self, val: JsVal) -> Result<f64, JsVal> {
+        match val {
+            JsNumber(n) => Ok(n),
+            JsUndefined => Ok(f64::NaN),
+            JsBool(false) | JsNull => Ok(0f64),
+            JsBool(true) => Ok(1f64),
+            JsString(utf16) => Ok(numconv::string_to_number(utf16)),
+            JsObject(_) => match self.toPrimitive(val, "Number") {
+                Ok(prim) => self.tryToNumber(prim),
+                Err(e) => Err(e)
+            },
+            _ => Err(self.internalValueError(val))
+        }
+    }
+    // ToString (9.8)
+    pub fn tryToString(&self, val: JsVal) -> Result<~str, JsVal> {
+        match val {
+            JsUndefined | JsNull | JsBool(_) | JsNumber(_) | JsString(_) =>
+                Ok(val.to_str()),
+            JsObject(_) => match self.toPrimitive(val, "String") {
+                Ok(prim) => self.tryToString(prim),
+                Err(e) => Err(e)
+            },
+            _ => Err(self.internalValueError(val))
+        }
+    }
+    // ToString, but keeping the UTF-16 representation
+    pub fn tryToUtf16(&self, val: JsVal) -> Result<@[u16], JsVal> {
+        match val {
+            JsString(utf16) => Ok(utf16),
+            _ => Ok(at_vec::to_managed_consume(str::to_utf16(
+                try_js!(self.tryToString(val)))))
+        }
+    }
+    // ToInteger (9.4)
+    pub fn tryToInteger(&self, val: JsVal) -> Result<f64, JsVal> {
+        let n = try_js!(self.tryToNumber(val));
+        Ok(if n.is_NaN() {
+            0f64
+        } else if !n.is_finite() || n == 0f64 {
+            n
+        } else if n < 0f64 {
+            -f64::floor(-n)
+        } else {
+            f64::floor(n)
+        })
+    }
+    // ToInt32 (9.5)
+    pub fn tryToInt32(&self, val: JsVal) -> Result<i32, JsVal> {
+        match self.tryToNumber(val) {
+            Ok(n) => Ok(numconv::to_uint32(n) as i32),
+            Err(e) => Err(e)
+        }
+    }
+    // ToUint32 (9.6)
+    pub fn tryToUint32(&self, val: JsVal) -> Result<u32, JsVal> {
+        match self.tryToNumber(val) {
+            Ok(n) => Ok(numconv::to_uint32(n)),
+            Err(e) => Err(e)
+        }
+    }
+    // the abstract relational comparison (11.8.5): whether left < right,
+    // or None if either is NaN.  leftFirst says which operand to convert
+    // first, so that a > b can be computed as b < a.
+    pub fn lessThan(&self, left: JsVal, right: JsVal,
+                    leftFirst: bool) -> Result<Option<bool>, JsVal> {
+        let l, r;
+        if leftFirst {
+            l = try_js!(self.toPrimitive(left, "Number"));
+            r = try_js!(self.toPrimitive(right, "Number"));
+        } else {
+            r = try_js!(self.toPrimitive(right, "Number"));
+            l = try_js!(self.toPrimitive(left, "Number"));
+        }
+        match (l, r) {
+            // compare code units, not code points
+            (JsString(a), JsString(b)) => Ok(Some(a < b)),
+            _ => {
+                let a = try_js!(self.tryToNumber(l));
+                let b = try_js!(self.tryToNumber(r));
+                if a.is_NaN() || b.is_NaN() { Ok(None) } else { Ok(Some(a < b)) }
+            }
+        }
+    }
+
+    // ToString for printing values and building error messages, which
+    // falls back to the debugging representation if it throws
+    pub fn toString(&self, val: JsVal) -> ~str {
+        match self.tryToString(val) {
+            Ok(s) => s,
+            Err(_) => val.to_str()
         }
     }
     pub fn toBoolean(&self, val: JsVal) -> bool {
@@ -527,41 +942,16 @@ impl Environment {
             JsBool(b) => b,
             JsNumber(n) => !(n.is_NaN() || n==0f64), //+0,-0, or NaN
             JsString(utf16) => !utf16.is_empty(),
-            //JsObject(obj) => match obj.get(self.fdType)...
-            JsObject(_) => true,
-            _ => fail!(fmt!("unimplemented case for toBoolean: %?", val))
+            // objects, and the internal values which stand in for them
+            _ => true
         }
     }
-    pub fn toNumber(&self, val: JsVal) -> f64 {
-        // this is the conversion done by (eg) bi_mul
-        match val {
-            JsObject(obj) => {
-                self.toNumber(self.toPrimitive(obj, "Number"))
-            },
-            JsString(utf16) => {
-                let s = str::from_utf16(utf16);
-                // XXX shouldn't have to break up this expression (rust bug)
-                match s.trim() {
-                    // these are rust constants, not javascript
-                    "inf" | "+inf" | "-inf" => f64::NaN,
-                    // these are the javascript names (which rust doesn't accept)
-                    "Infinity" | "+Infinity" => f64::infinity,
-                    "-Infinity" => f64::neg_infinity,
-                    // empty string is zero
-                    "" => 0f64,
-                    // use the rust from_str method for everything else
-                    // XXX should support 0xNN format.
-                    s => match f64::from_str(s) {
-                        Some(n) => n,
-                        None => f64::NaN
-                    }
-                }
-            },
-            JsNumber(n) => n,
-            JsUndefined => f64::NaN,
-            JsBool(false) | JsNull => 0f64,
-            JsBool(true) => 1f64,
-            _ => fail!(fmt!("can't convert %? to number", val))
+    // a property name as o[name] uses it: objects are converted to
+    // strings first, which may throw
+    priv fn property_name(&self, name: JsVal) -> Result<JsVal, JsVal> {
+        match name {
+            JsObject(_) => Ok(JsString(try_js!(self.tryToUtf16(name)))),
+            _ => Ok(name)
         }
     }
     pub fn get_slot(&self, obj: JsVal, name: JsVal) -> JsVal {
@@ -613,18 +1003,18 @@ impl Environment {
                 // XXX add basic typed array support here
                 o.get(desc) // xxx prototype chains can't include special types
             },
-            JsUndefined => {
-                fail!("dereference of undefined; should throw exception");
-            },
-            JsNull => {
-                fail!("dereference of null; should throw exception");
+            JsUndefined | JsNull => {
+                self.throwError("TypeError", fmt!(
+                    "Cannot read property '%s' of %s",
+                    intern_get(desc.name), self.toString(obj)))
             },
             _ => {
                 fail!("dereference of unexpected type!");
             }
         }
     }
-    pub fn set_slot(&self, obj: JsVal, name: JsVal, nval: JsVal) {
+    pub fn set_slot(&self, obj: JsVal, name: JsVal,
+                    nval: JsVal) -> Result<(), JsVal> {
         let desc = FieldDesc {
             name: intern(match name {
                 JsString(utf16) => str::from_utf16(utf16),
@@ -640,21 +1030,19 @@ impl Environment {
                         // sanity-check the new length.
                         let nlen = match nval.to_uint() {
                             Some(n) => n,
-                            // XXX this should throw RangeError
-                            _ => fail!(fmt!("RangeError %?", nval))
+                            _ => {
+                                return Err(self.throwError("RangeError",
+                                                           "Invalid array length"));
+                            }
                         };
-                        // truncate the array
-                        let mut olen = match obj.get(desc) {
-                            JsNumber(n) => n as uint,
-                            _ => fail!()
+                        // truncate the array, dropping every index at
+                        // or past the new length in one go
+                        do obj.delete_fields(self.root_map) |f| {
+                            !f.hidden && match intern_to_uint(f.name) {
+                                Some(n) => n >= nlen,
+                                None => false
+                            }
                         };
-                        while olen > nlen {
-                            // XXX should delete field
-                            let name = intern((olen-1).to_str());
-                            obj.set(FieldDesc { name:name, hidden:false },
-                                    JsUndefined);
-                            olen -= 1;
-                        }
                         obj.set(desc, JsNumber(nlen as f64));
                     } else {
                         match intern_to_uint(desc.name) {
@@ -690,13 +1078,25 @@ impl Environment {
                 /* ignore write to field of primitive value */
             },
             JsUndefined | JsNull => {
-                // XXX should throw TypeError
-                fail!(fmt!("TypeError: Cannot set property %? of %?",name,obj));
+                return Err(self.throwError("TypeError", fmt!(
+                    "Cannot set property '%s' of %s",
+                    intern_get(desc.name), self.toString(obj))));
             },
-            JsThrown(_) | JsFunctionCode(_) | JsNativeFunction(_) => {
+            JsThrown(_) | JsFunctionCode(_) | JsNativeFunction(_) |
+            JsBoundFunction(_) | JsRegExp(_) | JsCollection(_) => {
                 fail!(fmt!("%? shouldn't escape!", obj));
             }
         }
+        Ok(())
+    }
+
+    pub fn deleteSlot(&self, obj: JsVal, name: JsVal) -> bool {
+        match obj {
+            JsObject(o) => o.delete(self.root_map, FieldDesc {
+                name: intern(self.toString(name)), hidden: false
+            }),
+            _ => false
+        }
     }
 
     pub fn arrayCreate(&self, elements: &[JsVal]) -> JsVal {
@@ -758,9 +1158,9 @@ impl Environment {
         // get function object
         let func = match state.stack.pop() {
             JsObject(obj) => obj,
-            _f => {
-                // xxx throw wrapped TypeError
-                fail!(fmt!("Not a function at %u function %u: %?", state.pc, state.function.id, _f));
+            f => {
+                let message = fmt!("%s is not a function", self.toString(f));
+                return self.throw_error(state, "TypeError", message);
             }
         };
         self.invoke_internal(state, func, my_this, native_args)
@@ -773,14 +1173,17 @@ impl Environment {
                 /* okay! */
             },
             _ => {
-                // xxx throw wrapped TypeError
-                fail!(fmt!("Not a function at %u", state.pc));
+                return self.throw_error(state, "TypeError",
+                                        "object is not a function");
             }
         };
         match func.get(self.fdValue) {
             JsNativeFunction(f) => {
                 // "native code"
+                let saved = *self.native_caller;
+                *self.native_caller = ptr::to_unsafe_ptr(&*state);
                 let rv = f(this, args);
+                *self.native_caller = saved;
                 // handle "apply-like" natives
                 match (func.get(self.fdIsApply), rv) {
                     (JsBool(true), _) => {
@@ -818,21 +1221,52 @@ impl Environment {
                 return ~State::new(Some(state), nframe,
                                    f.module, f.function);
             },
+            JsBoundFunction(b) => {
+                // call the target directly, with the bound arguments first
+                return self.invoke_internal(state, b.target, b.this,
+                                            b.args + args);
+            },
             _ => { fail!("bad function object"); }
         };
     }
 
-    priv fn unary(&self, state: &mut State, uop: &fn(arg: JsVal) -> JsVal) {
+    // throw a new error from the current state
+    priv fn throw_error(&self, state: ~State, name: &str,
+                        message: &str) -> ~State {
+        match self.throwError(name, message) {
+            JsThrown(ex) => self.throw(state, ex),
+            _ => fail!()
+        }
+    }
+
+    // push the value of a property lookup, which may have thrown
+    priv fn push_slot(&self, mut state: ~State, rv: JsVal) -> ~State {
+        match rv {
+            JsThrown(ex) => self.throw(state, ex),
+            _ => { state.stack.push(rv); state }
+        }
+    }
+
+    // apply an operator which may throw
+    priv fn unary(&self, mut state: ~State,
+                  uop: &fn(arg: JsVal) -> Result<JsVal, JsVal>) -> ~State {
         let arg = state.stack.pop();
-        let rv = uop(arg);
-        state.stack.push(rv);
+        match uop(arg) {
+            Ok(rv) => { state.stack.push(rv); state },
+            Err(JsThrown(ex)) => self.throw(state, ex),
+            Err(ex) => self.throw(state, @ex)
+        }
     }
 
-    priv fn binary(&self, state: &mut State, bop: &fn(left: JsVal, right: JsVal) -> JsVal) {
+    priv fn binary(&self, mut state: ~State,
+                   bop: &fn(left: JsVal, right: JsVal) -> Result<JsVal, JsVal>) -> ~State {
         let right = state.stack.pop();
         let left = state.stack.pop();
-        let rv = bop(left, right);
-        state.stack.push(rv);
+        match bop(left, right) {
+            Ok(rv) => { state.stack.push(rv); state },
+            Err(JsThrown(ex)) => self.throw(state, ex),
+            Err(ex) => self.throw(state, @ex)
+        }
     }
 
     // interpret a function object stored in a JsVal
@@ -868,6 +1302,10 @@ impl Environment {
                 }, self.arrayCreate(args));
                 self.interpret(f.module, f.function.id, Some(nframe))
             },
+            (JsBoundFunction(b), _) => {
+                self.interpret_function(JsObject(b.target), b.this,
+                                        b.args + args)
+            },
             _ => fail!("not a function")
         }
     }
@@ -887,6 +1325,39 @@ impl Environment {
         state.stack.pop()
     }
 
+    // compile source to module bytes with a self-hosted compile
+    // function (compile_from_source or a REPL's), or return the syntax
+    // error it threw.  The bytes come with the (start, end) of each
+    // function after the first, unless the compiler is an older one
+    // which doesn't list them.
+    pub fn compile_with(&self, compiler: JsVal, source: &str)
+        -> Result<(~[u8], Option<~[(uint, uint)]>), JsVal> {
+        let bc = self.interpret_function(
+            compiler, JsNull, ~[JsVal::from_str(source)]);
+        match bc {
+            JsThrown(_) => { return Err(bc); }, // parser exception
+            _ => {}
+        };
+        let mut buf : ~[u8] = ~[];
+        for self.arrayEach(bc) |val| {
+            buf.push(try_js!(self.tryToNumber(val)) as u8);
+        }
+        // spans is a flat list: start, end, start, end...
+        let spans = match self.get_slot(bc, JsVal::from_str("spans")) {
+            JsUndefined => None,
+            list => {
+                let mut offsets : ~[uint] = ~[];
+                for self.arrayEach(list) |val| {
+                    offsets.push(try_js!(self.tryToNumber(val)) as uint);
+                }
+                Some(vec::from_fn(offsets.len() / 2, |i| {
+                    (offsets[2 * i], offsets[2 * i + 1])
+                }))
+            }
+        };
+        Ok((buf, spans))
+    }
+
     // take one step in the interpreter (ie interpret one bytecode op)
     pub fn interpret_one(&self, mut state: ~State) -> ~State {
         //io::println(fmt!("fid %u pc %u stack %?", state.function.id, state.pc, state.stack.len()));
@@ -931,7 +1402,7 @@ impl Environment {
                 // user-visible fields
                 f.set(FieldDesc{name:intern("name"),hidden:false},
                       match function.name {
-                          Some(copy s) => JsVal::from_str(s),
+                          Some(s) => JsString(s),
                           None => JsUndefined
                       });
                 f.set(self.fdLength, JsNumber(function.nargs as f64));
@@ -940,7 +1411,8 @@ impl Environment {
             Op_get_slot_direct => {
                 let obj = state.stack.pop();
                 let name = state.module.literals[arg1];
-                state.stack.push(self.get_slot(obj, name));
+                let result = self.get_slot(obj, name);
+                state = self.push_slot(state, result);
             },
             Op_get_slot_direct_check => {
                 let obj = state.stack.pop();
@@ -954,24 +1426,39 @@ impl Environment {
                                          name.to_str()));
                     }
                 }
-                state.stack.push(result);
+                state = self.push_slot(state, result);
             },
             Op_get_slot_indirect => {
-                let name = state.stack.pop();
-                let obj = state.stack.pop();
-                state.stack.push(self.get_slot(obj, name));
+                state = do self.binary(state) |obj, name| {
+                    match self.get_slot(obj, try_js!(self.property_name(name))) {
+                        JsThrown(ex) => Err(JsThrown(ex)),
+                        v => Ok(v)
+                    }
+                };
             },
             Op_set_slot_direct => {
                 let nval = state.stack.pop();
                 let name = state.module.literals[arg1];
                 let obj = state.stack.pop();
-                self.set_slot(obj, name, nval);
+                match self.set_slot(obj, name, nval) {
+                    Ok(()) => {},
+                    Err(JsThrown(ex)) => { state = self.throw(state, ex); },
+                    Err(ex) => { state = self.throw(state, @ex); }
+                }
             },
             Op_set_slot_indirect => {
                 let nval = state.stack.pop();
                 let name = state.stack.pop();
                 let obj = state.stack.pop();
-                self.set_slot(obj, name, nval);
+                let rv = match self.property_name(name) {
+                    Ok(name) => self.set_slot(obj, name, nval),
+                    Err(ex) => Err(ex)
+                };
+                match rv {
+                    Ok(()) => {},
+                    Err(JsThrown(ex)) => { state = self.throw(state, ex); },
+                    Err(ex) => { state = self.throw(state, @ex); }
+                }
             },
             Op_invoke => {
                 state = self.invoke(state, arg1);
@@ -1038,165 +1525,410 @@ impl Environment {
             },
 
             // unary operators
-            Op_un_not => do self.unary(state) |arg| {
-                JsBool(!self.toBoolean(arg))
+            Op_un_not => {
+                state = do self.unary(state) |arg| {
+                    Ok(JsBool(!self.toBoolean(arg)))
+                };
             },
-            Op_un_minus => do self.unary(state) |arg| {
-                match arg {
-                    JsNumber(n) => JsNumber(-n),
-                    _ => fail!(fmt!("unimplemented case for minus: %?", arg))
-                }
+            Op_un_minus => {
+                state = do self.unary(state) |arg| {
+                    Ok(JsNumber(-try_js!(self.tryToNumber(arg))))
+                };
             },
-            Op_un_typeof => do self.unary(state) |arg| {
-                match arg {
-                    JsUndefined => JsVal::from_str("undefined"),
-                    JsNull => JsVal::from_str("object"),
-                    _ => {
-                        let ty = self.get_slot_fd(arg, self.fdType);
-                        match ty.to_str() {
-                            ~"array" => {
-                                /* weird javascript misfeature */
-                                JsVal::from_str("object")
-                            },
-                            _ => ty
-                        }
+            Op_un_bitnot => {
+                state = do self.unary(state) |arg| {
+                    Ok(JsNumber((!try_js!(self.tryToInt32(arg))) as f64))
+                };
+            },
+            Op_un_void => {
+                state = do self.unary(state) |_arg| { Ok(JsUndefined) };
+            },
+            Op_un_typeof => {
+                state = do self.unary(state) |arg| {
+                    match arg {
+                        JsUndefined => Ok(JsVal::from_str("undefined")),
+                        JsNull => Ok(JsVal::from_str("object")),
+                        JsObject(_) | JsBool(_) | JsNumber(_) | JsString(_) => {
+                            let ty = self.get_slot_fd(arg, self.fdType);
+                            match ty.to_str() {
+                                ~"array" => {
+                                    /* weird javascript misfeature */
+                                    Ok(JsVal::from_str("object"))
+                                },
+                                _ => Ok(ty)
+                            }
+                        },
+                        _ => Err(self.internalValueError(arg))
                     }
-                }
+                };
             },
 
             // binary operators
-            Op_bi_eq => do self.binary(state) |left, right| {
-                let rv = match (left, right) {
-                    (JsNumber(l), JsNumber(r)) => (l == r),
-                    (JsString(l), JsString(r)) => (l == r),
-                    (JsBool(l), JsBool(r)) => (l == r),
-                    (JsObject(l), JsObject(r)) => ptr::ref_eq(l, r),
-                    (JsNull, JsNull) | (JsUndefined, JsUndefined) => true,
-                    (JsObject(_), _) |
-                    (JsNumber(_), _) |
-                    (JsBool(_),   _) |
-                    (JsString(_), _) |
-                    (JsNull,      _) |
-                    (JsUndefined, _)  => false,
-                    _ => fail!(fmt!("func %u pc %u: unimplemented case for bi_eq: %s %s", state.function.id, state.pc, left.to_str(), right.to_str()))
+            Op_bi_eq => {
+                state = do self.binary(state) |left, right| {
+                    Ok(JsBool(self.strictEquals(left, right)))
                 };
-                JsBool(rv)
-            },
-            Op_bi_gt => do self.binary(state) |left, right| {
-                let rv = match (left, right) {
-                    (JsString(l), JsString(r)) => (l > r),
-                    (_, JsNumber(_)) |
-                    (JsNumber(_), _) => (self.toNumber(left) > self.toNumber(right)),
-                    _ => fail!(fmt!("func %u pc %u: unimplemented case for bi_gt: %s %s", state.function.id, state.pc, left.to_str(), right.to_str()))
+            },
+            Op_bi_loose_eq => {
+                state = do self.binary(state) |left, right| {
+                    Ok(JsBool(try_js!(self.looseEquals(left, right))))
                 };
-                JsBool(rv)
-            },
-            Op_bi_gte => do self.binary(state) |left, right| {
-                let rv = match (left, right) {
-                    (JsString(l), JsString(r)) => (l >= r),
-                    (_, JsNumber(_)) |
-                    (JsNumber(_), _) => (self.toNumber(left) >= self.toNumber(right)),
-                    _ => fail!(fmt!("func %u pc %u: unimplemented case for bi_gte: %s %s", state.function.id, state.pc, left.to_str(), right.to_str()))
+            },
+            // (ES5 11.8.1-4) NaN compares false every way
+            Op_bi_lt => {
+                state = do self.binary(state) |left, right| {
+                    Ok(JsBool(try_js!(self.lessThan(left, right, true)) == Some(true)))
                 };
-                JsBool(rv)
             },
-            Op_bi_add => do self.binary(state) |left, right| {
-                let lprim = match left {
-                    JsObject(obj) => self.toPrimitive(obj, ""),
-                    _ => left
+            Op_bi_gt => {
+                state = do self.binary(state) |left, right| {
+                    Ok(JsBool(try_js!(self.lessThan(right, left, false)) == Some(true)))
                 };
-                let rprim = match right {
-                    JsObject(obj) => self.toPrimitive(obj, ""),
-                    _ => right
+            },
+            Op_bi_lte => {
+                state = do self.binary(state) |left, right| {
+                    Ok(JsBool(try_js!(self.lessThan(right, left, false)) == Some(false)))
+                };
+            },
+            Op_bi_gte => {
+                state = do self.binary(state) |left, right| {
+                    Ok(JsBool(try_js!(self.lessThan(left, right, true)) == Some(false)))
+                };
+            },
+            Op_bi_in => {
+                state = do self.binary(state) |left, right| {
+                    Ok(JsBool(try_js!(self.hasProperty(right, left))))
+                };
+            },
+            Op_bi_instanceof => {
+                state = do self.binary(state) |left, right| {
+                    Ok(JsBool(try_js!(self.instanceOf(left, right))))
+                };
+            },
+            // (ES5 11.6.1)
+            Op_bi_add => {
+                state = do self.binary(state) |left, right| {
+                    let lprim = try_js!(self.toPrimitive(left, ""));
+                    let rprim = try_js!(self.toPrimitive(right, ""));
+                    match (lprim, rprim) {
+                        // XXX we really need a faster algorithm for
+                        // string concat
+                        (JsString(l), JsString(r)) => Ok(JsString(l + r)),
+                        (JsString(_), _) | (_, JsString(_)) => {
+                            // XXX even slower!
+                            let l = try_js!(self.tryToString(lprim));
+                            let r = try_js!(self.tryToString(rprim));
+                            Ok(JsVal::from_str(l + r))
+                        },
+                        _ => Ok(JsNumber(try_js!(self.tryToNumber(lprim)) +
+                                         try_js!(self.tryToNumber(rprim))))
+                    }
+                };
+            },
+            Op_bi_sub => {
+                state = do self.binary(state) |left, right| {
+                    let l = try_js!(self.tryToNumber(left));
+                    Ok(JsNumber(l - try_js!(self.tryToNumber(right))))
+                };
+            },
+            Op_bi_mul => {
+                state = do self.binary(state) |left, right| {
+                    let l = try_js!(self.tryToNumber(left));
+                    Ok(JsNumber(l * try_js!(self.tryToNumber(right))))
+                };
+            },
+            Op_bi_div => {
+                state = do self.binary(state) |left, right| {
+                    let l = try_js!(self.tryToNumber(left));
+                    Ok(JsNumber(l / try_js!(self.tryToNumber(right))))
+                };
+            },
+            Op_bi_mod => {
+                state = do self.binary(state) |left, right| {
+                    // f64's % truncates, like javascript's
+                    let l = try_js!(self.tryToNumber(left));
+                    Ok(JsNumber(l % try_js!(self.tryToNumber(right))))
+                };
+            },
+
+            // bitwise operators (ES5 11.7 and 11.10)
+            Op_bi_bitand => {
+                state = do self.binary(state) |left, right| {
+                    let l = try_js!(self.tryToInt32(left));
+                    Ok(JsNumber((l & try_js!(self.tryToInt32(right))) as f64))
+                };
+            },
+            Op_bi_bitor => {
+                state = do self.binary(state) |left, right| {
+                    let l = try_js!(self.tryToInt32(left));
+                    Ok(JsNumber((l | try_js!(self.tryToInt32(right))) as f64))
                 };
-                match (lprim,rprim) {
-                    // XXX we really need a faster algorithm for
-                    // string concat
-                    (JsString(l), JsString(r)) => JsString(l + r),
-                    (JsString(_),_) | (_,JsString(_)) => {
-                        // XXX even slower!
-                        JsVal::from_str(self.toString(lprim) +
-                                        self.toString(rprim))
-                    },
-                    _ => JsNumber(self.toNumber(lprim) + self.toNumber(rprim))
-                }
             },
-            Op_bi_sub => do self.binary(state) |left, right| {
-                JsNumber(self.toNumber(left) - self.toNumber(right))
+            Op_bi_bitxor => {
+                state = do self.binary(state) |left, right| {
+                    let l = try_js!(self.tryToInt32(left));
+                    Ok(JsNumber((l ^ try_js!(self.tryToInt32(right))) as f64))
+                };
+            },
+            // shift counts are taken mod 32
+            Op_bi_lsh => {
+                state = do self.binary(state) |left, right| {
+                    let l = try_js!(self.tryToInt32(left));
+                    let shift = (try_js!(self.tryToUint32(right)) & 0x1F) as i32;
+                    Ok(JsNumber((l << shift) as f64))
+                };
             },
-            Op_bi_mul => do self.binary(state) |left, right| {
-                JsNumber(self.toNumber(left) * self.toNumber(right))
+            Op_bi_rsh => {
+                state = do self.binary(state) |left, right| {
+                    let l = try_js!(self.tryToInt32(left));
+                    let shift = (try_js!(self.tryToUint32(right)) & 0x1F) as i32;
+                    Ok(JsNumber((l >> shift) as f64))
+                };
             },
-            Op_bi_div => do self.binary(state) |left, right| {
-                JsNumber(self.toNumber(left) / self.toNumber(right))
+            Op_bi_ursh => {
+                state = do self.binary(state) |left, right| {
+                    let l = try_js!(self.tryToUint32(left));
+                    let shift = try_js!(self.tryToUint32(right)) & 0x1F;
+                    Ok(JsNumber((l >> shift) as f64))
+                };
             }
         }
         state
     }
 }
 
+// the message of a thrown error, or the thrown value itself
+priv fn thrown_message(env: &Environment, ex: JsVal) -> ~str {
+    match env.get_slot(ex, JsVal::from_str("message")) {
+        JsUndefined => env.toString(ex),
+        msg => env.toString(msg)
+    }
+}
+
+// throw a native compiler's syntax error
+priv fn syntax_error(env: &Environment, e: SyntaxError) -> JsVal {
+    // the name the self-hosted compiler gives its errors
+    env.throwError("Syntax Error", e.to_str())
+}
+
+// compile source into a module which knows its text, with the native
+// compiler or else the given self-hosted compile function.  Function
+// spans come from whichever compiler made the bytes; a module whose
+// spans are missing or don't fit it just can't show its source.
+pub fn compile_module(env: &Environment, self_hosted: JsVal,
+                      native: @mut bcompile::Compiler, use_native: bool,
+                      repl: bool, source: &str) -> Result<@Module, JsVal> {
+    let (buf, spans) = if use_native {
+        let rv = if repl {
+            native.compile_repl(source)
+        } else {
+            native.compile(source)
+        };
+        match rv {
+            Ok((buf, spans)) => (buf, Some(spans)),
+            Err(e) => { return Err(syntax_error(env, e)); }
+        }
+    } else {
+        match env.compile_with(self_hosted, source) {
+            Ok(output) => output,
+            Err(ex) => { return Err(ex); }
+        }
+    };
+    let module = match spans {
+        Some(spans) => match Module::new_from_source(copy buf, source, spans) {
+            Err(BadSpans(_)) => Module::new_from_bytes(buf),
+            Err(BadModule(e)) => Err(e),
+            Ok(m) => Ok(m)
+        },
+        None => Module::new_from_bytes(buf)
+    };
+    match module {
+        Ok(m) => Ok(@m),
+        Err(e) => Err(env.throwError("DecodeError", e.to_str()))
+    }
+}
+
 struct Interpreter {
     pub env: ~Environment,
+    pub inspect_options: console::InspectOptions,
+    // compile with bcompile.rs rather than the self-hosted compiler
+    pub native_compiler: bool,
     priv frame: @mut Object,
     priv compile_from_source: JsVal,
-    priv repl: JsVal
+    priv repl: JsVal,
+    priv compiler: @mut bcompile::Compiler,
+    // require's module cache
+    priv loader: @loader::Loader,
+    // the module we ran most recently, for the REPL's .dis command
+    priv last_module: @mut Option<@Module>
 }
 impl Interpreter {
     pub fn new() -> Interpreter {
+        match Interpreter::new_with_startup(@Module::new_startup_module()) {
+            Ok(i) => i,
+            Err(msg) => fail!(msg)
+        }
+    }
+    // use the standard library and compiler from the given module
+    // instead of startup.rs.  Running the module must return the
+    // compile_from_source function, with a make_repl method which
+    // returns the REPL's compile function.
+    pub fn new_with_startup(module: @Module) -> Result<Interpreter, ~str> {
+        if module.functions.is_empty() {
+            return Err(~"startup module has no functions");
+        }
+        match verify::verify(module) {
+            Ok(()) => {},
+            Err(e) => { return Err(~"startup module is invalid: " + e.to_str()); }
+        }
         // create an environment and run the startup code
         let env = Environment::new();
-        let module = @Module::new_startup_module();
         let frame = env.make_top_level_frame(JsNull, ~[]);
         let compile_from_source = env.interpret(module, 0, Some(frame));
+        match compile_from_source {
+            JsThrown(ex) => {
+                return Err(fmt!("startup module threw: %s",
+                                thrown_message(env, *ex)));
+            },
+            _ => {}
+        }
+        if !env.isCallable(compile_from_source) {
+            return Err(fmt!("startup module returned %s, not compile_from_source",
+                            env.toString(compile_from_source)));
+        }
+        env.replace_startup_natives(frame);
+        // the REPL's require is relative to the current directory
+        let compiler = @mut bcompile::Compiler::new();
+        let loader = loader::new_loader(*env, compile_from_source, compiler,
+                                        frame);
+        loader::add_require(loader, frame, os::getcwd());
         // create repl
         let make_repl = env.get_slot(compile_from_source,
                                      JsVal::from_str(~"make_repl"));
+        if !env.isCallable(make_repl) {
+            return Err(~"compile_from_source has no make_repl function");
+        }
         let repl = env.interpret_function(make_repl, JsNull, ~[]);
-        Interpreter {
+        match repl {
+            JsThrown(ex) => {
+                return Err(fmt!("make_repl threw: %s",
+                                thrown_message(env, *ex)));
+            },
+            _ => {}
+        }
+        if !env.isCallable(repl) {
+            return Err(fmt!("make_repl returned %s, not a function",
+                            env.toString(repl)));
+        }
+        Ok(Interpreter {
             env: env,
+            inspect_options: console::InspectOptions::new(),
+            native_compiler: false,
             frame: frame,
             compile_from_source: compile_from_source,
-            repl: repl
+            repl: repl,
+            compiler: compiler,
+            loader: loader,
+            last_module: @mut None
+        })
+    }
+    // compile source to module bytes, or return the syntax error
+    pub fn compile(&self, source: &str) -> Result<~[u8], JsVal> {
+        if self.native_compiler {
+            match self.compiler.compile(source) {
+                Ok((buf, _)) => Ok(buf),
+                Err(e) => Err(syntax_error(self.env, e))
+            }
+        } else {
+            match self.env.compile_with(self.compile_from_source, source) {
+                Ok((buf, _)) => Ok(buf),
+                Err(ex) => Err(ex)
+            }
         }
     }
-    pub fn interpret(&self, source: &str) -> JsVal {
-        // compile source to bytecode
-        let bc = self.env.interpret_function(
-            self.compile_from_source, JsNull,
-            ~[JsVal::from_str(source)]);
-        // create a new module from the bytecode
-        let mut buf : ~[u8] = ~[];
-        for self.env.arrayEach(bc) |val| {
-            buf.push(self.env.toNumber(val) as u8);
+    // the same for a REPL line, which sees the previous lines' variables
+    pub fn compile_repl(&self, source: &str) -> Result<~[u8], JsVal> {
+        if self.native_compiler {
+            match self.compiler.compile_repl(source) {
+                Ok((buf, _)) => Ok(buf),
+                Err(e) => Err(syntax_error(self.env, e))
+            }
+        } else {
+            match self.env.compile_with(self.repl, source) {
+                Ok((buf, _)) => Ok(buf),
+                Err(ex) => Err(ex)
+            }
         }
-        let nm = @Module::new_from_bytes(buf);
+    }
+    pub fn interpret(&self, source: &str) -> JsVal {
+        let nm = match compile_module(self.env, self.compile_from_source,
+                                      self.compiler, self.native_compiler,
+                                      false, source) {
+            Ok(m) => m,
+            Err(ex) => { return ex; }
+        };
         //io::println(fmt!("module: %?", nm));
         // execute the new module.
-        self.env.interpret(nm, 0, Some(self.frame))
+        self.run(nm)
+    }
+    // run source as the main script, which was read from path: it
+    // runs as a CommonJS module, and can require others relative to it
+    pub fn interpret_file(&self, path: &str, source: &str) -> JsVal {
+        *self.loader.native_compiler = self.native_compiler;
+        loader::run_main(self.loader, path, source)
+    }
+    // run a module compiled earlier, say read from a bytecode file.
+    // We didn't compile it, so verify it first.
+    pub fn run_module(&self, module: @Module) -> JsVal {
+        match verify::verify(module) {
+            Ok(()) => self.run(module),
+            Err(e) => self.env.throwError("VerifyError", e.to_str())
+        }
+    }
+    // the same for the main script, as interpret_file runs it
+    pub fn run_file_module(&self, path: &str, module: @Module) -> JsVal {
+        match verify::verify(module) {
+            Ok(()) => {
+                *self.last_module = Some(module);
+                *self.loader.native_compiler = self.native_compiler;
+                loader::run_main_module(self.loader, path, module)
+            },
+            Err(e) => self.env.throwError("VerifyError", e.to_str())
+        }
+    }
+    priv fn run(&self, module: @Module) -> JsVal {
+        *self.last_module = Some(module);
+        // for the modules this one requires
+        *self.loader.native_compiler = self.native_compiler;
+        self.env.interpret(module, 0, Some(self.frame))
+    }
+    pub fn last_module(&self) -> Option<@Module> {
+        *self.last_module
+    }
+    // render a result value the way the REPL prints it
+    pub fn inspect(&self, val: JsVal) -> ~str {
+        console::inspect_with(*self.env, val, &self.inspect_options)
     }
     pub fn repl(&self, source: &str) -> JsVal {
-        // compile source to bytecode
-        let bc = self.env.interpret_function(
-            self.repl, JsNull,
-            ~[JsVal::from_str(source)]);
-        match bc {
-            JsThrown(_) => { return bc; }, // parser exception
-            _ => {}
+        let nm = match compile_module(self.env, self.repl, self.compiler,
+                                      self.native_compiler, true, source) {
+            Ok(m) => m,
+            Err(ex) => { return ex; }
         };
-        // create a new module from the bytecode
-        let mut buf : ~[u8] = ~[];
-        for self.env.arrayEach(bc) |val| {
-            buf.push(self.env.toNumber(val) as u8);
-        }
-        let nm = @Module::new_from_bytes(buf);
         // execute the new module.
-        self.env.interpret(nm, 0, Some(self.frame))
+        self.run(nm)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use op::*;
+    use function::Function;
+    use module::Module;
+    use object::*;
+    use console;
+    use date::Clock;
 
     #[test]
     fn test_interpret1() {
@@ -1236,6 +1968,194 @@ mod tests {
         ]);
     }
 
+    // compile each source with both compilers, in one interpreter each
+    // so that they see the same history (including errors, which leave
+    // the parser's scope behind).  The modules must match byte for
+    // byte, and syntax errors must have the same message.
+    fn differential_test(sources: &[~str], repl: bool) {
+        let js = Interpreter::new();
+        let mut native = Interpreter::new();
+        native.native_compiler = true;
+        let message = |i: &Interpreter, ex: JsVal| -> ~str {
+            match ex {
+                JsThrown(v) => i.env.toString(
+                    i.env.get_slot(*v, JsVal::from_str("message"))).to_str(),
+                _ => fail!()
+            }
+        };
+        for sources.each |src| {
+            let (a, b) = if repl {
+                (js.compile_repl(*src), native.compile_repl(*src))
+            } else {
+                (js.compile(*src), native.compile(*src))
+            };
+            match (a, b) {
+                (Ok(a), Ok(b)) => assert_eq!(a, b),
+                (Err(a), Err(b)) => assert_eq!(message(&js, a), message(&native, b)),
+                _ => fail!(fmt!("the compilers disagree about %s", *src))
+            }
+        }
+    }
+
+    #[test]
+    fn test_native_compiler() {
+        differential_test(~[
+            ~"{ return 42; }",
+            ~"",
+            ~"var x = 1, y; x = y + 2;",
+            ~"var a = [1, 'two', [3]], o = { a: 1, 'b': 2, 3: null, f: function(x) { return x; } };",
+            ~"var o = {}, a = []; o.f.g = function() { return this; }; o['h'] = function() {}; a[0] += 2; o.a -= 1; o.b *= 3; a[1] /= 4;",
+            ~"var o = {a: 6}, a = [6]; o.a %= 4; a[0] &= 3; o.a |= 1; a[0] ^= 2; o.b <<= 1; o.c >>= 2; a[1] >>>= 3;",
+            ~"var z = 5 & 3 | 8 ^ 1 & 2 | ~5 + -7 % 3 * 2, s = (1 << 2 + 1) + (-16 >> 2) + (-16 >>> 28);",
+            ~"var P = function() {}, p = P.New(), o = {a: 1}; var r = ('a' in o) + (p instanceof P) + void 0, t = p < o <= r > P >= p;",
+            ~"function fact(n) { return (n < 2) ? 1 : n * fact(n - 1); }",
+            ~"var f = function g(x, y) { var z = x; return function() { z = z + y; return z; }; };",
+            ~"var i = 0; while (i <= 10) { if (i === 5) { break; } else if (i !== 3) { i = i + 2; } else { i = i + 1; } }",
+            ~"var i = 2; var s = \"tab\\there A\\\"\" + 'it\\'s' + typeof s; var t = !s && -i || s >= i && i > 0;",
+            ~"var n = [NaN, Infinity, 0x1F, 1.5e3, .25, 1e-7, 0, -0, true, false, null, undefined, Object, Array, this];",
+            ~"var o = {}; console.log(isNaN(parseInt('12', 10)), Math.floor(3.5), JSON.stringify(o));",
+            ~"/* comment */ var c = 1; // trailing",
+            ~"var q = 3",
+            ~"var q = 1; var q = 2;",
+            ~"var q = 'a' in {}; var in = 2;",
+            ~"var e = ('1' == 1) + (0 != '') + (null == undefined && 1 != 2), t = 1 == 2 == 3 != 4;",
+            ~"var r = /[/]\\/+/gi, s = 'x'.replace(/x/, typeof /y/), d = 4 / 2 / 1, e = (d) / 2;",
+            ~"var f = function(RegExp) { return /a/m; };",
+            ~"var if = 1;",
+            ~"x = ;",
+            ~"var s = 'unterminated",
+            ~"var u = /unterminated",
+            ~"var n = 12ab;",
+            ~"var n = 1e+;",
+            ~"var o = { 1 + 2 };",
+            ~"a.1 = 2;",
+            ~"return 1;",
+            ~"{ return 1; x = 2; }",
+            ~"undefinedName + 1;",
+            ~"1 + 2;",
+            ~"var x = 1 % 2;",
+            ~"var f = function(1) {};",
+            ~""
+        ], false);
+        differential_test(~[
+            ~"1 + 2",
+            ~"var x = 4*10 + 2;",
+            ~"x",
+            ~"var fib = function(n) { return (n<2) ? 1 : fib(n-1) + fib(n-2); };",
+            ~"fib(10)",
+            ~"function f(a) { return a + x; }",
+            ~"f",
+            ~"x = x + 1",
+            ~"y",
+            ~"var y = { a: [1, 2] }",
+            ~"var y = 3;",
+            ~"y.a[1]"
+        ], true);
+    }
+
+    #[test]
+    fn test_native_repl() {
+        let mut i = Interpreter::new();
+        i.native_compiler = true;
+        assert_eq!(i.repl("var fact = function(x) { return (x<2) ? x : (x * fact(x-1)); };").to_str(), ~"undefined");
+        assert_eq!(i.repl("fact(5)").to_str(), ~"120");
+        assert_eq!(i.interpret("{ var y = 3; return y + 1; }").to_str(), ~"4");
+    }
+
+    #[test]
+    fn test_operators() {
+        let i = Interpreter::new();
+        assert_eq!(i.interpret("{ var x = 10; x %= 4; return x << 1 | 1; }").to_str(), ~"5");
+        assert_eq!(i.interpret("{ var o = {a: 6}, a = [6]; o.a %= 4; a[0] &= 3; return o.a + a[0]; }").to_str(), ~"4");
+        script_test(~[
+            (~"7 % 3 + ' ' + -7 % 3 + ' ' + 5.5 % 2", ~"1 -1 1.5"),
+            (~"(6 & 3) + ' ' + (6 | 3) + ' ' + (6 ^ 3) + ' ' + ~5", ~"2 7 5 -6"),
+            (~"(1 << 2 + 1) + ' ' + (-16 >> 2) + ' ' + (-16 >>> 28)", ~"8 -4 15"),
+            // JavaScript's precedences: & above ^ above |, all below ===
+            (~"(5 & 3 | 8) + ' ' + (1 ^ 3 & 2) + ' ' + (1 | 2 === 2) + ' ' + 7 % 4 * 2", ~"9 3 1 6"),
+            (~"var x = 17; x %= 5; x <<= 3; x |= 5; x ^= 3; x &= 14; x >>= 1; x >>>= 0;", ~"undefined"),
+            (~"x", ~"3"),
+            (~"(x <<= 2) + x", ~"24")
+        ]);
+    }
+
+    #[test]
+    fn test_relational() {
+        script_test(~[
+            // the left operand is converted first, even for < and <=
+            (~"var log = '', a = { valueOf: function() { log = log + 'a'; return 1; } }, b = { valueOf: function() { log = log + 'b'; return 2; } };", ~"undefined"),
+            (~"(a < b) + log", ~"trueab"),
+            (~"(b <= a) + log", ~"falseabba"),
+            (~"var P = function() {}, p = P.New(), o = {a: 1};", ~"undefined"),
+            (~"('a' in o) + ' ' + ('b' in o) + ' ' + (0 in [5])", ~"true false true"),
+            (~"(p instanceof P) + ' ' + (o instanceof P) + ' ' + (p instanceof Object)", ~"true false true"),
+            (~"void 0", ~"undefined"),
+            (~"(void (log = 'v')) + log", ~"undefinedv")
+        ]);
+    }
+
+    #[test]
+    fn test_loose_equality() {
+        let i = Interpreter::new();
+        assert_eq!(i.interpret("{ var n = null; return n == undefined && n != 0; }").to_str(), ~"true");
+        script_test(~[
+            (~"('1' == 1) + ' ' + (0 == '') + ' ' + (null == 0) + ' ' + (NaN == NaN)", ~"true true false false"),
+            (~"(0 != '') + ' ' + ('a' != 'b') + ' ' + (undefined != null)", ~"false true false"),
+            (~"var d = { valueOf: function() { return 42; } };", ~"undefined"),
+            (~"(d == 42) + ' ' + (d != '42') + ' ' + (d == d) + ' ' + (d == {})", ~"true false true false"),
+            (~"1 + 1 == '2' && 2 != 1 + 2", ~"true")
+        ]);
+    }
+
+    #[test]
+    fn test_run_module() {
+        let i = Interpreter::new();
+        let buf = i.compile("{ var f = function(a) { return a + 'é'; }; return f(1.5) + f(-0) + f(null) + f(true) + f(1e21); }").get();
+        // to_bytes gives back what the compiler wrote, with a header
+        let module = @Module::new_from_bytes(copy buf).unwrap();
+        assert_eq!(module.to_bytes(), ~[0, 84, 83, 66, 67, 1] + buf);
+        assert_eq!(i.run_module(module).to_str(), ~"1.5é0énullétrueé1e+21é");
+        // a module which would pop an empty stack isn't run at all
+        let bad = @Module {
+            functions: ~[@Function { name: None, id: 0, nargs: 0, max_stack: 1,
+                                     source: None, bytecode: ~[11] }],
+            literals: ~[],
+            source: None
+        };
+        match i.run_module(bad) {
+            JsThrown(ex) => assert_eq!(
+                i.env.toString(i.env.get_slot(*ex, JsVal::from_str("message"))),
+                ~"function 0, pc 0: pops more than the 0 values on the stack"),
+            rv => fail!(fmt!("ran a bad module: %s", rv.to_str()))
+        }
+    }
+
+    #[test]
+    fn test_new_with_startup() {
+        let i = Interpreter::new();
+        let startup = |source: &str| -> Result<Interpreter, ~str> {
+            Interpreter::new_with_startup(
+                @Module::new_from_bytes(i.compile(source).get()).unwrap())
+        };
+        // a stand-in compiler which always returns the same module
+        let seven = i.compile("{ return 7; }").get();
+        let source = fmt!("{ var c = function(s) { return [%s]; }; c.make_repl = function() { return c; }; return c; }",
+                          str::connect(seven.map(|b| b.to_str()), ","));
+        let custom = startup(source).unwrap();
+        assert_eq!(custom.interpret("anything").to_str(), ~"7");
+        assert_eq!(custom.repl("at all").to_str(), ~"7");
+
+        let empty = Module { functions: ~[], literals: ~[], source: None };
+        assert_eq!(Interpreter::new_with_startup(@empty).get_err(),
+                   ~"startup module has no functions");
+        assert_eq!(startup("{ return 42; }").get_err(),
+                   ~"startup module returned 42, not compile_from_source");
+        assert_eq!(startup("{ return function(s) { return s; }; }").get_err(),
+                   ~"compile_from_source has no make_repl function");
+        assert_eq!(startup("{ var c = function(s) { return s; }; c.make_repl = function() { return 1; }; return c; }").get_err(),
+                   ~"make_repl returned 1, not a function");
+    }
+
     #[test]
     fn test_parseInt() {
         script_test(~[
@@ -1257,10 +2177,49 @@ mod tests {
             (~"parseInt('10', -Infinity)", ~"10"),
             (~"parseInt('10', '-Infinity')", ~"10"),
             (~"parseInt('11')", ~"11"),
-            //(~"parseInt('11z')", ~"11"), // xxx currently fails
-            //(~"parseInt(' 11z')", ~"11"), // xxx currently fails
+            (~"parseInt('11z')", ~"11"),
+            (~"parseInt(' 11z')", ~"11"),
+            (~"parseInt(' 10z ', 16)", ~"16"),
             (~"parseInt('10', '16.5')", ~"16"),
             (~"parseInt('10', 16.5)", ~"16"),
+            (~"parseInt('0x1f')", ~"31"),
+            (~"parseInt('-0X1f', 16)", ~"-31"),
+            (~"parseInt('0x1f', 10)", ~"0"),
+            (~"parseInt('+12')", ~"12"),
+            (~"parseInt('-12abc')", ~"-12"),
+            (~"1/parseInt('-0')", ~"-Infinity"),
+            (~"parseInt('11', 2)", ~"3"),
+            (~"parseInt('z', 36)", ~"35"),
+            (~"parseInt('')", ~"NaN"),
+            (~"parseInt('0x')", ~"NaN"),
+            (~"parseInt('4294967296')", ~"4294967296"),
+            (~"parseInt('123456789012345678901234567890')",
+             ~"1.2345678901234568e+29"),
+            (~"parseInt(0.0000005)", ~"5"),
+            (~"parseInt('Infinity')", ~"NaN"),
+        ]);
+    }
+
+    #[test]
+    fn test_parseFloat() {
+        script_test(~[
+            (~"parseFloat('3.14abc')", ~"3.14"),
+            (~"parseFloat('  -0.5e1x')", ~"-5"),
+            (~"parseFloat('.5')", ~"0.5"),
+            (~"parseFloat('5.')", ~"5"),
+            (~"parseFloat('1e')", ~"1"),
+            (~"parseFloat('e5')", ~"NaN"),
+            (~"parseFloat('.')", ~"NaN"),
+            (~"parseFloat('')", ~"NaN"),
+            (~"parseFloat('Infinityxyz')", ~"Infinity"),
+            (~"parseFloat('-Infinity')", ~"-Infinity"),
+            (~"parseFloat('1e400')", ~"Infinity"),
+            (~"parseFloat('1e-400')", ~"0"),
+            (~"parseFloat('0x10')", ~"0"),
+            (~"parseFloat('0.30000000000000004')", ~"0.30000000000000004"),
+            (~"parseFloat('123456789012345678901234567890')",
+             ~"1.2345678901234568e+29"),
+            (~"parseFloat(' \\n42')", ~"42"),
         ]);
     }
 
@@ -1293,7 +2252,42 @@ mod tests {
         script_test(~[
             (~"Infinity.toString()", ~"Infinity"),
             (~"Infinity.toString(16)", ~"Infinity"),
-            (~"NaN.toString(16)", ~"NaN")
+            (~"NaN.toString(16)", ~"NaN"),
+            (~"(255).toString(16)", ~"ff"),
+            (~"(-255).toString(2)", ~"-11111111"),
+            (~"(0.5).toString(2)", ~"0.1"),
+            (~"(35).toString(36)", ~"z"),
+            (~"(-0).toString()", ~"0"),
+            (~"0.1 + 0.2", ~"0.30000000000000004"),
+            (~"1/3", ~"0.3333333333333333"),
+            (~"1e21", ~"1e+21"),
+            (~"1e-7", ~"1e-7"),
+            (~"0.000001", ~"0.000001"),
+            (~"123456789012345680000", ~"123456789012345680000"),
+            (~"var fact = function(x) { return (x<2) ? x : (x * fact(x-1)); };", ~"undefined"),
+            (~"fact(42)", ~"1.4050061177528798e+51"),
+        ]);
+    }
+
+    #[test]
+    fn test_Number_toFixed() {
+        script_test(~[
+            (~"(1.005).toFixed(2)", ~"1.00"),
+            (~"(2.5).toFixed()", ~"3"),
+            (~"(-1.5).toFixed(0)", ~"-2"),
+            (~"(0.000001).toFixed(2)", ~"0.00"),
+            (~"(99.995).toFixed(1)", ~"100.0"),
+            (~"(1e21).toFixed(2)", ~"1e+21"),
+            (~"NaN.toFixed(2)", ~"NaN"),
+            (~"(123.456).toExponential(2)", ~"1.23e+2"),
+            (~"(0).toExponential()", ~"0e+0"),
+            (~"(0.00015).toExponential()", ~"1.5e-4"),
+            (~"Infinity.toExponential()", ~"Infinity"),
+            (~"(123456).toPrecision(2)", ~"1.2e+5"),
+            (~"(0.00001).toPrecision(1)", ~"0.00001"),
+            (~"(1e-7).toPrecision(1)", ~"1e-7"),
+            (~"(99.99).toPrecision(3)", ~"100"),
+            (~"(1.5).toPrecision()", ~"1.5"),
         ]);
     }
 
@@ -1365,6 +2359,42 @@ mod tests {
         ]);
     }
 
+    #[test]
+    fn test_conversion_exceptions() {
+        // natives pass on exceptions from valueOf and toString
+        script_test(~[
+            (~"var bad = { valueOf: function() { return Object.Throw('valueOf'); }, toString: function() { return Object.Throw('toString'); } };", ~"undefined"),
+            (~"var caught = function(f) { var err = 'none'; Object.Try(this, f, function(e) { err = e; }); return err; };", ~"undefined"),
+            (~"caught(function() { return 1; })", ~"none"),
+            (~"caught(function() { isNaN(bad); })", ~"valueOf"),
+            (~"caught(function() { parseInt(bad, 10); })", ~"toString"),
+            (~"caught(function() { bad.hasOwnProperty(bad); })", ~"toString"),
+            (~"caught(function() { [3, 1].slice(bad); })", ~"valueOf"),
+            (~"caught(function() { [bad, 1].sort(); })", ~"toString"),
+            (~"caught(function() { 'abc'.split(bad); })", ~"toString"),
+            (~"caught(function() { JSON.parse(bad); })", ~"toString"),
+            (~"caught(function() { Date.UTC(2000, bad); })", ~"valueOf"),
+            // and so do property names
+            (~"caught(function() { var o = {}; o[bad] = 1; })", ~"toString"),
+            (~"caught(function() { var o = {}; return o[bad]; })", ~"toString")
+        ]);
+    }
+
+    #[test]
+    fn test_type_errors() {
+        // undefined and null have no properties, and only functions can
+        // be called
+        script_test(~[
+            (~"var caught = function(f) { var err = 'none'; Object.Try(this, f, function(e) { err = e.name + ': ' + e.message; }); return err; };", ~"undefined"),
+            (~"caught(function() { var u; return u.x; })", ~"TypeError: Cannot read property 'x' of undefined"),
+            (~"caught(function() { var n = null; return n[0]; })", ~"TypeError: Cannot read property '0' of null"),
+            (~"caught(function() { var n = null; n.x = 1; })", ~"TypeError: Cannot set property 'x' of null"),
+            (~"caught(function() { var f = 3; f(); })", ~"TypeError: 3 is not a function"),
+            (~"caught(function() { var o = {}; o(); })", ~"TypeError: object is not a function"),
+            (~"caught(function() { var a = []; a.length = -1; })", ~"RangeError: Invalid array length")
+        ]);
+    }
+
     #[test]
     fn test_obj_eq() {
         script_test(~[
@@ -1392,6 +2422,159 @@ mod tests {
         ]);
     }
 
+    #[test]
+    fn test_JSON() {
+        script_test(~[
+            (~"JSON.stringify({a:1, b:[1,'x',null,true], c:{}})",
+             ~"{\"a\":1,\"b\":[1,\"x\",null,true],\"c\":{}}"),
+            (~"JSON.stringify([undefined, function(){}, NaN, -0])",
+             ~"[null,null,null,0]"),
+            (~"JSON.stringify({u:undefined, f:function(){}})", ~"{}"),
+            (~"JSON.stringify(undefined)", ~"undefined"),
+            (~"JSON.stringify('a\"b\\\\\\n\\t')",
+             ~"\"a\\\"b\\\\\\n\\t\""),
+            (~"JSON.stringify({'2':'b', z:1, '1':'a'})",
+             ~"{\"1\":\"a\",\"2\":\"b\",\"z\":1}"),
+            (~"JSON.stringify({a:[1,{b:2}],c:[]}, null, 2)",
+             ~"{\n  \"a\": [\n    1,\n    {\n      \"b\": 2\n    }\n  ],\n  \"c\": []\n}"),
+            (~"JSON.stringify([1], null, '--')", ~"[\n--1\n]"),
+            (~"JSON.stringify({a:1,b:2,c:3}, ['c','a',1])",
+             ~"{\"c\":3,\"a\":1}"),
+            (~"JSON.stringify({a:1,b:'x'}, function(k, v) { return (typeof v === 'number') ? v*2 : v; })",
+             ~"{\"a\":2,\"b\":\"x\"}"),
+            (~"JSON.stringify({d:{toJSON: function(k) { return 'key:'+k; }}})",
+             ~"{\"d\":\"key:d\"}"),
+            (~"var o = {}; o.self = o;", ~"undefined"),
+            (~"var err; Object.Try(this, function() { JSON.stringify(o); }, function(e) { err = e.name; });", ~"undefined"),
+            (~"err", ~"TypeError"),
+            (~"JSON.parse('{\"a\": [1, 2.5e1, -0.5], \"b\": \"\\\\u0041\\\\n\"}').a[1]", ~"25"),
+            (~"JSON.parse(' [true, false, null] ').length", ~"3"),
+            (~"JSON.parse('\"\\\\u0041\"')", ~"A"),
+            (~"JSON.parse('{\"a\":1,\"b\":2}', function(k, v) { return (k === 'a') ? undefined : v; }).hasOwnProperty('a')", ~"false"),
+            (~"JSON.parse('[1,2]', function(k, v) { return (typeof v === 'number') ? v+1 : v; })[1]", ~"3"),
+            (~"Object.Try(this, function() { JSON.parse('[1, 2'); }, function(e) { err = e.name + ': ' + e.message; });", ~"undefined"),
+            (~"err", ~"SyntaxError: Unexpected end of JSON input"),
+            (~"Object.Try(this, function() { JSON.parse('{\"a\" 1}'); }, function(e) { err = e.message; });", ~"undefined"),
+            (~"err", ~"Unexpected token 1 in JSON at position 5"),
+            (~"Object.Try(this, function() { JSON.parse('01'); }, function(e) { err = e.message; });", ~"undefined"),
+            (~"err", ~"Unexpected token 1 in JSON at position 1"),
+        ]);
+    }
+
+    #[test]
+    fn test_RegExp() {
+        script_test(~[
+            (~"var re = RegExp.New('(\\\\d+)-(\\\\d+)', 'g');", ~"undefined"),
+            (~"re.toString()", ~"/(\\d+)-(\\d+)/g"),
+            (~"re.source + re.global + re.ignoreCase + re.multiline", ~"(\\d+)-(\\d+)truefalsefalse"),
+            (~"var m = re.exec('a 12-34 b 5-6');", ~"undefined"),
+            (~"m.join(',')", ~"12-34,12,34"),
+            (~"m.index + ' ' + re.lastIndex", ~"2 7"),
+            (~"re.exec('a 12-34 b 5-6')[0] + ' ' + re.lastIndex", ~"5-6 13"),
+            (~"re.exec('a 12-34 b 5-6') + ' ' + re.lastIndex", ~"null 0"),
+            (~"RegExp('ab+c', 'i').test('xABBBC')", ~"true"),
+            (~"RegExp('^b', 'm').test('a\\nb')", ~"true"),
+            (~"RegExp().toString()", ~"/(?:)/"),
+            (~"var err; Object.Try(this, function() { RegExp('a**'); }, function(e) { err = e.name + ': ' + e.message; });", ~"undefined"),
+            (~"err", ~"SyntaxError: Invalid regular expression: /a**/: Nothing to repeat"),
+            (~"Object.Try(this, function() { RegExp('a', 'gg'); }, function(e) { err = e.message; });", ~"undefined"),
+            (~"err", ~"Invalid flags supplied to RegExp constructor 'gg'"),
+        ]);
+    }
+
+    #[test]
+    fn test_RegExp_literal() {
+        script_test(~[
+            (~"/(\\d+)-(\\d+)/g.exec('a 12-34 b').join(',')", ~"12-34,12,34"),
+            (~"var re = /[/]x\\/y/i;", ~"undefined"),
+            (~"re.source + ' ' + re.ignoreCase + ' ' + re.test('A/X/Y')", ~"[/]x\\/y true true"),
+            (~"'a1b22'.replace(/[0-9]+/g, '#')", ~"a#b#"),
+            // a / after an operand is still division
+            (~"var a = 12, b = 3; var c = (a) / b / 2;", ~"undefined"),
+            (~"c", ~"2"),
+            // and a local RegExp doesn't change what a literal means
+            (~"var f = function() { var RegExp = 0; return /b/; };", ~"undefined"),
+            (~"f().test('abc')", ~"true")
+        ]);
+    }
+
+    #[test]
+    fn test_Date() {
+        let i = Interpreter::new();
+        // 2001-09-09T01:46:40Z, five hours behind UTC
+        *i.env.clock = Clock::fixed(1e12, -300f64);
+        let script = ~[
+            (~"now()", ~"1000000000000"),
+            (~"Date.now() === Date.New().getTime()", ~"true"),
+            (~"typeof Date()", ~"string"),
+            (~"var d = Date.New(2001, 8, 8, 20, 46, 40);", ~"undefined"),
+            (~"d.getTime()", ~"1000000000000"),
+            (~"d.toISOString()", ~"2001-09-09T01:46:40.000Z"),
+            (~"d.getHours() + ' ' + d.getUTCHours()", ~"20 1"),
+            (~"d.getDay() + ' ' + d.getUTCDay()", ~"6 0"),
+            (~"d.getTimezoneOffset()", ~"300"),
+            (~"d.toString()", ~"Sat Sep 08 2001 20:46:40 GMT-0500"),
+            (~"'' + d", ~"Sat Sep 08 2001 20:46:40 GMT-0500"),
+            (~"d.toUTCString()", ~"Sun, 09 Sep 2001 01:46:40 GMT"),
+            (~"d - 1", ~"999999999999"),
+            (~"Date.parse(d.toString()) === d.getTime()", ~"true"),
+            (~"Date.parse(d.toUTCString()) === d.getTime()", ~"true"),
+            (~"Date.UTC(2000, 0, 1)", ~"946684800000"),
+            (~"Date.UTC(99, 11, 31)", ~"946598400000"),
+            (~"Date.parse('2000-01-01')", ~"946684800000"),
+            (~"Date.parse('2000-01-01T00:00')", ~"946702800000"),
+            (~"Date.parse('2000-01-01T00:00:00.5+01:00')", ~"946681200500"),
+            (~"Date.parse('2000-13-01')", ~"NaN"),
+            (~"Date.parse('2001-02-29')", ~"NaN"),
+            (~"d.setMonth(0);", ~"undefined"),
+            (~"d.toISOString()", ~"2001-01-09T01:46:40.000Z"),
+            (~"d.setDate(32);", ~"undefined"),
+            (~"d.getMonth() + '/' + d.getDate()", ~"1/1"),
+            (~"d.setUTCHours(25, 0);", ~"undefined"),
+            (~"d.toISOString()", ~"2001-02-03T01:00:40.000Z"),
+            (~"var bad = Date.New(NaN);", ~"undefined"),
+            (~"bad.getTime()", ~"NaN"),
+            (~"bad.toString()", ~"Invalid Date"),
+            (~"bad.setHours(1)", ~"NaN"),
+            (~"var err; Object.Try(this, function() { bad.toISOString(); }, function(e) { err = e.name; });", ~"undefined"),
+            (~"err", ~"RangeError"),
+            (~"bad.setFullYear(2000);", ~"undefined"),
+            (~"bad.toISOString()", ~"2000-01-01T05:00:00.000Z"),
+            (~"JSON.stringify({d: Date.New(0), bad: Date.New(NaN)})",
+             ~"{\"d\":\"1970-01-01T00:00:00.000Z\",\"bad\":null}"),
+        ];
+        for script.each() |&(given, expected)| {
+            assert_eq!(i.repl(given).to_str(), expected);
+        }
+    }
+
+    #[test]
+    fn test_String_regexp() {
+        script_test(~[
+            (~"'a1b22c333'.match(RegExp('\\\\d+', 'g')).join(',')", ~"1,22,333"),
+            (~"'a1b22c333'.match(RegExp('(\\\\d)(\\\\d)')).join(',')", ~"22,2,2"),
+            (~"'abc'.match(RegExp('x', 'g'))", ~"null"),
+            (~"'abc'.match('b').index", ~"1"),
+            (~"'hello world'.search(RegExp('o\\\\b'))", ~"4"),
+            (~"'hello'.search('z')", ~"-1"),
+            (~"'aaa'.replace('a', 'b')", ~"baa"),
+            (~"'aaa'.replace(RegExp('a', 'g'), 'b')", ~"bbb"),
+            (~"'John Smith'.replace(RegExp('(\\\\w+)\\\\s(\\\\w+)'), '$2, $1')", ~"Smith, John"),
+            (~"'abc'.replace('b', '[$&|$`|$\\'|$$|$1]')", ~"a[b|a|c|$|$1]c"),
+            (~"'x-1-22'.replace(RegExp('\\\\d+', 'g'), function(m, pos) { return '<' + m*2 + '@' + pos + '>'; })", ~"x-<2@2>-<44@4>"),
+            (~"'abc'.replace(RegExp('', 'g'), '-')", ~"-a-b-c-"),
+            (~"'a,b,,c'.split(',').join('|')", ~"a|b||c"),
+            (~"'a,b,,c'.split(',', 2).join('|')", ~"a|b"),
+            (~"'abc'.split('').join('|')", ~"a|b|c"),
+            (~"'abc'.split().length", ~"1"),
+            (~"''.split('').length", ~"0"),
+            (~"''.split(',').length", ~"1"),
+            (~"'a1b22c'.split(RegExp('\\\\d+')).join('|')", ~"a|b|c"),
+            (~"'a1b22c'.split(RegExp('(\\\\d)\\\\d*')).join('|')", ~"a|1|b|2|c"),
+            (~"'abc'.split(RegExp('x*')).join('|')", ~"a|b|c"),
+        ]);
+    }
+
     #[test]
     fn test_Array_join() {
         script_test(~[
@@ -1401,4 +2584,319 @@ mod tests {
             (~"a.join(4)", ~"14243"),
         ]);
     }
+
+    #[test]
+    fn test_Array_methods() {
+        script_test(~[
+            (~"var a = [1, 2, 3, 4, 5];", ~"undefined"),
+            (~"a.slice(1, -1).join(',')", ~"2,3,4"),
+            (~"a.slice(-2).join(',') + ' ' + a.slice(3, 1).length", ~"4,5 0"),
+            (~"a.splice(1, 2, 'x', 'y', 'z').join(',') + ' ' + a.join(',')", ~"2,3 1,x,y,z,4,5"),
+            (~"a.splice(-2).join(',') + ' ' + a.join(',')", ~"4,5 1,x,y,z"),
+            (~"a.splice(1, 3).join(',') + ' ' + a.length", ~"x,y,z 1"),
+            (~"a.unshift(-1, 0) + ' ' + a.join(',')", ~"3 -1,0,1"),
+            (~"a.shift() + ' ' + a.join(',') + ' ' + a.length", ~"-1 0,1 2"),
+            (~"[].shift()", ~"undefined"),
+            (~"[1, 2, 3].reverse().join(',')", ~"3,2,1"),
+            (~"var s = [1, 2];", ~"undefined"),
+            (~"s[3] = 4;", ~"undefined"),
+            (~"s.reverse();", ~"undefined"),
+            (~"s[0] + ' ' + s.hasOwnProperty(1) + ' ' + s.hasOwnProperty(2)", ~"4 false true"),
+            (~"var b = [1, 2, 1, NaN];", ~"undefined"),
+            (~"b.indexOf(1) + ' ' + b.indexOf(1, 1) + ' ' + b.indexOf('1') + ' ' + b.indexOf(NaN)", ~"0 2 -1 -1"),
+            (~"b.lastIndexOf(1) + ' ' + b.lastIndexOf(1, 1) + ' ' + b.lastIndexOf(1, -3)", ~"2 0 0"),
+            (~"[1, 2, 3, 4].filter(function(x) { return x > 2; }).join(',')", ~"3,4"),
+            (~"[1, 2, 3].some(function(x) { return x > 2; })", ~"true"),
+            (~"[1, 2, 3].every(function(x) { return x > 2; })", ~"false"),
+            (~"[].every(function(x) { return false; })", ~"true"),
+            (~"[1, 2, 3].reduce(function(a, x) { return a + x; })", ~"6"),
+            (~"['a', 'b', 'c'].reduceRight(function(a, x) { return a + x; }, '')", ~"cba"),
+            (~"var err; Object.Try(this, function() { [].reduce(function() {}); }, function(e) { err = e.name; });", ~"undefined"),
+            (~"err", ~"TypeError"),
+            (~"Object.Try(this, function() { [1].filter(3); }, function(e) { err = e.message; });", ~"undefined"),
+            (~"err", ~"3 is not a function"),
+            (~"var t = [1, 2, 3, 4]; t.x = 'x'; t[1000000] = 5; t.length = 2;", ~"undefined"),
+            (~"t.join() + ' ' + t[3] + ' ' + t[1000000] + ' ' + t.x", ~"1,2 undefined undefined x"),
+            (~"Array.isArray([]) + ' ' + Array.isArray({length: 0})", ~"true false"),
+        ]);
+    }
+
+    #[test]
+    fn test_Map_Set() {
+        script_test(~[
+            (~"var o = {}, m = Map.New([[1, 'one'], ['1', 'string one']]);", ~"undefined"),
+            (~"m.get(1) + ' ' + m.get('1') + ' ' + m.size", ~"one string one 2"),
+            (~"m.set(o, 'obj').set(NaN, 'nan').set(-0, 'zero');", ~"undefined"),
+            (~"m.size", ~"5"),
+            (~"m.get(o) + ' ' + m.get({}) + ' ' + m.get(NaN) + ' ' + m.get(0)", ~"obj undefined nan zero"),
+            (~"m.has('toString') + ' ' + m.has(o)", ~"false true"),
+            (~"m.keys()[4]", ~"0"),
+            (~"m.delete('1') + ' ' + m.delete('1') + ' ' + m.size", ~"true false 4"),
+            (~"m.set(1, 'uno');", ~"undefined"),
+            (~"m.entries()[0].join('=')", ~"1=uno"),
+            (~"var seen = ''; m.forEach(function(v, k) { seen += v + ';'; if (k === 1) { m.delete(o); m.set('late', 'L'); } });", ~"undefined"),
+            (~"seen", ~"uno;nan;zero;L;"),
+            (~"m.clear();", ~"undefined"),
+            (~"m.size + ' ' + m.has(1)", ~"0 false"),
+            (~"var s = Set.New([3, 1, 3, 2, 1]);", ~"undefined"),
+            (~"s.size + ' ' + s.values().join(',')", ~"3 3,1,2"),
+            (~"s.add(4).add(3).has(4) + ' ' + s.size", ~"true 4"),
+            (~"var err; Object.Try(this, function() { Map(); }, function(e) { err = e.message; });", ~"undefined"),
+            (~"err", ~"Constructor Map requires 'new'"),
+            (~"Object.Try(this, function() { s.get(1); }, function(e) { err = e.name; });", ~"undefined"),
+            (~"err", ~"TypeError"),
+            (~"Object.Try(this, function() { Map.prototype.has.call(s, 1); }, function(e) { err = e.message; });", ~"undefined"),
+            (~"err", ~"Method Map.prototype.has called on incompatible receiver"),
+        ]);
+    }
+
+    #[test]
+    fn test_console_inspect() {
+        let i = Interpreter::new();
+        let env = *i.env;
+        let show = |src: &str| console::inspect(env, i.repl(src), console::DEFAULT_DEPTH);
+        i.repl("var o = {a: 1, b: 'it\\'s', c: [1, 2, {d: {e: 1}}], 'x-y': null};");
+        assert_eq!(show("o"), ~"{ a: 1, b: 'it\\'s', c: [ 1, 2, [Object] ], 'x-y': null }");
+        i.repl("o.self = o;");
+        assert_eq!(show("o.self.self.c.length"), ~"3");
+        assert_eq!(show("o"), ~"{ a: 1, b: 'it\\'s', c: [ 1, 2, [Object] ], 'x-y': null, self: [Circular] }");
+        i.repl("var a = [1, -0]; a[4] = 2; a.x = 'y';");
+        assert_eq!(show("a"), ~"[ 1, -0, <2 empty items>, 2, x: 'y' ]");
+        assert_eq!(show("[]") + show("{}"), ~"[]{}");
+        assert_eq!(show("Map.New([[1, 'a'], [{}, [2]]])"), ~"Map(2) { 1 => 'a', {} => [ 2 ] }");
+        assert_eq!(show("Set.New([1, 2, 1])"), ~"Set(2) { 1, 2 }");
+        assert_eq!(show("Date.New(0)"), ~"1970-01-01T00:00:00.000Z");
+        assert_eq!(show("RegExp('a+', 'g')"), ~"/a+/g");
+
+        let format = |src: &str| {
+            let args = i.repl(src);
+            let mut v : ~[JsVal] = ~[];
+            for env.arrayEach(args) |a| { v.push(a); }
+            console::format_args(env, v)
+        };
+        assert_eq!(format("['%s is %d years and %o', 'Bob', '42.5', {a: 1}, 'extra']"),
+                   ~"Bob is 42 years and { a: 1 } extra");
+        assert_eq!(format("['100%% sure %x %s']"), ~"100% sure %x %s");
+        assert_eq!(format("['a', 'b', 1, {}, [undefined]]"), ~"a b 1 {} [ undefined ]");
+        assert_eq!(format("[1, 'x']"), ~"1 x");
+    }
+
+    #[test]
+    fn test_repl_inspect() {
+        let mut i = Interpreter::new();
+        let show = |i: &Interpreter, src: &str| i.inspect(i.repl(src));
+        assert_eq!(show(&i, "[1, 2, 3]"), ~"[ 1, 2, 3 ]");
+        i.repl("var o = {a: 1, b: [1, 2]};");
+        assert_eq!(show(&i, "o"), ~"{ a: 1, b: [ 1, 2 ] }");
+        assert_eq!(show(&i, "'str'"), ~"'str'");
+        i.repl("var fact = function(n) { return n < 2 ? 1 : n * fact(n-1); };");
+        assert_eq!(show(&i, "fact"), ~"[Function: fact]");
+        assert_eq!(show(&i, "(function() {})"), ~"[Function]");
+        i.repl("o.o = o;");
+        assert_eq!(show(&i, "o"), ~"{ a: 1, b: [ 1, 2 ], o: [Circular] }");
+        assert_eq!(show(&i, "[[[[1]]]]"), ~"[ [ [Array] ] ]");
+        i.inspect_options.depth = 0;
+        assert_eq!(show(&i, "o"), ~"[Object]");
+        i.inspect_options.structured = false;
+        assert_eq!(show(&i, "[1, 2, 3]"), ~"1,2,3");
+    }
+
+    #[test]
+    fn test_Array_sort() {
+        script_test(~[
+            (~"[10, 9, 1, 100].sort().join(',')", ~"1,10,100,9"),
+            (~"[10, 9, 1, 100].sort(function(a, b) { return a - b; }).join(',')", ~"1,9,10,100"),
+            (~"['b', undefined, 'a'].sort()[2]", ~"undefined"),
+            (~"var a = [3, undefined, 1];", ~"undefined"),
+            (~"a[5] = 2;", ~"undefined"),
+            (~"a.sort();", ~"undefined"),
+            (~"'' + a[0] + a[1] + a[2] + a[3]", ~"123undefined"),
+            (~"a.hasOwnProperty(3) + ' ' + a.hasOwnProperty(4) + ' ' + a.length", ~"true false 6"),
+            // a sparse array is sorted by the elements it has, not its length
+            (~"var s = [3]; s[4000000000] = 1; s[70] = 2;", ~"undefined"),
+            (~"s.sort(); s[0] + ' ' + s[1] + ' ' + s[2] + ' ' + s.hasOwnProperty(70) + ' ' + s.length", ~"1 2 3 false 4000000001"),
+            (~"s[4000000000] = 5; s.reduce(function(a, x) { return a + x; })", ~"11"),
+            // stable: equal keys keep their order
+            (~"var p = [{k:1,v:'a'},{k:0,v:'b'},{k:1,v:'c'},{k:0,v:'d'}];", ~"undefined"),
+            (~"p.sort(function(x, y) { return x.k - y.k; }).map(function(e) { return e.v; }).join('')", ~"bdac"),
+            // an exception leaves the array as it was
+            (~"var b = [3, 2, 1], err;", ~"undefined"),
+            (~"Object.Try(this, function() { b.sort(function(x, y) { Object.Throw('oops'); }); }, function(e) { err = e; });", ~"undefined"),
+            (~"err + ' ' + b.join()", ~"oops 3,2,1"),
+        ]);
+    }
+
+    #[test]
+    fn test_Function_toString() {
+        script_test(~[
+            (~"var f = function add(a, b) { /* sum */ return a + b; };", ~"undefined"),
+            (~"f.toString()", ~"function add(a, b) { /* sum */ return a + b; }"),
+            (~"var g = function() { var h = function(x) { return '}'; }; return h; };", ~"undefined"),
+            (~"g().toString()", ~"function(x) { return '}'; }"),
+            (~"'' + g", ~"function() { var h = function(x) { return '}'; }; return h; }"),
+            (~"[].push.toString()", ~"function push() { [native code] }"),
+            (~"Math.floor.toString()", ~"function floor() { [native code] }"),
+            (~"Math.floor.name + ' ' + Math.floor.bind(null).name", ~"floor bound floor"),
+            (~"var err; Object.Try(this, function() { f.toString.call({}); }, function(e) { err = e.name; });", ~"undefined"),
+            (~"err", ~"TypeError")
+        ]);
+    }
+
+    #[test]
+    fn test_Function_bind() {
+        script_test(~[
+            (~"var add = function(a, b, c) { return a + b + c; };", ~"undefined"),
+            (~"var add1 = add.bind(null, 1);", ~"undefined"),
+            (~"add1.length + ' ' + add1.name", ~"2 bound add"),
+            (~"add1(2, 3) + add1.call(null, 2, 3) + add1.apply(null, [2, 3])", ~"18"),
+            (~"add.bind(null, 1, 2, 3, 4).length", ~"0"),
+            (~"add1.bind(null, 2)(3)", ~"6"),
+            // the bound this wins
+            (~"var o = {x: 5, f: function() { return this.x; }}; var g = o.f.bind(o);", ~"undefined"),
+            (~"g() + ({x: 1, g: g}).g() + g.call({x: 2})", ~"15"),
+            // except under new
+            (~"var P = function(x, y) { this.x = x; this.y = y; };", ~"undefined"),
+            (~"var BP = P.bind({x: 0}, 1); var p = BP.New(2);", ~"undefined"),
+            (~"p.x + ',' + p.y", ~"1,2"),
+            (~"P.hasInstance(p) + ' ' + BP.hasInstance(p) + ' ' + BP.hasInstance({})", ~"true true false"),
+            (~"BP.toString()", ~"function bound P() { [native code] }"),
+            (~"var err; Object.Try(this, function() { add.bind.call({}); }, function(e) { err = e.name; });", ~"undefined"),
+            (~"err", ~"TypeError")
+        ]);
+    }
+
+    // run hand-assembled bytecode, for operators the startup compiler
+    // can't generate
+    fn run_bytecode(i: &Interpreter, literals: ~[JsVal], bytecode: ~[uint]) -> JsVal {
+        let module = @Module {
+            functions: ~[@Function {
+                name: None, id: 0, nargs: 0, max_stack: 8,
+                source: None, bytecode: bytecode
+            }],
+            literals: literals,
+            source: None
+        };
+        i.env.interpret(module, 0, None)
+    }
+
+    #[test]
+    fn test_arithmetic_ops() {
+        let i = Interpreter::new();
+        let binop = |op: Op, l: JsVal, r: JsVal| -> ~str {
+            run_bytecode(&i, ~[l, r], ~[Op_push_literal as uint, 0,
+                                    Op_push_literal as uint, 1,
+                                    op as uint, Op_return as uint]).to_str()
+        };
+        let n = |f: f64| JsNumber(f);
+        assert_eq!(binop(Op_bi_mod, n(7f64), n(3f64)), ~"1");
+        assert_eq!(binop(Op_bi_mod, n(-7f64), n(3f64)), ~"-1");
+        assert_eq!(binop(Op_bi_mod, n(5.5f64), n(2f64)), ~"1.5");
+        assert_eq!(binop(Op_bi_mod, n(1f64), n(0f64)), ~"NaN");
+        assert_eq!(binop(Op_bi_mod, JsVal::from_str("8"), n(5f64)), ~"3");
+        assert_eq!(binop(Op_bi_bitand, n(12f64), n(10f64)), ~"8");
+        assert_eq!(binop(Op_bi_bitor, n(12f64), n(10f64)), ~"14");
+        assert_eq!(binop(Op_bi_bitxor, n(12f64), n(10f64)), ~"6");
+        // operands are truncated to 32 bits
+        assert_eq!(binop(Op_bi_bitor, n(4294967297f64), n(0f64)), ~"1");
+        assert_eq!(binop(Op_bi_bitor, n(2147483648f64), n(0f64)), ~"-2147483648");
+        assert_eq!(binop(Op_bi_bitand, n(f64::NaN), n(-1f64)), ~"0");
+        assert_eq!(binop(Op_bi_lsh, n(1f64), n(31f64)), ~"-2147483648");
+        assert_eq!(binop(Op_bi_lsh, n(1f64), n(33f64)), ~"2");
+        assert_eq!(binop(Op_bi_rsh, n(-16f64), n(2f64)), ~"-4");
+        assert_eq!(binop(Op_bi_ursh, n(-16f64), n(28f64)), ~"15");
+        assert_eq!(binop(Op_bi_ursh, n(-1f64), n(0f64)), ~"4294967295");
+        let bitnot = |v: JsVal| -> ~str {
+            run_bytecode(&i, ~[v], ~[Op_push_literal as uint, 0,
+                                 Op_un_bitnot as uint, Op_return as uint]).to_str()
+        };
+        assert_eq!(bitnot(n(5f64)), ~"-6");
+        assert_eq!(bitnot(n(-1f64)), ~"0");
+        assert_eq!(bitnot(JsVal::from_str("7")), ~"-8");
+    }
+
+    #[test]
+    fn test_relational_ops() {
+        let i = Interpreter::new();
+        let binop = |op: Op, l: JsVal, r: JsVal| -> ~str {
+            let rv = run_bytecode(&i, ~[l, r], ~[Op_push_literal as uint, 0,
+                                                 Op_push_literal as uint, 1,
+                                                 op as uint, Op_return as uint]);
+            match rv {
+                JsThrown(@ex) => ~"throws " + i.env.get_slot(ex, JsVal::from_str("name")).to_str(),
+                _ => rv.to_str()
+            }
+        };
+        let n = |f: f64| JsNumber(f);
+        let s = |s: &str| JsVal::from_str(s);
+        assert_eq!(binop(Op_bi_lt, n(1f64), n(2f64)), ~"true");
+        assert_eq!(binop(Op_bi_lt, n(2f64), n(2f64)), ~"false");
+        assert_eq!(binop(Op_bi_lte, n(2f64), n(2f64)), ~"true");
+        assert_eq!(binop(Op_bi_lt, s("a"), s("b")), ~"true");
+        assert_eq!(binop(Op_bi_lte, s("b"), s("a")), ~"false");
+        assert_eq!(binop(Op_bi_lt, n(f64::NaN), n(1f64)), ~"false");
+        assert_eq!(binop(Op_bi_lte, s("10"), n(9f64)), ~"false");
+
+        i.repl("var P = function() {}; var p = P.New(); var o = {a: 1}; var q = Object.create(o);");
+        let get = |name: &str| i.repl(name);
+        assert_eq!(binop(Op_bi_in, s("a"), get("o")), ~"true");
+        assert_eq!(binop(Op_bi_in, s("a"), get("q")), ~"true"); // inherited
+        assert_eq!(binop(Op_bi_in, s("b"), get("q")), ~"false");
+        assert_eq!(binop(Op_bi_in, n(0f64), get("[5]")), ~"true");
+        assert_eq!(binop(Op_bi_in, s("length"), get("[5]")), ~"true");
+        assert_eq!(binop(Op_bi_in, s("a"), n(5f64)), ~"throws TypeError");
+        assert_eq!(binop(Op_bi_instanceof, get("p"), get("P")), ~"true");
+        assert_eq!(binop(Op_bi_instanceof, get("p"), get("Object")), ~"true");
+        assert_eq!(binop(Op_bi_instanceof, get("o"), get("P")), ~"false");
+        assert_eq!(binop(Op_bi_instanceof, n(1f64), get("P")), ~"false");
+        assert_eq!(binop(Op_bi_instanceof, get("p"), get("P.bind(null)")), ~"true");
+        assert_eq!(binop(Op_bi_instanceof, get("p"), get("o")), ~"throws TypeError");
+        assert_eq!(binop(Op_bi_instanceof, get("p"), JsUndefined), ~"throws TypeError");
+
+        let void = run_bytecode(&i, ~[n(1f64)], ~[Op_push_literal as uint, 0,
+                                                  Op_un_void as uint, Op_return as uint]);
+        assert_eq!(void.to_str(), ~"undefined");
+    }
+
+    #[test]
+    fn test_equality_ops() {
+        let i = Interpreter::new();
+        let eq = |op: Op, l: JsVal, r: JsVal| -> bool {
+            let rv = run_bytecode(&i, ~[l, r], ~[Op_push_literal as uint, 0,
+                                                 Op_push_literal as uint, 1,
+                                                 op as uint, Op_return as uint]);
+            i.env.toBoolean(rv)
+        };
+        let n = |f: f64| JsNumber(f);
+        let s = |s: &str| JsVal::from_str(s);
+        i.repl("var o = {}; var d = { valueOf: function() { return 42; } }; var t = { toString: function() { return 'x'; } };");
+        let o = i.repl("o");
+        let d = i.repl("d");
+        let t = i.repl("t");
+        // loose equality
+        assert!(eq(Op_bi_loose_eq, JsNull, JsUndefined));
+        assert!(eq(Op_bi_loose_eq, JsUndefined, JsNull));
+        assert!(!eq(Op_bi_loose_eq, JsNull, n(0f64)));
+        assert!(!eq(Op_bi_loose_eq, JsUndefined, JsBool(false)));
+        assert!(eq(Op_bi_loose_eq, s("1"), n(1f64)));
+        assert!(eq(Op_bi_loose_eq, n(0f64), s("")));
+        assert!(eq(Op_bi_loose_eq, s(" 2 "), n(2f64)));
+        assert!(!eq(Op_bi_loose_eq, n(f64::NaN), n(f64::NaN)));
+        assert!(eq(Op_bi_loose_eq, JsBool(true), n(1f64)));
+        assert!(eq(Op_bi_loose_eq, s("1"), JsBool(true)));
+        assert!(!eq(Op_bi_loose_eq, s("true"), JsBool(true)));
+        assert!(eq(Op_bi_loose_eq, d, n(42f64)));
+        assert!(eq(Op_bi_loose_eq, s("42"), d));
+        assert!(eq(Op_bi_loose_eq, t, s("x")));
+        assert!(eq(Op_bi_loose_eq, o, o));
+        assert!(!eq(Op_bi_loose_eq, o, d));
+        assert!(!eq(Op_bi_loose_eq, o, JsNull));
+        // strict equality is total, including over internal values
+        assert!(!eq(Op_bi_eq, s("1"), n(1f64)));
+        assert!(eq(Op_bi_eq, n(0f64), n(-0f64)));
+        let f = JsNativeFunction(|_, _| JsUndefined);
+        assert!(!i.env.strictEquals(f, f));
+        let code = i.env.get_slot_fd(i.repl("(function() {})"), i.env.fdValue);
+        assert!(i.env.strictEquals(code, code));
+        assert!(!i.env.strictEquals(code, n(1f64)));
+    }
 }
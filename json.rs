@@ -0,0 +1,573 @@
+// the JSON object (ES5 section 15.12)
+use interp::Environment;
+use intern::intern_get;
+use numconv;
+use object::*;
+
+pub fn make_json(env: Environment, frame: @mut Object) -> @mut Object {
+    let myJSON = Object::create(env.root_map, env.myObject);
+    do env.add_native_func_str(frame, myJSON, "parse") |_this, args| {
+        let src = try_native!(env.tryToUtf16(getarg(args, 0)));
+        let mut p = Parser { env: env, src: src, pos: 0 };
+        match p.parse() {
+            Err(ex) => ex,
+            Ok(val) => {
+                let reviver = getarg(args, 1);
+                if env.isCallable(reviver) {
+                    let root = Object::create(env.root_map, env.myObject);
+                    let empty = JsVal::from_str("");
+                    env.set_slot(JsObject(root), empty, val);
+                    internalize(env, reviver, JsObject(root), empty)
+                } else {
+                    val
+                }
+            }
+        }
+    };
+    do env.add_native_func_str(frame, myJSON, "stringify") |_this, args| {
+        stringify(env, getarg(args, 0), getarg(args, 1), getarg(args, 2))
+    };
+    myJSON
+}
+
+// JSON.parse
+
+priv struct Parser {
+    env: Environment,
+    src: @[u16],
+    pos: uint
+}
+
+priv fn utf16(s: &str) -> ~[u16] { str::to_utf16(s) }
+
+impl Parser {
+    fn error(&self) -> JsVal {
+        let msg = if self.pos >= self.src.len() {
+            ~"Unexpected end of JSON input"
+        } else {
+            fmt!("Unexpected token %s in JSON at position %u",
+                 str::from_utf16([self.src[self.pos]]), self.pos)
+        };
+        self.env.throwError("SyntaxError", msg)
+    }
+
+    fn peek(&self) -> Option<char> {
+        if self.pos < self.src.len() {
+            Some(self.src[self.pos] as char)
+        } else {
+            None
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        loop {
+            match self.peek() {
+                Some(' ') | Some('\t') | Some('\n') | Some('\r') => {
+                    self.pos += 1;
+                },
+                _ => break
+            }
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), JsVal> {
+        self.skip_whitespace();
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error())
+        }
+    }
+
+    fn parse(&mut self) -> Result<JsVal, JsVal> {
+        let val = match self.parse_value() {
+            Ok(val) => val,
+            Err(ex) => { return Err(ex); }
+        };
+        self.skip_whitespace();
+        if self.pos < self.src.len() {
+            return Err(self.error());
+        }
+        Ok(val)
+    }
+
+    fn parse_value(&mut self) -> Result<JsVal, JsVal> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('{') => self.parse_object(),
+            Some('[') => self.parse_array(),
+            Some('"') => match self.parse_string() {
+                Ok(s) => Ok(JsString(s)),
+                Err(ex) => Err(ex)
+            },
+            Some('-') | Some('0'..'9') => self.parse_number(),
+            Some('t') => self.parse_word("true", JsBool(true)),
+            Some('f') => self.parse_word("false", JsBool(false)),
+            Some('n') => self.parse_word("null", JsNull),
+            _ => Err(self.error())
+        }
+    }
+
+    fn parse_word(&mut self, word: &str, val: JsVal) -> Result<JsVal, JsVal> {
+        for utf16(word).each |c| {
+            if self.pos >= self.src.len() || self.src[self.pos] != *c {
+                return Err(self.error());
+            }
+            self.pos += 1;
+        }
+        Ok(val)
+    }
+
+    fn parse_object(&mut self) -> Result<JsVal, JsVal> {
+        self.pos += 1; // '{'
+        let obj = Object::create(self.env.root_map, self.env.myObject);
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.pos += 1;
+            return Ok(JsObject(obj));
+        }
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some('"') { return Err(self.error()); }
+            let key = match self.parse_string() {
+                Ok(s) => JsString(s),
+                Err(ex) => { return Err(ex); }
+            };
+            match self.expect(':') {
+                Err(ex) => { return Err(ex); },
+                Ok(_) => {}
+            }
+            match self.parse_value() {
+                Ok(val) => { self.env.set_slot(JsObject(obj), key, val); },
+                Err(ex) => { return Err(ex); }
+            }
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => { self.pos += 1; },
+                Some('}') => { self.pos += 1; return Ok(JsObject(obj)); },
+                _ => { return Err(self.error()); }
+            }
+        }
+    }
+
+    fn parse_array(&mut self) -> Result<JsVal, JsVal> {
+        self.pos += 1; // '['
+        let mut elements : ~[JsVal] = ~[];
+        self.skip_whitespace();
+        if self.peek() == Some(']') {
+            self.pos += 1;
+            return Ok(self.env.arrayCreate(elements));
+        }
+        loop {
+            match self.parse_value() {
+                Ok(val) => elements.push(val),
+                Err(ex) => { return Err(ex); }
+            }
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => { self.pos += 1; },
+                Some(']') => {
+                    self.pos += 1;
+                    return Ok(self.env.arrayCreate(elements));
+                },
+                _ => { return Err(self.error()); }
+            }
+        }
+    }
+
+    fn parse_hex4(&mut self) -> Result<u16, JsVal> {
+        let mut n = 0u16;
+        for 4.times {
+            let d = match self.peek() {
+                Some(c @ '0'..'9') => (c as u16) - ('0' as u16),
+                Some(c @ 'a'..'f') => (c as u16) - ('a' as u16) + 10,
+                Some(c @ 'A'..'F') => (c as u16) - ('A' as u16) + 10,
+                _ => { return Err(self.error()); }
+            };
+            n = n * 16 + d;
+            self.pos += 1;
+        }
+        Ok(n)
+    }
+
+    fn parse_string(&mut self) -> Result<@[u16], JsVal> {
+        self.pos += 1; // opening quote
+        let mut out : ~[u16] = ~[];
+        loop {
+            if self.pos >= self.src.len() { return Err(self.error()); }
+            let c = self.src[self.pos];
+            if c < 0x20 { return Err(self.error()); }
+            self.pos += 1;
+            if c == ('"' as u16) { break; }
+            if c != ('\\' as u16) { out.push(c); loop; }
+            let esc = match self.peek() {
+                Some('"') => '"' as u16,
+                Some('\\') => '\\' as u16,
+                Some('/') => '/' as u16,
+                Some('b') => 0x08,
+                Some('f') => 0x0C,
+                Some('n') => 0x0A,
+                Some('r') => 0x0D,
+                Some('t') => 0x09,
+                Some('u') => {
+                    self.pos += 1;
+                    match self.parse_hex4() {
+                        Ok(n) => { out.push(n); loop; },
+                        Err(ex) => { return Err(ex); }
+                    }
+                },
+                _ => { return Err(self.error()); }
+            };
+            self.pos += 1;
+            out.push(esc);
+        }
+        Ok(at_vec::to_managed_consume(out))
+    }
+
+    fn parse_digits(&mut self, digits: &mut ~[u8]) -> Result<uint, JsVal> {
+        let mut n = 0u;
+        loop {
+            match self.peek() {
+                Some(c @ '0'..'9') => {
+                    digits.push((c as u8) - ('0' as u8));
+                    self.pos += 1;
+                    n += 1;
+                },
+                _ => break
+            }
+        }
+        if n == 0 { Err(self.error()) } else { Ok(n) }
+    }
+
+    fn parse_number(&mut self) -> Result<JsVal, JsVal> {
+        let mut negative = false;
+        if self.peek() == Some('-') { negative = true; self.pos += 1; }
+        let mut digits : ~[u8] = ~[];
+        let mut e = 0i;
+        if self.peek() == Some('0') {
+            // no leading zeros
+            self.pos += 1;
+        } else {
+            match self.parse_digits(&mut digits) {
+                Err(ex) => { return Err(ex); },
+                Ok(_) => {}
+            }
+        }
+        if self.peek() == Some('.') {
+            self.pos += 1;
+            match self.parse_digits(&mut digits) {
+                Err(ex) => { return Err(ex); },
+                Ok(n) => { e -= n as int; }
+            }
+        }
+        if self.peek() == Some('e') || self.peek() == Some('E') {
+            self.pos += 1;
+            let mut esign = 1i;
+            match self.peek() {
+                Some('-') => { esign = -1; self.pos += 1; },
+                Some('+') => { self.pos += 1; },
+                _ => {}
+            }
+            let mut edigits : ~[u8] = ~[];
+            match self.parse_digits(&mut edigits) {
+                Err(ex) => { return Err(ex); },
+                Ok(_) => {}
+            }
+            let mut exp = 0i;
+            for edigits.each |d| {
+                // saturate; anything this large is already 0 or Infinity
+                if exp < 100000 { exp = exp * 10 + (*d as int); }
+            }
+            e += esign * exp;
+        }
+        let v = numconv::decimal_to_number(digits, e);
+        Ok(JsNumber(if negative { -v } else { v }))
+    }
+}
+
+// the abstract operation Walk from ES5 15.12.2
+priv fn internalize(env: Environment, reviver: JsVal,
+                    holder: JsVal, name: JsVal) -> JsVal {
+    let val = env.get_slot(holder, name);
+    match val {
+        JsObject(obj) => {
+            let keys : ~[JsVal] = if env.isArray(val) {
+                let len = try_native!(env.tryToUint32(env.get_slot_fd(val, env.fdLength)));
+                vec::from_fn(len as uint, |i| JsVal::from_str(i.to_str()))
+            } else {
+                vec::map(env.ownKeys(obj), |n| JsVal::from_str(intern_get(*n)))
+            };
+            for keys.each |key| {
+                let nval = internalize(env, reviver, val, *key);
+                match nval {
+                    JsThrown(_) => { return nval; },
+                    JsUndefined => { env.deleteSlot(val, *key); },
+                    _ => { env.set_slot(val, *key, nval); }
+                }
+            }
+        },
+        _ => {}
+    }
+    env.interpret_function(reviver, holder, ~[name, val])
+}
+
+// JSON.stringify
+
+priv struct Stringifier {
+    env: Environment,
+    replacer: Option<JsVal>,
+    property_list: Option<~[JsVal]>,
+    gap: ~[u16],
+    indent: ~[u16],
+    stack: ~[@mut Object]
+}
+
+// the abstract operation Quote from ES5 15.12.3, escaping lone
+// surrogates so that the output is well-formed UTF-16.
+pub fn quote(out: &mut ~[u16], s: &[u16]) {
+    let hex = utf16("0123456789abcdef");
+    out.push('"' as u16);
+    let mut i = 0u;
+    while i < s.len() {
+        let c = s[i];
+        let simple = match c as char {
+            '"' => Some('"'),
+            '\\' => Some('\\'),
+            '\x08' => Some('b'),
+            '\x0c' => Some('f'),
+            '\n' => Some('n'),
+            '\r' => Some('r'),
+            '\t' => Some('t'),
+            _ => None
+        };
+        let lone_surrogate = if c >= 0xD800 && c <= 0xDBFF {
+            if i + 1 < s.len() && s[i+1] >= 0xDC00 && s[i+1] <= 0xDFFF {
+                // a valid pair; copy both halves
+                out.push(c);
+                out.push(s[i+1]);
+                i += 2;
+                loop;
+            }
+            true
+        } else {
+            c >= 0xDC00 && c <= 0xDFFF
+        };
+        match simple {
+            Some(e) => { out.push('\\' as u16); out.push(e as u16); },
+            None if c < 0x20 || lone_surrogate => {
+                out.push_all(utf16("\\u"));
+                out.push(hex[(((c >> 12) & 0xF) as uint]);
+                out.push(hex[(((c >> 8) & 0xF) as uint]);
+                out.push(hex[(((c >> 4) & 0xF) as uint]);
+                out.push(hex[(c & 0xF) as uint]);
+            },
+            None => out.push(c)
+        }
+        i += 1;
+    }
+    out.push('"' as u16);
+}
+
+impl Stringifier {
+    fn on_stack(&self, obj: @mut Object) -> bool {
+        for self.stack.each |o| {
+            if ptr::ref_eq(*o, obj) { return true; }
+        }
+        false
+    }
+
+    // the abstract operation Str from ES5 15.12.3; None means undefined.
+    fn str(&mut self, key: JsVal, holder: JsVal) -> Result<Option<~[u16]>, JsVal> {
+        let mut value = self.env.get_slot(holder, key);
+        match value {
+            JsObject(_) => {
+                let toJSON = self.env.get_slot(value, JsVal::from_str("toJSON"));
+                if self.env.isCallable(toJSON) {
+                    value = self.env.interpret_function(toJSON, value, ~[key]);
+                }
+            },
+            _ => {}
+        }
+        match (self.replacer, value) {
+            (_, JsThrown(_)) => { return Err(value); },
+            (Some(f), _) => {
+                value = self.env.interpret_function(f, holder, ~[key, value]);
+            },
+            (None, _) => {}
+        }
+        match value {
+            JsThrown(_) => Err(value),
+            JsNull => Ok(Some(utf16("null"))),
+            JsBool(b) => Ok(Some(utf16(if b { "true" } else { "false" }))),
+            JsString(s) => {
+                let mut out : ~[u16] = ~[];
+                quote(&mut out, s);
+                Ok(Some(out))
+            },
+            JsNumber(n) if n.is_finite() =>
+                Ok(Some(utf16(numconv::number_to_str(n)))),
+            JsNumber(_) => Ok(Some(utf16("null"))),
+            JsObject(obj) if !self.env.isCallable(value) => {
+                if self.on_stack(obj) {
+                    return Err(self.env.throwError(
+                        "TypeError", "Converting circular structure to JSON"));
+                }
+                self.stack.push(obj);
+                let stepback = self.indent.clone();
+                self.indent.push_all(self.gap);
+                let rv = if self.env.isArray(value) {
+                    self.serialize_array(value)
+                } else {
+                    self.serialize_object(obj)
+                };
+                self.indent = stepback;
+                self.stack.pop();
+                rv
+            },
+            _ => Ok(None)
+        }
+    }
+
+    // the abstract operation JO
+    fn serialize_object(&mut self, obj: @mut Object) -> Result<Option<~[u16]>, JsVal> {
+        let keys : ~[JsVal] = match self.property_list {
+            Some(ref list) => list.map(|v| *v),
+            None => vec::map(self.env.ownKeys(obj),
+                             |n| JsVal::from_str(intern_get(*n)))
+        };
+        let mut partial : ~[~[u16]] = ~[];
+        for keys.each |key| {
+            match self.str(*key, JsObject(obj)) {
+                Err(ex) => { return Err(ex); },
+                Ok(None) => {},
+                Ok(Some(s)) => {
+                    let mut member : ~[u16] = ~[];
+                    match self.env.tryToUtf16(*key) {
+                        Ok(k) => quote(&mut member, k),
+                        Err(ex) => { return Err(ex); }
+                    }
+                    member.push(':' as u16);
+                    if !self.gap.is_empty() { member.push(' ' as u16); }
+                    member.push_all(s);
+                    partial.push(member);
+                }
+            }
+        }
+        Ok(Some(self.wrap(partial, '{', '}')))
+    }
+
+    // the abstract operation JA
+    fn serialize_array(&mut self, arr: JsVal) -> Result<Option<~[u16]>, JsVal> {
+        let len = match self.env.tryToUint32(self.env.get_slot_fd(arr, self.env.fdLength)) {
+            Ok(n) => n,
+            Err(ex) => { return Err(ex); }
+        };
+        let mut partial : ~[~[u16]] = ~[];
+        for uint::range(0, len as uint) |i| {
+            match self.str(JsVal::from_str(i.to_str()), arr) {
+                Err(ex) => { return Err(ex); },
+                Ok(None) => partial.push(utf16("null")),
+                Ok(Some(s)) => partial.push(s)
+            }
+        }
+        Ok(Some(self.wrap(partial, '[', ']')))
+    }
+
+    // join the members, indenting if there is a gap.  self.indent is the
+    // indentation of the members; the stepback has one less gap.
+    fn wrap(&self, partial: &[~[u16]], open: char, close: char) -> ~[u16] {
+        let mut out : ~[u16] = ~[open as u16];
+        if !partial.is_empty() {
+            let stepback = self.indent.slice(0, self.indent.len() - self.gap.len());
+            for partial.eachi |i, member| {
+                if i > 0 { out.push(',' as u16); }
+                if !self.gap.is_empty() {
+                    out.push('\n' as u16);
+                    out.push_all(self.indent);
+                }
+                out.push_all(*member);
+            }
+            if !self.gap.is_empty() {
+                out.push('\n' as u16);
+                out.push_all(stepback);
+            }
+        }
+        out.push(close as u16);
+        out
+    }
+}
+
+pub fn stringify(env: Environment, value: JsVal, replacer: JsVal,
+                 space: JsVal) -> JsVal {
+    let mut replacer_fn = None;
+    let mut property_list = None;
+    if env.isCallable(replacer) {
+        replacer_fn = Some(replacer);
+    } else if env.isArray(replacer) {
+        let mut list : ~[JsVal] = ~[];
+        for env.arrayEach(replacer) |v| {
+            let item = match v {
+                JsString(_) => Some(v),
+                JsNumber(n) => Some(JsVal::from_str(numconv::number_to_str(n))),
+                _ => None
+            };
+            match item {
+                Some(JsString(s)) => {
+                    let mut dup = false;
+                    for list.each |l| {
+                        match *l { JsString(t) if s == t => { dup = true; }, _ => {} }
+                    }
+                    if !dup { list.push(JsString(s)); }
+                },
+                _ => {}
+            }
+        }
+        property_list = Some(list);
+    }
+    let gap = match space {
+        JsNumber(_) => {
+            let n = try_native!(env.tryToInteger(space));
+            let n = if n > 10f64 { 10u } else if n < 1f64 { 0u } else { n as uint };
+            vec::from_elem(n, ' ' as u16)
+        },
+        JsString(s) => s.slice(0, uint::min(10, s.len())).to_owned(),
+        _ => ~[]
+    };
+    let mut st = Stringifier {
+        env: env,
+        replacer: replacer_fn,
+        property_list: property_list,
+        gap: gap,
+        indent: ~[],
+        stack: ~[]
+    };
+    let wrapper = Object::create(env.root_map, env.myObject);
+    let empty = JsVal::from_str("");
+    env.set_slot(JsObject(wrapper), empty, value);
+    match st.str(empty, JsObject(wrapper)) {
+        Ok(Some(s)) => JsString(at_vec::to_managed_consume(s)),
+        Ok(None) => JsUndefined,
+        Err(ex) => ex
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quote() {
+        let q = |s: &[u16]| {
+            let mut out : ~[u16] = ~[];
+            quote(&mut out, s);
+            out
+        };
+        // lone surrogates are escaped, valid pairs are kept
+        assert_eq!(q([0xD800, 0x78, 0xD83D, 0xDE00, 0xDC00]),
+                   str::to_utf16("\"\\ud800x") + ~[0xD83D, 0xDE00] +
+                   str::to_utf16("\\udc00\""));
+        assert_eq!(q([0x01, 0x1F, 0x7F]),
+                   str::to_utf16("\"\\u0001\\u001f\x7f\""));
+    }
+}
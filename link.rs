@@ -0,0 +1,273 @@
+// a linker, which merges several modules into one.
+//
+// Function 0 of the linked module runs each module's top-level code in
+// turn, in the same frame, as if the modules were REPL lines or
+// scripts run one after another: a module's return ends only that
+// module, and the last one's return value is the linked module's.  The
+// modules' other functions follow, renumbered, and their literal pools
+// are merged without duplicates.  Source text isn't kept.
+//
+// This suits plain scripts, not CommonJS modules: there is no module
+// table, so the linked modules would share one module and exports, and
+// a require would read its file from disk.  Modules which look up
+// require don't link.
+use core::hashmap::HashMap;
+
+use disasm;
+use function::Function;
+use module::Module;
+use object::{JsVal, JsString};
+use op::*;
+use verify;
+
+pub struct LinkError {
+    module: uint,
+    message: ~str
+}
+
+impl LinkError {
+    pub fn to_str(&self) -> ~str {
+        fmt!("module %u: %s", self.module, self.message)
+    }
+}
+
+// copy a function's bytecode to the end of out, renumbering its
+// literals and functions.  With returns_to_end, each return instead
+// drops the value it would return and jumps past the copy, so the
+// next module's code runs.
+priv fn relink(id: uint, code: &[uint], literals: &[uint], base: uint,
+               returns_to_end: bool, out: &mut ~[uint]) -> Result<(), ~str> {
+    // where each instruction goes
+    let mut pcs = vec::from_elem(code.len(), 0u);
+    let mut at = out.len();
+    let mut pc = 0;
+    while pc < code.len() {
+        let op = Op::new_from_uint(code[pc]);
+        pcs[pc] = at;
+        at += match op {
+            Op_return if returns_to_end => 3,
+            _ => 1 + op.args()
+        };
+        pc += 1 + op.args();
+    }
+    let end = at;
+    pc = 0;
+    while pc < code.len() {
+        let op = Op::new_from_uint(code[pc]);
+        let arg = if op.args() > 0 { code[pc + 1] } else { 0 };
+        match op {
+            Op_return if returns_to_end => {
+                out.push(Op_pop as uint);
+                out.push(Op_jmp as uint);
+                out.push(end);
+            },
+            Op_push_literal | Op_get_slot_direct |
+            Op_get_slot_direct_check | Op_set_slot_direct => {
+                out.push(code[pc]);
+                out.push(literals[arg]);
+            },
+            Op_new_function if arg == 0 => {
+                return Err(fmt!("function %u, pc %u: can't link a reference to the top-level function",
+                                id, pc));
+            },
+            Op_new_function => {
+                out.push(code[pc]);
+                out.push(base + arg - 1);
+            },
+            Op_jmp | Op_jmp_unless => {
+                out.push(code[pc]);
+                out.push(pcs[arg]);
+            },
+            _ => {
+                out.push_all(code.slice(pc, pc + 1 + op.args()));
+            }
+        }
+        pc += 1 + op.args();
+    }
+    Ok(())
+}
+
+// where a module first looks up the variable require, as
+// (function, pc): a get_slot_direct of 'require' on the frame or one of
+// its parents
+priv fn find_require(m: &Module) -> Option<(uint, uint)> {
+    let is_name = |arg: uint, name: &str| match m.literals[arg] {
+        JsString(s) => str::from_utf16(s) == name.to_owned(),
+        _ => false
+    };
+    for m.functions.each |f| {
+        let code = &f.bytecode;
+        // whether the top of the stack is a frame
+        let mut frame = false;
+        let mut pc = 0;
+        while pc < code.len() {
+            let op = Op::new_from_uint(code[pc]);
+            let arg = if op.args() > 0 { code[pc + 1] } else { 0 };
+            frame = match op {
+                Op_push_frame => true,
+                Op_get_slot_direct | Op_get_slot_direct_check
+                    if frame && is_name(arg, "require") => {
+                    return Some((f.id, pc));
+                },
+                Op_get_slot_direct => frame && is_name(arg, "__proto__"),
+                _ => false
+            };
+            pc += 1 + op.args();
+        }
+    }
+    None
+}
+
+pub fn link(modules: &[@Module]) -> Result<Module, LinkError> {
+    if modules.is_empty() {
+        return Err(LinkError { module: 0, message: ~"no modules to link" });
+    }
+    let mut literals : ~[JsVal] = ~[];
+    // literals by how a listing shows them, which tells -0 from 0 and
+    // '1' from 1
+    let mut literal_ids : HashMap<~str, uint> = HashMap::new();
+    let mut entry : ~[uint] = ~[];
+    let mut max_stack = 0;
+    // where each module's top-level code starts in entry
+    let mut starts : ~[uint] = ~[];
+    let mut functions : ~[@Function] = ~[];
+    for modules.eachi |i, m| {
+        let err = |message: ~str| -> Result<Module, LinkError> {
+            Err(LinkError { module: i, message: message })
+        };
+        // relink relies on the bytecode making sense
+        match verify::verify(*m) {
+            Ok(()) => {},
+            Err(e) => { return err(e.to_str()); }
+        }
+        match find_require(*m) {
+            Some((id, pc)) => {
+                return err(fmt!("function %u, pc %u: can't link a module which uses require",
+                                id, pc));
+            },
+            None => {}
+        }
+        let mut ids = ~[];
+        for m.literals.each |l| {
+            let key = disasm::literal_str(*l);
+            let existing = match literal_ids.find(&key) {
+                Some(&id) => Some(id),
+                None => None
+            };
+            ids.push(match existing {
+                Some(id) => id,
+                None => {
+                    literals.push(*l);
+                    literal_ids.insert(key, literals.len() - 1);
+                    literals.len() - 1
+                }
+            });
+        }
+        // this module's function n becomes base + n - 1
+        let base = functions.len() + 1;
+        starts.push(entry.len());
+        let last = i == modules.len() - 1;
+        match relink(0, m.functions[0].bytecode, ids, base, !last, &mut entry) {
+            Ok(()) => {},
+            Err(e) => { return err(e); }
+        }
+        max_stack = uint::max(max_stack, m.functions[0].max_stack);
+        for m.functions.eachi |id, f| {
+            if id == 0 { loop; }
+            let mut bytecode = ~[];
+            match relink(id, f.bytecode, ids, base, false, &mut bytecode) {
+                Ok(()) => {},
+                Err(e) => { return err(e); }
+            }
+            functions.push(@Function {
+                name: copy f.name,
+                id: base + id - 1,
+                nargs: f.nargs,
+                max_stack: f.max_stack,
+                source: None,
+                bytecode: bytecode
+            });
+        }
+    }
+    let linked = Module {
+        functions: ~[@Function {
+            name: None, id: 0, nargs: 0, max_stack: max_stack,
+            source: None, bytecode: entry
+        }] + functions,
+        literals: literals,
+        source: None
+    };
+    // every module verified, but one which returns with more than its
+    // return value on the stack leaves the next running on top of the
+    // rest, which may not
+    match verify::verify(&linked) {
+        Ok(()) => Ok(linked),
+        Err(e) => {
+            let mut module = 0;
+            if e.function == 0 {
+                while module + 1 < starts.len() && starts[module + 1] <= e.pc {
+                    module += 1;
+                }
+            }
+            Err(LinkError { module: module, message: ~"linking made a bad module: " +
+                            e.to_str() })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use disasm;
+    use function::Function;
+    use interp::Interpreter;
+    use module::Module;
+
+    #[test]
+    fn test_link() {
+        let i = Interpreter::new();
+        let line = |src: &str| @Module::new_from_bytes(i.compile_repl(src).get()).unwrap();
+        let modules = ~[
+            line("var x = 40, s = 'x';"),
+            line("var f = function(y) { return x + y; }, g = function() { return f; };"),
+            line("s"), // its return only ends this module
+            line("g()(2) + s")
+        ];
+        let linked = link(modules).unwrap();
+        assert_eq!(linked.functions.len(), 3);
+        // 'x' the string and x the variable share a literal
+        let mut xs = 0;
+        for linked.literals.each |l| {
+            if disasm::literal_str(*l) == ~"\"x\"" { xs += 1; }
+        }
+        assert_eq!(xs, 1);
+        let shipped = @Module::new_from_bytes(linked.to_bytes()).unwrap();
+        // run it somewhere which has seen none of it
+        let j = Interpreter::new();
+        assert_eq!(j.run_module(@linked).to_str(), ~"42x");
+        let k = Interpreter::new();
+        assert_eq!(k.run_module(shipped).to_str(), ~"42x");
+
+        let one = |bytecode: ~[uint]| @Module {
+            functions: ~[@Function { name: None, id: 0, nargs: 0, max_stack: 1,
+                                     source: None, bytecode: bytecode }],
+            literals: ~[],
+            source: None
+        };
+        let message = |modules: ~[@Module]| match link(modules) {
+            Ok(_) => ~"ok",
+            Err(e) => e.to_str()
+        };
+        assert_eq!(message(~[]), ~"module 0: no modules to link");
+        assert_eq!(message(~[modules[0], one(~[11])]),
+                   ~"module 1: function 0, pc 0: pops more than the 0 values on the stack");
+        assert_eq!(message(~[one(~[4, 0, 11])]),
+                   ~"module 0: function 0, pc 0: can't link a reference to the top-level function");
+        // there's no module table for require to look in
+        let requires = line("var f = function() { return require('./b'); };");
+        assert!(message(~[modules[0], requires]).starts_with(
+            "module 1: function 1, pc "));
+        assert!(message(~[requires]).ends_with(": can't link a module which uses require"));
+        assert_eq!(message(~[line("var o = {}; o.require = 1;")]), ~"ok");
+    }
+}
@@ -0,0 +1,222 @@
+// CommonJS modules.
+//
+// require(name) finds name relative to the directory of the file which
+// calls it (trying name.js as well), compiles it as the Interpreter
+// compiles the main script and runs it in a frame of its own, in which
+// `require`, `module` and `exports` are defined; whatever it leaves in
+// module.exports is what require returns.  Files are cached by their
+// absolute, normalized path, so each runs once.  As in node, a module
+// goes into the cache before it runs: a circular require gets whatever
+// the module has exported so far, and a module which throws is taken
+// out again.
+use core::hashmap::HashMap;
+
+use bcompile;
+use interp::{Environment, compile_module};
+use intern::intern;
+use module::Module;
+use object::*;
+
+pub struct Loader {
+    env: Environment,
+    compile_from_source: JsVal,
+    compiler: @mut bcompile::Compiler,
+    // the Interpreter's native_compiler, which it updates before it
+    // runs anything
+    native_compiler: @mut bool,
+    // the top-level frame, which each file's frame inherits from
+    frame: @mut Object,
+    // module objects, by path
+    cache: @mut HashMap<~str, JsVal>
+}
+
+pub fn new_loader(env: Environment, compile_from_source: JsVal,
+                  compiler: @mut bcompile::Compiler,
+                  frame: @mut Object) -> @Loader {
+    @Loader {
+        env: env,
+        compile_from_source: compile_from_source,
+        compiler: compiler,
+        native_compiler: @mut false,
+        frame: frame,
+        cache: @mut HashMap::new()
+    }
+}
+
+// define require in frame, for code in the directory dir
+pub fn add_require(loader: @Loader, frame: @mut Object, dir: Path) {
+    let dir = @dir;
+    do loader.env.add_native_func_str(frame, frame, "require") |_this, args| {
+        match getarg(args, 0) {
+            JsString(name) => require(loader, dir, str::from_utf16(name)),
+            v => loader.env.throwError("TypeError", fmt!(
+                "require needs a module name, not %s", loader.env.toString(v)))
+        }
+    };
+}
+
+// run source as the module in the file at path (the main script, which
+// we've read already), returning what it returns
+pub fn run_main(loader: @Loader, path: &str, source: &str) -> JsVal {
+    match compile(loader, source) {
+        Ok(m) => run_main_module(loader, path, m),
+        Err(ex) => ex
+    }
+}
+
+// the same for a main script compiled earlier, say read from a
+// bytecode file
+pub fn run_main_module(loader: @Loader, path: &str, m: @Module) -> JsVal {
+    let path = os::make_absolute(&Path(path)).normalize();
+    let module = new_module(loader.env, &path);
+    loader.cache.insert(path.to_str(), module);
+    run_compiled(loader, &path, m, module)
+}
+
+// the file a require of name from dir means, if there is one
+priv fn resolve(dir: &Path, name: &str) -> Option<Path> {
+    let given = Path(name);
+    let path = if given.is_absolute { given } else { dir.push_rel(&given) };
+    let path = os::make_absolute(&path).normalize();
+    let candidates = ~[copy path, Path(path.to_str() + ".js")];
+    for candidates.each |p| {
+        if os::path_exists(p) && !os::path_is_dir(p) {
+            return Some(copy *p);
+        }
+    }
+    None
+}
+
+priv fn require(loader: @Loader, dir: &Path, name: ~str) -> JsVal {
+    let env = loader.env;
+    let path = match resolve(dir, name) {
+        Some(p) => p,
+        None => {
+            return env.throwError("Error", fmt!("Cannot find module '%s'", name));
+        }
+    };
+    let key = path.to_str();
+    let cached = match loader.cache.find(&key) {
+        Some(&module) => Some(module),
+        None => None
+    };
+    match cached {
+        // loaded, or still loading
+        Some(module) => { return env.get_slot(module, JsVal::from_str("exports")); },
+        None => {}
+    }
+    let source = match io::read_whole_file_str(&path) {
+        Ok(s) => s,
+        Err(msg) => {
+            return env.throwError("Error", fmt!("Cannot read %s: %s", key, msg));
+        }
+    };
+    let module = new_module(env, &path);
+    loader.cache.insert(copy key, module);
+    match run_file(loader, &path, source, module) {
+        JsThrown(ex) => {
+            loader.cache.remove(&key);
+            JsThrown(ex)
+        },
+        _ => env.get_slot(module, JsVal::from_str("exports"))
+    }
+}
+
+// the module object for the file at path, with empty exports
+priv fn new_module(env: Environment, path: &Path) -> JsVal {
+    let module = JsObject(Object::create(env.root_map, env.myObject));
+    let exports = JsObject(Object::create(env.root_map, env.myObject));
+    env.set_slot(module, JsVal::from_str("id"), JsVal::from_str(path.to_str()));
+    env.set_slot(module, JsVal::from_str("exports"), exports);
+    env.set_slot(module, JsVal::from_str("loaded"), JsBool(false));
+    module
+}
+
+priv fn compile(loader: @Loader, source: &str) -> Result<@Module, JsVal> {
+    compile_module(&loader.env, loader.compile_from_source, loader.compiler,
+                   *loader.native_compiler, false, source)
+}
+
+priv fn run_file(loader: @Loader, path: &Path, source: &str,
+                 module: JsVal) -> JsVal {
+    match compile(loader, source) {
+        Ok(m) => run_compiled(loader, path, m, module),
+        Err(ex) => ex
+    }
+}
+
+priv fn run_compiled(loader: @Loader, path: &Path, m: @Module,
+                     module: JsVal) -> JsVal {
+    let env = loader.env;
+    // this is exports at the top level, as in node
+    let exports = env.get_slot(module, JsVal::from_str("exports"));
+    let frame = Object::create(env.root_map, loader.frame);
+    frame.set(FieldDesc { name: intern("this"), hidden: false }, exports);
+    frame.set(FieldDesc { name: intern("module"), hidden: false }, module);
+    frame.set(FieldDesc { name: intern("exports"), hidden: false }, exports);
+    add_require(loader, frame, path.dir_path());
+    let rv = env.interpret(m, 0, Some(frame));
+    match rv {
+        JsThrown(_) => {},
+        _ => { env.set_slot(module, JsVal::from_str("loaded"), JsBool(true)); }
+    }
+    rv
+}
+
+#[cfg(test)]
+mod tests {
+    use interp::Interpreter;
+    use module::Module;
+
+    fn write(dir: &Path, name: &str, source: &str) {
+        match io::file_writer(&dir.push_rel(&Path(name)),
+                              [io::Create, io::Truncate]) {
+            Ok(writer) => writer.write_str(source),
+            Err(msg) => fail!(msg)
+        }
+    }
+
+    #[test]
+    fn test_require() {
+        let dir = os::tmpdir().push(fmt!("rustyturtle-require-%d", os::getpid() as int));
+        assert!(os::make_dir(&dir, 0x1ff));
+        assert!(os::make_dir(&dir.push("lib"), 0x1ff));
+        // node's example of a cycle: b sees a half-loaded a
+        write(&dir, "a.js", "exports.done = false; var b = require('./b.js'); exports.fromB = b.done; exports.done = true;");
+        write(&dir, "b.js", "exports.done = false; var a = require('./a'); exports.sawA = a.done; exports.done = true;");
+        write(&dir, "inc.js", "module.exports = function(x) { return x + 1; };");
+        write(&dir, "lib/twice.js", "var inc = require('../inc'); exports.twice = function(x) { return inc(inc(x)); };");
+        write(&dir, "bad.js", "Object.Throw('bad');");
+        write(&dir, "mod.js", "exports.r = 7 % 4;");
+        let i = Interpreter::new();
+        let run = |source: &str| {
+            i.interpret_file(dir.push("main.js").to_str(), source).to_str()
+        };
+        assert_eq!(run("{ var a = require('./a'), b = require('./b'); return a.done + ' ' + a.fromB + ' ' + b.sawA + ' ' + b.done; }"),
+                   ~"true true false true");
+        assert_eq!(run("{ return require('./b') === require('./lib/../b.js'); }"), ~"true");
+        assert_eq!(run("{ return require('./lib/twice').twice(1) + require('./inc')(0); }"), ~"4");
+        assert_eq!(run("{ var err; Object.Try(this, function() { require('./missing'); }, function(e) { err = e.message; }); return err; }"),
+                   ~"Cannot find module './missing'");
+        assert_eq!(run("{ var err; Object.Try(this, function() { require('./bad'); }, function(e) { err = e; }); return err; }"),
+                   ~"bad");
+        // the REPL has require too
+        assert_eq!(i.repl(fmt!("require('%s').twice(5)",
+                               dir.push_many([~"lib", ~"twice.js"]).to_str())).to_str(), ~"7");
+        // a precompiled main script is a module as well
+        let main = "{ return require('./lib/twice').twice(module.exports === exports ? 1 : 0); }";
+        let m = @Module::new_from_bytes(i.compile(main).get()).unwrap();
+        assert_eq!(i.run_file_module(dir.push("main.tsbc").to_str(), m).to_str(), ~"3");
+        // and required files are compiled as the main script is
+        let mut n = Interpreter::new();
+        n.native_compiler = true;
+        let main = dir.push("main.js").to_str();
+        assert_eq!(n.interpret_file(main, "{ return require('./mod').r; }").to_str(), ~"3");
+        assert_eq!(i.interpret_file(main, "{ return require('./mod').r; }").to_str(), ~"3");
+        for [~"a.js", ~"b.js", ~"inc.js", ~"lib/twice.js", ~"bad.js", ~"mod.js"].each |f| {
+            os::remove_file(&dir.push_rel(&Path(*f)));
+        }
+        os::remove_dir(&dir.push("lib"));
+        os::remove_dir(&dir);
+    }
+}
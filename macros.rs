@@ -0,0 +1,8 @@
+// macros for the whole crate.  main.rc declares this module first, and
+// with #[macro_escape], so that every module after it can use them.
+
+// unwrap a conversion's result in a native function, or return the
+// exception it threw
+macro_rules! try_native(
+    ($e:expr) => (match $e { Ok(v) => v, Err(e) => { return e; } })
+)
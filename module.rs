@@ -1,8 +1,50 @@
 use function::Function;
-use object::{JsVal,JsNumber,JsBool,JsUndefined,JsNull};
+use numconv;
+use object::{JsVal,JsNumber,JsString,JsBool,JsUndefined,JsNull};
 
 use startup_init = startup::init;
 
+// module files start with MAGIC and then the format version.  The
+// compiler's own output has no header, and is version 0; version 1
+// only added the header.  A format change gets a new version, which
+// decode reads alongside the old ones, and upgrade rewrites old bytes
+// in the current version.
+pub static MAGIC: &'static [u8] = &[0, 84, 83, 66, 67]; // "\0TSBC"
+pub static VERSION: uint = 1;
+
+// why some bytes aren't a module, and where the problem starts
+pub struct DecodeError {
+    offset: uint,
+    message: ~str
+}
+
+impl DecodeError {
+    pub fn to_str(&self) -> ~str {
+        fmt!("byte %u: %s", self.offset, self.message)
+    }
+}
+
+// why a module's source doesn't go with its bytes
+pub enum SourceError {
+    // the bytes aren't a module at all
+    BadModule(DecodeError),
+    // the function spans don't match the module's functions
+    BadSpans(~str)
+}
+
+impl SourceError {
+    pub fn to_str(&self) -> ~str {
+        match *self {
+            BadModule(ref e) => e.to_str(),
+            BadSpans(ref message) => copy *message
+        }
+    }
+}
+
+macro_rules! try_decode(
+    ($e:expr) => (match $e { Ok(v) => v, Err(e) => { return Err(e); } })
+)
+
 // utility structure
 priv struct Reader {
     buf : ~[u8],
@@ -12,26 +54,111 @@ impl Reader {
     fn new(buf : ~[u8]) -> Reader {
         Reader { buf : buf, pos : 0 }
     }
-    fn decode_uint(&mut self) -> uint {
-        let val = self.buf[self.pos] as uint;
-        self.pos += 1;
-        if val < 128 { return val; }
-        (val - 128u) + (128u * self.decode_uint())
+    fn error<T>(&self, offset : uint, message : ~str) -> Result<T, DecodeError> {
+        Err(DecodeError { offset : offset, message : message })
+    }
+    fn decode_uint(&mut self) -> Result<uint, DecodeError> {
+        let start = self.pos;
+        let mut val = 0u;
+        let mut scale = 1u;
+        loop {
+            if self.pos >= self.buf.len() {
+                return self.error(self.pos, ~"unexpected end of module");
+            }
+            let byte = self.buf[self.pos] as uint;
+            self.pos += 1;
+            let digit = if byte < 128 { byte } else { byte - 128 };
+            if digit > (uint::max_value - val) / scale {
+                return self.error(start, ~"number too large");
+            }
+            val += digit * scale;
+            if byte < 128 { return Ok(val); }
+            if scale > uint::max_value / 128 {
+                return self.error(start, ~"number too large");
+            }
+            scale *= 128;
+        }
     }
-    fn decode_str(&mut self) -> ~str {
-        let len = self.decode_uint();
+    // a count of things which take at least a byte each, so we don't
+    // try to allocate more than could possibly follow
+    fn decode_len(&mut self) -> Result<uint, DecodeError> {
+        let start = self.pos;
+        let len = try_decode!(self.decode_uint());
+        if len > self.buf.len() - self.pos {
+            return self.error(start, fmt!("length %u runs past the end of the module", len));
+        }
+        Ok(len)
+    }
+    // a string, as JavaScript sees it: UTF-16 code units, which
+    // needn't be valid Unicode
+    fn decode_utf16(&mut self) -> Result<~[u16], DecodeError> {
+        let start = self.pos;
+        let len = try_decode!(self.decode_len());
         let mut utf16 : ~[u16] = vec::with_capacity(len);
         while vec::len(utf16) < len {
-            vec::push(&mut utf16, self.decode_uint() as u16);
+            let c = try_decode!(self.decode_uint());
+            if c > 0xFFFF {
+                return self.error(start, fmt!("bad UTF-16 code unit %u", c));
+            }
+            vec::push(&mut utf16, c as u16);
         }
-        str::from_utf16(utf16)
+        Ok(utf16)
+    }
+    // a string we need as a ~str
+    fn decode_str(&mut self) -> Result<~str, DecodeError> {
+        let start = self.pos;
+        let utf16 = try_decode!(self.decode_utf16());
+        let len = utf16.len();
+        // from_utf16 fails on unpaired surrogates
+        let mut i = 0;
+        while i < len {
+            let c = utf16[i];
+            if c >= 0xD800 && c <= 0xDBFF && i + 1 < len &&
+                utf16[i+1] >= 0xDC00 && utf16[i+1] <= 0xDFFF {
+                i += 2;
+            } else if c >= 0xD800 && c <= 0xDFFF {
+                return self.error(start, ~"unpaired surrogate in string");
+            } else {
+                i += 1;
+            }
+        }
+        Ok(str::from_utf16(utf16))
+    }
+}
+
+// the inverse of Reader
+priv struct Writer {
+    buf : ~[u8]
+}
+impl Writer {
+    fn new() -> Writer {
+        let mut writer = Writer { buf : MAGIC.to_owned() };
+        writer.encode_uint(VERSION);
+        writer
+    }
+    fn encode_uint(&mut self, val : uint) {
+        if val < 128 {
+            self.buf.push(val as u8);
+        } else {
+            self.buf.push(((val % 128) + 128) as u8);
+            self.encode_uint(val / 128);
+        }
+    }
+    fn encode_utf16(&mut self, utf16 : &[u16]) {
+        self.encode_uint(utf16.len());
+        for utf16.each |c| { self.encode_uint(*c as uint); }
+    }
+    fn encode_str(&mut self, s : &str) {
+        self.encode_utf16(str::to_utf16(s));
     }
 }
 
 // this represents a compilation unit (which can be as small as a function)
 pub struct Module {
     functions: ~[@Function],
-    literals: ~[JsVal]
+    literals: ~[JsVal],
+    // the source text, if we compiled it ourselves
+    source: Option<@[u16]>
 }
 
 impl Module {
@@ -39,60 +166,257 @@ impl Module {
         let mut functions : ~[@Function] = ~[];
         let mut literals : ~[JsVal] = ~[];
         startup_init(&mut functions, &mut literals);
-        Module { functions: functions, literals: literals }
+        Module { functions: functions, literals: literals, source: None }
     }
 
-    pub fn new_from_bytes(buf : ~[u8]) -> Module {
+    pub fn new_from_bytes(buf : ~[u8]) -> Result<Module, DecodeError> {
         let mut reader = Reader::new(buf);
+        let has_magic = reader.buf.len() >= MAGIC.len() &&
+            vec::eq(reader.buf.slice(0, MAGIC.len()), MAGIC);
+        let version = if has_magic {
+            reader.pos = MAGIC.len();
+            try_decode!(reader.decode_uint())
+        } else {
+            0
+        };
+        match version {
+            0 | 1 => Module::decode_body(&mut reader),
+            _ => reader.error(MAGIC.len(), fmt!(
+                "module format version %u is newer than this interpreter's %u",
+                version, VERSION))
+        }
+    }
+
+    // a module compiled from the given source, so that functions can
+    // find their text; spans gives the (start, end) of each function
+    // after the first, as the compiler reports them.
+    pub fn new_from_source(buf : ~[u8], source: &str,
+                           spans: ~[(uint, uint)]) -> Result<Module, SourceError> {
+        let module = match Module::new_from_bytes(buf) {
+            Ok(m) => m,
+            Err(e) => { return Err(BadModule(e)); }
+        };
+        let source = at_vec::to_managed_consume(str::to_utf16(source));
+        // function 0 is the module body, which has no span
+        if spans.len() + 1 != module.functions.len() {
+            return Err(BadSpans(fmt!("%u functions, but the source has %u",
+                                     module.functions.len(), spans.len() + 1)));
+        }
+        for spans.each |&(a, b)| {
+            if a > b || b > source.len() {
+                return Err(BadSpans(fmt!(
+                    "function span %u-%u is outside the source", a, b)));
+            }
+        }
+        let functions = do module.functions.mapi |i, f| {
+            if i == 0 {
+                *f
+            } else {
+                @Function {
+                    name: f.name,
+                    id: f.id,
+                    nargs: f.nargs,
+                    max_stack: f.max_stack,
+                    source: Some(spans[i - 1]),
+                    bytecode: copy f.bytecode
+                }
+            }
+        };
+        Ok(Module { functions: functions, literals: module.literals,
+                    source: Some(source) })
+    }
+
+    // rewrite a module from an older version of the format
+    pub fn upgrade(buf : ~[u8]) -> Result<~[u8], DecodeError> {
+        let module = try_decode!(Module::new_from_bytes(buf));
+        Ok(module.to_bytes())
+    }
+
+    priv fn decode_body(reader : &mut Reader) -> Result<Module, DecodeError> {
         // parse functions
-        let num_funcs = reader.decode_uint();
+        let num_funcs = try_decode!(reader.decode_len());
         let mut functions : ~[@Function] = vec::with_capacity(num_funcs);
         let mut func_id = 0;
         while func_id < num_funcs {
-            let nargs = reader.decode_uint();
-            let max_stack = reader.decode_uint();
-            let name = reader.decode_str();
-            let blen = reader.decode_uint();
+            let nargs = try_decode!(reader.decode_uint());
+            let max_stack = try_decode!(reader.decode_uint());
+            let name = try_decode!(reader.decode_utf16());
+            let blen = try_decode!(reader.decode_len());
             let mut bytecode : ~[uint] = vec::with_capacity(blen);
             while vec::len(bytecode) < blen {
-                vec::push(&mut bytecode, reader.decode_uint());
+                vec::push(&mut bytecode, try_decode!(reader.decode_uint()));
             }
             vec::push(&mut functions, @Function {
-                name: if str::is_empty(name) { None } else { Some(name) },
+                name: if name.is_empty() {
+                    None
+                } else {
+                    Some(at_vec::to_managed_consume(name))
+                },
                 id: func_id,
                 nargs: nargs,
                 max_stack: max_stack,
+                source: None,
                 bytecode: bytecode
             });
             func_id += 1;
         }
         // parse literals
-        let num_lits = reader.decode_uint();
+        let num_lits = try_decode!(reader.decode_len());
         let mut literals : ~[JsVal] = vec::with_capacity(num_lits);
         while vec::len(literals) < num_lits {
-            let l = match reader.decode_uint() {
+            let start = reader.pos;
+            let l = match try_decode!(reader.decode_uint()) {
                 0 => { // number tag
-                    let num = reader.decode_str();
+                    let num = try_decode!(reader.decode_str());
                     if "Infinity" == num { // xxx rust doesn't allow commutative
                         JsNumber(f64::infinity)
                     } else if "-Infinity" == num {
                         JsNumber(f64::neg_infinity)
+                    } else if "-0" == num { // which from_str makes 0
+                        JsNumber(-0f64)
                     } else {
                         match f64::from_str(num) {
                             Some(f) => JsNumber(f),
-                            _ => fail!()
+                            _ => {
+                                return reader.error(start, fmt!("bad number %s", num));
+                            }
                         }
                     }
                 },
-                1 => JsVal::from_str(reader.decode_str()), // string tag
+                1 => JsString(at_vec::to_managed_consume(
+                    try_decode!(reader.decode_utf16()))), // string tag
                 2 => JsBool(true), // boolean tags
                 3 => JsBool(false),
                 4 => JsNull,
                 5 => JsUndefined,
-                _ => fail!()
+                tag => {
+                    return reader.error(start, fmt!("unknown literal tag %u", tag));
+                }
             };
             vec::push(&mut literals, l);
         }
-        Module { functions: functions, literals: literals }
+        if reader.pos < reader.buf.len() {
+            return reader.error(reader.pos, ~"trailing bytes after the literals");
+        }
+        Ok(Module { functions: functions, literals: literals, source: None })
+    }
+
+    // the bytes new_from_bytes reads this module from.  The source
+    // text isn't part of them.
+    pub fn to_bytes(&self) -> ~[u8] {
+        let mut writer = Writer::new();
+        writer.encode_uint(self.functions.len());
+        for self.functions.each |f| {
+            writer.encode_uint(f.nargs);
+            writer.encode_uint(f.max_stack);
+            match f.name {
+                Some(name) => writer.encode_utf16(name),
+                None => writer.encode_str("")
+            }
+            writer.encode_uint(f.bytecode.len());
+            for f.bytecode.each |w| { writer.encode_uint(*w); }
+        }
+        writer.encode_uint(self.literals.len());
+        for self.literals.each |l| {
+            match *l {
+                JsNumber(n) => {
+                    writer.encode_uint(0);
+                    // number_to_str drops the sign of -0
+                    writer.encode_str(if numconv::is_negative_zero(n) {
+                        ~"-0"
+                    } else {
+                        numconv::number_to_str(n)
+                    });
+                },
+                JsString(s) => {
+                    writer.encode_uint(1);
+                    writer.encode_utf16(s);
+                },
+                JsBool(true) => writer.encode_uint(2),
+                JsBool(false) => writer.encode_uint(3),
+                JsNull => writer.encode_uint(4),
+                JsUndefined => writer.encode_uint(5),
+                _ => fail!(~"not a literal")
+            }
+        }
+        writer.buf
+    }
+
+    // the source text of a function, if we have it
+    pub fn function_source(&self, f: &Function) -> Option<@[u16]> {
+        match (self.source, f.source) {
+            (Some(src), Some((start, end))) =>
+                Some(at_vec::from_fn(end - start, |i| src[start + i])),
+            _ => None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Module;
+    use object::JsString;
+
+    #[test]
+    fn test_to_bytes() {
+        // big varints, a name, and one literal of each kind
+        let buf = ~[1, 0, 200, 1, 1, 102, 1, 172, 2,
+                    7, 0, 2, 45, 48, 0, 3, 49, 46, 53, 1, 1, 233, 1,
+                    2, 3, 4, 5];
+        assert_eq!(Module::new_from_bytes(copy buf).unwrap().to_bytes(),
+                   ~[0, 84, 83, 66, 67, 1] + buf);
+        let startup = Module::new_startup_module().to_bytes();
+        assert_eq!(Module::new_from_bytes(copy startup).unwrap().to_bytes(), startup);
+        // old modules, without the header, get one
+        assert_eq!(Module::upgrade(~[0, 0]).get(), ~[0, 84, 83, 66, 67, 1, 0, 0]);
+    }
+
+    #[test]
+    fn test_unpaired_surrogates() {
+        // strings and function names are UTF-16, valid Unicode or not
+        let buf = ~[1, 0, 0, 1, 128, 176, 3, 0, 1, 1, 1, 128, 184, 3];
+        let module = Module::new_from_bytes(copy buf).unwrap();
+        assert_eq!(module.functions[0].name, Some(@[0xD800]));
+        match module.literals[0] {
+            JsString(s) => assert_eq!(s, @[0xDC00]),
+            _ => fail!()
+        }
+        assert_eq!(module.to_bytes(), ~[0, 84, 83, 66, 67, 1] + buf);
+    }
+
+    fn decode_error(buf: ~[u8]) -> ~str {
+        match Module::new_from_bytes(buf) {
+            Ok(_) => ~"ok",
+            Err(e) => e.to_str()
+        }
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        assert_eq!(decode_error(~[1, 0]), ~"byte 2: unexpected end of module");
+        assert_eq!(decode_error(vec::from_elem(12, 255u8)), ~"byte 0: number too large");
+        assert_eq!(decode_error(~[100]),
+                   ~"byte 0: length 100 runs past the end of the module");
+        assert_eq!(decode_error(~[0, 1, 9]), ~"byte 2: unknown literal tag 9");
+        assert_eq!(decode_error(~[0, 1, 0, 1, 120]), ~"byte 2: bad number x");
+        assert_eq!(decode_error(~[0, 1, 0, 1, 128, 176, 3]),
+                   ~"byte 3: unpaired surrogate in string");
+        assert_eq!(decode_error(~[0, 0, 7]), ~"byte 2: trailing bytes after the literals");
+        assert_eq!(decode_error(~[0, 84, 83, 66, 67, 2, 0, 0]),
+                   ~"byte 5: module format version 2 is newer than this interpreter's 1");
+        assert_eq!(decode_error(~[0, 84, 83, 66, 67, 1, 0, 0]), ~"ok");
+        // the source must account for every function
+        let one = ~[1, 0, 0, 0, 0, 0];
+        let error = |spans: ~[(uint, uint)]| match Module::new_from_source(copy one, "xy", spans) {
+            Ok(m) => str::from_utf16(m.function_source(&*m.functions[0]).get_or_default(@[])),
+            Err(e) => e.to_str()
+        };
+        assert_eq!(error(~[]), ~"");
+        assert_eq!(error(~[(0, 2)]), ~"1 functions, but the source has 2");
+        // and bad bytes are still reported where they are
+        match Module::new_from_source(~[1, 0], "xy", ~[]) {
+            Ok(_) => fail!(~"decoded a truncated module"),
+            Err(e) => assert_eq!(e.to_str(), ~"byte 2: unexpected end of module")
+        }
     }
 }
@@ -0,0 +1,887 @@
+// number/string conversion, following ECMAScript 5 section 9.8.1
+// (Number::toString), 15.7.4.5-7 (toFixed, toExponential, toPrecision)
+// and 15.1.2.2-3 (parseInt, parseFloat).
+//
+// Digits are generated exactly using a small arbitrary-precision integer
+// type, using the "free-format" algorithm of Steele & White / Burger &
+// Dybvig for the shortest round-trip representation.  This matches the
+// output of the major browser engines byte-for-byte.  In the other
+// direction, decimal strings are converted with correct rounding.
+
+// little-endian base 2^32 unsigned integer; just enough to do digit
+// generation.
+priv struct Bignum {
+    d: ~[u32]
+}
+impl Bignum {
+    fn from_u64(n: u64) -> Bignum {
+        let mut b = Bignum { d: ~[n as u32, (n >> 32) as u32] };
+        b.trim();
+        b
+    }
+    fn copy(&self) -> Bignum {
+        Bignum { d: self.d.clone() }
+    }
+    fn trim(&mut self) {
+        while self.d.len() > 0 && self.d[self.d.len()-1] == 0 {
+            self.d.pop();
+        }
+    }
+    fn mul_small(&mut self, m: u32) {
+        let mut carry = 0u64;
+        for uint::range(0, self.d.len()) |i| {
+            let t = (self.d[i] as u64) * (m as u64) + carry;
+            self.d[i] = t as u32;
+            carry = t >> 32;
+        }
+        if carry > 0 { self.d.push(carry as u32); }
+    }
+    fn mul_pow2(&mut self, n: uint) {
+        let bits = n % 32;
+        if bits > 0 {
+            let mut carry = 0u32;
+            for uint::range(0, self.d.len()) |i| {
+                let t = self.d[i];
+                self.d[i] = (t << bits) | carry;
+                carry = t >> (32 - bits);
+            }
+            if carry > 0 { self.d.push(carry); }
+        }
+        let words = n / 32;
+        if words > 0 && self.d.len() > 0 {
+            let mut nd = vec::from_elem(words, 0u32);
+            nd.push_all(self.d);
+            self.d = nd;
+        }
+    }
+    fn mul_pow10(&mut self, n: uint) {
+        let mut i = n;
+        while i >= 9 { self.mul_small(1000000000u32); i -= 9; }
+        while i > 0 { self.mul_small(10u32); i -= 1; }
+    }
+    fn add(&mut self, other: &Bignum) {
+        let mut carry = 0u64;
+        let mut i = 0u;
+        while i < other.d.len() || carry > 0 {
+            if i == self.d.len() { self.d.push(0u32); }
+            let o = if i < other.d.len() { other.d[i] as u64 } else { 0u64 };
+            let t = (self.d[i] as u64) + o + carry;
+            self.d[i] = t as u32;
+            carry = t >> 32;
+            i += 1;
+        }
+    }
+    // requires self >= other
+    fn sub(&mut self, other: &Bignum) {
+        let mut borrow = 0i64;
+        for uint::range(0, self.d.len()) |i| {
+            let o = if i < other.d.len() { other.d[i] as i64 } else { 0i64 };
+            let mut t = (self.d[i] as i64) - o - borrow;
+            if t < 0 { t += 1i64 << 32; borrow = 1; } else { borrow = 0; }
+            self.d[i] = t as u32;
+        }
+        assert_eq!(borrow, 0);
+        self.trim();
+    }
+    // returns -1, 0, or 1
+    fn compare(&self, other: &Bignum) -> int {
+        if self.d.len() != other.d.len() {
+            return if self.d.len() < other.d.len() { -1 } else { 1 };
+        }
+        let mut i = self.d.len();
+        while i > 0 {
+            i -= 1;
+            if self.d[i] != other.d[i] {
+                return if self.d[i] < other.d[i] { -1 } else { 1 };
+            }
+        }
+        0
+    }
+    // compare (self + other) to third
+    fn plus_compare(&self, other: &Bignum, third: &Bignum) -> int {
+        let mut sum = self.copy();
+        sum.add(other);
+        sum.compare(third)
+    }
+    fn add_small(&mut self, n: u32) {
+        let mut carry = n as u64;
+        let mut i = 0u;
+        while carry > 0 {
+            if i == self.d.len() { self.d.push(0u32); }
+            let t = (self.d[i] as u64) + carry;
+            self.d[i] = t as u32;
+            carry = t >> 32;
+            i += 1;
+        }
+    }
+    fn bit_len(&self) -> uint {
+        let n = self.d.len();
+        if n == 0 { return 0; }
+        let mut top = self.d[n-1];
+        let mut bits = 0u;
+        while top > 0 { top >>= 1; bits += 1; }
+        (n - 1) * 32 + bits
+    }
+    fn bit(&self, i: uint) -> bool {
+        (self.d[i / 32] >> (i % 32)) & 1 == 1
+    }
+    // convert to the nearest double, breaking ties toward even
+    fn to_f64(&self) -> f64 {
+        let len = self.bit_len();
+        if len > 1024 { return f64::infinity; }
+        if len <= 53 {
+            let mut v = 0f64;
+            let mut i = self.d.len();
+            while i > 0 { i -= 1; v = v * 4294967296f64 + (self.d[i] as f64); }
+            return v;
+        }
+        // take the top 53 bits, plus a rounding bit and a sticky bit
+        let mut m = 0u64;
+        for uint::range(0, 53) |i| {
+            m = (m << 1) | (if self.bit(len - 1 - i) { 1 } else { 0 });
+        }
+        let round = self.bit(len - 54);
+        let mut sticky = false;
+        for uint::range(0, len - 54) |i| {
+            if self.bit(i) { sticky = true; break; }
+        }
+        if round && (sticky || (m & 1) == 1) { m += 1; }
+        // m may have carried to 2^53; that is still exact
+        (m as f64) * f64::pow(2f64, (len - 53) as f64)
+    }
+    // replace self with (self*10) mod s and return (self*10)/s, which
+    // must be less than 10.
+    fn next_digit(&mut self, s: &Bignum) -> u8 {
+        self.mul_small(10u32);
+        let mut d = 0u8;
+        while self.compare(s) >= 0 {
+            self.sub(s);
+            d += 1;
+        }
+        d
+    }
+}
+
+// split a positive finite double into an integer significand and a
+// binary exponent, such that v = f * 2^e.
+priv fn decode(v: f64) -> (u64, int) {
+    let bits : u64 = unsafe { cast::transmute(v) };
+    let biased = ((bits >> 52) & 0x7ff) as int;
+    let frac = bits & 0xfffffffffffffu64;
+    if biased == 0 {
+        (frac, -1074)
+    } else {
+        (frac | (1u64 << 52), biased - 1075)
+    }
+}
+
+pub fn is_negative_zero(v: f64) -> bool {
+    let bits : u64 = unsafe { cast::transmute(v) };
+    v == 0f64 && (bits >> 63) != 0
+}
+
+// first estimate of k such that 10^(k-1) <= v < 10^k; may be off by one,
+// which the callers correct for.
+priv fn estimate_exponent(v: f64) -> int {
+    f64::ceil(f64::log10(v)) as int
+}
+
+// Computes the shortest digit string d1 d2 ... dn which uniquely
+// identifies the positive finite double v, along with the decimal
+// exponent k such that v is (approximately) 0.d1d2...dn * 10^k.
+pub fn shortest_digits(v: f64) -> (~[u8], int) {
+    assert!(v > 0f64 && v.is_finite());
+    let (f, e) = decode(v);
+    // the boundaries of v are included iff the significand is even
+    // (since then round-to-even reading will produce v).
+    let even = (f & 1) == 0;
+    let lower_closer = (f == (1u64 << 52)) && e > -1074;
+    let mut r = Bignum::from_u64(f);
+    let mut s = Bignum::from_u64(1);
+    let mut mp = Bignum::from_u64(1);
+    let mut mm = Bignum::from_u64(1);
+    if e >= 0 {
+        r.mul_pow2(e as uint);
+        mp.mul_pow2(e as uint);
+        mm.mul_pow2(e as uint);
+        if lower_closer {
+            r.mul_pow2(2); s.mul_pow2(2); mp.mul_pow2(1);
+        } else {
+            r.mul_pow2(1); s.mul_pow2(1);
+        }
+    } else {
+        if lower_closer {
+            r.mul_pow2(2); s.mul_pow2((2 - e) as uint); mp.mul_pow2(1);
+        } else {
+            r.mul_pow2(1); s.mul_pow2((1 - e) as uint);
+        }
+    }
+    let mut k = estimate_exponent(v);
+    if k >= 0 {
+        s.mul_pow10(k as uint);
+    } else {
+        r.mul_pow10((-k) as uint);
+        mp.mul_pow10((-k) as uint);
+        mm.mul_pow10((-k) as uint);
+    }
+    // fix up the estimate so that the high boundary is below 10^k
+    let too_low = |c: int| if even { c >= 0 } else { c > 0 };
+    while too_low(r.plus_compare(&mp, &s)) {
+        s.mul_small(10u32);
+        k += 1;
+    }
+    loop {
+        let mut high = r.copy();
+        high.add(&mp);
+        high.mul_small(10u32);
+        if too_low(high.compare(&s)) { break; }
+        r.mul_small(10u32);
+        mp.mul_small(10u32);
+        mm.mul_small(10u32);
+        k -= 1;
+    }
+    // generate digits
+    let mut digits : ~[u8] = ~[];
+    loop {
+        let d = r.next_digit(&s);
+        mp.mul_small(10u32);
+        mm.mul_small(10u32);
+        let c1 = r.compare(&mm);
+        let tc1 = if even { c1 <= 0 } else { c1 < 0 };
+        let tc2 = too_low(r.plus_compare(&mp, &s));
+        if !tc1 && !tc2 {
+            digits.push(d);
+            loop;
+        }
+        if tc1 && tc2 {
+            // both d and d+1 are in range; pick the closer one, breaking
+            // ties toward the even digit.
+            let c = r.plus_compare(&r, &s);
+            if c < 0 || (c == 0 && (d % 2) == 0) {
+                digits.push(d);
+            } else {
+                digits.push(d + 1);
+            }
+        } else if tc1 {
+            digits.push(d);
+        } else {
+            digits.push(d + 1);
+        }
+        break;
+    }
+    (digits, k)
+}
+
+// Computes exactly `count` digits of the positive finite double v,
+// rounding the last one half-up (as toFixed, toExponential and
+// toPrecision require).  Returns the digits and the decimal exponent k
+// such that v is (approximately) 0.d1d2...dn * 10^k.  If `absolute` is
+// true, `count` is instead the number of digits to produce after the
+// decimal point, and fewer (or no) digits may be returned.
+pub fn fixed_digits(v: f64, count: int, absolute: bool) -> (~[u8], int) {
+    assert!(v > 0f64 && v.is_finite());
+    let (f, e) = decode(v);
+    let mut r = Bignum::from_u64(f);
+    let mut s = Bignum::from_u64(1);
+    if e >= 0 { r.mul_pow2(e as uint); } else { s.mul_pow2((-e) as uint); }
+    let mut k = estimate_exponent(v);
+    if k >= 0 { s.mul_pow10(k as uint); } else { r.mul_pow10((-k) as uint); }
+    // fix up the estimate so that 0.1 <= r/s < 1
+    while r.compare(&s) >= 0 {
+        s.mul_small(10u32);
+        k += 1;
+    }
+    loop {
+        let mut r10 = r.copy();
+        r10.mul_small(10u32);
+        if r10.compare(&s) >= 0 { break; }
+        r = r10;
+        k -= 1;
+    }
+    let n = if absolute { k + count } else { count };
+    let mut digits : ~[u8] = ~[];
+    if n < 0 {
+        // v rounds to zero
+        return (digits, k);
+    }
+    for uint::range(0, n as uint) |_| {
+        digits.push(r.next_digit(&s));
+    }
+    // round half-up
+    if r.plus_compare(&r, &s) >= 0 {
+        let mut i = digits.len();
+        loop {
+            if i == 0 {
+                // carried out of the leading digit: 99.9 -> 100
+                digits.unshift(1u8);
+                k += 1;
+                if digits.len() > 1 && !absolute { digits.pop(); }
+                break;
+            }
+            i -= 1;
+            if digits[i] == 9 {
+                digits[i] = 0;
+            } else {
+                digits[i] += 1;
+                break;
+            }
+        }
+    }
+    (digits, k)
+}
+
+// compare digits*10^e with m*2^k exactly
+priv fn compare_dyadic(digits: &Bignum, e: int, m: u64, k: int) -> int {
+    let mut lhs = digits.copy();
+    let mut rhs = Bignum::from_u64(m);
+    if e >= 0 { lhs.mul_pow10(e as uint); } else { rhs.mul_pow10((-e) as uint); }
+    if k >= 0 { rhs.mul_pow2(k as uint); } else { lhs.mul_pow2((-k) as uint); }
+    lhs.compare(&rhs)
+}
+
+priv fn prev_double(v: f64) -> f64 {
+    let bits : u64 = unsafe { cast::transmute(v) };
+    unsafe { cast::transmute(bits - 1) }
+}
+
+// Convert the decimal number 0.d1d2...dn * 10^(n+e) (that is, the integer
+// d1d2...dn times 10^e) to the nearest double, breaking ties toward even.
+// Digit strings may be arbitrarily long.
+pub fn decimal_to_number(digits: &[u8], e: int) -> f64 {
+    // strip leading and trailing zeros
+    let mut start = 0u;
+    while start < digits.len() && digits[start] == 0 { start += 1; }
+    if start == digits.len() { return 0f64; }
+    let mut end = digits.len();
+    let mut e = e;
+    while digits[end-1] == 0 { end -= 1; e += 1; }
+    let digits = digits.slice(start, end);
+    let n = digits.len() as int;
+    // quick checks for overflow and underflow
+    if n + e > 310 { return f64::infinity; }
+    if n + e < -324 { return 0f64; }
+    let mut big = Bignum::from_u64(0);
+    for digits.each |d| { big.mul_small(10u32); big.add_small(*d as u32); }
+    if e >= 0 {
+        let mut b = big.copy();
+        b.mul_pow10(e as uint);
+        return b.to_f64();
+    }
+    // make a first approximation from the first 19 digits, then correct
+    // it by comparing against the exact value.
+    let taken = if n > 19 { 19 } else { n };
+    let mut w = 0u64;
+    for uint::range(0, taken as uint) |i| { w = w * 10 + (digits[i] as u64); }
+    let e2 = e + n - taken;
+    let mut b = if e2 < -300 {
+        ((w as f64) * f64::pow(10f64, (e2 + 40) as f64)) * 1e-40f64
+    } else if e2 > 300 {
+        ((w as f64) * f64::pow(10f64, (e2 - 20) as f64)) * 1e20f64
+    } else if e2 < 0 {
+        (w as f64) / f64::pow(10f64, (-e2) as f64)
+    } else {
+        (w as f64) * f64::pow(10f64, e2 as f64)
+    };
+    loop {
+        if b == f64::infinity {
+            // is the value below the midpoint of f64::max_value and 2^1024?
+            if compare_dyadic(&big, e, (1u64 << 54) - 1, 970) < 0 {
+                b = f64::max_value;
+                loop;
+            }
+            return b;
+        }
+        let (m, k) = decode(b);
+        let c = compare_dyadic(&big, e, 2*m + 1, k - 1);
+        if c > 0 || (c == 0 && (m & 1) == 1) {
+            b = next_double(b);
+            loop;
+        }
+        if m == 0 { return b; }
+        let c = if m == (1u64 << 52) && k > -1074 {
+            compare_dyadic(&big, e, 4*m - 1, k - 2)
+        } else {
+            compare_dyadic(&big, e, 2*m - 1, k - 1)
+        };
+        if c < 0 || (c == 0 && (m & 1) == 1) {
+            b = prev_double(b);
+            loop;
+        }
+        return b;
+    }
+}
+
+// ToUint32 applied to a number (ES5 9.6); ToInt32 is the same bits
+pub fn to_uint32(v: f64) -> u32 {
+    if !v.is_finite() { return 0; }
+    let n = if v < 0f64 { -f64::floor(-v) } else { f64::floor(v) };
+    let m = n % 4294967296f64;
+    (if m < 0f64 { m + 4294967296f64 } else { m }) as u32
+}
+
+// WhiteSpace and LineTerminator, as used by StrWhiteSpaceChar (ES5 9.3.1)
+pub fn is_str_whitespace(c: u16) -> bool {
+    match c {
+        0x09 | 0x0A | 0x0B | 0x0C | 0x0D | 0x20 | 0xA0 | 0x1680 | 0x180E |
+        0x2028 | 0x2029 | 0x202F | 0x205F | 0x3000 | 0xFEFF => true,
+        _ => c >= 0x2000 && c <= 0x200A
+    }
+}
+
+priv fn digit_value(c: u16) -> uint {
+    let c = c as uint;
+    if c >= ('0' as uint) && c <= ('9' as uint) { c - ('0' as uint) }
+    else if c >= ('a' as uint) && c <= ('z' as uint) { c - ('a' as uint) + 10 }
+    else if c >= ('A' as uint) && c <= ('Z' as uint) { c - ('A' as uint) + 10 }
+    else { 36 }
+}
+
+priv fn skip_whitespace(s: &[u16]) -> uint {
+    let mut i = 0u;
+    while i < s.len() && is_str_whitespace(s[i]) { i += 1; }
+    i
+}
+
+priv fn starts_with_at(s: &[u16], i: uint, prefix: &str) -> bool {
+    let p = str::to_utf16(prefix);
+    if i + p.len() > s.len() { return false; }
+    for p.eachi |j, c| {
+        if s[i + j] != *c { return false; }
+    }
+    true
+}
+
+// the global parseInt function (ES5 15.1.2.2), given the string and the
+// result of ToInt32(radix).
+pub fn parse_int(s: &[u16], radix: i32) -> f64 {
+    let mut i = skip_whitespace(s);
+    let mut sign = 1f64;
+    if i < s.len() && s[i] == ('-' as u16) { sign = -1f64; }
+    if i < s.len() && (s[i] == ('-' as u16) || s[i] == ('+' as u16)) {
+        i += 1;
+    }
+    let mut r = radix as uint;
+    let mut strip_prefix = true;
+    if radix != 0 {
+        if radix < 2 || radix > 36 { return f64::NaN; }
+        if radix != 16 { strip_prefix = false; }
+    } else {
+        r = 10;
+    }
+    if strip_prefix && (starts_with_at(s, i, "0x") ||
+                        starts_with_at(s, i, "0X")) {
+        i += 2;
+        r = 16;
+    }
+    let mut digits : ~[u8] = ~[];
+    while i < s.len() && digit_value(s[i]) < r {
+        digits.push(digit_value(s[i]) as u8);
+        i += 1;
+    }
+    if digits.is_empty() { return f64::NaN; }
+    let v = if r == 10 {
+        decimal_to_number(digits, 0)
+    } else {
+        // exact for every radix, not just the powers of two
+        let mut big = Bignum::from_u64(0);
+        for digits.each |d| { big.mul_small(r as u32); big.add_small(*d as u32); }
+        big.to_f64()
+    };
+    sign * v
+}
+
+// the longest prefix of s[start..] which satisfies
+// StrUnsignedDecimalLiteral (ES5 9.3.1), as its value and the index
+// just past it.
+priv fn scan_unsigned_decimal(s: &[u16], start: uint) -> Option<(f64, uint)> {
+    let is_digit = |i: uint| i < s.len() && digit_value(s[i]) < 10;
+    let mut i = start;
+    if starts_with_at(s, i, "Infinity") { return Some((f64::infinity, i + 8)); }
+    let mut digits : ~[u8] = ~[];
+    let mut e = 0i;
+    let mut any = false;
+    while is_digit(i) {
+        digits.push(digit_value(s[i]) as u8);
+        any = true;
+        i += 1;
+    }
+    if i < s.len() && s[i] == ('.' as u16) && (any || is_digit(i + 1)) {
+        i += 1;
+        while is_digit(i) {
+            digits.push(digit_value(s[i]) as u8);
+            e -= 1;
+            any = true;
+            i += 1;
+        }
+    }
+    if !any { return None; }
+    if i < s.len() && (s[i] == ('e' as u16) || s[i] == ('E' as u16)) {
+        let mut j = i + 1;
+        let mut esign = 1i;
+        if j < s.len() && (s[j] == ('-' as u16) || s[j] == ('+' as u16)) {
+            if s[j] == ('-' as u16) { esign = -1; }
+            j += 1;
+        }
+        if is_digit(j) {
+            let mut exp = 0i;
+            while is_digit(j) {
+                // saturate; anything this large is already 0 or Infinity
+                if exp < 100000 { exp = exp * 10 + (digit_value(s[j]) as int); }
+                j += 1;
+            }
+            e += esign * exp;
+            i = j;
+        }
+    }
+    Some((decimal_to_number(digits, e), i))
+}
+
+// an optional sign at s[i], as a multiplier and the index past it
+priv fn scan_sign(s: &[u16], i: uint) -> (f64, uint) {
+    if i < s.len() && s[i] == ('-' as u16) { (-1f64, i + 1) }
+    else if i < s.len() && s[i] == ('+' as u16) { (1f64, i + 1) }
+    else { (1f64, i) }
+}
+
+// the global parseFloat function (ES5 15.1.2.3): the longest prefix of
+// the trimmed string which satisfies StrDecimalLiteral.
+pub fn parse_float(s: &[u16]) -> f64 {
+    let (sign, i) = scan_sign(s, skip_whitespace(s));
+    match scan_unsigned_decimal(s, i) {
+        Some((v, _)) => sign * v,
+        None => f64::NaN
+    }
+}
+
+// ToNumber applied to the String type (ES5 9.3.1): unlike parseFloat,
+// the whole trimmed string must be a numeric literal.
+pub fn string_to_number(s: &[u16]) -> f64 {
+    let start = skip_whitespace(s);
+    let mut end = s.len();
+    while end > start && is_str_whitespace(s[end - 1]) { end -= 1; }
+    let s = s.slice(start, end);
+    if s.is_empty() { return 0f64; }
+    if starts_with_at(s, 0, "0x") || starts_with_at(s, 0, "0X") {
+        if s.len() == 2 { return f64::NaN; }
+        for uint::range(2, s.len()) |i| {
+            if digit_value(s[i]) >= 16 { return f64::NaN; }
+        }
+        return parse_int(s, 16);
+    }
+    let (sign, i) = scan_sign(s, 0);
+    match scan_unsigned_decimal(s, i) {
+        Some((v, end)) if end == s.len() => sign * v,
+        _ => f64::NaN
+    }
+}
+
+priv fn digit_char(d: u8) -> char {
+    "0123456789abcdefghijklmnopqrstuvwxyz"[d as uint] as char
+}
+
+priv fn push_digits(s: &mut ~str, digits: &[u8]) {
+    for digits.each |d| { s.push_char(digit_char(*d)); }
+}
+
+priv fn push_zeros(s: &mut ~str, n: int) {
+    let mut i = 0;
+    while i < n { s.push_char('0'); i += 1; }
+}
+
+priv fn push_exponent(s: &mut ~str, e: int) {
+    s.push_char('e');
+    s.push_char(if e < 0 { '-' } else { '+' });
+    s.push_str(int::abs(e).to_str());
+}
+
+// digits with a decimal point after the first
+priv fn push_mantissa(s: &mut ~str, digits: &[u8]) {
+    s.push_char(digit_char(digits[0]));
+    if digits.len() > 1 {
+        s.push_char('.');
+        push_digits(s, digits.slice(1, digits.len()));
+    }
+}
+
+// ECMAScript ToString applied to the Number type (section 9.8.1)
+pub fn number_to_str(v: f64) -> ~str {
+    if v.is_NaN() { return ~"NaN"; }
+    if v == 0f64 { return ~"0"; } // includes -0
+    if v < 0f64 { return ~"-" + number_to_str(-v); }
+    if v == f64::infinity { return ~"Infinity"; }
+    let (digits, n) = shortest_digits(v);
+    let k = digits.len() as int;
+    let mut s = ~"";
+    if k <= n && n <= 21 {
+        push_digits(&mut s, digits);
+        push_zeros(&mut s, n - k);
+    } else if 0 < n && n <= 21 {
+        push_digits(&mut s, digits.slice(0, n as uint));
+        s.push_char('.');
+        push_digits(&mut s, digits.slice(n as uint, k as uint));
+    } else if -6 < n && n <= 0 {
+        s.push_str("0.");
+        push_zeros(&mut s, -n);
+        push_digits(&mut s, digits);
+    } else {
+        push_mantissa(&mut s, digits);
+        push_exponent(&mut s, n - 1);
+    }
+    s
+}
+
+priv fn next_double(v: f64) -> f64 {
+    let bits : u64 = unsafe { cast::transmute(v) };
+    unsafe { cast::transmute(bits + 1) }
+}
+
+// Number.prototype.toString(radix) for radix != 10.  The spec leaves the
+// algorithm implementation-dependent; this is the one used by V8 (and
+// matched by the other engines), which prints the shortest fraction
+// that still distinguishes v from its neighbors.
+pub fn number_to_str_radix(v: f64, radix: uint) -> ~str {
+    assert!(radix >= 2 && radix <= 36);
+    if radix == 10 { return number_to_str(v); }
+    if v.is_NaN() { return ~"NaN"; }
+    if v == 0f64 { return ~"0"; }
+    if v < 0f64 { return ~"-" + number_to_str_radix(-v, radix); }
+    if v == f64::infinity { return ~"Infinity"; }
+    let fradix = radix as f64;
+    let mut integer = f64::floor(v);
+    let mut fraction = v - integer;
+    // we only compute fractional digits up to the input double's precision
+    let mut delta = 0.5f64 * (next_double(v) - v);
+    let min_delta = next_double(0f64);
+    if delta < min_delta { delta = min_delta; }
+    let mut fdigits : ~[u8] = ~[];
+    if fraction >= delta {
+        loop {
+            fraction *= fradix;
+            delta *= fradix;
+            let digit = fraction as u8;
+            fdigits.push(digit);
+            fraction -= digit as f64;
+            if fraction > 0.5f64 || (fraction == 0.5f64 && (digit & 1) == 1) {
+                if fraction + delta > 1f64 {
+                    // round up and stop
+                    loop {
+                        if fdigits.is_empty() {
+                            integer += 1f64;
+                            break;
+                        }
+                        let d = fdigits.pop();
+                        if ((d as uint) + 1) < radix {
+                            fdigits.push(d + 1);
+                            break;
+                        }
+                        // otherwise carry into the previous digit
+                    }
+                    break;
+                }
+            }
+            if fraction < delta { break; }
+        }
+    }
+    // integer part, built in reverse
+    let mut idigits : ~[u8] = ~[];
+    // past 2^53 the low digits are not significant; use zeros.
+    loop {
+        let (_, e) = decode(integer / fradix);
+        if e <= 0 { break; }
+        integer /= fradix;
+        idigits.push(0u8);
+    }
+    loop {
+        let remainder = integer % fradix;
+        idigits.push(remainder as u8);
+        integer = (integer - remainder) / fradix;
+        if !(integer > 0f64) { break; }
+    }
+    vec::reverse(idigits);
+    let mut s = ~"";
+    push_digits(&mut s, idigits);
+    if fdigits.len() > 0 {
+        s.push_char('.');
+        push_digits(&mut s, fdigits);
+    }
+    s
+}
+
+// Number.prototype.toFixed; `f` has already been range-checked.
+pub fn to_fixed(v: f64, f: uint) -> ~str {
+    if v.is_NaN() { return ~"NaN"; }
+    let mut x = v;
+    let mut s = ~"";
+    if x < 0f64 { s.push_char('-'); x = -x; }
+    if x >= 1e21f64 { return number_to_str(v); }
+    // m is the decimal representation of round(x * 10^f)
+    let mut m = ~"";
+    if x == 0f64 {
+        m.push_char('0');
+    } else {
+        let (digits, k) = fixed_digits(x, f as int, true);
+        if digits.len() == 0 {
+            m.push_char('0');
+        } else {
+            push_digits(&mut m, digits);
+            // after a carry we may be one digit short
+            push_zeros(&mut m, k + (f as int) - (digits.len() as int));
+        }
+    }
+    if f != 0 {
+        let k = m.len();
+        if k <= f {
+            let mut z = ~"";
+            push_zeros(&mut z, (f + 1 - k) as int);
+            z.push_str(m);
+            m = z;
+        }
+        let a = m.len() - f;
+        s.push_str(m.slice(0, a));
+        s.push_char('.');
+        s.push_str(m.slice(a, m.len()));
+    } else {
+        s.push_str(m);
+    }
+    s
+}
+
+// Number.prototype.toExponential; `f` has already been range-checked,
+// and is None if the fractionDigits argument was undefined.
+pub fn to_exponential(v: f64, f: Option<uint>) -> ~str {
+    if v.is_NaN() { return ~"NaN"; }
+    let mut x = v;
+    let mut s = ~"";
+    if x < 0f64 { s.push_char('-'); x = -x; }
+    if x == f64::infinity { s.push_str("Infinity"); return s; }
+    let (digits, e) = if x == 0f64 {
+        (vec::from_elem(f.get_or_default(0) + 1, 0u8), 0)
+    } else {
+        let (digits, k) = match f {
+            None => shortest_digits(x),
+            Some(f) => fixed_digits(x, (f as int) + 1, false)
+        };
+        (digits, k - 1)
+    };
+    push_mantissa(&mut s, digits);
+    push_exponent(&mut s, e);
+    s
+}
+
+// Number.prototype.toPrecision; `p` has already been range-checked.
+pub fn to_precision(v: f64, p: uint) -> ~str {
+    if v.is_NaN() { return ~"NaN"; }
+    let mut x = v;
+    let mut s = ~"";
+    if x < 0f64 { s.push_char('-'); x = -x; }
+    if x == f64::infinity { s.push_str("Infinity"); return s; }
+    let (digits, e) = if x == 0f64 {
+        (vec::from_elem(p, 0u8), 0)
+    } else {
+        let (digits, k) = fixed_digits(x, p as int, false);
+        (digits, k - 1)
+    };
+    let p = p as int;
+    if e < -6 || e >= p {
+        push_mantissa(&mut s, digits);
+        push_exponent(&mut s, e);
+    } else if e == p - 1 {
+        push_digits(&mut s, digits);
+    } else if e >= 0 {
+        push_digits(&mut s, digits.slice(0, (e + 1) as uint));
+        s.push_char('.');
+        push_digits(&mut s, digits.slice((e + 1) as uint, digits.len()));
+    } else {
+        s.push_str("0.");
+        push_zeros(&mut s, -(e + 1));
+        push_digits(&mut s, digits);
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_number_to_str() {
+        assert_eq!(number_to_str(0f64), ~"0");
+        assert_eq!(number_to_str(-0f64), ~"0");
+        assert_eq!(number_to_str(42f64), ~"42");
+        assert_eq!(number_to_str(-1.5f64), ~"-1.5");
+        assert_eq!(number_to_str(0.1f64 + 0.2f64), ~"0.30000000000000004");
+        assert_eq!(number_to_str(1f64/3f64), ~"0.3333333333333333");
+        assert_eq!(number_to_str(1e21f64), ~"1e+21");
+        assert_eq!(number_to_str(123456789012345680000f64),
+                   ~"123456789012345680000");
+        assert_eq!(number_to_str(0.000001f64), ~"0.000001");
+        assert_eq!(number_to_str(1e-7f64), ~"1e-7");
+        assert_eq!(number_to_str(1.7976931348623157e308f64),
+                   ~"1.7976931348623157e+308");
+        assert_eq!(number_to_str(5e-324f64), ~"5e-324");
+        assert_eq!(number_to_str(9007199254740992f64), ~"9007199254740992");
+    }
+
+    #[test]
+    fn test_number_to_str_radix() {
+        assert_eq!(number_to_str_radix(255f64, 16), ~"ff");
+        assert_eq!(number_to_str_radix(-255f64, 2), ~"-11111111");
+        assert_eq!(number_to_str_radix(0.5f64, 2), ~"0.1");
+        assert_eq!(number_to_str_radix(35f64, 36), ~"z");
+    }
+
+    #[test]
+    fn test_fixed() {
+        assert_eq!(to_fixed(1.005f64, 2), ~"1.00");
+        assert_eq!(to_fixed(2.5f64, 0), ~"3");
+        assert_eq!(to_fixed(-1.5f64, 0), ~"-2");
+        assert_eq!(to_fixed(0.000001f64, 2), ~"0.00");
+        assert_eq!(to_fixed(0.5f64, 0), ~"1");
+        assert_eq!(to_fixed(9.995f64, 2), ~"9.99");
+        assert_eq!(to_fixed(99.995f64, 1), ~"100.0");
+        assert_eq!(to_fixed(1e21f64, 2), ~"1e+21");
+        assert_eq!(to_exponential(123.456f64, Some(2)), ~"1.23e+2");
+        assert_eq!(to_exponential(0f64, None), ~"0e+0");
+        assert_eq!(to_exponential(0.00015f64, None), ~"1.5e-4");
+        assert_eq!(to_precision(123456f64, 2), ~"1.2e+5");
+        assert_eq!(to_precision(0.00001f64, 1), ~"0.00001");
+        assert_eq!(to_precision(0.000001234f64, 2), ~"0.0000012");
+        assert_eq!(to_precision(1e-7f64, 1), ~"1e-7");
+        assert_eq!(to_precision(99.99f64, 3), ~"100");
+    }
+
+    #[test]
+    fn test_parse() {
+        let p = |s: &str| parse_float(str::to_utf16(s));
+        assert_eq!(p("0.1"), 0.1f64);
+        assert_eq!(p("  -0.5e1x"), -5f64);
+        assert_eq!(p("1.7976931348623157e308"), 1.7976931348623157e308f64);
+        assert_eq!(p("1.7976931348623159e308"), f64::infinity);
+        assert_eq!(p("4.9e-324"), 5e-324f64);
+        assert_eq!(p("2e-324"), 0f64);
+        assert_eq!(p("0.30000000000000004"), 0.1f64 + 0.2f64);
+        assert_eq!(p("9007199254740993"), 9007199254740992f64);
+        assert!(p("e5").is_NaN());
+        let i = |s: &str, r: i32| parse_int(str::to_utf16(s), r);
+        assert_eq!(i("0x1F", 0), 31f64);
+        assert_eq!(i("-0x1F", 16), -31f64);
+        assert_eq!(i("0x1F", 10), 0f64);
+        assert_eq!(i("11z", 0), 11f64);
+        assert_eq!(i("4294967296", 0), 4294967296f64);
+        assert_eq!(i("18446744073709551617", 0), 18446744073709551616f64);
+        assert!(i("10", 1).is_NaN());
+        let n = |s: &str| string_to_number(str::to_utf16(s));
+        assert_eq!(n(" \t12.5e1\n"), 125f64);
+        assert_eq!(n(""), 0f64);
+        assert_eq!(n("   "), 0f64);
+        assert_eq!(n("-Infinity"), f64::neg_infinity);
+        assert_eq!(n("0x1f"), 31f64);
+        assert_eq!(n(".5"), 0.5f64);
+        assert_eq!(n("5."), 5f64);
+        assert!(n("12px").is_NaN());
+        assert!(n("-0x1f").is_NaN());
+        assert!(n("0x").is_NaN());
+        assert!(n("1e").is_NaN());
+        assert!(n("inf").is_NaN());
+    }
+}
@@ -2,6 +2,9 @@
 use function::Function;
 use intern::IString;
 use module::Module;
+use numconv;
+use regexp::Regex;
+use collections::Collection;
 
 // this describes the fields in the object map.
 // we use some fields for internal implementation details (like the Function
@@ -49,6 +52,17 @@ impl ObjectMap {
     pub fn new() -> ObjectMap {
         ObjectMap { fields: ~[], children: ~[] }
     }
+    // the names of the fields visible to user code, in insertion order.
+    // hidden fields and __proto__ are skipped.
+    pub fn visible_names(&self) -> ~[IString] {
+        let mut names : ~[IString] = ~[];
+        for self.fields.each |f| {
+            if !f.hidden && *f != FieldDesc::proto() {
+                names.push(f.name);
+            }
+        }
+        names
+    }
     fn with_field(&mut self, desc: FieldDesc) -> @mut ObjectMap {
         assert_eq!(self.find(desc), None);
         let pos : uint;
@@ -146,6 +160,33 @@ impl Object {
             }
         }
     }
+
+    // remove a field, returning false if it wasn't present.
+    pub fn delete(&mut self, root_map: &mut ObjectMap, desc: FieldDesc) -> bool {
+        self.delete_fields(root_map, |f| f == desc) > 0
+    }
+
+    // remove every field pred picks, returning how many went.  The new
+    // map is rebuilt from the root, once, so that objects with the same
+    // fields continue to share maps.
+    pub fn delete_fields(&mut self, root_map: &mut ObjectMap,
+                         pred: &fn(FieldDesc) -> bool) -> uint {
+        let old_fields = self.map.fields.clone();
+        // __proto__ is always the first field, and can't be removed
+        if !vec::any(old_fields.tail(), |f| pred(*f)) { return 0; }
+        let mut map = root_map.with_field(old_fields[0]);
+        let mut fields : ~[JsVal] = ~[self.fields[0]];
+        for uint::range(1, old_fields.len()) |i| {
+            if !pred(old_fields[i]) {
+                map = map.with_field(old_fields[i]);
+                fields.push(self.fields[i]);
+            }
+        }
+        let removed = self.fields.len() - fields.len();
+        self.map = map;
+        self.fields = fields;
+        removed
+    }
 }
 
 pub struct InterpretedFunction {
@@ -153,6 +194,12 @@ pub struct InterpretedFunction {
     function: @Function
 }
 pub type NativeFunction = @fn(JsVal, ~[JsVal]) -> JsVal;
+// the result of Function.prototype.bind
+pub struct BoundFunction {
+    target: @mut Object,
+    this: JsVal,
+    args: ~[JsVal]
+}
 
 pub enum JsVal {
     JsObject(@mut Object),
@@ -165,15 +212,16 @@ pub enum JsVal {
     JsThrown(@JsVal), // thrown exception by reference so we don't bloat type
     // not visible to user code
     JsFunctionCode(@InterpretedFunction),
-    JsNativeFunction(NativeFunction)
+    JsNativeFunction(NativeFunction),
+    JsBoundFunction(@BoundFunction),
+    JsRegExp(@Regex),
+    JsCollection(@mut Collection)
 }
 impl JsVal {
     pub fn to_str(self) -> ~str {
         match(self) {
             JsObject(_) => ~"[object]",
-            JsNumber(n) if n==f64::infinity => ~"Infinity",
-            JsNumber(n) if n==f64::neg_infinity => ~"-Infinity",
-            JsNumber(n) => n.to_str(),
+            JsNumber(n) => numconv::number_to_str(n),
             JsString(utf16) => str::from_utf16(utf16),
             JsBool(b) => if b { ~"true" } else { ~"false" },
             JsUndefined => ~"undefined",
@@ -181,7 +229,10 @@ impl JsVal {
             // special values
             JsThrown(v) => fmt!("[thrown %s]", v.to_str()),
             JsFunctionCode(_) => ~"[function]", // xxx use f.name
-            JsNativeFunction(_) => ~"[native function]"
+            JsNativeFunction(_) => ~"[native function]",
+            JsBoundFunction(_) => ~"[bound function]",
+            JsRegExp(_) => ~"[regexp]",
+            JsCollection(_) => ~"[collection]"
         }
     }
     pub fn to_uint(self) -> Option<uint> {
@@ -206,6 +257,11 @@ impl JsVal {
     }
 }
 
+// a native function's i'th argument, or undefined if it wasn't passed
+pub fn getarg(args: &[JsVal], i: uint) -> JsVal {
+    if args.len() > i { args[i] } else { JsUndefined }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -255,5 +311,31 @@ mod tests {
         assert!( myArray.contains(fdHiddenFoo) );
         assert_eq!(myArray.get(fdHiddenFoo).to_str(), ~"42");
         assert_eq!(myArray.get(fdFoo).to_str(), ~"bar");
+
+        // visible names skip __proto__ and hidden fields
+        assert_eq!(myArray.map.visible_names(),
+                   ~[intern("length"), intern("foo")]);
+
+        // deleting a field shares the map of an object built without it
+        let fdBar = FieldDesc { name: intern("bar"), hidden: false };
+        let o1 = Object::create(root_map, myObject);
+        o1.set(fdFoo, JsNumber(1f64));
+        let o2 = Object::create(root_map, myObject);
+        o2.set(fdBar, JsNumber(2f64));
+        o2.set(fdFoo, JsNumber(1f64));
+        assert!( o2.delete(root_map, fdBar) );
+        assert!( !o2.delete(root_map, fdBar) );
+        assert!( !o2.contains(fdBar) );
+        assert_eq!(o2.get(fdFoo).to_str(), ~"1");
+        assert!( ptr::ref_eq(o1.map, o2.map) );
+        // and so does deleting several at once
+        o2.set(fdBar, JsNumber(2f64));
+        o2.set(fdHiddenFoo, JsNumber(3f64));
+        assert_eq!(o2.delete_fields(root_map, |f| f.name == intern("foo")), 2);
+        assert_eq!(o2.delete_fields(root_map, |f| f.name == intern("foo")), 0);
+        assert_eq!(o2.get(fdBar).to_str(), ~"2");
+        let o3 = Object::create(root_map, myObject);
+        o3.set(fdBar, JsNumber(2f64));
+        assert!( ptr::ref_eq(o2.map, o3.map) );
     }
 }
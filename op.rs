@@ -1,4 +1,5 @@
-// generated by TurtleScript write-rust-ops.js
+// first generated by TurtleScript write-rust-ops.js, and maintained by
+// hand since: the opcodes from un_bitnot on are rusty-turtle's own.
 
 pub enum Op {
   Op_push_frame = 0,
@@ -30,7 +31,21 @@ pub enum Op {
   Op_bi_add = 26,
   Op_bi_sub = 27,
   Op_bi_mul = 28,
-  Op_bi_div = 29
+  Op_bi_div = 29,
+  Op_un_bitnot = 30,
+  Op_bi_mod = 31,
+  Op_bi_bitand = 32,
+  Op_bi_bitor = 33,
+  Op_bi_bitxor = 34,
+  Op_bi_lsh = 35,
+  Op_bi_rsh = 36,
+  Op_bi_ursh = 37,
+  Op_un_void = 38,
+  Op_bi_in = 39,
+  Op_bi_instanceof = 40,
+  Op_bi_lt = 41,
+  Op_bi_lte = 42,
+  Op_bi_loose_eq = 43
 }
 
 impl Op {
@@ -65,7 +80,21 @@ impl Op {
       Op_bi_add => 0,
       Op_bi_sub => 0,
       Op_bi_mul => 0,
-      Op_bi_div => 0
+      Op_bi_div => 0,
+      Op_un_bitnot => 0,
+      Op_bi_mod => 0,
+      Op_bi_bitand => 0,
+      Op_bi_bitor => 0,
+      Op_bi_bitxor => 0,
+      Op_bi_lsh => 0,
+      Op_bi_rsh => 0,
+      Op_bi_ursh => 0,
+      Op_un_void => 0,
+      Op_bi_in => 0,
+      Op_bi_instanceof => 0,
+      Op_bi_lt => 0,
+      Op_bi_lte => 0,
+      Op_bi_loose_eq => 0
     }
   }
   pub fn stackpush(&self) -> uint {
@@ -99,7 +128,21 @@ impl Op {
       Op_bi_add => 1,
       Op_bi_sub => 1,
       Op_bi_mul => 1,
-      Op_bi_div => 1
+      Op_bi_div => 1,
+      Op_un_bitnot => 1,
+      Op_bi_mod => 1,
+      Op_bi_bitand => 1,
+      Op_bi_bitor => 1,
+      Op_bi_bitxor => 1,
+      Op_bi_lsh => 1,
+      Op_bi_rsh => 1,
+      Op_bi_ursh => 1,
+      Op_un_void => 1,
+      Op_bi_in => 1,
+      Op_bi_instanceof => 1,
+      Op_bi_lt => 1,
+      Op_bi_lte => 1,
+      Op_bi_loose_eq => 1
     }
   }
   pub fn stackpop(&self, args: &[int]) -> uint {
@@ -133,7 +176,69 @@ impl Op {
       Op_bi_add => 2,
       Op_bi_sub => 2,
       Op_bi_mul => 2,
-      Op_bi_div => 2
+      Op_bi_div => 2,
+      Op_un_bitnot => 1,
+      Op_bi_mod => 2,
+      Op_bi_bitand => 2,
+      Op_bi_bitor => 2,
+      Op_bi_bitxor => 2,
+      Op_bi_lsh => 2,
+      Op_bi_rsh => 2,
+      Op_bi_ursh => 2,
+      Op_un_void => 1,
+      Op_bi_in => 2,
+      Op_bi_instanceof => 2,
+      Op_bi_lt => 2,
+      Op_bi_lte => 2,
+      Op_bi_loose_eq => 2
+    }
+  }
+  pub fn name(&self) -> &'static str {
+    match *self {
+      Op_push_frame => "push_frame",
+      Op_push_literal => "push_literal",
+      Op_new_object => "new_object",
+      Op_new_array => "new_array",
+      Op_new_function => "new_function",
+      Op_get_slot_direct => "get_slot_direct",
+      Op_get_slot_indirect => "get_slot_indirect",
+      Op_get_slot_direct_check => "get_slot_direct_check",
+      Op_set_slot_direct => "set_slot_direct",
+      Op_set_slot_indirect => "set_slot_indirect",
+      Op_invoke => "invoke",
+      Op_return => "return",
+      Op_jmp => "jmp",
+      Op_jmp_unless => "jmp_unless",
+      Op_pop => "pop",
+      Op_dup => "dup",
+      Op_2dup => "2dup",
+      Op_over => "over",
+      Op_over2 => "over2",
+      Op_swap => "swap",
+      Op_un_not => "un_not",
+      Op_un_minus => "un_minus",
+      Op_un_typeof => "un_typeof",
+      Op_bi_eq => "bi_eq",
+      Op_bi_gt => "bi_gt",
+      Op_bi_gte => "bi_gte",
+      Op_bi_add => "bi_add",
+      Op_bi_sub => "bi_sub",
+      Op_bi_mul => "bi_mul",
+      Op_bi_div => "bi_div",
+      Op_un_bitnot => "un_bitnot",
+      Op_bi_mod => "bi_mod",
+      Op_bi_bitand => "bi_bitand",
+      Op_bi_bitor => "bi_bitor",
+      Op_bi_bitxor => "bi_bitxor",
+      Op_bi_lsh => "bi_lsh",
+      Op_bi_rsh => "bi_rsh",
+      Op_bi_ursh => "bi_ursh",
+      Op_un_void => "un_void",
+      Op_bi_in => "bi_in",
+      Op_bi_instanceof => "bi_instanceof",
+      Op_bi_lt => "bi_lt",
+      Op_bi_lte => "bi_lte",
+      Op_bi_loose_eq => "bi_loose_eq"
     }
   }
   pub fn new_from_uint(val: uint) -> Op {
@@ -168,11 +273,30 @@ impl Op {
       27 => Op_bi_sub,
       28 => Op_bi_mul,
       29 => Op_bi_div,
+      30 => Op_un_bitnot,
+      31 => Op_bi_mod,
+      32 => Op_bi_bitand,
+      33 => Op_bi_bitor,
+      34 => Op_bi_bitxor,
+      35 => Op_bi_lsh,
+      36 => Op_bi_rsh,
+      37 => Op_bi_ursh,
+      38 => Op_un_void,
+      39 => Op_bi_in,
+      40 => Op_bi_instanceof,
+      41 => Op_bi_lt,
+      42 => Op_bi_lte,
+      43 => Op_bi_loose_eq,
       _ => fail!()
     }
   }
 }
 
+// whether new_from_uint knows this opcode
+pub fn is_op(val: uint) -> bool {
+  val <= Op_bi_loose_eq as uint
+}
+
 #[test]
 fn test_invoke() {
   let op = Op_invoke;
@@ -0,0 +1,953 @@
+// the TurtleScript parser, ported from parse.js
+//
+// A top down operator precedence parser.  As in parse.js, a token is
+// created from the symbol (or the variable) it names by prototype
+// inheritance, so a node which doesn't set a field itself answers with
+// its prototype's.  The self-hosted compiler depends on this in odd
+// places (a keyword reserved in a scope becomes the prototype of its
+// later uses there) and we have to parse exactly like it, so nodes
+// keep a `proto` link and every field is optional.
+//
+// The parser keeps its scope between calls, which the REPL relies on;
+// like parse.js it doesn't unwind the scope when it finds an error.
+use core::hashmap::HashMap;
+
+use tokenize::{Token, TName, TOperator, TString, TNumber, TRegExp, SyntaxError, tokenize};
+use tokenize;
+
+// unwrap an Ok, or return the syntax error
+macro_rules! try_parse(
+    ($e:expr) => (match $e { Ok(v) => v, Err(e) => { return Err(e); } })
+)
+
+#[deriving(Eq)]
+pub enum Value {
+    VUndefined,
+    VNull,
+    VBool(bool),
+    VNumber(f64),
+    VString(~[u16]),
+    VObject, // the Object constructor
+    VArray   // the Array constructor
+}
+
+#[deriving(Eq)]
+pub enum Arity {
+    AName,
+    AOperator,
+    ALiteral,
+    AThis,
+    AUnary,
+    ABinary,
+    ATernary,
+    AStatement,
+    AFunction
+}
+
+// what a token does at the start of an expression
+#[deriving(Eq)]
+priv enum Nud {
+    NUndefined,
+    NItself,
+    NConstant,
+    NThis,
+    NPrefix,
+    NParen,
+    NFunction,
+    NArray,
+    NObject,
+    NRegExp
+}
+
+// what a token does after the left operand
+#[deriving(Eq)]
+priv enum Led {
+    LMissing,
+    LInfix(uint),
+    LInfixr(uint),
+    LAssign,
+    LTernary,
+    LDot,
+    LIndex,
+    LCall
+}
+
+// what a token does at the start of a statement
+#[deriving(Eq)]
+priv enum Std {
+    SBlock,
+    SVar,
+    SIf,
+    SReturn,
+    SBreak,
+    SWhile
+}
+
+// the operands of a node
+pub enum Child {
+    CNull,
+    CNode(@mut Node),
+    CList(@~[@mut Node])
+}
+
+pub struct Node {
+    proto: Option<@mut Node>,
+    id: Option<~str>,
+    value: Option<Value>,
+    arity: Option<Arity>,
+    from: Option<uint>,
+    to: Option<uint>,
+    lbp: Option<uint>,
+    nud: Option<Nud>,
+    led: Option<Option<Led>>,
+    std: Option<Option<Std>>,
+    reserved: Option<bool>,
+    scope: Option<@mut Scope>,
+    first: Option<Child>,
+    second: Option<Child>,
+    third: Option<Child>,
+    name: Option<Option<~[u16]>>,
+    assignment: Option<bool>,
+    key: Option<Value>,
+    extra_name: Option<~[u16]>,
+    // the end of a function literal
+    end: Option<uint>
+}
+
+pub struct Scope {
+    def: HashMap<~[u16], @mut Node>,
+    parent: Option<@mut Scope>,
+    level: uint
+}
+
+impl Node {
+    pub fn new(proto: Option<@mut Node>) -> Node {
+        Node {
+            proto: proto, id: None, value: None, arity: None,
+            from: None, to: None, lbp: None, nud: None, led: None,
+            std: None, reserved: None, scope: None, first: None,
+            second: None, third: None, name: None, assignment: None,
+            key: None, extra_name: None, end: None
+        }
+    }
+
+    // a node for one of the statements the parser makes up
+    priv fn statement(value: &str, first: Child) -> @mut Node {
+        let n = @mut Node::new(None);
+        n.value = Some(VString(str::to_utf16(value)));
+        n.arity = Some(AStatement);
+        n.first = Some(first);
+        n
+    }
+
+    // a field of this node, or the nearest prototype which has it
+    priv fn get<T: Copy>(&self, field: &fn(&Node) -> Option<T>) -> Option<T> {
+        match field(self) {
+            Some(v) => { return Some(v); },
+            None => {}
+        }
+        let mut p = self.proto;
+        loop {
+            match p {
+                None => { return None; },
+                Some(n) => {
+                    match field(&*n) {
+                        Some(v) => { return Some(v); },
+                        None => {}
+                    }
+                    p = n.proto;
+                }
+            }
+        }
+    }
+
+    pub fn id(&self) -> Option<~str> { self.get(|n| copy n.id) }
+    pub fn value(&self) -> Value {
+        self.get(|n| copy n.value).get_or_default(VUndefined)
+    }
+    pub fn arity(&self) -> Option<Arity> { self.get(|n| n.arity) }
+    pub fn from(&self) -> uint { self.get(|n| n.from).get_or_default(0) }
+    pub fn to(&self) -> uint { self.get(|n| n.to).get_or_default(0) }
+    priv fn lbp(&self) -> uint { self.get(|n| n.lbp).get_or_default(0) }
+    priv fn nud(&self) -> Option<Nud> { self.get(|n| n.nud) }
+    priv fn led(&self) -> Option<Led> {
+        self.get(|n| n.led).get_or_default(None)
+    }
+    priv fn std(&self) -> Option<Std> {
+        self.get(|n| n.std).get_or_default(None)
+    }
+    priv fn reserved(&self) -> bool {
+        self.get(|n| n.reserved).get_or_default(false)
+    }
+    pub fn scope(&self) -> Option<@mut Scope> { self.get(|n| n.scope) }
+    pub fn first(&self) -> Child { self.get(|n| n.first).get_or_default(CNull) }
+    pub fn second(&self) -> Child { self.get(|n| n.second).get_or_default(CNull) }
+    pub fn third(&self) -> Child { self.get(|n| n.third).get_or_default(CNull) }
+    pub fn name(&self) -> Option<~[u16]> {
+        self.get(|n| copy n.name).get_or_default(None)
+    }
+    priv fn assignment(&self) -> bool {
+        self.get(|n| n.assignment).get_or_default(false)
+    }
+    pub fn key(&self) -> Value { self.get(|n| copy n.key).get_or_default(VUndefined) }
+    pub fn extra_name(&self) -> Option<~[u16]> { self.get(|n| copy n.extra_name) }
+    pub fn end(&self) -> uint { self.get(|n| n.end).get_or_default(0) }
+
+    // is this node's value the given string?
+    pub fn value_is(&self, s: &str) -> bool {
+        match self.value() {
+            VString(v) => v == str::to_utf16(s),
+            _ => false
+        }
+    }
+
+    // the string value of a name or operator
+    pub fn str_value(&self) -> ~[u16] {
+        match self.value() {
+            VString(v) => v,
+            _ => fail!(~"not a name")
+        }
+    }
+}
+
+impl Child {
+    pub fn node(&self) -> @mut Node {
+        match *self {
+            CNode(n) => n,
+            _ => fail!(~"expected a node")
+        }
+    }
+    pub fn list(&self) -> @~[@mut Node] {
+        match *self {
+            CList(l) => l,
+            _ => fail!(~"expected a list")
+        }
+    }
+    pub fn is_null(&self) -> bool {
+        match *self { CNull => true, _ => false }
+    }
+}
+
+priv fn error(t: &Node, message: &str) -> SyntaxError {
+    SyntaxError::new(message, t.from(), t.to())
+}
+
+// names and operators are ASCII unless they're bad operators
+priv fn ascii(s: &[u16]) -> Option<~str> {
+    if s.all(|c| *c < 128) { Some(str::from_utf16(s)) } else { None }
+}
+
+// every frame has a __proto__ slot, so it can't be a variable
+priv fn is_proto(name: &[u16]) -> bool {
+    name == str::to_utf16("__proto__")
+}
+
+pub struct Parser {
+    priv symbol_table: HashMap<~str, @mut Node>,
+    priv scope: Option<@mut Scope>,
+    priv tokens: ~[Token],
+    priv token_nr: uint,
+    priv token: @mut Node
+}
+
+impl Parser {
+    pub fn new() -> Parser {
+        let end = @mut Node::new(None);
+        let mut p = Parser {
+            symbol_table: HashMap::new(),
+            scope: None,
+            tokens: ~[],
+            token_nr: 0,
+            token: end
+        };
+        for ["(end)", "(name)", ":", ";", ")", "]", "}", ",", "else"].each |id| {
+            p.symbol(*id, 0);
+        }
+        p.constant("true", VBool(true));
+        p.constant("false", VBool(false));
+        p.constant("null", VNull);
+        p.constant("undefined", VUndefined);
+        p.constant("NaN", VNumber(f64::NaN));
+        p.constant("Infinity", VNumber(f64::infinity));
+        p.constant("Object", VObject);
+        p.constant("Array", VArray);
+        p.symbol("(literal)", 0).nud = Some(NItself);
+        p.symbol("(regexp)", 0).nud = Some(NRegExp);
+        p.symbol("this", 0).nud = Some(NThis);
+        for ["=", "+=", "-=", "*=", "/=", "%=", "&=", "|=", "^=",
+             "<<=", ">>=", ">>>="].each |id| {
+            p.infix(*id, 10, LAssign);
+        }
+        p.infix("?", 20, LTernary);
+        p.infix("||", 30, LInfixr(30));
+        p.infix("&&", 35, LInfixr(35));
+        // the bitwise operators and % have JavaScript's precedences,
+        // and associate to the left
+        p.infix("|", 36, LInfix(36));
+        p.infix("^", 37, LInfix(37));
+        p.infix("&", 38, LInfix(38));
+        p.infix("===", 40, LInfixr(40));
+        p.infix("!==", 40, LInfixr(40));
+        p.infix("==", 40, LInfixr(40));
+        p.infix("!=", 40, LInfixr(40));
+        p.infix("<", 45, LInfixr(45));
+        p.infix("<=", 45, LInfixr(45));
+        p.infix(">", 45, LInfixr(45));
+        p.infix(">=", 45, LInfixr(45));
+        p.infix("in", 45, LInfixr(45));
+        p.infix("instanceof", 45, LInfixr(45));
+        p.infix("<<", 47, LInfix(47));
+        p.infix(">>", 47, LInfix(47));
+        p.infix(">>>", 47, LInfix(47));
+        p.infix("+", 50, LInfix(50));
+        p.infix("-", 50, LInfix(50));
+        p.infix("*", 60, LInfix(60));
+        p.infix("/", 60, LInfix(60));
+        p.infix("%", 60, LInfix(60));
+        p.infix(".", 80, LDot);
+        p.infix("[", 80, LIndex);
+        p.infix("(", 75, LCall);
+        p.prefix("!", NPrefix);
+        p.prefix("-", NPrefix);
+        p.prefix("typeof", NPrefix);
+        p.prefix("~", NPrefix);
+        p.prefix("void", NPrefix);
+        p.prefix("(", NParen);
+        p.prefix("function", NFunction);
+        p.prefix("[", NArray);
+        p.prefix("{", NObject);
+        p.stmt("{", SBlock);
+        p.stmt("var", SVar);
+        p.stmt("if", SIf);
+        p.stmt("return", SReturn);
+        p.stmt("break", SBreak);
+        p.stmt("while", SWhile);
+        p.token = p.symbol("(end)", 0);
+        p
+    }
+
+    // the symbol table
+
+    priv fn symbol(&mut self, id: &str, bp: uint) -> @mut Node {
+        let existing = match self.symbol_table.find(&id.to_owned()) {
+            Some(&s) => Some(s),
+            None => None
+        };
+        match existing {
+            Some(s) => {
+                if bp >= s.lbp() { s.lbp = Some(bp); }
+                return s;
+            },
+            None => {}
+        }
+        let s = @mut Node::new(None);
+        s.id = Some(id.to_owned());
+        s.value = Some(VString(str::to_utf16(id)));
+        s.lbp = Some(bp);
+        s.nud = Some(NUndefined);
+        s.led = Some(Some(LMissing));
+        self.symbol_table.insert(id.to_owned(), s);
+        s
+    }
+
+    priv fn constant(&mut self, id: &str, v: Value) {
+        let s = self.symbol(id, 0);
+        s.nud = Some(NConstant);
+        s.value = Some(v);
+    }
+
+    priv fn infix(&mut self, id: &str, bp: uint, led: Led) {
+        self.symbol(id, bp).led = Some(Some(led));
+    }
+
+    priv fn prefix(&mut self, id: &str, nud: Nud) {
+        self.symbol(id, 0).nud = Some(nud);
+    }
+
+    priv fn stmt(&mut self, id: &str, std: Std) {
+        self.symbol(id, 0).std = Some(Some(std));
+    }
+
+    priv fn lookup_symbol(&self, id: &str) -> Option<@mut Node> {
+        match self.symbol_table.find(&id.to_owned()) {
+            Some(&s) => Some(s),
+            None => None
+        }
+    }
+
+    // scopes
+
+    priv fn new_scope(&mut self) {
+        let level = match self.scope { Some(s) => s.level + 1, None => 0 };
+        self.scope = Some(@mut Scope {
+            def: HashMap::new(),
+            parent: self.scope,
+            level: level
+        });
+    }
+
+    priv fn pop_scope(&mut self) {
+        self.scope = self.scope.get().parent;
+    }
+
+    priv fn define(&mut self, n: @mut Node) -> Result<@mut Node, SyntaxError> {
+        let scope = self.scope.get();
+        let name = n.str_value();
+        if is_proto(name) {
+            return Err(error(n, "Already defined."));
+        }
+        match scope.def.find(&name) {
+            Some(t) => {
+                return Err(error(n, if t.reserved() {
+                    "Already reserved."
+                } else {
+                    "Already defined."
+                }));
+            },
+            None => {}
+        }
+        scope.def.insert(name, n);
+        n.reserved = Some(false);
+        n.nud = Some(NItself);
+        n.led = Some(None);
+        n.std = Some(None);
+        n.lbp = Some(0);
+        n.scope = Some(scope);
+        Ok(n)
+    }
+
+    // keywords are reserved in a scope once they're used there
+    priv fn reserve(&mut self, n: @mut Node) -> Result<(), SyntaxError> {
+        if n.arity() != Some(AName) || n.reserved() { return Ok(()); }
+        let scope = self.scope.get();
+        let name = n.str_value();
+        let existing = match scope.def.find(&name) {
+            Some(&t) => Some(t),
+            None => None
+        };
+        match existing {
+            Some(t) => {
+                if t.reserved() { return Ok(()); }
+                if t.arity() == Some(AName) {
+                    return Err(error(n, "Already defined."));
+                }
+            },
+            None => {}
+        }
+        scope.def.insert(name, n);
+        n.reserved = Some(true);
+        Ok(())
+    }
+
+    // the variable or keyword a name refers to
+    priv fn find(&self, name: &[u16]) -> @mut Node {
+        let mut e = self.scope;
+        loop {
+            match e {
+                None => break,
+                Some(s) => {
+                    match s.def.find(&name.to_owned()) {
+                        Some(&o) => { return o; },
+                        None => {}
+                    }
+                    e = s.parent;
+                }
+            }
+        }
+        let sym = if is_proto(name) { None } else {
+            match ascii(name) {
+                Some(id) => self.lookup_symbol(id),
+                None => None
+            }
+        };
+        match sym {
+            Some(o) => o,
+            None => self.lookup_symbol("(name)").get()
+        }
+    }
+
+    // tokens
+
+    priv fn advance(&mut self, id: Option<&str>) -> Result<(), SyntaxError> {
+        match id {
+            Some(id) if self.token.id() != Some(id.to_owned()) => {
+                return Err(error(self.token, fmt!("Expected '%s'.", id)));
+            },
+            _ => {}
+        }
+        if self.token_nr >= self.tokens.len() {
+            self.token = self.lookup_symbol("(end)").get();
+            return Ok(());
+        }
+        let Token { value: value, from: from, to: to } = copy self.tokens[self.token_nr];
+        self.token_nr += 1;
+        let (o, v, a) = match value {
+            TName(s) => (self.find(s), VString(s), AName),
+            TOperator(s) => {
+                let o = match ascii(s) {
+                    Some(id) => self.lookup_symbol(id),
+                    None => None
+                };
+                match o {
+                    Some(o) => (o, VString(s), AOperator),
+                    None => {
+                        return Err(SyntaxError::new("Unknown operator.", from, to));
+                    }
+                }
+            },
+            TString(s) => (self.lookup_symbol("(literal)").get(),
+                           VString(s), ALiteral),
+            TNumber(n) => (self.lookup_symbol("(literal)").get(),
+                           VNumber(n), ALiteral),
+            TRegExp(pattern, flags) => {
+                let literal = self.lookup_symbol("(literal)").get();
+                let parts = do [pattern, flags].map |s| {
+                    let n = @mut Node::new(Some(literal));
+                    n.from = Some(from);
+                    n.to = Some(to);
+                    n.value = Some(VString(copy *s));
+                    n.arity = Some(ALiteral);
+                    n
+                };
+                let token = @mut Node::new(self.lookup_symbol("(regexp)"));
+                token.from = Some(from);
+                token.to = Some(to);
+                token.second = Some(CList(@parts));
+                self.token = token;
+                return Ok(());
+            }
+        };
+        let token = @mut Node::new(Some(o));
+        token.from = Some(from);
+        token.to = Some(to);
+        token.value = Some(v);
+        token.arity = Some(a);
+        self.token = token;
+        Ok(())
+    }
+
+    priv fn expression(&mut self, rbp: uint) -> Result<@mut Node, SyntaxError> {
+        let mut t = self.token;
+        try_parse!(self.advance(None));
+        let mut left = try_parse!(self.nud(t));
+        while rbp < self.token.lbp() {
+            t = self.token;
+            try_parse!(self.advance(None));
+            left = try_parse!(self.led(t, left));
+        }
+        Ok(left)
+    }
+
+    priv fn statement(&mut self) -> Result<~[@mut Node], SyntaxError> {
+        let n = self.token;
+        match n.std() {
+            Some(std) => {
+                try_parse!(self.advance(None));
+                try_parse!(self.reserve(n));
+                return self.std(n, std);
+            },
+            None => {}
+        }
+        let v = try_parse!(self.expression(0));
+        let named_function = v.arity() == Some(AFunction) && v.name().is_some();
+        if !v.assignment() && v.id() != Some(~"(") && !named_function {
+            return Err(error(v, "Bad expression statement."));
+        }
+        if !(named_function && self.token.id() != Some(~";")) {
+            try_parse!(self.advance(Some(";")));
+        }
+        Ok(~[v])
+    }
+
+    priv fn statements(&mut self) -> Result<~[@mut Node], SyntaxError> {
+        let mut a : ~[@mut Node] = ~[];
+        loop {
+            if self.token.id() == Some(~"}") || self.token.id() == Some(~"(end)") {
+                break;
+            }
+            a.push_all_move(try_parse!(self.statement()));
+        }
+        // hoist var declarations to the top of the block
+        let (vars, rest) = vec::partition(a, |n| n.value_is("var"));
+        Ok(vars + rest)
+    }
+
+    priv fn block(&mut self) -> Result<~[@mut Node], SyntaxError> {
+        let t = self.token;
+        try_parse!(self.advance(Some("{")));
+        self.std(t, t.std().get())
+    }
+
+    // a comma-separated list of expressions, up to the closing token
+    priv fn expression_list(&mut self, close: &str) -> Result<~[@mut Node], SyntaxError> {
+        let mut a : ~[@mut Node] = ~[];
+        if self.token.id() != Some(close.to_owned()) {
+            loop {
+                a.push(try_parse!(self.expression(0)));
+                if self.token.id() != Some(~",") { break; }
+                try_parse!(self.advance(Some(",")));
+            }
+        }
+        try_parse!(self.advance(Some(close)));
+        Ok(a)
+    }
+
+    priv fn nud(&mut self, t: @mut Node) -> Result<@mut Node, SyntaxError> {
+        match t.nud() {
+            Some(NItself) => Ok(t),
+            Some(NConstant) => {
+                try_parse!(self.reserve(t));
+                let s = self.lookup_symbol(t.id().get()).get();
+                t.value = Some(s.value());
+                t.arity = Some(ALiteral);
+                Ok(t)
+            },
+            Some(NThis) => {
+                try_parse!(self.reserve(t));
+                t.arity = Some(AThis);
+                Ok(t)
+            },
+            Some(NPrefix) => {
+                try_parse!(self.reserve(t));
+                t.first = Some(CNode(try_parse!(self.expression(70))));
+                t.arity = Some(AUnary);
+                Ok(t)
+            },
+            Some(NParen) => {
+                let e = try_parse!(self.expression(0));
+                try_parse!(self.advance(Some(")")));
+                Ok(e)
+            },
+            Some(NFunction) => {
+                if self.token.arity() == Some(AName) {
+                    let name = self.token;
+                    try_parse!(self.define(name));
+                    t.name = Some(Some(name.str_value()));
+                    t.scope = self.scope;
+                    try_parse!(self.advance(None));
+                } else {
+                    t.name = Some(None);
+                }
+                self.new_scope();
+                let arguments = @mut Node::new(None);
+                arguments.value = Some(VString(str::to_utf16("arguments")));
+                arguments.arity = Some(AName);
+                try_parse!(self.define(arguments));
+                try_parse!(self.advance(Some("(")));
+                let mut a : ~[@mut Node] = ~[];
+                if self.token.id() != Some(~")") {
+                    loop {
+                        let param = self.token;
+                        if param.arity() != Some(AName) {
+                            return Err(error(param, "Expected a parameter name."));
+                        }
+                        try_parse!(self.define(param));
+                        a.push(param);
+                        try_parse!(self.advance(None));
+                        if self.token.id() != Some(~",") { break; }
+                        try_parse!(self.advance(Some(",")));
+                    }
+                }
+                t.first = Some(CList(@a));
+                try_parse!(self.advance(Some(")")));
+                try_parse!(self.advance(Some("{")));
+                t.second = Some(CList(@try_parse!(self.statements())));
+                let end = self.token.to();
+                try_parse!(self.advance(Some("}")));
+                t.end = Some(end);
+                t.arity = Some(AFunction);
+                self.pop_scope();
+                Ok(t)
+            },
+            Some(NArray) => {
+                let a = try_parse!(self.expression_list("]"));
+                t.first = Some(CList(@a));
+                t.arity = Some(AUnary);
+                Ok(t)
+            },
+            Some(NObject) => {
+                let mut a : ~[@mut Node] = ~[];
+                if self.token.id() != Some(~"}") {
+                    loop {
+                        let n = self.token;
+                        if n.arity() != Some(AName) && n.arity() != Some(ALiteral) {
+                            return Err(error(n, "Bad property name."));
+                        }
+                        try_parse!(self.advance(None));
+                        try_parse!(self.advance(Some(":")));
+                        let v = try_parse!(self.expression(0));
+                        v.key = Some(n.value());
+                        a.push(v);
+                        if self.token.id() != Some(~",") { break; }
+                        try_parse!(self.advance(Some(",")));
+                    }
+                }
+                try_parse!(self.advance(Some("}")));
+                t.first = Some(CList(@a));
+                t.arity = Some(AUnary);
+                Ok(t)
+            },
+            Some(NRegExp) => {
+                // /pattern/flags is RegExp('pattern', 'flags'), with the
+                // top-level RegExp even if a local one hides it
+                let mut scope = self.scope.get();
+                while scope.parent.is_some() { scope = scope.parent.get(); }
+                let regexp = str::to_utf16("RegExp");
+                let def = match scope.def.find(&regexp) {
+                    Some(&def) => def,
+                    None => { return Err(error(t, "Undefined.")); }
+                };
+                let name = @mut Node::new(Some(def));
+                name.from = t.from;
+                name.to = t.to;
+                name.value = Some(VString(regexp));
+                name.arity = Some(AName);
+                t.value = Some(VString(str::to_utf16("(")));
+                t.first = Some(CNode(name));
+                t.arity = Some(ABinary);
+                Ok(t)
+            },
+            Some(NUndefined) | None => Err(error(t, "Undefined."))
+        }
+    }
+
+    priv fn led(&mut self, t: @mut Node, left: @mut Node) -> Result<@mut Node, SyntaxError> {
+        match t.led() {
+            Some(LInfix(bp)) => {
+                try_parse!(self.reserve(t));
+                t.first = Some(CNode(left));
+                t.second = Some(CNode(try_parse!(self.expression(bp))));
+                t.arity = Some(ABinary);
+            },
+            Some(LInfixr(bp)) => {
+                try_parse!(self.reserve(t));
+                t.first = Some(CNode(left));
+                t.second = Some(CNode(try_parse!(self.expression(bp - 1))));
+                t.arity = Some(ABinary);
+            },
+            Some(LAssign) => {
+                if left.id() != Some(~".") && left.id() != Some(~"[") &&
+                    left.arity() != Some(AName) {
+                    return Err(error(left, "Bad lvalue."));
+                }
+                t.first = Some(CNode(left));
+                t.second = Some(CNode(try_parse!(self.expression(9))));
+                t.assignment = Some(true);
+                t.arity = Some(ABinary);
+            },
+            Some(LTernary) => {
+                t.first = Some(CNode(left));
+                t.second = Some(CNode(try_parse!(self.expression(0))));
+                try_parse!(self.advance(Some(":")));
+                t.third = Some(CNode(try_parse!(self.expression(0))));
+                t.arity = Some(ATernary);
+            },
+            Some(LDot) => {
+                t.first = Some(CNode(left));
+                if self.token.arity() != Some(AName) {
+                    return Err(error(self.token, "Expected a property name."));
+                }
+                self.token.arity = Some(ALiteral);
+                t.second = Some(CNode(self.token));
+                t.arity = Some(ABinary);
+                try_parse!(self.advance(None));
+            },
+            Some(LIndex) => {
+                t.first = Some(CNode(left));
+                t.second = Some(CNode(try_parse!(self.expression(0))));
+                t.arity = Some(ABinary);
+                try_parse!(self.advance(Some("]")));
+            },
+            Some(LCall) => {
+                let is_method = left.id() == Some(~".") || left.id() == Some(~"[");
+                if !is_method {
+                    let ok = match left.id() {
+                        Some(~"(") | Some(~"&&") | Some(~"||") | Some(~"?") => true,
+                        _ => left.arity() == Some(AFunction) ||
+                            left.arity() == Some(AName)
+                    };
+                    if !ok {
+                        return Err(error(left, "Expected a variable name."));
+                    }
+                }
+                let a = try_parse!(self.expression_list(")"));
+                if is_method {
+                    t.arity = Some(ATernary);
+                    t.first = Some(left.first());
+                    t.second = Some(left.second());
+                    t.third = Some(CList(@a));
+                } else {
+                    t.arity = Some(ABinary);
+                    t.first = Some(CNode(left));
+                    t.second = Some(CList(@a));
+                }
+            },
+            // names have a null led, and their lbp of 0 keeps it uncalled
+            Some(LMissing) | None => {
+                return Err(error(t, "Missing operator."));
+            }
+        }
+        Ok(t)
+    }
+
+    priv fn std(&mut self, t: @mut Node, std: Std) -> Result<~[@mut Node], SyntaxError> {
+        match std {
+            SBlock => {
+                let a = try_parse!(self.statements());
+                try_parse!(self.advance(Some("}")));
+                Ok(~[Node::statement("block", CList(@a))])
+            },
+            SVar => {
+                let mut a : ~[@mut Node] = ~[];
+                loop {
+                    let n = self.token;
+                    if n.arity() != Some(AName) {
+                        return Err(error(n, "Expected a new variable name."));
+                    }
+                    try_parse!(self.define(n));
+                    a.push(Node::statement("var", CNode(n)));
+                    try_parse!(self.advance(None));
+                    if self.token.id() == Some(~"=") {
+                        let assign = self.token;
+                        try_parse!(self.advance(Some("=")));
+                        assign.first = Some(CNode(n));
+                        assign.second = Some(CNode(try_parse!(self.expression(0))));
+                        assign.arity = Some(ABinary);
+                        a.push(assign);
+                    }
+                    if self.token.id() != Some(~",") { break; }
+                    try_parse!(self.advance(Some(",")));
+                }
+                try_parse!(self.advance(Some(";")));
+                Ok(a)
+            },
+            SIf => {
+                try_parse!(self.advance(Some("(")));
+                t.first = Some(CNode(try_parse!(self.expression(0))));
+                try_parse!(self.advance(Some(")")));
+                let then = try_parse!(self.block());
+                t.second = Some(CNode(then[0]));
+                if self.token.id() == Some(~"else") {
+                    let else_token = self.token;
+                    try_parse!(self.reserve(else_token));
+                    try_parse!(self.advance(Some("else")));
+                    let otherwise = if self.token.id() == Some(~"if") {
+                        Node::statement("block", CList(@try_parse!(self.statement())))
+                    } else {
+                        let b = try_parse!(self.block());
+                        b[0]
+                    };
+                    t.third = Some(CNode(otherwise));
+                } else {
+                    t.third = Some(CNull);
+                }
+                t.arity = Some(AStatement);
+                Ok(~[t])
+            },
+            SReturn => {
+                t.first = Some(if self.token.id() != Some(~";") {
+                    CNode(try_parse!(self.expression(0)))
+                } else {
+                    CNull
+                });
+                try_parse!(self.advance(Some(";")));
+                if self.token.id() != Some(~"}") {
+                    return Err(error(self.token, "Unreachable statement."));
+                }
+                t.arity = Some(AStatement);
+                Ok(~[t])
+            },
+            SBreak => {
+                try_parse!(self.advance(Some(";")));
+                if self.token.id() != Some(~"}") {
+                    return Err(error(self.token, "Unreachable statement."));
+                }
+                t.arity = Some(AStatement);
+                Ok(~[t])
+            },
+            SWhile => {
+                try_parse!(self.advance(Some("(")));
+                t.first = Some(CNode(try_parse!(self.expression(0))));
+                try_parse!(self.advance(Some(")")));
+                let body = try_parse!(self.block());
+                t.second = Some(CNode(body[0]));
+                t.arity = Some(AStatement);
+                Ok(~[t])
+            }
+        }
+    }
+
+    // the predefined names, a space-separated list
+    priv fn define_top_level(&mut self, top_level: &[u16]) -> Result<(), SyntaxError> {
+        let names = try_parse!(tokenize(top_level, "<>+-&", "=>&:"));
+        for names.each |name| {
+            let n = @mut Node::new(None);
+            n.value = Some(match name.value {
+                TName(ref s) | TOperator(ref s) | TString(ref s) => VString(copy *s),
+                TNumber(_) | TRegExp(*) => fail!(~"not a name")
+            });
+            n.from = Some(name.from);
+            n.to = Some(name.to);
+            try_parse!(self.define(n));
+        }
+        Ok(())
+    }
+
+    // parse a program: a list of statements
+    pub fn parse(&mut self, source: &[u16],
+                 top_level: &[u16]) -> Result<~[@mut Node], SyntaxError> {
+        self.tokens = try_parse!(tokenize(source, tokenize::PREFIX,
+                                          tokenize::SUFFIX));
+        self.token_nr = 0;
+        self.new_scope();
+        try_parse!(self.define_top_level(top_level));
+        try_parse!(self.advance(None));
+        let s = try_parse!(self.statements());
+        try_parse!(self.advance(Some("(end)")));
+        self.pop_scope();
+        Ok(s)
+    }
+
+    // parse a REPL line, in the scope left by the previous one (or a
+    // fresh one if state is None).  An expression is returned as its
+    // value; otherwise the line is parsed as statements.  Returns the
+    // scope for the next line.
+    pub fn parse_repl(&mut self, state: Option<@mut Scope>, source: &[u16],
+                      top_level: &[u16]) -> Result<(@mut Scope, ~[@mut Node]), SyntaxError> {
+        let old_scope = self.scope;
+        match state {
+            Some(s) => { self.scope = Some(s); },
+            None => {
+                self.new_scope();
+                try_parse!(self.define_top_level(top_level));
+            }
+        }
+        self.tokens = try_parse!(tokenize(source, tokenize::PREFIX,
+                                          tokenize::SUFFIX));
+        // an expression?  A failed attempt keeps whatever it defined.
+        self.token_nr = 0;
+        let attempt = match self.advance(None) {
+            Ok(()) => match self.expression(0) {
+                Ok(e) => match self.advance(Some("(end)")) {
+                    Ok(()) => Some(e),
+                    Err(_) => None
+                },
+                Err(_) => None
+            },
+            Err(_) => None
+        };
+        let tree = match attempt {
+            Some(e) => ~[Node::statement("return", CNode(e))],
+            None => {
+                self.token_nr = 0;
+                try_parse!(self.advance(None));
+                let s = try_parse!(self.statements());
+                try_parse!(self.advance(Some("(end)")));
+                s
+            }
+        };
+        let new_state = self.scope.get();
+        self.scope = old_scope;
+        Ok((new_state, tree))
+    }
+}
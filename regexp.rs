@@ -0,0 +1,1300 @@
+// regular expressions (ES5 section 15.10)
+//
+// Patterns are parsed into a tree, then compiled to a small program for
+// a backtracking matcher which works on UTF-16 code units, as the spec
+// requires.  The RegExp constructor and the regexp-based String methods
+// are at the end of this file.
+use interp::Environment;
+use intern::intern;
+use object::*;
+
+// matcher program
+priv enum Inst {
+    IChar(u16),              // one code unit (canonicalized if ignoreCase)
+    IAny,                    // any code unit except a line terminator
+    IClass(uint, bool),      // class index, negated?
+    ISplit(uint, uint),      // try the first pc, backtrack to the second
+    IJmp(uint),
+    ISave(uint),             // record the position in a capture slot
+    IReset(uint, uint),      // clear capture groups [from, to)
+    ISetMark(uint),          // record the position in a loop register
+    ICheckMark(uint),        // fail if the loop body matched empty
+    IBol,
+    IEol,
+    IWordBoundary(bool),
+    IBackref(uint),
+    ILook(bool, uint),       // lookahead: positive?, pc after the body
+    IMatch
+}
+
+// parse tree
+priv enum Node {
+    NChar(u16),
+    NAny,
+    NClass(~[(u16, u16)], bool),
+    NBol,
+    NEol,
+    NWordBoundary(bool),
+    NBackref(uint),
+    NGroup(~Node, Option<uint>),
+    NLook(~Node, bool),
+    NConcat(~[Node]),
+    NAlt(~[Node]),
+    // body, min, max, greedy, and the capture groups [from, to) inside
+    NRepeat(~Node, uint, Option<uint>, bool, uint, uint)
+}
+
+pub struct Regex {
+    priv prog: ~[Inst],
+    priv classes: ~[~[(u16, u16)]],
+    priv nmarks: uint,
+    ncaptures: uint, // including the whole match, group 0
+    global: bool,
+    ignore_case: bool,
+    multiline: bool
+}
+
+// a successful match: the (start, end) of each capture group
+pub type Captures = ~[Option<(uint, uint)>];
+
+priv static MAX_PROGRAM: uint = 100000;
+
+// character sets
+
+priv fn is_line_terminator(c: u16) -> bool {
+    c == 0x0A || c == 0x0D || c == 0x2028 || c == 0x2029
+}
+
+priv fn is_word_char(c: u16) -> bool {
+    (c >= ('a' as u16) && c <= ('z' as u16)) ||
+    (c >= ('A' as u16) && c <= ('Z' as u16)) ||
+    (c >= ('0' as u16) && c <= ('9' as u16)) || c == ('_' as u16)
+}
+
+priv fn digit_ranges() -> ~[(u16, u16)] { ~[(0x30, 0x39)] }
+
+priv fn word_ranges() -> ~[(u16, u16)] {
+    ~[(0x30, 0x39), (0x41, 0x5A), (0x5F, 0x5F), (0x61, 0x7A)]
+}
+
+priv fn space_ranges() -> ~[(u16, u16)] {
+    ~[(0x09, 0x0D), (0x20, 0x20), (0xA0, 0xA0), (0x1680, 0x1680),
+      (0x180E, 0x180E), (0x2000, 0x200A), (0x2028, 0x2029),
+      (0x202F, 0x202F), (0x205F, 0x205F), (0x3000, 0x3000),
+      (0xFEFF, 0xFEFF)]
+}
+
+// the complement of a sorted, non-overlapping list of ranges
+priv fn complement(ranges: &[(u16, u16)]) -> ~[(u16, u16)] {
+    let mut result : ~[(u16, u16)] = ~[];
+    let mut next = 0u;
+    for ranges.each |&(lo, hi)| {
+        if (lo as uint) > next { result.push((next as u16, lo - 1)); }
+        next = (hi as uint) + 1;
+    }
+    if next <= 0xFFFF { result.push((next as u16, 0xFFFF)); }
+    result
+}
+
+priv fn in_ranges(ranges: &[(u16, u16)], c: u16) -> bool {
+    for ranges.each |&(lo, hi)| {
+        if c >= lo && c <= hi { return true; }
+    }
+    false
+}
+
+// the Canonicalize operation of ES5 15.10.2.8, for the common alphabets:
+// map to upper case, unless that would turn a non-ASCII character into
+// an ASCII one.
+priv fn canonicalize(c: u16) -> u16 {
+    match c {
+        0x61..0x7A => c - 0x20,
+        0xB5 => 0x39C,
+        0xE0..0xFE if c != 0xF7 => c - 0x20,
+        0xFF => 0x178,
+        0x3C2 => 0x3A3,
+        0x3B1..0x3C9 => c - 0x20,
+        0x430..0x44F => c - 0x20,
+        0x450..0x45F => c - 0x50,
+        _ => c
+    }
+}
+
+priv fn to_lower(c: u16) -> u16 {
+    match c {
+        0x41..0x5A => c + 0x20,
+        0xC0..0xDE if c != 0xD7 => c + 0x20,
+        0x391..0x3A9 if c != 0x3A2 => c + 0x20,
+        0x410..0x42F => c + 0x20,
+        0x400..0x40F => c + 0x50,
+        _ => c
+    }
+}
+
+// parser
+
+priv struct Parser {
+    src: ~[u16],
+    pos: uint,
+    ncaps: uint,       // capture groups seen so far
+    total_caps: uint   // capture groups in the whole pattern
+}
+
+priv fn count_captures(src: &[u16]) -> uint {
+    let mut n = 0u;
+    let mut i = 0u;
+    let mut in_class = false;
+    while i < src.len() {
+        let c = src[i] as char;
+        if c == '\\' {
+            i += 1;
+        } else if in_class {
+            if c == ']' { in_class = false; }
+        } else if c == '[' {
+            in_class = true;
+        } else if c == '(' {
+            if i + 1 >= src.len() || src[i+1] != ('?' as u16) { n += 1; }
+        }
+        i += 1;
+    }
+    n
+}
+
+priv fn hex_value(c: u16) -> Option<u16> {
+    match c as char {
+        '0'..'9' => Some(c - ('0' as u16)),
+        'a'..'f' => Some(c - ('a' as u16) + 10),
+        'A'..'F' => Some(c - ('A' as u16) + 10),
+        _ => None
+    }
+}
+
+// an escape inside or outside a class: either one code unit or a set
+priv enum Escape {
+    EChar(u16),
+    ESet(~[(u16, u16)]),
+    EBackref(uint),
+    EWordBoundary(bool)
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        if self.pos < self.src.len() {
+            Some(self.src[self.pos] as char)
+        } else {
+            None
+        }
+    }
+
+    fn peek_at(&self, offset: uint) -> Option<char> {
+        if self.pos + offset < self.src.len() {
+            Some(self.src[self.pos + offset] as char)
+        } else {
+            None
+        }
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) { self.pos += 1; true } else { false }
+    }
+
+    fn parse_disjunction(&mut self) -> Result<Node, ~str> {
+        let mut alts : ~[Node] = ~[];
+        loop {
+            match self.parse_alternative() {
+                Ok(n) => alts.push(n),
+                Err(e) => { return Err(e); }
+            }
+            if !self.eat('|') { break; }
+        }
+        if alts.len() == 1 { Ok(alts.pop()) } else { Ok(NAlt(alts)) }
+    }
+
+    fn parse_alternative(&mut self) -> Result<Node, ~str> {
+        let mut terms : ~[Node] = ~[];
+        loop {
+            match self.peek() {
+                None | Some('|') | Some(')') => break,
+                _ => match self.parse_term() {
+                    Ok(t) => terms.push(t),
+                    Err(e) => { return Err(e); }
+                }
+            }
+        }
+        Ok(NConcat(terms))
+    }
+
+    fn parse_term(&mut self) -> Result<Node, ~str> {
+        match (self.peek(), self.peek_at(1), self.peek_at(2)) {
+            (Some('^'), _, _) => { self.pos += 1; return Ok(NBol); },
+            (Some('$'), _, _) => { self.pos += 1; return Ok(NEol); },
+            (Some('('), Some('?'), Some(c)) if c == '=' || c == '!' => {
+                self.pos += 3;
+                let body = match self.parse_disjunction() {
+                    Ok(n) => n,
+                    Err(e) => { return Err(e); }
+                };
+                if !self.eat(')') { return Err(~"Unterminated group"); }
+                return Ok(NLook(~body, c == '='));
+            },
+            _ => {}
+        }
+        let caps_before = self.ncaps;
+        let atom = match self.parse_atom() {
+            Ok(a) => a,
+            Err(e) => { return Err(e); }
+        };
+        let (min, max) = match self.peek() {
+            Some('*') => { self.pos += 1; (0u, None) },
+            Some('+') => { self.pos += 1; (1u, None) },
+            Some('?') => { self.pos += 1; (0u, Some(1u)) },
+            Some('{') => match self.parse_braces() {
+                Some(q) => q,
+                None => { return Ok(atom); }
+            },
+            _ => { return Ok(atom); }
+        };
+        match atom {
+            NBol | NEol | NWordBoundary(_) => {
+                return Err(~"Nothing to repeat");
+            },
+            _ => {}
+        }
+        match max {
+            Some(m) if m < min => {
+                return Err(~"numbers out of order in {} quantifier");
+            },
+            _ => {}
+        }
+        let greedy = !self.eat('?');
+        Ok(NRepeat(~atom, min, max, greedy, caps_before + 1, self.ncaps + 1))
+    }
+
+    fn parse_decimal(&mut self) -> Option<uint> {
+        let start = self.pos;
+        let mut n = 0u;
+        loop {
+            match self.peek() {
+                Some(c @ '0'..'9') => {
+                    // saturate rather than overflow
+                    if n < 1000000000 { n = n * 10 + ((c as uint) - ('0' as uint)); }
+                    self.pos += 1;
+                },
+                _ => break
+            }
+        }
+        if self.pos == start { None } else { Some(n) }
+    }
+
+    // {n}, {n,} or {n,m}; if this isn't a quantifier, the brace is
+    // an ordinary character (as web browsers allow) and nothing is
+    // consumed.
+    fn parse_braces(&mut self) -> Option<(uint, Option<uint>)> {
+        let start = self.pos;
+        self.pos += 1;
+        let min = match self.parse_decimal() {
+            Some(n) => n,
+            None => { self.pos = start; return None; }
+        };
+        let max = if self.eat(',') {
+            self.parse_decimal()
+        } else {
+            Some(min)
+        };
+        if !self.eat('}') { self.pos = start; return None; }
+        Some((min, max))
+    }
+
+    fn parse_atom(&mut self) -> Result<Node, ~str> {
+        let c = self.src[self.pos];
+        match c as char {
+            '.' => { self.pos += 1; Ok(NAny) },
+            '(' => {
+                self.pos += 1;
+                let idx = if self.peek() == Some('?') && self.peek_at(1) == Some(':') {
+                    self.pos += 2;
+                    None
+                } else {
+                    self.ncaps += 1;
+                    Some(self.ncaps)
+                };
+                let body = match self.parse_disjunction() {
+                    Ok(n) => n,
+                    Err(e) => { return Err(e); }
+                };
+                if !self.eat(')') { return Err(~"Unterminated group"); }
+                Ok(NGroup(~body, idx))
+            },
+            '[' => self.parse_class(),
+            '\\' => {
+                self.pos += 1;
+                match self.parse_escape(false) {
+                    Ok(EChar(c)) => Ok(NChar(c)),
+                    Ok(ESet(ranges)) => Ok(NClass(ranges, false)),
+                    Ok(EBackref(n)) => Ok(NBackref(n)),
+                    Ok(EWordBoundary(b)) => Ok(NWordBoundary(b)),
+                    Err(e) => Err(e)
+                }
+            },
+            '*' | '+' | '?' => Err(~"Nothing to repeat"),
+            '{' => match self.parse_braces() {
+                Some(_) => Err(~"Nothing to repeat"),
+                None => { self.pos += 1; Ok(NChar(c)) }
+            },
+            _ => { self.pos += 1; Ok(NChar(c)) }
+        }
+    }
+
+    // parse the escape following a backslash
+    fn parse_escape(&mut self, in_class: bool) -> Result<Escape, ~str> {
+        let c = match self.peek() {
+            None => { return Err(~"\\ at end of pattern"); },
+            Some(c) => c
+        };
+        self.pos += 1;
+        let simple = |n: u16| Ok(EChar(n));
+        match c {
+            'd' => Ok(ESet(digit_ranges())),
+            'D' => Ok(ESet(complement(digit_ranges()))),
+            'w' => Ok(ESet(word_ranges())),
+            'W' => Ok(ESet(complement(word_ranges()))),
+            's' => Ok(ESet(space_ranges())),
+            'S' => Ok(ESet(complement(space_ranges()))),
+            'b' if in_class => simple(0x08),
+            'b' => Ok(EWordBoundary(true)),
+            'B' if !in_class => Ok(EWordBoundary(false)),
+            'f' => simple(0x0C),
+            'n' => simple(0x0A),
+            'r' => simple(0x0D),
+            't' => simple(0x09),
+            'v' => simple(0x0B),
+            'c' => match self.peek() {
+                Some(l) if (l >= 'a' && l <= 'z') || (l >= 'A' && l <= 'Z') => {
+                    self.pos += 1;
+                    simple((l as u16) % 32)
+                },
+                _ => {
+                    // "\c" is a literal backslash; reread the 'c'
+                    self.pos -= 1;
+                    simple('\\' as u16)
+                }
+            },
+            'x' | 'u' => {
+                let n = if c == 'x' { 2 } else { 4 };
+                let mut v = 0u16;
+                for uint::range(0, n) |i| {
+                    if self.pos + i >= self.src.len() {
+                        return simple(c as u16);
+                    }
+                    match hex_value(self.src[self.pos + i]) {
+                        Some(d) => { v = v * 16 + d; },
+                        None => { return simple(c as u16); }
+                    }
+                }
+                self.pos += n;
+                simple(v)
+            },
+            '0'..'9' => {
+                self.pos -= 1;
+                if c != '0' && !in_class {
+                    let start = self.pos;
+                    let n = self.parse_decimal().get();
+                    if n <= self.total_caps { return Ok(EBackref(n)); }
+                    self.pos = start;
+                }
+                if c >= '8' {
+                    // not octal; the digit itself
+                    self.pos += 1;
+                    return simple(c as u16);
+                }
+                // legacy octal escape, at most \377
+                let mut v = 0u16;
+                let mut i = 0;
+                loop {
+                    match self.peek() {
+                        Some(d @ '0'..'7') if i < 3 && v * 8 + ((d as u16) - 0x30) <= 0xFF => {
+                            v = v * 8 + ((d as u16) - 0x30);
+                            self.pos += 1;
+                            i += 1;
+                        },
+                        _ => break
+                    }
+                }
+                simple(v)
+            },
+            _ => simple(c as u16)
+        }
+    }
+
+    fn parse_class(&mut self) -> Result<Node, ~str> {
+        self.pos += 1; // '['
+        let negated = self.eat('^');
+        let mut ranges : ~[(u16, u16)] = ~[];
+        loop {
+            let lo = match self.parse_class_atom() {
+                Err(e) => { return Err(e); },
+                Ok(None) => break,
+                Ok(Some(a)) => a
+            };
+            let is_range = self.peek() == Some('-') &&
+                self.peek_at(1).is_some() && self.peek_at(1) != Some(']');
+            match lo {
+                ESet(set) => { ranges.push_all(set); loop; },
+                EChar(lo) if is_range => {
+                    self.pos += 1; // '-'
+                    match self.parse_class_atom() {
+                        Err(e) => { return Err(e); },
+                        Ok(Some(EChar(hi))) => {
+                            if hi < lo {
+                                return Err(~"Range out of order in character class");
+                            }
+                            ranges.push((lo, hi));
+                        },
+                        Ok(Some(ESet(set))) => {
+                            // "[a-\d]": the dash is literal
+                            ranges.push((lo, lo));
+                            ranges.push(('-' as u16, '-' as u16));
+                            ranges.push_all(set);
+                        },
+                        _ => { return Err(~"Unterminated character class"); }
+                    }
+                },
+                EChar(c) => ranges.push((c, c)),
+                _ => fail!()
+            }
+        }
+        Ok(NClass(ranges, negated))
+    }
+
+    // None at the closing bracket
+    fn parse_class_atom(&mut self) -> Result<Option<Escape>, ~str> {
+        match self.peek() {
+            None => Err(~"Unterminated character class"),
+            Some(']') => { self.pos += 1; Ok(None) },
+            Some('\\') => {
+                self.pos += 1;
+                match self.parse_escape(true) {
+                    Ok(e) => Ok(Some(e)),
+                    Err(e) => Err(e)
+                }
+            },
+            Some(_) => {
+                self.pos += 1;
+                Ok(Some(EChar(self.src[self.pos - 1])))
+            }
+        }
+    }
+}
+
+// compiler
+
+priv struct Compiler {
+    prog: ~[Inst],
+    classes: ~[~[(u16, u16)]],
+    nmarks: uint,
+    ignore_case: bool
+}
+
+impl Compiler {
+    fn emit(&mut self, node: &Node) {
+        match *node {
+            NChar(c) => {
+                self.prog.push(IChar(if self.ignore_case { canonicalize(c) } else { c }));
+            },
+            NAny => self.prog.push(IAny),
+            NClass(ref ranges, negated) => {
+                let mut sorted = ranges.map(|r| *r);
+                sort_ranges(&mut sorted);
+                self.classes.push(sorted);
+                self.prog.push(IClass(self.classes.len() - 1, negated));
+            },
+            NBol => self.prog.push(IBol),
+            NEol => self.prog.push(IEol),
+            NWordBoundary(b) => self.prog.push(IWordBoundary(b)),
+            NBackref(n) => self.prog.push(IBackref(n)),
+            NGroup(ref body, Some(idx)) => {
+                self.prog.push(ISave(2 * idx));
+                self.emit(&**body);
+                self.prog.push(ISave(2 * idx + 1));
+            },
+            NGroup(ref body, None) => self.emit(&**body),
+            NLook(ref body, positive) => {
+                let at = self.prog.len();
+                self.prog.push(ILook(positive, 0));
+                self.emit(&**body);
+                self.prog.push(IMatch);
+                self.prog[at] = ILook(positive, self.prog.len());
+            },
+            NConcat(ref terms) => {
+                for terms.each |t| { self.emit(t); }
+            },
+            NAlt(ref alts) => {
+                let mut jumps : ~[uint] = ~[];
+                for alts.eachi |i, alt| {
+                    if i + 1 < alts.len() {
+                        let split = self.prog.len();
+                        self.prog.push(ISplit(split + 1, 0));
+                        self.emit(alt);
+                        jumps.push(self.prog.len());
+                        self.prog.push(IJmp(0));
+                        self.prog[split] = ISplit(split + 1, self.prog.len());
+                    } else {
+                        self.emit(alt);
+                    }
+                }
+                let end = self.prog.len();
+                for jumps.each |j| { self.prog[*j] = IJmp(end); }
+            },
+            NRepeat(ref body, min, max, greedy, cfrom, cto) => {
+                for min.times {
+                    if cto > cfrom { self.prog.push(IReset(cfrom, cto)); }
+                    self.emit(&**body);
+                    if self.prog.len() > MAX_PROGRAM { return; }
+                }
+                let mark = self.nmarks;
+                self.nmarks += 1;
+                match max {
+                    Some(max) => {
+                        let mut splits : ~[uint] = ~[];
+                        for (max - min).times {
+                            splits.push(self.prog.len());
+                            self.prog.push(ISplit(0, 0));
+                            self.emit_iteration(&**body, mark, cfrom, cto);
+                            if self.prog.len() > MAX_PROGRAM { return; }
+                        }
+                        let end = self.prog.len();
+                        for splits.each |s| {
+                            self.prog[*s] = if greedy {
+                                ISplit(*s + 1, end)
+                            } else {
+                                ISplit(end, *s + 1)
+                            };
+                        }
+                    },
+                    None => {
+                        let top = self.prog.len();
+                        self.prog.push(ISplit(0, 0));
+                        self.emit_iteration(&**body, mark, cfrom, cto);
+                        self.prog.push(IJmp(top));
+                        let end = self.prog.len();
+                        self.prog[top] = if greedy {
+                            ISplit(top + 1, end)
+                        } else {
+                            ISplit(end, top + 1)
+                        };
+                    }
+                }
+            }
+        }
+    }
+
+    // one optional iteration of a loop, which may not match empty
+    fn emit_iteration(&mut self, body: &Node, mark: uint, cfrom: uint, cto: uint) {
+        self.prog.push(ISetMark(mark));
+        if cto > cfrom { self.prog.push(IReset(cfrom, cto)); }
+        self.emit(body);
+        self.prog.push(ICheckMark(mark));
+    }
+}
+
+priv fn sort_ranges(ranges: &mut ~[(u16, u16)]) {
+    // insertion sort; classes are short
+    for uint::range(1, ranges.len()) |i| {
+        let mut j = i;
+        while j > 0 && ranges[j-1].first() > ranges[j].first() {
+            ranges.swap(j - 1, j);
+            j -= 1;
+        }
+    }
+}
+
+pub fn parse_flags(flags: &[u16]) -> Option<(bool, bool, bool)> {
+    let mut g = false;
+    let mut i = false;
+    let mut m = false;
+    for flags.each |f| {
+        match *f as char {
+            'g' if !g => { g = true; },
+            'i' if !i => { i = true; },
+            'm' if !m => { m = true; },
+            _ => { return None; }
+        }
+    }
+    Some((g, i, m))
+}
+
+// compile a pattern; the error message describes a syntax error.
+pub fn compile(pattern: &[u16], global: bool, ignore_case: bool,
+               multiline: bool) -> Result<Regex, ~str> {
+    let mut p = Parser {
+        src: pattern.to_owned(),
+        pos: 0,
+        ncaps: 0,
+        total_caps: count_captures(pattern)
+    };
+    let tree = match p.parse_disjunction() {
+        Ok(t) => t,
+        Err(e) => { return Err(e); }
+    };
+    if p.pos < pattern.len() {
+        // the only way the disjunction stops early
+        return Err(~"Unmatched ')'");
+    }
+    let mut c = Compiler {
+        prog: ~[ISave(0)],
+        classes: ~[],
+        nmarks: 0,
+        ignore_case: ignore_case
+    };
+    c.emit(&tree);
+    if c.prog.len() > MAX_PROGRAM {
+        return Err(~"Regular expression too large");
+    }
+    c.prog.push(ISave(1));
+    c.prog.push(IMatch);
+    Ok(Regex {
+        prog: c.prog,
+        classes: c.classes,
+        nmarks: c.nmarks,
+        ncaptures: p.ncaps + 1,
+        global: global,
+        ignore_case: ignore_case,
+        multiline: multiline
+    })
+}
+
+// matcher
+
+priv enum Backtrack {
+    BtBranch(uint, uint), // pc, position
+    BtSlot(uint, int),    // restore a capture slot
+    BtMark(uint, uint)    // restore a loop register
+}
+
+impl Regex {
+    priv fn canon(&self, c: u16) -> u16 {
+        if self.ignore_case { canonicalize(c) } else { c }
+    }
+
+    priv fn class_matches(&self, idx: uint, c: u16) -> bool {
+        let ranges : &[(u16, u16)] = self.classes[idx];
+        in_ranges(ranges, c) || (self.ignore_case &&
+            (in_ranges(ranges, canonicalize(c)) || in_ranges(ranges, to_lower(c))))
+    }
+
+    // run the program from pc at pos; returns the end position of a match
+    priv fn run(&self, input: &[u16], pc: uint, pos: uint,
+                slots: &mut ~[int], marks: &mut ~[uint]) -> Option<uint> {
+        let mut stack : ~[Backtrack] = ~[];
+        let mut pc = pc;
+        let mut pos = pos;
+        let len = input.len();
+        loop {
+            let ok = match self.prog[pc] {
+                IChar(c) => {
+                    if pos < len && self.canon(input[pos]) == c {
+                        pos += 1; pc += 1; true
+                    } else { false }
+                },
+                IAny => {
+                    if pos < len && !is_line_terminator(input[pos]) {
+                        pos += 1; pc += 1; true
+                    } else { false }
+                },
+                IClass(idx, negated) => {
+                    if pos < len && self.class_matches(idx, input[pos]) != negated {
+                        pos += 1; pc += 1; true
+                    } else { false }
+                },
+                ISplit(first, second) => {
+                    stack.push(BtBranch(second, pos));
+                    pc = first;
+                    true
+                },
+                IJmp(target) => { pc = target; true },
+                ISave(slot) => {
+                    stack.push(BtSlot(slot, slots[slot]));
+                    slots[slot] = pos as int;
+                    pc += 1;
+                    true
+                },
+                IReset(from, to) => {
+                    for uint::range(2 * from, 2 * to) |slot| {
+                        if slots[slot] != -1 {
+                            stack.push(BtSlot(slot, slots[slot]));
+                            slots[slot] = -1;
+                        }
+                    }
+                    pc += 1;
+                    true
+                },
+                ISetMark(r) => {
+                    stack.push(BtMark(r, marks[r]));
+                    marks[r] = pos;
+                    pc += 1;
+                    true
+                },
+                ICheckMark(r) => {
+                    if marks[r] == pos { false } else { pc += 1; true }
+                },
+                IBol => {
+                    if pos == 0 || (self.multiline && is_line_terminator(input[pos-1])) {
+                        pc += 1; true
+                    } else { false }
+                },
+                IEol => {
+                    if pos == len || (self.multiline && is_line_terminator(input[pos])) {
+                        pc += 1; true
+                    } else { false }
+                },
+                IWordBoundary(want) => {
+                    let a = pos > 0 && is_word_char(input[pos-1]);
+                    let b = pos < len && is_word_char(input[pos]);
+                    if (a != b) == want { pc += 1; true } else { false }
+                },
+                IBackref(n) => {
+                    let (s, e) = (slots[2*n], slots[2*n+1]);
+                    if s < 0 || e < 0 {
+                        // an undefined capture matches the empty string
+                        pc += 1;
+                        true
+                    } else {
+                        let (s, e) = (s as uint, e as uint);
+                        let n = e - s;
+                        let mut same = pos + n <= len;
+                        let mut i = 0u;
+                        while same && i < n {
+                            same = self.canon(input[s+i]) == self.canon(input[pos+i]);
+                            i += 1;
+                        }
+                        if same { pos += n; pc += 1; true } else { false }
+                    }
+                },
+                ILook(positive, end) => {
+                    // lookaheads are atomic: once they succeed we never
+                    // backtrack into them.
+                    let mut sub_slots = slots.clone();
+                    let mut sub_marks = marks.clone();
+                    let found = self.run(input, pc + 1, pos,
+                                         &mut sub_slots, &mut sub_marks).is_some();
+                    if found != positive {
+                        false
+                    } else {
+                        if positive {
+                            for uint::range(0, slots.len()) |i| {
+                                if sub_slots[i] != slots[i] {
+                                    stack.push(BtSlot(i, slots[i]));
+                                    slots[i] = sub_slots[i];
+                                }
+                            }
+                        }
+                        pc = end;
+                        true
+                    }
+                },
+                IMatch => { return Some(pos); }
+            };
+            if !ok {
+                // backtrack
+                loop {
+                    if stack.is_empty() { return None; }
+                    match stack.pop() {
+                        BtBranch(p, q) => { pc = p; pos = q; break; },
+                        BtSlot(slot, v) => { slots[slot] = v; },
+                        BtMark(r, v) => { marks[r] = v; }
+                    }
+                }
+            }
+        }
+    }
+
+    // match starting exactly at pos
+    pub fn match_at(&self, input: &[u16], pos: uint) -> Option<Captures> {
+        let mut slots = vec::from_elem(2 * self.ncaptures, -1i);
+        let mut marks = vec::from_elem(self.nmarks, uint::max_value);
+        match self.run(input, 0, pos, &mut slots, &mut marks) {
+            None => None,
+            Some(_) => Some(vec::from_fn(self.ncaptures, |i| {
+                if slots[2*i] < 0 || slots[2*i+1] < 0 {
+                    None
+                } else {
+                    Some((slots[2*i] as uint, slots[2*i+1] as uint))
+                }
+            }))
+        }
+    }
+
+    // the first match starting at or after start
+    pub fn exec(&self, input: &[u16], start: uint) -> Option<Captures> {
+        let mut i = start;
+        while i <= input.len() {
+            match self.match_at(input, i) {
+                Some(caps) => { return Some(caps); },
+                None => { i += 1; }
+            }
+        }
+        None
+    }
+}
+
+// JavaScript bindings
+
+priv fn substr(s: @[u16], start: uint, end: uint) -> JsVal {
+    JsString(at_vec::from_fn(end - start, |i| s[start + i]))
+}
+
+priv fn field(name: &str) -> JsVal { JsVal::from_str(name) }
+
+// the compiled Regex behind a RegExp object
+pub fn get_regex(env: Environment, val: JsVal) -> Option<@Regex> {
+    match val {
+        JsObject(obj) => match obj.get_simple(env.fdValue) {
+            Some(JsRegExp(re)) => Some(re),
+            _ => None
+        },
+        _ => None
+    }
+}
+
+priv fn new_regexp(env: Environment, proto: @mut Object,
+                   source: @[u16], flags: @[u16]) -> JsVal {
+    let (g, i, m) = match parse_flags(flags) {
+        Some(f) => f,
+        None => {
+            return env.throwError("SyntaxError", fmt!(
+                "Invalid flags supplied to RegExp constructor '%s'",
+                str::from_utf16(flags)));
+        }
+    };
+    let re = match compile(source, g, i, m) {
+        Ok(re) => re,
+        Err(msg) => {
+            return env.throwError("SyntaxError", fmt!(
+                "Invalid regular expression: /%s/: %s",
+                str::from_utf16(source), msg));
+        }
+    };
+    let obj = Object::create(env.root_map, proto);
+    obj.set(env.fdValue, JsRegExp(@re));
+    let rx = JsObject(obj);
+    env.set_slot(rx, field("source"), if source.is_empty() {
+        field("(?:)")
+    } else {
+        JsString(source)
+    });
+    env.set_slot(rx, field("global"), JsBool(g));
+    env.set_slot(rx, field("ignoreCase"), JsBool(i));
+    env.set_slot(rx, field("multiline"), JsBool(m));
+    env.set_slot(rx, field("lastIndex"), JsNumber(0f64));
+    rx
+}
+
+// convert the argument of match/search into a RegExp
+priv fn to_regexp(env: Environment, proto: @mut Object, val: JsVal) -> JsVal {
+    match get_regex(env, val) {
+        Some(_) => val,
+        None => {
+            let source = match val {
+                JsUndefined => @[],
+                _ => try_native!(env.tryToUtf16(val))
+            };
+            new_regexp(env, proto, source, @[])
+        }
+    }
+}
+
+priv fn match_array(env: Environment, s: @[u16], caps: &Captures) -> JsVal {
+    let elements = caps.map(|c| match *c {
+        Some((start, end)) => substr(s, start, end),
+        None => JsUndefined
+    });
+    let arr = env.arrayCreate(elements);
+    env.set_slot(arr, field("index"),
+                 JsNumber(caps[0].get().first() as f64));
+    env.set_slot(arr, field("input"), JsString(s));
+    arr
+}
+
+// RegExp.prototype.exec, without building the result array
+priv fn regexp_exec(env: Environment, rx: JsVal, re: @Regex,
+                    s: @[u16]) -> Result<Option<Captures>, JsVal> {
+    let start = if re.global {
+        match env.tryToInteger(env.get_slot(rx, field("lastIndex"))) {
+            Ok(n) => n,
+            Err(e) => { return Err(e); }
+        }
+    } else {
+        0f64
+    };
+    if start < 0f64 || start > (s.len() as f64) {
+        env.set_slot(rx, field("lastIndex"), JsNumber(0f64));
+        return Ok(None);
+    }
+    let rv = re.exec(s, start as uint);
+    if re.global {
+        let last = match rv {
+            Some(ref caps) => caps[0].get().second(),
+            None => 0
+        };
+        env.set_slot(rx, field("lastIndex"), JsNumber(last as f64));
+    }
+    Ok(rv)
+}
+
+// every match of a global regexp, as String.prototype.match and replace
+// find them.
+priv fn all_matches(env: Environment, rx: JsVal, re: @Regex,
+                    s: @[u16]) -> ~[Captures] {
+    let mut result : ~[Captures] = ~[];
+    let mut pos = 0u;
+    while pos <= s.len() {
+        match re.exec(s, pos) {
+            None => break,
+            Some(caps) => {
+                let (start, end) = caps[0].get();
+                pos = if end == start { end + 1 } else { end };
+                result.push(caps);
+            }
+        }
+    }
+    env.set_slot(rx, field("lastIndex"), JsNumber(0f64));
+    result
+}
+
+// the replacement template of String.prototype.replace (ES5 table 22)
+priv fn expand_template(out: &mut ~[u16], template: &[u16], s: &[u16],
+                        caps: &Captures) {
+    let m = caps.len() - 1;
+    let push_cap = |out: &mut ~[u16], n: uint| {
+        match caps[n] {
+            Some((start, end)) => out.push_all(s.slice(start, end)),
+            None => {}
+        }
+    };
+    let digit = |i: uint| -> Option<uint> {
+        if i < template.len() && template[i] >= ('0' as u16) &&
+            template[i] <= ('9' as u16) {
+            Some((template[i] - ('0' as u16)) as uint)
+        } else {
+            None
+        }
+    };
+    let (start, end) = caps[0].get();
+    let mut i = 0u;
+    while i < template.len() {
+        let c = template[i];
+        if c != ('$' as u16) || i + 1 >= template.len() {
+            out.push(c);
+            i += 1;
+            loop;
+        }
+        match template[i+1] as char {
+            '$' => { out.push(c); i += 2; },
+            '&' => { out.push_all(s.slice(start, end)); i += 2; },
+            '`' => { out.push_all(s.slice(0, start)); i += 2; },
+            '\'' => { out.push_all(s.slice(end, s.len())); i += 2; },
+            _ => match (digit(i+1), digit(i+2)) {
+                (Some(d1), Some(d2)) if d1*10 + d2 >= 1 && d1*10 + d2 <= m => {
+                    push_cap(out, d1*10 + d2);
+                    i += 3;
+                },
+                (Some(d1), _) if d1 >= 1 && d1 <= m => {
+                    push_cap(out, d1);
+                    i += 2;
+                },
+                _ => { out.push(c); i += 1; }
+            }
+        }
+    }
+}
+
+priv fn index_of(s: &[u16], search: &[u16], from: uint) -> Option<uint> {
+    let mut i = from;
+    while i + search.len() <= s.len() {
+        if s.slice(i, i + search.len()) == search { return Some(i); }
+        i += 1;
+    }
+    None
+}
+
+pub fn make_regexp(env: Environment, frame: @mut Object) -> @mut Object {
+    let myRegExp = Object::create(env.root_map, env.myObject);
+    let cons = Object::create(env.root_map, env.myFunction);
+    cons.set(FieldDesc { name: intern("prototype"), hidden: false },
+             JsObject(myRegExp));
+    cons.set(env.fdParentFrame, JsObject(frame));
+    cons.set(env.fdValue, JsNativeFunction(|_this, args| {
+        let pattern = getarg(args, 0);
+        let flags = getarg(args, 1);
+        match (get_regex(env, pattern), flags) {
+            (Some(_), JsUndefined) => pattern,
+            (Some(_), _) => env.throwError(
+                "TypeError",
+                "Cannot supply flags when constructing one RegExp from another"),
+            (None, _) => {
+                let source = match pattern {
+                    JsUndefined => @[],
+                    _ => try_native!(env.tryToUtf16(pattern))
+                };
+                let flags = match flags {
+                    JsUndefined => @[],
+                    _ => try_native!(env.tryToUtf16(flags))
+                };
+                new_regexp(env, myRegExp, source, flags)
+            }
+        }
+    }));
+
+    do env.add_native_func_str(frame, myRegExp, "exec") |this, args| {
+        match get_regex(env, this) {
+            None => env.throwError("TypeError", "RegExp.prototype.exec called on incompatible receiver"),
+            Some(re) => {
+                let s = try_native!(env.tryToUtf16(getarg(args, 0)));
+                match try_native!(regexp_exec(env, this, re, s)) {
+                    Some(caps) => match_array(env, s, &caps),
+                    None => JsNull
+                }
+            }
+        }
+    };
+    do env.add_native_func_str(frame, myRegExp, "test") |this, args| {
+        match get_regex(env, this) {
+            None => env.throwError("TypeError", "RegExp.prototype.test called on incompatible receiver"),
+            Some(re) => {
+                let s = try_native!(env.tryToUtf16(getarg(args, 0)));
+                JsBool(try_native!(regexp_exec(env, this, re, s)).is_some())
+            }
+        }
+    };
+    do env.add_native_func_str(frame, myRegExp, "toString") |this, _args| {
+        match get_regex(env, this) {
+            None => env.throwError("TypeError", "RegExp.prototype.toString called on incompatible receiver"),
+            Some(re) => {
+                let source = try_native!(env.tryToString(env.get_slot(this, field("source"))));
+                JsVal::from_str(fmt!("/%s/%s%s%s", source,
+                                     if re.global { "g" } else { "" },
+                                     if re.ignore_case { "i" } else { "" },
+                                     if re.multiline { "m" } else { "" }))
+            }
+        }
+    };
+
+    // regexp-based String methods
+    do env.add_native_func_str(frame, env.myString, "match") |this, args| {
+        let s = try_native!(env.tryToUtf16(this));
+        let rx = to_regexp(env, myRegExp, getarg(args, 0));
+        match get_regex(env, rx) {
+            None => rx, // exception
+            Some(re) if !re.global => match try_native!(regexp_exec(env, rx, re, s)) {
+                Some(caps) => match_array(env, s, &caps),
+                None => JsNull
+            },
+            Some(re) => {
+                let matches = all_matches(env, rx, re, s);
+                if matches.is_empty() {
+                    JsNull
+                } else {
+                    env.arrayCreate(matches.map(|caps| {
+                        let (start, end) = caps[0].get();
+                        substr(s, start, end)
+                    }))
+                }
+            }
+        }
+    };
+    do env.add_native_func_str(frame, env.myString, "search") |this, args| {
+        let s = try_native!(env.tryToUtf16(this));
+        let rx = to_regexp(env, myRegExp, getarg(args, 0));
+        match get_regex(env, rx) {
+            None => rx, // exception
+            Some(re) => JsNumber(match re.exec(s, 0) {
+                Some(caps) => caps[0].get().first() as f64,
+                None => -1f64
+            })
+        }
+    };
+    do env.add_native_func_str(frame, env.myString, "replace") |this, args| {
+        let s = try_native!(env.tryToUtf16(this));
+        let search = getarg(args, 0);
+        let replace = getarg(args, 1);
+        let matches : ~[Captures] = match get_regex(env, search) {
+            Some(re) if re.global => all_matches(env, search, re, s),
+            Some(re) => match re.exec(s, 0) {
+                Some(caps) => ~[caps],
+                None => ~[]
+            },
+            None => {
+                let needle = try_native!(env.tryToUtf16(search));
+                match index_of(s, needle, 0) {
+                    Some(i) => ~[~[Some((i, i + needle.len()))]],
+                    None => ~[]
+                }
+            }
+        };
+        let template = if env.isCallable(replace) {
+            None
+        } else {
+            Some(try_native!(env.tryToUtf16(replace)))
+        };
+        let mut out : ~[u16] = ~[];
+        let mut last = 0u;
+        for matches.each |caps| {
+            let (start, end) = caps[0].get();
+            out.push_all(s.slice(last, start));
+            match template {
+                Some(t) => expand_template(&mut out, t, s, caps),
+                None => {
+                    let mut fargs = caps.map(|c| match *c {
+                        Some((a, b)) => substr(s, a, b),
+                        None => JsUndefined
+                    });
+                    fargs.push(JsNumber(start as f64));
+                    fargs.push(JsString(s));
+                    let rv = env.interpret_function(replace, JsUndefined, fargs);
+                    match rv {
+                        JsThrown(_) => { return rv; },
+                        _ => out.push_all(try_native!(env.tryToUtf16(rv)))
+                    }
+                }
+            }
+            last = end;
+        }
+        out.push_all(s.slice(last, s.len()));
+        JsString(at_vec::to_managed_consume(out))
+    };
+    do env.add_native_func_str(frame, env.myString, "split") |this, args| {
+        // ES5 15.5.4.14
+        let s = try_native!(env.tryToUtf16(this));
+        let separator = getarg(args, 0);
+        let lim = match getarg(args, 1) {
+            JsUndefined => 0xFFFFFFFFu,
+            l => try_native!(env.tryToUint32(l)) as uint
+        };
+        let re = get_regex(env, separator);
+        let sep = match re {
+            Some(_) => @[],
+            None => try_native!(env.tryToUtf16(separator))
+        };
+        // returns the end index and captures of a match exactly at q
+        let split_match = |q: uint| -> Option<(uint, ~[JsVal])> {
+            match re {
+                Some(re) => match re.match_at(s, q) {
+                    None => None,
+                    Some(caps) => Some((caps[0].get().second(),
+                                        caps.slice(1, caps.len()).map(|c| match *c {
+                                            Some((a, b)) => substr(s, a, b),
+                                            None => JsUndefined
+                                        })))
+                },
+                None => {
+                    if q + sep.len() <= s.len() &&
+                        s.slice(q, q + sep.len()) == sep.slice(0, sep.len()) {
+                        Some((q + sep.len(), ~[]))
+                    } else {
+                        None
+                    }
+                }
+            }
+        };
+        let mut a : ~[JsVal] = ~[];
+        if lim == 0 { return env.arrayCreate(a); }
+        match separator {
+            JsUndefined => { return env.arrayCreate([JsString(s)]); },
+            _ => {}
+        }
+        if s.is_empty() {
+            if split_match(0).is_none() { a.push(JsString(s)); }
+            return env.arrayCreate(a);
+        }
+        let mut p = 0u;
+        let mut q = p;
+        while q != s.len() {
+            match split_match(q) {
+                None => { q += 1; },
+                Some((e, _)) if e == p => { q += 1; },
+                Some((e, caps)) => {
+                    a.push(substr(s, p, q));
+                    if a.len() == lim { return env.arrayCreate(a); }
+                    p = e;
+                    for caps.each |c| {
+                        a.push(*c);
+                        if a.len() == lim { return env.arrayCreate(a); }
+                    }
+                    q = p;
+                }
+            }
+        }
+        a.push(substr(s, p, s.len()));
+        env.arrayCreate(a)
+    };
+    cons
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find(pattern: &str, flags: &str, input: &str) -> Option<~[Option<~str>]> {
+        let (g, i, m) = parse_flags(str::to_utf16(flags)).get();
+        let re = compile(str::to_utf16(pattern), g, i, m).get();
+        let s = str::to_utf16(input);
+        do re.exec(s, 0).map |caps| {
+            caps.map(|c| c.map(|&(a, b)| str::from_utf16(s.slice(a, b))))
+        }
+    }
+
+    fn whole(pattern: &str, flags: &str, input: &str) -> Option<~str> {
+        find(pattern, flags, input).map(|caps| caps[0].get())
+    }
+
+    #[test]
+    fn test_basic() {
+        assert_eq!(whole("b+", "", "abbbc"), Some(~"bbb"));
+        assert_eq!(whole("b+?", "", "abbbc"), Some(~"b"));
+        assert_eq!(whole("a.c", "", "xa\nc abc"), Some(~"abc"));
+        assert_eq!(whole("^abc$", "", "x\nabc"), None);
+        assert_eq!(whole("^abc$", "m", "x\nabc\ny"), Some(~"abc"));
+        assert_eq!(whole("ABC", "i", "xabcx"), Some(~"abc"));
+        assert_eq!(whole("[a-c]+", "i", "xxBCAd"), Some(~"BCA"));
+        assert_eq!(whole("[^a-c]+", "", "abcdefa"), Some(~"def"));
+        assert_eq!(whole("\\d{2,3}", "", "a1234"), Some(~"123"));
+        assert_eq!(whole("\\d{2,}?", "", "a1234"), Some(~"12"));
+        assert_eq!(whole("a{,2}", "", "a{,2}"), Some(~"a{,2}"));
+        assert_eq!(whole("\\bfoo\\b", "", "afoo foo"), Some(~"foo"));
+        assert_eq!(whole("[\\w-]+", "", "  ab-c d"), Some(~"ab-c"));
+        assert_eq!(whole("\\u0041\\x42", "", "AB"), Some(~"AB"));
+        assert_eq!(whole("cat|dog", "", "hotdog"), Some(~"dog"));
+        assert_eq!(whole("(?:ab)*c", "", "ababc"), Some(~"ababc"));
+        assert_eq!(whole("(a*)*b", "", "aaab"), Some(~"aaab"));
+        assert_eq!(whole("(a*)*b", "", "aaa"), None);
+    }
+
+    #[test]
+    fn test_captures() {
+        assert_eq!(find("(a)|(b)", "", "b"), Some(~[Some(~"b"), None, Some(~"b")]));
+        assert_eq!(find("(\\w)\\1", "", "abccd"), Some(~[Some(~"cc"), Some(~"c")]));
+        assert_eq!(find("(a)\\1", "i", "aA"), Some(~[Some(~"aA"), Some(~"a")]));
+        // captures are reset on each iteration (ES5 15.10.2.5)
+        assert_eq!(find("(z)((a+)?(b+)?(c))*", "", "zaacbbbcac"),
+                   Some(~[Some(~"zaacbbbcac"), Some(~"z"), Some(~"ac"),
+                          Some(~"a"), None, Some(~"c")]));
+        assert_eq!(find("(?=(a+))a*b\\1", "", "baaabac"),
+                   Some(~[Some(~"aba"), Some(~"a")]));
+        assert_eq!(find("(.*?)a(?!(a+)b\\2c)\\2(.*)", "", "baaabaac"),
+                   Some(~[Some(~"baaabaac"), Some(~"ba"), None, Some(~"abaac")]));
+    }
+
+    #[test]
+    fn test_errors() {
+        let err = |p: &str| compile(str::to_utf16(p), false, false, false).is_err();
+        assert!(err("a**"));
+        assert!(err("(ab"));
+        assert!(err("ab)"));
+        assert!(err("[b-a]"));
+        assert!(err("a{2,1}"));
+        assert!(err("\\"));
+        assert!(!err("a{"));
+        assert!(parse_flags(str::to_utf16("gg")).is_none());
+        assert!(parse_flags(str::to_utf16("x")).is_none());
+    }
+}
@@ -1,8 +1,17 @@
-// generated by TurtleScript write-rust-bytecode.js
+// first generated by TurtleScript write-rust-bytecode.js, and maintained
+// by hand since: the self-hosted compiler here has opcodes, operators,
+// regexp literals and function spans which TurtleScript's doesn't, so
+// regenerating this file would lose them.
 #[allow(unused_imports)];
 use function::Function;
 use object::{JsVal,JsNumber,JsBool,JsUndefined,JsNull};
 
+// edited by hand: function names are UTF-16, so each name below is
+// wrapped in this.  write-rust-bytecode.js should emit them that way.
+priv fn utf16(s: &str) -> @[u16] {
+    at_vec::to_managed_consume(str::to_utf16(s))
+}
+
 pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
   // functions
   vec::push(functions, @Function {
@@ -10,6 +19,7 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     id: 0,
     nargs: 0,
     max_stack: 7,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       1, 0,	// 1: push_literal(0)
@@ -59,7 +69,7 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
       1, 286,	// 69: push_literal(286)
       3,	// 71: new_array
       0,	// 72: push_frame
-      4, 77,	// 73: new_function(77)
+      4, 78,	// 73: new_function(78)
       17,	// 75: over
       8, 335,	// 76: set_slot_direct(335)
       10, 3,	// 78: invoke(3)
@@ -74,7 +84,7 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
       1, 286,	// 91: push_literal(286)
       8, 6,	// 93: set_slot_direct(6)
       0,	// 95: push_frame
-      4, 87,	// 96: new_function(87)
+      4, 88,	// 96: new_function(88)
       17,	// 98: over
       8, 404,	// 99: set_slot_direct(404)
       10, 3,	// 101: invoke(3)
@@ -85,7 +95,7 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
       5, 12,	// 108: get_slot_direct(12)
       1, 405,	// 110: push_literal(405)
       3,	// 112: new_array
-      4, 144,	// 113: new_function(144)
+      4, 146,	// 113: new_function(146)
       10, 3,	// 115: invoke(3)
       14,	// 117: pop
       0,	// 118: push_frame
@@ -103,7 +113,7 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
       15,	// 137: dup
       1, 405,	// 138: push_literal(405)
       8, 79,	// 140: set_slot_direct(79)
-      4, 145,	// 142: new_function(145)
+      4, 147,	// 142: new_function(147)
       10, 3,	// 144: invoke(3)
       14,	// 146: pop
       0,	// 147: push_frame
@@ -118,6 +128,7 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     id: 1,
     nargs: 0,
     max_stack: 3,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -241,10 +252,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // ".indexOf"
-    name: Some(~".indexOf"),
+    name: Some(utf16(".indexOf")),
     id: 2,
     nargs: 2,
     max_stack: 4,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -371,10 +383,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // ".trim"
-    name: Some(~".trim"),
+    name: Some(utf16(".trim")),
     id: 3,
     nargs: 0,
     max_stack: 6,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -529,10 +542,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // ".push"
-    name: Some(~".push"),
+    name: Some(utf16(".push")),
     id: 4,
     nargs: 0,
     max_stack: 4,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -601,10 +615,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // ".pop"
-    name: Some(~".pop"),
+    name: Some(utf16(".pop")),
     id: 5,
     nargs: 0,
     max_stack: 4,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -643,10 +658,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // ".join"
-    name: Some(~".join"),
+    name: Some(utf16(".join")),
     id: 6,
     nargs: 1,
     max_stack: 3,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -743,10 +759,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // ".concat"
-    name: Some(~".concat"),
+    name: Some(utf16(".concat")),
     id: 7,
     nargs: 0,
     max_stack: 4,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -890,10 +907,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // ".forEach"
-    name: Some(~".forEach"),
+    name: Some(utf16(".forEach")),
     id: 8,
     nargs: 2,
     max_stack: 6,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -976,10 +994,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // ".map"
-    name: Some(~".map"),
+    name: Some(utf16(".map")),
     id: 9,
     nargs: 1,
     max_stack: 8,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -1082,10 +1101,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // ".join"
-    name: Some(~".join"),
+    name: Some(utf16(".join")),
     id: 10,
     nargs: 1,
     max_stack: 4,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -1163,10 +1183,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // ".bind"
-    name: Some(~".bind"),
+    name: Some(utf16(".bind")),
     id: 11,
     nargs: 0,
     max_stack: 5,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -1271,10 +1292,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // "addHasInstance"
-    name: Some(~"addHasInstance"),
+    name: Some(utf16("addHasInstance")),
     id: 12,
     nargs: 1,
     max_stack: 3,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -1294,10 +1316,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // ".hasInstance"
-    name: Some(~".hasInstance"),
+    name: Some(utf16(".hasInstance")),
     id: 13,
     nargs: 1,
     max_stack: 3,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -1321,10 +1344,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // "bind0"
-    name: Some(~"bind0"),
+    name: Some(utf16("bind0")),
     id: 14,
     nargs: 0,
     max_stack: 4,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -1345,10 +1369,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // "bindN"
-    name: Some(~"bindN"),
+    name: Some(utf16("bindN")),
     id: 15,
     nargs: 0,
     max_stack: 7,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -1380,10 +1405,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // ".hasInstance"
-    name: Some(~".hasInstance"),
+    name: Some(utf16(".hasInstance")),
     id: 16,
     nargs: 1,
     max_stack: 3,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -1458,6 +1484,7 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     id: 17,
     nargs: 0,
     max_stack: 5,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -1517,10 +1544,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // ".toString"
-    name: Some(~".toString"),
+    name: Some(utf16(".toString")),
     id: 18,
     nargs: 0,
     max_stack: 3,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -1555,10 +1583,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // ".toString"
-    name: Some(~".toString"),
+    name: Some(utf16(".toString")),
     id: 19,
     nargs: 0,
     max_stack: 3,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -1587,6 +1616,7 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     id: 20,
     nargs: 3,
     max_stack: 5,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -1643,6 +1673,7 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     id: 21,
     nargs: 3,
     max_stack: 3,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -1671,6 +1702,7 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     id: 22,
     nargs: 3,
     max_stack: 3,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -1706,6 +1738,7 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     id: 23,
     nargs: 3,
     max_stack: 3,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -1737,10 +1770,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // "define"
-    name: Some(~"define"),
+    name: Some(utf16("define")),
     id: 24,
     nargs: 3,
     max_stack: 6,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -1797,6 +1831,7 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     id: 25,
     nargs: 1,
     max_stack: 3,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -1817,10 +1852,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // "make_tokenize"
-    name: Some(~"make_tokenize"),
+    name: Some(utf16("make_tokenize")),
     id: 26,
     nargs: 0,
     max_stack: 3,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -1846,10 +1882,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // "tokenize"
-    name: Some(~"tokenize"),
+    name: Some(utf16("tokenize")),
     id: 27,
     nargs: 4,
     max_stack: 8,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -1957,7 +1994,7 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
       5, 10,	// 157: get_slot_direct(10)
       10, 1,	// 159: invoke(1)
       8, 94,	// 161: set_slot_direct(94)
-      12, 2129,	// 163: jmp(2129)
+      12, 2672,	// 163: jmp(2672)
       0,	// 165: push_frame
       0,	// 166: push_frame
       5, 10,	// 167: get_slot_direct(10)
@@ -1984,7 +2021,7 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
       5, 10,	// 198: get_slot_direct(10)
       10, 1,	// 200: invoke(1)
       8, 94,	// 202: set_slot_direct(94)
-      12, 2129,	// 204: jmp(2129)
+      12, 2672,	// 204: jmp(2672)
       0,	// 206: push_frame
       5, 94,	// 207: get_slot_direct(94)
       1, 113,	// 209: push_literal(113)
@@ -2145,7 +2182,7 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
       10, 2,	// 432: invoke(2)
       10, 1,	// 434: invoke(1)
       14,	// 436: pop
-      12, 2129,	// 437: jmp(2129)
+      12, 2672,	// 437: jmp(2672)
       0,	// 439: push_frame
       5, 94,	// 440: get_slot_direct(94)
       1, 119,	// 442: push_literal(119)
@@ -2655,7 +2692,7 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
       1, 129,	// 1197: push_literal(129)
       10, 2,	// 1199: invoke(2)
       14,	// 1201: pop
-      12, 2129,	// 1202: jmp(2129)
+      12, 2672,	// 1202: jmp(2672)
       0,	// 1204: push_frame
       5, 94,	// 1205: get_slot_direct(94)
       1, 134,	// 1207: push_literal(134)
@@ -2983,7 +3020,7 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
       5, 10,	// 1707: get_slot_direct(10)
       10, 1,	// 1709: invoke(1)
       8, 94,	// 1711: set_slot_direct(94)
-      12, 2129,	// 1713: jmp(2129)
+      12, 2672,	// 1713: jmp(2672)
       0,	// 1715: push_frame
       5, 94,	// 1716: get_slot_direct(94)
       1, 151,	// 1718: push_literal(151)
@@ -3052,7 +3089,7 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
       1, 63,	// 1812: push_literal(63)
       20,	// 1814: un_not
       13, 1756,	// 1815: jmp_unless(1756)
-      12, 2129,	// 1817: jmp(2129)
+      12, 2672,	// 1817: jmp(2672)
       0,	// 1819: push_frame
       5, 94,	// 1820: get_slot_direct(94)
       1, 151,	// 1822: push_literal(151)
@@ -3144,134 +3181,496 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
       1, 63,	// 1951: push_literal(63)
       20,	// 1953: un_not
       13, 1860,	// 1954: jmp_unless(1860)
-      12, 2129,	// 1956: jmp(2129)
+      12, 2672,	// 1956: jmp(2672)
       0,	// 1958: push_frame
-      5, 90,	// 1959: get_slot_direct(90)
-      15,	// 1961: dup
-      7, 15,	// 1962: get_slot_direct_check(15)
-      19,	// 1964: swap
-      0,	// 1965: push_frame
-      5, 94,	// 1966: get_slot_direct(94)
-      10, 1,	// 1968: invoke(1)
-      1, 6,	// 1970: push_literal(6)
-      25,	// 1972: bi_gte
-      13, 2082,	// 1973: jmp_unless(2082)
-      0,	// 1975: push_frame
-      0,	// 1976: push_frame
-      5, 94,	// 1977: get_slot_direct(94)
-      8, 16,	// 1979: set_slot_direct(16)
+      5, 94,	// 1959: get_slot_direct(94)
+      1, 151,	// 1961: push_literal(151)
+      23,	// 1963: bi_eq
+      15,	// 1964: dup
+      13, 2107,	// 1965: jmp_unless(2107)
+      14,	// 1967: pop
+      0,	// 1968: push_frame
+      5, 29,	// 1969: get_slot_direct(29)
+      5, 13,	// 1971: get_slot_direct(13)
+      1, 6,	// 1973: push_literal(6)
+      23,	// 1975: bi_eq
+      15,	// 1976: dup
+      20,	// 1977: un_not
+      13, 2107,	// 1978: jmp_unless(2107)
+      14,	// 1980: pop
       0,	// 1981: push_frame
-      15,	// 1982: dup
-      5, 10,	// 1983: get_slot_direct(10)
-      1, 8,	// 1985: push_literal(8)
-      26,	// 1987: bi_add
-      8, 10,	// 1988: set_slot_direct(10)
-      12, 2046,	// 1990: jmp(2046)
-      0,	// 1992: push_frame
-      0,	// 1993: push_frame
-      5, 77,	// 1994: get_slot_direct(77)
-      15,	// 1996: dup
-      7, 14,	// 1997: get_slot_direct_check(14)
-      19,	// 1999: swap
-      0,	// 2000: push_frame
-      5, 10,	// 2001: get_slot_direct(10)
-      10, 1,	// 2003: invoke(1)
-      8, 94,	// 2005: set_slot_direct(94)
-      0,	// 2007: push_frame
-      5, 91,	// 2008: get_slot_direct(91)
-      15,	// 2010: dup
-      7, 15,	// 2011: get_slot_direct_check(15)
-      19,	// 2013: swap
-      0,	// 2014: push_frame
-      5, 94,	// 2015: get_slot_direct(94)
-      10, 1,	// 2017: invoke(1)
-      1, 6,	// 2019: push_literal(6)
-      19,	// 2021: swap
-      24,	// 2022: bi_gt
-      13, 2027,	// 2023: jmp_unless(2027)
-      12, 2057,	// 2025: jmp(2057)
+      5, 29,	// 1982: get_slot_direct(29)
+      0,	// 1984: push_frame
+      5, 29,	// 1985: get_slot_direct(29)
+      5, 13,	// 1987: get_slot_direct(13)
+      1, 8,	// 1989: push_literal(8)
+      27,	// 1991: bi_sub
+      6,	// 1992: get_slot_indirect
+      5, 107,	// 1993: get_slot_direct(107)
+      1, 153,	// 1995: push_literal(153)
+      23,	// 1997: bi_eq
+      15,	// 1998: dup
+      13, 2042,	// 1999: jmp_unless(2042)
+      14,	// 2001: pop
+      0,	// 2002: push_frame
+      5, 29,	// 2003: get_slot_direct(29)
+      0,	// 2005: push_frame
+      5, 29,	// 2006: get_slot_direct(29)
+      5, 13,	// 2008: get_slot_direct(13)
+      1, 8,	// 2010: push_literal(8)
+      27,	// 2012: bi_sub
+      6,	// 2013: get_slot_indirect
+      5, 108,	// 2014: get_slot_direct(108)
+      1, 224,	// 2016: push_literal(224)
+      23,	// 2018: bi_eq
+      20,	// 2019: un_not
+      15,	// 2020: dup
+      13, 2042,	// 2021: jmp_unless(2042)
+      14,	// 2023: pop
+      0,	// 2024: push_frame
+      5, 29,	// 2025: get_slot_direct(29)
       0,	// 2027: push_frame
-      15,	// 2028: dup
-      5, 16,	// 2029: get_slot_direct(16)
-      0,	// 2031: push_frame
-      5, 94,	// 2032: get_slot_direct(94)
-      26,	// 2034: bi_add
-      8, 16,	// 2035: set_slot_direct(16)
-      0,	// 2037: push_frame
-      15,	// 2038: dup
-      5, 10,	// 2039: get_slot_direct(10)
-      1, 8,	// 2041: push_literal(8)
-      26,	// 2043: bi_add
-      8, 10,	// 2044: set_slot_direct(10)
-      0,	// 2046: push_frame
-      5, 10,	// 2047: get_slot_direct(10)
-      0,	// 2049: push_frame
-      5, 13,	// 2050: get_slot_direct(13)
-      19,	// 2052: swap
-      24,	// 2053: bi_gt
-      20,	// 2054: un_not
-      13, 1992,	// 2055: jmp_unless(1992)
-      0,	// 2057: push_frame
-      5, 29,	// 2058: get_slot_direct(29)
-      15,	// 2060: dup
-      7, 23,	// 2061: get_slot_direct_check(23)
-      19,	// 2063: swap
-      0,	// 2064: push_frame
-      5, 98,	// 2065: get_slot_direct(98)
-      0,	// 2067: push_frame
-      5, 12,	// 2068: get_slot_direct(12)
-      1, 153,	// 2070: push_literal(153)
-      0,	// 2072: push_frame
-      5, 16,	// 2073: get_slot_direct(16)
-      10, 2,	// 2075: invoke(2)
-      10, 1,	// 2077: invoke(1)
-      14,	// 2079: pop
-      12, 2129,	// 2080: jmp(2129)
-      0,	// 2082: push_frame
-      15,	// 2083: dup
-      5, 10,	// 2084: get_slot_direct(10)
-      1, 8,	// 2086: push_literal(8)
-      26,	// 2088: bi_add
-      8, 10,	// 2089: set_slot_direct(10)
-      0,	// 2091: push_frame
-      5, 29,	// 2092: get_slot_direct(29)
-      15,	// 2094: dup
-      7, 23,	// 2095: get_slot_direct_check(23)
-      19,	// 2097: swap
-      0,	// 2098: push_frame
-      5, 98,	// 2099: get_slot_direct(98)
-      0,	// 2101: push_frame
-      5, 12,	// 2102: get_slot_direct(12)
-      1, 153,	// 2104: push_literal(153)
-      0,	// 2106: push_frame
-      5, 94,	// 2107: get_slot_direct(94)
-      10, 2,	// 2109: invoke(2)
-      10, 1,	// 2111: invoke(1)
-      14,	// 2113: pop
+      5, 29,	// 2028: get_slot_direct(29)
+      5, 13,	// 2030: get_slot_direct(13)
+      1, 8,	// 2032: push_literal(8)
+      27,	// 2034: bi_sub
+      6,	// 2035: get_slot_indirect
+      5, 108,	// 2036: get_slot_direct(108)
+      1, 106,	// 2038: push_literal(106)
+      23,	// 2040: bi_eq
+      20,	// 2041: un_not
+      15,	// 2042: dup
+      20,	// 2043: un_not
+      13, 2107,	// 2044: jmp_unless(2107)
+      14,	// 2046: pop
+      0,	// 2047: push_frame
+      5, 29,	// 2048: get_slot_direct(29)
+      0,	// 2050: push_frame
+      5, 29,	// 2051: get_slot_direct(29)
+      5, 13,	// 2053: get_slot_direct(13)
+      1, 8,	// 2055: push_literal(8)
+      27,	// 2057: bi_sub
+      6,	// 2058: get_slot_indirect
+      5, 107,	// 2059: get_slot_direct(107)
+      1, 67,	// 2061: push_literal(67)
+      23,	// 2063: bi_eq
+      15,	// 2064: dup
+      13, 2107,	// 2065: jmp_unless(2107)
+      14,	// 2067: pop
+      0,	// 2068: push_frame
+      5, 29,	// 2069: get_slot_direct(29)
+      0,	// 2071: push_frame
+      5, 29,	// 2072: get_slot_direct(29)
+      5, 13,	// 2074: get_slot_direct(13)
+      1, 8,	// 2076: push_literal(8)
+      27,	// 2078: bi_sub
+      6,	// 2079: get_slot_indirect
+      5, 108,	// 2080: get_slot_direct(108)
+      1, 266,	// 2082: push_literal(266)
+      23,	// 2084: bi_eq
+      15,	// 2085: dup
+      20,	// 2086: un_not
+      13, 2107,	// 2087: jmp_unless(2107)
+      14,	// 2089: pop
+      0,	// 2090: push_frame
+      5, 29,	// 2091: get_slot_direct(29)
+      0,	// 2093: push_frame
+      5, 29,	// 2094: get_slot_direct(29)
+      5, 13,	// 2096: get_slot_direct(13)
+      1, 8,	// 2098: push_literal(8)
+      27,	// 2100: bi_sub
+      6,	// 2101: get_slot_indirect
+      5, 108,	// 2102: get_slot_direct(108)
+      1, 260,	// 2104: push_literal(260)
+      23,	// 2106: bi_eq
+      13, 2501,	// 2107: jmp_unless(2501)
+      0,	// 2109: push_frame
+      1, 20,	// 2110: push_literal(20)
+      8, 16,	// 2112: set_slot_direct(16)
       0,	// 2114: push_frame
-      0,	// 2115: push_frame
-      5, 77,	// 2116: get_slot_direct(77)
-      15,	// 2118: dup
-      7, 14,	// 2119: get_slot_direct_check(14)
-      19,	// 2121: swap
-      0,	// 2122: push_frame
-      5, 10,	// 2123: get_slot_direct(10)
-      10, 1,	// 2125: invoke(1)
-      8, 94,	// 2127: set_slot_direct(94)
-      0,	// 2129: push_frame
-      5, 94,	// 2130: get_slot_direct(94)
-      20,	// 2132: un_not
-      13, 165,	// 2133: jmp_unless(165)
-      0,	// 2135: push_frame
-      5, 29,	// 2136: get_slot_direct(29)
-      11	// 2138: return
+      1, 59,	// 2115: push_literal(59)
+      8, 96,	// 2117: set_slot_direct(96)
+      0,	// 2119: push_frame
+      15,	// 2120: dup
+      5, 10,	// 2121: get_slot_direct(10)
+      1, 8,	// 2123: push_literal(8)
+      26,	// 2125: bi_add
+      8, 10,	// 2126: set_slot_direct(10)
+      12, 2362,	// 2128: jmp(2362)
+      0,	// 2130: push_frame
+      0,	// 2131: push_frame
+      5, 77,	// 2132: get_slot_direct(77)
+      15,	// 2134: dup
+      7, 14,	// 2135: get_slot_direct_check(14)
+      19,	// 2137: swap
+      0,	// 2138: push_frame
+      5, 10,	// 2139: get_slot_direct(10)
+      10, 1,	// 2141: invoke(1)
+      8, 94,	// 2143: set_slot_direct(94)
+      0,	// 2145: push_frame
+      5, 94,	// 2146: get_slot_direct(94)
+      1, 20,	// 2148: push_literal(20)
+      23,	// 2150: bi_eq
+      15,	// 2151: dup
+      20,	// 2152: un_not
+      13, 2173,	// 2153: jmp_unless(2173)
+      14,	// 2155: pop
+      0,	// 2156: push_frame
+      5, 94,	// 2157: get_slot_direct(94)
+      1, 136,	// 2159: push_literal(136)
+      23,	// 2161: bi_eq
+      15,	// 2162: dup
+      20,	// 2163: un_not
+      13, 2173,	// 2164: jmp_unless(2173)
+      14,	// 2166: pop
+      0,	// 2167: push_frame
+      5, 94,	// 2168: get_slot_direct(94)
+      1, 137,	// 2170: push_literal(137)
+      23,	// 2172: bi_eq
+      13, 2199,	// 2173: jmp_unless(2199)
+      0,	// 2175: push_frame
+      5, 97,	// 2176: get_slot_direct(97)
+      0,	// 2178: push_frame
+      5, 12,	// 2179: get_slot_direct(12)
+      0,	// 2181: push_frame
+      5, 98,	// 2182: get_slot_direct(98)
+      0,	// 2184: push_frame
+      5, 12,	// 2185: get_slot_direct(12)
+      1, 451,	// 2187: push_literal(451)
+      0,	// 2189: push_frame
+      5, 16,	// 2190: get_slot_direct(16)
+      10, 2,	// 2192: invoke(2)
+      1, 452,	// 2194: push_literal(452)
+      10, 2,	// 2196: invoke(2)
+      14,	// 2198: pop
+      0,	// 2199: push_frame
+      5, 94,	// 2200: get_slot_direct(94)
+      1, 151,	// 2202: push_literal(151)
+      23,	// 2204: bi_eq
+      15,	// 2205: dup
+      13, 2213,	// 2206: jmp_unless(2213)
+      14,	// 2208: pop
+      0,	// 2209: push_frame
+      5, 96,	// 2210: get_slot_direct(96)
+      20,	// 2212: un_not
+      13, 2217,	// 2213: jmp_unless(2217)
+      12, 2367,	// 2215: jmp(2367)
+      0,	// 2217: push_frame
+      5, 94,	// 2218: get_slot_direct(94)
+      1, 218,	// 2220: push_literal(218)
+      23,	// 2222: bi_eq
+      13, 2232,	// 2223: jmp_unless(2232)
+      0,	// 2225: push_frame
+      1, 63,	// 2226: push_literal(63)
+      8, 96,	// 2228: set_slot_direct(96)
+      12, 2343,	// 2230: jmp(2343)
+      0,	// 2232: push_frame
+      5, 94,	// 2233: get_slot_direct(94)
+      1, 106,	// 2235: push_literal(106)
+      23,	// 2237: bi_eq
+      13, 2247,	// 2238: jmp_unless(2247)
+      0,	// 2240: push_frame
+      1, 59,	// 2241: push_literal(59)
+      8, 96,	// 2243: set_slot_direct(96)
+      12, 2343,	// 2245: jmp(2343)
+      0,	// 2247: push_frame
+      5, 94,	// 2248: get_slot_direct(94)
+      1, 140,	// 2250: push_literal(140)
+      23,	// 2252: bi_eq
+      13, 2343,	// 2253: jmp_unless(2343)
+      0,	// 2255: push_frame
+      15,	// 2256: dup
+      5, 16,	// 2257: get_slot_direct(16)
+      0,	// 2259: push_frame
+      5, 94,	// 2260: get_slot_direct(94)
+      26,	// 2262: bi_add
+      8, 16,	// 2263: set_slot_direct(16)
+      0,	// 2265: push_frame
+      15,	// 2266: dup
+      5, 10,	// 2267: get_slot_direct(10)
+      1, 8,	// 2269: push_literal(8)
+      26,	// 2271: bi_add
+      8, 10,	// 2272: set_slot_direct(10)
+      0,	// 2274: push_frame
+      0,	// 2275: push_frame
+      5, 77,	// 2276: get_slot_direct(77)
+      15,	// 2278: dup
+      7, 14,	// 2279: get_slot_direct_check(14)
+      19,	// 2281: swap
+      0,	// 2282: push_frame
+      5, 10,	// 2283: get_slot_direct(10)
+      10, 1,	// 2285: invoke(1)
+      8, 94,	// 2287: set_slot_direct(94)
+      0,	// 2289: push_frame
+      5, 94,	// 2290: get_slot_direct(94)
+      1, 20,	// 2292: push_literal(20)
+      23,	// 2294: bi_eq
+      15,	// 2295: dup
+      20,	// 2296: un_not
+      13, 2317,	// 2297: jmp_unless(2317)
+      14,	// 2299: pop
+      0,	// 2300: push_frame
+      5, 94,	// 2301: get_slot_direct(94)
+      1, 136,	// 2303: push_literal(136)
+      23,	// 2305: bi_eq
+      15,	// 2306: dup
+      20,	// 2307: un_not
+      13, 2317,	// 2308: jmp_unless(2317)
+      14,	// 2310: pop
+      0,	// 2311: push_frame
+      5, 94,	// 2312: get_slot_direct(94)
+      1, 137,	// 2314: push_literal(137)
+      23,	// 2316: bi_eq
+      13, 2343,	// 2317: jmp_unless(2343)
+      0,	// 2319: push_frame
+      5, 97,	// 2320: get_slot_direct(97)
+      0,	// 2322: push_frame
+      5, 12,	// 2323: get_slot_direct(12)
+      0,	// 2325: push_frame
+      5, 98,	// 2326: get_slot_direct(98)
+      0,	// 2328: push_frame
+      5, 12,	// 2329: get_slot_direct(12)
+      1, 451,	// 2331: push_literal(451)
+      0,	// 2333: push_frame
+      5, 16,	// 2334: get_slot_direct(16)
+      10, 2,	// 2336: invoke(2)
+      1, 452,	// 2338: push_literal(452)
+      10, 2,	// 2340: invoke(2)
+      14,	// 2342: pop
+      0,	// 2343: push_frame
+      15,	// 2344: dup
+      5, 16,	// 2345: get_slot_direct(16)
+      0,	// 2347: push_frame
+      5, 94,	// 2348: get_slot_direct(94)
+      26,	// 2350: bi_add
+      8, 16,	// 2351: set_slot_direct(16)
+      0,	// 2353: push_frame
+      15,	// 2354: dup
+      5, 10,	// 2355: get_slot_direct(10)
+      1, 8,	// 2357: push_literal(8)
+      26,	// 2359: bi_add
+      8, 10,	// 2360: set_slot_direct(10)
+      1, 63,	// 2362: push_literal(63)
+      20,	// 2364: un_not
+      13, 2130,	// 2365: jmp_unless(2130)
+      0,	// 2367: push_frame
+      15,	// 2368: dup
+      5, 10,	// 2369: get_slot_direct(10)
+      1, 8,	// 2371: push_literal(8)
+      26,	// 2373: bi_add
+      8, 10,	// 2374: set_slot_direct(10)
+      0,	// 2376: push_frame
+      1, 20,	// 2377: push_literal(20)
+      8, 95,	// 2379: set_slot_direct(95)
+      12, 2461,	// 2381: jmp(2461)
+      0,	// 2383: push_frame
+      0,	// 2384: push_frame
+      5, 77,	// 2385: get_slot_direct(77)
+      15,	// 2387: dup
+      7, 14,	// 2388: get_slot_direct_check(14)
+      19,	// 2390: swap
+      0,	// 2391: push_frame
+      5, 10,	// 2392: get_slot_direct(10)
+      10, 1,	// 2394: invoke(1)
+      8, 94,	// 2396: set_slot_direct(94)
+      0,	// 2398: push_frame
+      5, 94,	// 2399: get_slot_direct(94)
+      1, 113,	// 2401: push_literal(113)
+      25,	// 2403: bi_gte
+      15,	// 2404: dup
+      13, 2415,	// 2405: jmp_unless(2415)
+      14,	// 2407: pop
+      0,	// 2408: push_frame
+      5, 94,	// 2409: get_slot_direct(94)
+      1, 114,	// 2411: push_literal(114)
+      19,	// 2413: swap
+      25,	// 2414: bi_gte
+      15,	// 2415: dup
+      20,	// 2416: un_not
+      13, 2437,	// 2417: jmp_unless(2437)
+      14,	// 2419: pop
+      0,	// 2420: push_frame
+      5, 94,	// 2421: get_slot_direct(94)
+      1, 115,	// 2423: push_literal(115)
+      25,	// 2425: bi_gte
+      15,	// 2426: dup
+      13, 2437,	// 2427: jmp_unless(2437)
+      14,	// 2429: pop
+      0,	// 2430: push_frame
+      5, 94,	// 2431: get_slot_direct(94)
+      1, 116,	// 2433: push_literal(116)
+      19,	// 2435: swap
+      25,	// 2436: bi_gte
+      20,	// 2437: un_not
+      13, 2442,	// 2438: jmp_unless(2442)
+      12, 2466,	// 2440: jmp(2466)
+      0,	// 2442: push_frame
+      15,	// 2443: dup
+      5, 95,	// 2444: get_slot_direct(95)
+      0,	// 2446: push_frame
+      5, 94,	// 2447: get_slot_direct(94)
+      26,	// 2449: bi_add
+      8, 95,	// 2450: set_slot_direct(95)
+      0,	// 2452: push_frame
+      15,	// 2453: dup
+      5, 10,	// 2454: get_slot_direct(10)
+      1, 8,	// 2456: push_literal(8)
+      26,	// 2458: bi_add
+      8, 10,	// 2459: set_slot_direct(10)
+      1, 63,	// 2461: push_literal(63)
+      20,	// 2463: un_not
+      13, 2383,	// 2464: jmp_unless(2383)
+      0,	// 2466: push_frame
+      5, 29,	// 2467: get_slot_direct(29)
+      15,	// 2469: dup
+      7, 23,	// 2470: get_slot_direct_check(23)
+      19,	// 2472: swap
+      0,	// 2473: push_frame
+      5, 98,	// 2474: get_slot_direct(98)
+      0,	// 2476: push_frame
+      5, 12,	// 2477: get_slot_direct(12)
+      1, 451,	// 2479: push_literal(451)
+      3,	// 2481: new_array
+      15,	// 2482: dup
+      0,	// 2483: push_frame
+      5, 16,	// 2484: get_slot_direct(16)
+      8, 6,	// 2486: set_slot_direct(6)
+      15,	// 2488: dup
+      0,	// 2489: push_frame
+      5, 95,	// 2490: get_slot_direct(95)
+      8, 8,	// 2492: set_slot_direct(8)
+      10, 2,	// 2494: invoke(2)
+      10, 1,	// 2496: invoke(1)
+      14,	// 2498: pop
+      12, 2672,	// 2499: jmp(2672)
+      0,	// 2501: push_frame
+      5, 90,	// 2502: get_slot_direct(90)
+      15,	// 2504: dup
+      7, 15,	// 2505: get_slot_direct_check(15)
+      19,	// 2507: swap
+      0,	// 2508: push_frame
+      5, 94,	// 2509: get_slot_direct(94)
+      10, 1,	// 2511: invoke(1)
+      1, 6,	// 2513: push_literal(6)
+      25,	// 2515: bi_gte
+      13, 2625,	// 2516: jmp_unless(2625)
+      0,	// 2518: push_frame
+      0,	// 2519: push_frame
+      5, 94,	// 2520: get_slot_direct(94)
+      8, 16,	// 2522: set_slot_direct(16)
+      0,	// 2524: push_frame
+      15,	// 2525: dup
+      5, 10,	// 2526: get_slot_direct(10)
+      1, 8,	// 2528: push_literal(8)
+      26,	// 2530: bi_add
+      8, 10,	// 2531: set_slot_direct(10)
+      12, 2589,	// 2533: jmp(2589)
+      0,	// 2535: push_frame
+      0,	// 2536: push_frame
+      5, 77,	// 2537: get_slot_direct(77)
+      15,	// 2539: dup
+      7, 14,	// 2540: get_slot_direct_check(14)
+      19,	// 2542: swap
+      0,	// 2543: push_frame
+      5, 10,	// 2544: get_slot_direct(10)
+      10, 1,	// 2546: invoke(1)
+      8, 94,	// 2548: set_slot_direct(94)
+      0,	// 2550: push_frame
+      5, 91,	// 2551: get_slot_direct(91)
+      15,	// 2553: dup
+      7, 15,	// 2554: get_slot_direct_check(15)
+      19,	// 2556: swap
+      0,	// 2557: push_frame
+      5, 94,	// 2558: get_slot_direct(94)
+      10, 1,	// 2560: invoke(1)
+      1, 6,	// 2562: push_literal(6)
+      19,	// 2564: swap
+      24,	// 2565: bi_gt
+      13, 2570,	// 2566: jmp_unless(2570)
+      12, 2600,	// 2568: jmp(2600)
+      0,	// 2570: push_frame
+      15,	// 2571: dup
+      5, 16,	// 2572: get_slot_direct(16)
+      0,	// 2574: push_frame
+      5, 94,	// 2575: get_slot_direct(94)
+      26,	// 2577: bi_add
+      8, 16,	// 2578: set_slot_direct(16)
+      0,	// 2580: push_frame
+      15,	// 2581: dup
+      5, 10,	// 2582: get_slot_direct(10)
+      1, 8,	// 2584: push_literal(8)
+      26,	// 2586: bi_add
+      8, 10,	// 2587: set_slot_direct(10)
+      0,	// 2589: push_frame
+      5, 10,	// 2590: get_slot_direct(10)
+      0,	// 2592: push_frame
+      5, 13,	// 2593: get_slot_direct(13)
+      19,	// 2595: swap
+      24,	// 2596: bi_gt
+      20,	// 2597: un_not
+      13, 2535,	// 2598: jmp_unless(2535)
+      0,	// 2600: push_frame
+      5, 29,	// 2601: get_slot_direct(29)
+      15,	// 2603: dup
+      7, 23,	// 2604: get_slot_direct_check(23)
+      19,	// 2606: swap
+      0,	// 2607: push_frame
+      5, 98,	// 2608: get_slot_direct(98)
+      0,	// 2610: push_frame
+      5, 12,	// 2611: get_slot_direct(12)
+      1, 153,	// 2613: push_literal(153)
+      0,	// 2615: push_frame
+      5, 16,	// 2616: get_slot_direct(16)
+      10, 2,	// 2618: invoke(2)
+      10, 1,	// 2620: invoke(1)
+      14,	// 2622: pop
+      12, 2672,	// 2623: jmp(2672)
+      0,	// 2625: push_frame
+      15,	// 2626: dup
+      5, 10,	// 2627: get_slot_direct(10)
+      1, 8,	// 2629: push_literal(8)
+      26,	// 2631: bi_add
+      8, 10,	// 2632: set_slot_direct(10)
+      0,	// 2634: push_frame
+      5, 29,	// 2635: get_slot_direct(29)
+      15,	// 2637: dup
+      7, 23,	// 2638: get_slot_direct_check(23)
+      19,	// 2640: swap
+      0,	// 2641: push_frame
+      5, 98,	// 2642: get_slot_direct(98)
+      0,	// 2644: push_frame
+      5, 12,	// 2645: get_slot_direct(12)
+      1, 153,	// 2647: push_literal(153)
+      0,	// 2649: push_frame
+      5, 94,	// 2650: get_slot_direct(94)
+      10, 2,	// 2652: invoke(2)
+      10, 1,	// 2654: invoke(1)
+      14,	// 2656: pop
+      0,	// 2657: push_frame
+      0,	// 2658: push_frame
+      5, 77,	// 2659: get_slot_direct(77)
+      15,	// 2661: dup
+      7, 14,	// 2662: get_slot_direct_check(14)
+      19,	// 2664: swap
+      0,	// 2665: push_frame
+      5, 10,	// 2666: get_slot_direct(10)
+      10, 1,	// 2668: invoke(1)
+      8, 94,	// 2670: set_slot_direct(94)
+      0,	// 2672: push_frame
+      5, 94,	// 2673: get_slot_direct(94)
+      20,	// 2675: un_not
+      13, 165,	// 2676: jmp_unless(165)
+      0,	// 2678: push_frame
+      5, 29,	// 2679: get_slot_direct(29)
+      11	// 2681: return
     ]
   });
   vec::push(functions, @Function { // "error"
-    name: Some(~"error"),
+    name: Some(utf16("error")),
     id: 28,
     nargs: 3,
     max_stack: 4,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -3353,10 +3752,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // "make"
-    name: Some(~"make"),
+    name: Some(utf16("make")),
     id: 29,
     nargs: 2,
     max_stack: 3,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -3394,10 +3794,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // "make_parse"
-    name: Some(~"make_parse"),
+    name: Some(utf16("make_parse")),
     id: 30,
     nargs: 1,
     max_stack: 5,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -3700,316 +4101,476 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
       5, 172,	// 480: get_slot_direct(172)
       0,	// 482: push_frame
       5, 12,	// 483: get_slot_direct(12)
-      1, 12,	// 485: push_literal(12)
+      1, 453,	// 485: push_literal(453)
       10, 1,	// 487: invoke(1)
       4, 58,	// 489: new_function(58)
       8, 183,	// 491: set_slot_direct(183)
       0,	// 493: push_frame
-      5, 176,	// 494: get_slot_direct(176)
+      5, 172,	// 494: get_slot_direct(172)
       0,	// 496: push_frame
       5, 12,	// 497: get_slot_direct(12)
-      1, 232,	// 499: push_literal(232)
+      1, 12,	// 499: push_literal(12)
       10, 1,	// 501: invoke(1)
-      14,	// 503: pop
-      0,	// 504: push_frame
-      5, 176,	// 505: get_slot_direct(176)
+      4, 59,	// 503: new_function(59)
+      8, 183,	// 505: set_slot_direct(183)
       0,	// 507: push_frame
-      5, 12,	// 508: get_slot_direct(12)
-      1, 233,	// 510: push_literal(233)
-      10, 1,	// 512: invoke(1)
-      14,	// 514: pop
-      0,	// 515: push_frame
-      5, 176,	// 516: get_slot_direct(176)
+      5, 176,	// 508: get_slot_direct(176)
+      0,	// 510: push_frame
+      5, 12,	// 511: get_slot_direct(12)
+      1, 232,	// 513: push_literal(232)
+      10, 1,	// 515: invoke(1)
+      14,	// 517: pop
       0,	// 518: push_frame
-      5, 12,	// 519: get_slot_direct(12)
-      1, 234,	// 521: push_literal(234)
-      10, 1,	// 523: invoke(1)
-      14,	// 525: pop
-      0,	// 526: push_frame
-      5, 176,	// 527: get_slot_direct(176)
+      5, 176,	// 519: get_slot_direct(176)
+      0,	// 521: push_frame
+      5, 12,	// 522: get_slot_direct(12)
+      1, 233,	// 524: push_literal(233)
+      10, 1,	// 526: invoke(1)
+      14,	// 528: pop
       0,	// 529: push_frame
-      5, 12,	// 530: get_slot_direct(12)
-      1, 235,	// 532: push_literal(235)
-      10, 1,	// 534: invoke(1)
-      14,	// 536: pop
-      0,	// 537: push_frame
-      5, 176,	// 538: get_slot_direct(176)
+      5, 176,	// 530: get_slot_direct(176)
+      0,	// 532: push_frame
+      5, 12,	// 533: get_slot_direct(12)
+      1, 234,	// 535: push_literal(234)
+      10, 1,	// 537: invoke(1)
+      14,	// 539: pop
       0,	// 540: push_frame
-      5, 12,	// 541: get_slot_direct(12)
-      1, 236,	// 543: push_literal(236)
-      10, 1,	// 545: invoke(1)
-      14,	// 547: pop
-      0,	// 548: push_frame
-      5, 174,	// 549: get_slot_direct(174)
+      5, 176,	// 541: get_slot_direct(176)
+      0,	// 543: push_frame
+      5, 12,	// 544: get_slot_direct(12)
+      1, 235,	// 546: push_literal(235)
+      10, 1,	// 548: invoke(1)
+      14,	// 550: pop
       0,	// 551: push_frame
-      5, 12,	// 552: get_slot_direct(12)
-      1, 237,	// 554: push_literal(237)
-      1, 238,	// 556: push_literal(238)
-      4, 59,	// 558: new_function(59)
-      10, 3,	// 560: invoke(3)
-      14,	// 562: pop
-      0,	// 563: push_frame
-      5, 175,	// 564: get_slot_direct(175)
-      0,	// 566: push_frame
-      5, 12,	// 567: get_slot_direct(12)
-      1, 241,	// 569: push_literal(241)
-      1, 242,	// 571: push_literal(242)
-      10, 2,	// 573: invoke(2)
-      14,	// 575: pop
+      5, 176,	// 552: get_slot_direct(176)
+      0,	// 554: push_frame
+      5, 12,	// 555: get_slot_direct(12)
+      1, 236,	// 557: push_literal(236)
+      10, 1,	// 559: invoke(1)
+      14,	// 561: pop
+      0,	// 562: push_frame
+      5, 176,	// 563: get_slot_direct(176)
+      0,	// 565: push_frame
+      5, 12,	// 566: get_slot_direct(12)
+      1, 421,	// 568: push_literal(421)
+      10, 1,	// 570: invoke(1)
+      14,	// 572: pop
+      0,	// 573: push_frame
+      5, 176,	// 574: get_slot_direct(176)
       0,	// 576: push_frame
-      5, 175,	// 577: get_slot_direct(175)
-      0,	// 579: push_frame
-      5, 12,	// 580: get_slot_direct(12)
-      1, 243,	// 582: push_literal(243)
-      1, 244,	// 584: push_literal(244)
-      10, 2,	// 586: invoke(2)
-      14,	// 588: pop
-      0,	// 589: push_frame
-      5, 175,	// 590: get_slot_direct(175)
-      0,	// 592: push_frame
-      5, 12,	// 593: get_slot_direct(12)
-      1, 245,	// 595: push_literal(245)
-      1, 246,	// 597: push_literal(246)
-      10, 2,	// 599: invoke(2)
-      14,	// 601: pop
-      0,	// 602: push_frame
-      5, 175,	// 603: get_slot_direct(175)
-      0,	// 605: push_frame
-      5, 12,	// 606: get_slot_direct(12)
-      1, 247,	// 608: push_literal(247)
-      1, 246,	// 610: push_literal(246)
-      10, 2,	// 612: invoke(2)
-      14,	// 614: pop
-      0,	// 615: push_frame
-      5, 175,	// 616: get_slot_direct(175)
-      0,	// 618: push_frame
-      5, 12,	// 619: get_slot_direct(12)
-      1, 248,	// 621: push_literal(248)
-      1, 249,	// 623: push_literal(249)
-      10, 2,	// 625: invoke(2)
+      5, 12,	// 577: get_slot_direct(12)
+      1, 422,	// 579: push_literal(422)
+      10, 1,	// 581: invoke(1)
+      14,	// 583: pop
+      0,	// 584: push_frame
+      5, 176,	// 585: get_slot_direct(176)
+      0,	// 587: push_frame
+      5, 12,	// 588: get_slot_direct(12)
+      1, 423,	// 590: push_literal(423)
+      10, 1,	// 592: invoke(1)
+      14,	// 594: pop
+      0,	// 595: push_frame
+      5, 176,	// 596: get_slot_direct(176)
+      0,	// 598: push_frame
+      5, 12,	// 599: get_slot_direct(12)
+      1, 424,	// 601: push_literal(424)
+      10, 1,	// 603: invoke(1)
+      14,	// 605: pop
+      0,	// 606: push_frame
+      5, 176,	// 607: get_slot_direct(176)
+      0,	// 609: push_frame
+      5, 12,	// 610: get_slot_direct(12)
+      1, 425,	// 612: push_literal(425)
+      10, 1,	// 614: invoke(1)
+      14,	// 616: pop
+      0,	// 617: push_frame
+      5, 176,	// 618: get_slot_direct(176)
+      0,	// 620: push_frame
+      5, 12,	// 621: get_slot_direct(12)
+      1, 426,	// 623: push_literal(426)
+      10, 1,	// 625: invoke(1)
       14,	// 627: pop
       0,	// 628: push_frame
-      5, 175,	// 629: get_slot_direct(175)
+      5, 176,	// 629: get_slot_direct(176)
       0,	// 631: push_frame
       5, 12,	// 632: get_slot_direct(12)
-      1, 250,	// 634: push_literal(250)
-      1, 249,	// 636: push_literal(249)
-      10, 2,	// 638: invoke(2)
-      14,	// 640: pop
-      0,	// 641: push_frame
-      5, 175,	// 642: get_slot_direct(175)
-      0,	// 644: push_frame
-      5, 12,	// 645: get_slot_direct(12)
-      1, 251,	// 647: push_literal(251)
-      1, 249,	// 649: push_literal(249)
-      10, 2,	// 651: invoke(2)
+      1, 427,	// 634: push_literal(427)
+      10, 1,	// 636: invoke(1)
+      14,	// 638: pop
+      0,	// 639: push_frame
+      5, 174,	// 640: get_slot_direct(174)
+      0,	// 642: push_frame
+      5, 12,	// 643: get_slot_direct(12)
+      1, 237,	// 645: push_literal(237)
+      1, 238,	// 647: push_literal(238)
+      4, 60,	// 649: new_function(60)
+      10, 3,	// 651: invoke(3)
       14,	// 653: pop
       0,	// 654: push_frame
       5, 175,	// 655: get_slot_direct(175)
       0,	// 657: push_frame
       5, 12,	// 658: get_slot_direct(12)
-      1, 252,	// 660: push_literal(252)
-      1, 249,	// 662: push_literal(249)
+      1, 241,	// 660: push_literal(241)
+      1, 242,	// 662: push_literal(242)
       10, 2,	// 664: invoke(2)
       14,	// 666: pop
       0,	// 667: push_frame
-      5, 174,	// 668: get_slot_direct(174)
+      5, 175,	// 668: get_slot_direct(175)
       0,	// 670: push_frame
       5, 12,	// 671: get_slot_direct(12)
-      1, 126,	// 673: push_literal(126)
-      1, 253,	// 675: push_literal(253)
+      1, 243,	// 673: push_literal(243)
+      1, 244,	// 675: push_literal(244)
       10, 2,	// 677: invoke(2)
       14,	// 679: pop
       0,	// 680: push_frame
       5, 174,	// 681: get_slot_direct(174)
       0,	// 683: push_frame
       5, 12,	// 684: get_slot_direct(12)
-      1, 105,	// 686: push_literal(105)
-      1, 253,	// 688: push_literal(253)
+      1, 428,	// 686: push_literal(428)
+      1, 429,	// 688: push_literal(429)
       10, 2,	// 690: invoke(2)
       14,	// 692: pop
       0,	// 693: push_frame
       5, 174,	// 694: get_slot_direct(174)
       0,	// 696: push_frame
       5, 12,	// 697: get_slot_direct(12)
-      1, 152,	// 699: push_literal(152)
-      1, 254,	// 701: push_literal(254)
+      1, 430,	// 699: push_literal(430)
+      1, 431,	// 701: push_literal(431)
       10, 2,	// 703: invoke(2)
       14,	// 705: pop
       0,	// 706: push_frame
       5, 174,	// 707: get_slot_direct(174)
       0,	// 709: push_frame
       5, 12,	// 710: get_slot_direct(12)
-      1, 151,	// 712: push_literal(151)
-      1, 254,	// 714: push_literal(254)
+      1, 432,	// 712: push_literal(432)
+      1, 433,	// 714: push_literal(433)
       10, 2,	// 716: invoke(2)
       14,	// 718: pop
       0,	// 719: push_frame
-      5, 174,	// 720: get_slot_direct(174)
+      5, 175,	// 720: get_slot_direct(175)
       0,	// 722: push_frame
       5, 12,	// 723: get_slot_direct(12)
-      1, 121,	// 725: push_literal(121)
-      1, 255,	// 727: push_literal(255)
-      4, 60,	// 729: new_function(60)
-      10, 3,	// 731: invoke(3)
-      14,	// 733: pop
-      0,	// 734: push_frame
-      5, 174,	// 735: get_slot_direct(174)
-      0,	// 737: push_frame
-      5, 12,	// 738: get_slot_direct(12)
-      1, 218,	// 740: push_literal(218)
-      1, 255,	// 742: push_literal(255)
-      4, 61,	// 744: new_function(61)
-      10, 3,	// 746: invoke(3)
-      14,	// 748: pop
-      0,	// 749: push_frame
-      5, 174,	// 750: get_slot_direct(174)
-      0,	// 752: push_frame
-      5, 12,	// 753: get_slot_direct(12)
-      1, 204,	// 755: push_literal(204)
-      1, 257,	// 757: push_literal(257)
-      4, 62,	// 759: new_function(62)
-      10, 3,	// 761: invoke(3)
-      14,	// 763: pop
-      0,	// 764: push_frame
-      5, 90,	// 765: get_slot_direct(90)
-      0,	// 767: push_frame
-      5, 12,	// 768: get_slot_direct(12)
-      1, 259,	// 770: push_literal(259)
-      10, 1,	// 772: invoke(1)
-      14,	// 774: pop
-      0,	// 775: push_frame
-      5, 90,	// 776: get_slot_direct(90)
-      0,	// 778: push_frame
-      5, 12,	// 779: get_slot_direct(12)
-      1, 105,	// 781: push_literal(105)
-      10, 1,	// 783: invoke(1)
-      14,	// 785: pop
-      0,	// 786: push_frame
-      5, 90,	// 787: get_slot_direct(90)
-      0,	// 789: push_frame
-      5, 12,	// 790: get_slot_direct(12)
-      1, 260,	// 792: push_literal(260)
-      10, 1,	// 794: invoke(1)
+      1, 245,	// 725: push_literal(245)
+      1, 246,	// 727: push_literal(246)
+      10, 2,	// 729: invoke(2)
+      14,	// 731: pop
+      0,	// 732: push_frame
+      5, 175,	// 733: get_slot_direct(175)
+      0,	// 735: push_frame
+      5, 12,	// 736: get_slot_direct(12)
+      1, 247,	// 738: push_literal(247)
+      1, 246,	// 740: push_literal(246)
+      10, 2,	// 742: invoke(2)
+      14,	// 744: pop
+      0,	// 745: push_frame
+      5, 175,	// 746: get_slot_direct(175)
+      0,	// 748: push_frame
+      5, 12,	// 749: get_slot_direct(12)
+      1, 449,	// 751: push_literal(449)
+      1, 246,	// 753: push_literal(246)
+      10, 2,	// 755: invoke(2)
+      14,	// 757: pop
+      0,	// 758: push_frame
+      5, 175,	// 759: get_slot_direct(175)
+      0,	// 761: push_frame
+      5, 12,	// 762: get_slot_direct(12)
+      1, 450,	// 764: push_literal(450)
+      1, 246,	// 766: push_literal(246)
+      10, 2,	// 768: invoke(2)
+      14,	// 770: pop
+      0,	// 771: push_frame
+      5, 175,	// 772: get_slot_direct(175)
+      0,	// 774: push_frame
+      5, 12,	// 775: get_slot_direct(12)
+      1, 248,	// 777: push_literal(248)
+      1, 249,	// 779: push_literal(249)
+      10, 2,	// 781: invoke(2)
+      14,	// 783: pop
+      0,	// 784: push_frame
+      5, 175,	// 785: get_slot_direct(175)
+      0,	// 787: push_frame
+      5, 12,	// 788: get_slot_direct(12)
+      1, 250,	// 790: push_literal(250)
+      1, 249,	// 792: push_literal(249)
+      10, 2,	// 794: invoke(2)
       14,	// 796: pop
       0,	// 797: push_frame
-      5, 90,	// 798: get_slot_direct(90)
+      5, 175,	// 798: get_slot_direct(175)
       0,	// 800: push_frame
       5, 12,	// 801: get_slot_direct(12)
-      1, 204,	// 803: push_literal(204)
-      4, 63,	// 805: new_function(63)
+      1, 251,	// 803: push_literal(251)
+      1, 249,	// 805: push_literal(249)
       10, 2,	// 807: invoke(2)
       14,	// 809: pop
       0,	// 810: push_frame
-      5, 90,	// 811: get_slot_direct(90)
+      5, 175,	// 811: get_slot_direct(175)
       0,	// 813: push_frame
       5, 12,	// 814: get_slot_direct(12)
-      1, 205,	// 816: push_literal(205)
-      4, 64,	// 818: new_function(64)
+      1, 252,	// 816: push_literal(252)
+      1, 249,	// 818: push_literal(249)
       10, 2,	// 820: invoke(2)
       14,	// 822: pop
       0,	// 823: push_frame
-      5, 90,	// 824: get_slot_direct(90)
+      5, 175,	// 824: get_slot_direct(175)
       0,	// 826: push_frame
       5, 12,	// 827: get_slot_direct(12)
-      1, 218,	// 829: push_literal(218)
-      4, 65,	// 831: new_function(65)
+      1, 445,	// 829: push_literal(445)
+      1, 249,	// 831: push_literal(249)
       10, 2,	// 833: invoke(2)
       14,	// 835: pop
       0,	// 836: push_frame
-      5, 90,	// 837: get_slot_direct(90)
+      5, 175,	// 837: get_slot_direct(175)
       0,	// 839: push_frame
       5, 12,	// 840: get_slot_direct(12)
-      1, 211,	// 842: push_literal(211)
-      4, 66,	// 844: new_function(66)
+      1, 446,	// 842: push_literal(446)
+      1, 249,	// 844: push_literal(249)
       10, 2,	// 846: invoke(2)
       14,	// 848: pop
       0,	// 849: push_frame
-      5, 177,	// 850: get_slot_direct(177)
+      5, 174,	// 850: get_slot_direct(174)
       0,	// 852: push_frame
       5, 12,	// 853: get_slot_direct(12)
-      1, 211,	// 855: push_literal(211)
-      4, 67,	// 857: new_function(67)
+      1, 434,	// 855: push_literal(434)
+      1, 435,	// 857: push_literal(435)
       10, 2,	// 859: invoke(2)
       14,	// 861: pop
       0,	// 862: push_frame
-      5, 177,	// 863: get_slot_direct(177)
+      5, 174,	// 863: get_slot_direct(174)
       0,	// 865: push_frame
       5, 12,	// 866: get_slot_direct(12)
-      1, 209,	// 868: push_literal(209)
-      4, 68,	// 870: new_function(68)
+      1, 436,	// 868: push_literal(436)
+      1, 435,	// 870: push_literal(435)
       10, 2,	// 872: invoke(2)
       14,	// 874: pop
       0,	// 875: push_frame
-      5, 177,	// 876: get_slot_direct(177)
+      5, 174,	// 876: get_slot_direct(174)
       0,	// 878: push_frame
       5, 12,	// 879: get_slot_direct(12)
-      1, 265,	// 881: push_literal(265)
-      4, 69,	// 883: new_function(69)
+      1, 437,	// 881: push_literal(437)
+      1, 435,	// 883: push_literal(435)
       10, 2,	// 885: invoke(2)
       14,	// 887: pop
       0,	// 888: push_frame
-      5, 177,	// 889: get_slot_direct(177)
+      5, 174,	// 889: get_slot_direct(174)
       0,	// 891: push_frame
       5, 12,	// 892: get_slot_direct(12)
-      1, 266,	// 894: push_literal(266)
-      4, 70,	// 896: new_function(70)
+      1, 126,	// 894: push_literal(126)
+      1, 253,	// 896: push_literal(253)
       10, 2,	// 898: invoke(2)
       14,	// 900: pop
       0,	// 901: push_frame
-      5, 177,	// 902: get_slot_direct(177)
+      5, 174,	// 902: get_slot_direct(174)
       0,	// 904: push_frame
       5, 12,	// 905: get_slot_direct(12)
-      1, 268,	// 907: push_literal(268)
-      4, 71,	// 909: new_function(71)
+      1, 105,	// 907: push_literal(105)
+      1, 253,	// 909: push_literal(253)
       10, 2,	// 911: invoke(2)
       14,	// 913: pop
       0,	// 914: push_frame
-      5, 177,	// 915: get_slot_direct(177)
+      5, 174,	// 915: get_slot_direct(174)
       0,	// 917: push_frame
       5, 12,	// 918: get_slot_direct(12)
-      1, 76,	// 920: push_literal(76)
-      4, 72,	// 922: new_function(72)
+      1, 152,	// 920: push_literal(152)
+      1, 254,	// 922: push_literal(254)
       10, 2,	// 924: invoke(2)
       14,	// 926: pop
       0,	// 927: push_frame
-      4, 73,	// 928: new_function(73)
-      8, 157,	// 930: set_slot_direct(157)
-      0,	// 932: push_frame
-      4, 74,	// 933: new_function(74)
-      8, 178,	// 935: set_slot_direct(178)
-      0,	// 937: push_frame
-      5, 157,	// 938: get_slot_direct(157)
-      1, 157,	// 940: push_literal(157)
-      8, 154,	// 942: set_slot_direct(154)
-      0,	// 944: push_frame
-      5, 157,	// 945: get_slot_direct(157)
-      0,	// 947: push_frame
-      5, 3,	// 948: get_slot_direct(3)
-      5, 283,	// 950: get_slot_direct(283)
-      8, 156,	// 952: set_slot_direct(156)
-      0,	// 954: push_frame
-      5, 157,	// 955: get_slot_direct(157)
-      3,	// 957: new_array
-      15,	// 958: dup
-      1, 89,	// 959: push_literal(89)
-      8, 6,	// 961: set_slot_direct(6)
-      8, 284,	// 963: set_slot_direct(284)
-      0,	// 965: push_frame
-      5, 157,	// 966: get_slot_direct(157)
+      5, 174,	// 928: get_slot_direct(174)
+      0,	// 930: push_frame
+      5, 12,	// 931: get_slot_direct(12)
+      1, 151,	// 933: push_literal(151)
+      1, 254,	// 935: push_literal(254)
+      10, 2,	// 937: invoke(2)
+      14,	// 939: pop
+      0,	// 940: push_frame
+      5, 174,	// 941: get_slot_direct(174)
+      0,	// 943: push_frame
+      5, 12,	// 944: get_slot_direct(12)
+      1, 438,	// 946: push_literal(438)
+      1, 254,	// 948: push_literal(254)
+      10, 2,	// 950: invoke(2)
+      14,	// 952: pop
+      0,	// 953: push_frame
+      5, 174,	// 954: get_slot_direct(174)
+      0,	// 956: push_frame
+      5, 12,	// 957: get_slot_direct(12)
+      1, 121,	// 959: push_literal(121)
+      1, 255,	// 961: push_literal(255)
+      4, 61,	// 963: new_function(61)
+      10, 3,	// 965: invoke(3)
+      14,	// 967: pop
       0,	// 968: push_frame
-      5, 178,	// 969: get_slot_direct(178)
-      8, 285,	// 971: set_slot_direct(285)
-      0,	// 973: push_frame
-      5, 157,	// 974: get_slot_direct(157)
-      11	// 976: return
+      5, 174,	// 969: get_slot_direct(174)
+      0,	// 971: push_frame
+      5, 12,	// 972: get_slot_direct(12)
+      1, 218,	// 974: push_literal(218)
+      1, 255,	// 976: push_literal(255)
+      4, 62,	// 978: new_function(62)
+      10, 3,	// 980: invoke(3)
+      14,	// 982: pop
+      0,	// 983: push_frame
+      5, 174,	// 984: get_slot_direct(174)
+      0,	// 986: push_frame
+      5, 12,	// 987: get_slot_direct(12)
+      1, 204,	// 989: push_literal(204)
+      1, 257,	// 991: push_literal(257)
+      4, 63,	// 993: new_function(63)
+      10, 3,	// 995: invoke(3)
+      14,	// 997: pop
+      0,	// 998: push_frame
+      5, 90,	// 999: get_slot_direct(90)
+      0,	// 1001: push_frame
+      5, 12,	// 1002: get_slot_direct(12)
+      1, 259,	// 1004: push_literal(259)
+      10, 1,	// 1006: invoke(1)
+      14,	// 1008: pop
+      0,	// 1009: push_frame
+      5, 90,	// 1010: get_slot_direct(90)
+      0,	// 1012: push_frame
+      5, 12,	// 1013: get_slot_direct(12)
+      1, 105,	// 1015: push_literal(105)
+      10, 1,	// 1017: invoke(1)
+      14,	// 1019: pop
+      0,	// 1020: push_frame
+      5, 90,	// 1021: get_slot_direct(90)
+      0,	// 1023: push_frame
+      5, 12,	// 1024: get_slot_direct(12)
+      1, 260,	// 1026: push_literal(260)
+      10, 1,	// 1028: invoke(1)
+      14,	// 1030: pop
+      0,	// 1031: push_frame
+      5, 90,	// 1032: get_slot_direct(90)
+      0,	// 1034: push_frame
+      5, 12,	// 1035: get_slot_direct(12)
+      1, 439,	// 1037: push_literal(439)
+      10, 1,	// 1039: invoke(1)
+      14,	// 1041: pop
+      0,	// 1042: push_frame
+      5, 90,	// 1043: get_slot_direct(90)
+      0,	// 1045: push_frame
+      5, 12,	// 1046: get_slot_direct(12)
+      1, 447,	// 1048: push_literal(447)
+      10, 1,	// 1050: invoke(1)
+      14,	// 1052: pop
+      0,	// 1053: push_frame
+      5, 90,	// 1054: get_slot_direct(90)
+      0,	// 1056: push_frame
+      5, 12,	// 1057: get_slot_direct(12)
+      1, 204,	// 1059: push_literal(204)
+      4, 64,	// 1061: new_function(64)
+      10, 2,	// 1063: invoke(2)
+      14,	// 1065: pop
+      0,	// 1066: push_frame
+      5, 90,	// 1067: get_slot_direct(90)
+      0,	// 1069: push_frame
+      5, 12,	// 1070: get_slot_direct(12)
+      1, 205,	// 1072: push_literal(205)
+      4, 65,	// 1074: new_function(65)
+      10, 2,	// 1076: invoke(2)
+      14,	// 1078: pop
+      0,	// 1079: push_frame
+      5, 90,	// 1080: get_slot_direct(90)
+      0,	// 1082: push_frame
+      5, 12,	// 1083: get_slot_direct(12)
+      1, 218,	// 1085: push_literal(218)
+      4, 66,	// 1087: new_function(66)
+      10, 2,	// 1089: invoke(2)
+      14,	// 1091: pop
+      0,	// 1092: push_frame
+      5, 90,	// 1093: get_slot_direct(90)
+      0,	// 1095: push_frame
+      5, 12,	// 1096: get_slot_direct(12)
+      1, 211,	// 1098: push_literal(211)
+      4, 67,	// 1100: new_function(67)
+      10, 2,	// 1102: invoke(2)
+      14,	// 1104: pop
+      0,	// 1105: push_frame
+      5, 177,	// 1106: get_slot_direct(177)
+      0,	// 1108: push_frame
+      5, 12,	// 1109: get_slot_direct(12)
+      1, 211,	// 1111: push_literal(211)
+      4, 68,	// 1113: new_function(68)
+      10, 2,	// 1115: invoke(2)
+      14,	// 1117: pop
+      0,	// 1118: push_frame
+      5, 177,	// 1119: get_slot_direct(177)
+      0,	// 1121: push_frame
+      5, 12,	// 1122: get_slot_direct(12)
+      1, 209,	// 1124: push_literal(209)
+      4, 69,	// 1126: new_function(69)
+      10, 2,	// 1128: invoke(2)
+      14,	// 1130: pop
+      0,	// 1131: push_frame
+      5, 177,	// 1132: get_slot_direct(177)
+      0,	// 1134: push_frame
+      5, 12,	// 1135: get_slot_direct(12)
+      1, 265,	// 1137: push_literal(265)
+      4, 70,	// 1139: new_function(70)
+      10, 2,	// 1141: invoke(2)
+      14,	// 1143: pop
+      0,	// 1144: push_frame
+      5, 177,	// 1145: get_slot_direct(177)
+      0,	// 1147: push_frame
+      5, 12,	// 1148: get_slot_direct(12)
+      1, 266,	// 1150: push_literal(266)
+      4, 71,	// 1152: new_function(71)
+      10, 2,	// 1154: invoke(2)
+      14,	// 1156: pop
+      0,	// 1157: push_frame
+      5, 177,	// 1158: get_slot_direct(177)
+      0,	// 1160: push_frame
+      5, 12,	// 1161: get_slot_direct(12)
+      1, 268,	// 1163: push_literal(268)
+      4, 72,	// 1165: new_function(72)
+      10, 2,	// 1167: invoke(2)
+      14,	// 1169: pop
+      0,	// 1170: push_frame
+      5, 177,	// 1171: get_slot_direct(177)
+      0,	// 1173: push_frame
+      5, 12,	// 1174: get_slot_direct(12)
+      1, 76,	// 1176: push_literal(76)
+      4, 73,	// 1178: new_function(73)
+      10, 2,	// 1180: invoke(2)
+      14,	// 1182: pop
+      0,	// 1183: push_frame
+      4, 74,	// 1184: new_function(74)
+      8, 157,	// 1186: set_slot_direct(157)
+      0,	// 1188: push_frame
+      4, 75,	// 1189: new_function(75)
+      8, 178,	// 1191: set_slot_direct(178)
+      0,	// 1193: push_frame
+      5, 157,	// 1194: get_slot_direct(157)
+      1, 157,	// 1196: push_literal(157)
+      8, 154,	// 1198: set_slot_direct(154)
+      0,	// 1200: push_frame
+      5, 157,	// 1201: get_slot_direct(157)
+      0,	// 1203: push_frame
+      5, 3,	// 1204: get_slot_direct(3)
+      5, 283,	// 1206: get_slot_direct(283)
+      8, 156,	// 1208: set_slot_direct(156)
+      0,	// 1210: push_frame
+      5, 157,	// 1211: get_slot_direct(157)
+      3,	// 1213: new_array
+      15,	// 1214: dup
+      1, 89,	// 1215: push_literal(89)
+      8, 6,	// 1217: set_slot_direct(6)
+      8, 284,	// 1219: set_slot_direct(284)
+      0,	// 1221: push_frame
+      5, 157,	// 1222: get_slot_direct(157)
+      0,	// 1224: push_frame
+      5, 178,	// 1225: get_slot_direct(178)
+      8, 285,	// 1227: set_slot_direct(285)
+      0,	// 1229: push_frame
+      5, 157,	// 1230: get_slot_direct(157)
+      11	// 1232: return
     ]
   });
   vec::push(functions, @Function { // "itself"
-    name: Some(~"itself"),
+    name: Some(utf16("itself")),
     id: 31,
     nargs: 0,
     max_stack: 1,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -4020,10 +4581,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // "error"
-    name: Some(~"error"),
+    name: Some(utf16("error")),
     id: 32,
     nargs: 3,
     max_stack: 4,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -4105,10 +4667,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // "define:"
-    name: Some(~"define:"),
+    name: Some(utf16("define:")),
     id: 33,
     nargs: 1,
     max_stack: 4,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -4195,10 +4758,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // "find:"
-    name: Some(~"find:"),
+    name: Some(utf16("find:")),
     id: 34,
     nargs: 1,
     max_stack: 4,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -4281,10 +4845,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // "pop:"
-    name: Some(~"pop:"),
+    name: Some(utf16("pop:")),
     id: 35,
     nargs: 0,
     max_stack: 2,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -4300,10 +4865,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // "reserve:"
-    name: Some(~"reserve:"),
+    name: Some(utf16("reserve:")),
     id: 36,
     nargs: 1,
     max_stack: 4,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -4397,10 +4963,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // "new_scope"
-    name: Some(~"new_scope"),
+    name: Some(utf16("new_scope")),
     id: 37,
     nargs: 0,
     max_stack: 4,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -4457,10 +5024,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // "advance"
-    name: Some(~"advance"),
+    name: Some(utf16("advance")),
     id: 38,
     nargs: 1,
     max_stack: 5,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -4573,7 +5141,7 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
       5, 52,	// 168: get_slot_direct(52)
       10, 1,	// 170: invoke(1)
       8, 58,	// 172: set_slot_direct(58)
-      12, 273,	// 174: jmp(273)
+      12, 294,	// 174: jmp(294)
       0,	// 176: push_frame
       5, 113,	// 177: get_slot_direct(113)
       1, 153,	// 179: push_literal(153)
@@ -4601,7 +5169,7 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
       1, 198,	// 213: push_literal(198)
       10, 2,	// 215: invoke(2)
       14,	// 217: pop
-      12, 273,	// 218: jmp(273)
+      12, 294,	// 218: jmp(294)
       0,	// 220: push_frame
       5, 113,	// 221: get_slot_direct(113)
       1, 109,	// 223: push_literal(109)
@@ -4625,65 +5193,79 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
       0,	// 250: push_frame
       1, 200,	// 251: push_literal(200)
       8, 113,	// 253: set_slot_direct(113)
-      12, 273,	// 255: jmp(273)
+      12, 294,	// 255: jmp(294)
       0,	// 257: push_frame
-      5, 3,	// 258: get_slot_direct(3)
-      5, 97,	// 260: get_slot_direct(97)
-      0,	// 262: push_frame
-      5, 12,	// 263: get_slot_direct(12)
+      5, 113,	// 258: get_slot_direct(113)
+      1, 451,	// 260: push_literal(451)
+      23,	// 262: bi_eq
+      13, 278,	// 263: jmp_unless(278)
       0,	// 265: push_frame
-      5, 101,	// 266: get_slot_direct(101)
-      1, 201,	// 268: push_literal(201)
-      10, 2,	// 270: invoke(2)
-      14,	// 272: pop
-      0,	// 273: push_frame
-      5, 3,	// 274: get_slot_direct(3)
-      0,	// 276: push_frame
-      5, 60,	// 277: get_slot_direct(60)
-      15,	// 279: dup
-      7, 65,	// 280: get_slot_direct_check(65)
-      19,	// 282: swap
+      0,	// 266: push_frame
+      5, 3,	// 267: get_slot_direct(3)
+      5, 159,	// 269: get_slot_direct(159)
+      1, 453,	// 271: push_literal(453)
+      6,	// 273: get_slot_indirect
+      8, 58,	// 274: set_slot_direct(58)
+      12, 294,	// 276: jmp(294)
+      0,	// 278: push_frame
+      5, 3,	// 279: get_slot_direct(3)
+      5, 97,	// 281: get_slot_direct(97)
       0,	// 283: push_frame
-      5, 58,	// 284: get_slot_direct(58)
-      10, 1,	// 286: invoke(1)
-      8, 160,	// 288: set_slot_direct(160)
-      0,	// 290: push_frame
-      5, 3,	// 291: get_slot_direct(3)
-      5, 160,	// 293: get_slot_direct(160)
-      0,	// 295: push_frame
-      5, 101,	// 296: get_slot_direct(101)
-      5, 9,	// 298: get_slot_direct(9)
-      8, 9,	// 300: set_slot_direct(9)
-      0,	// 302: push_frame
-      5, 3,	// 303: get_slot_direct(3)
-      5, 160,	// 305: get_slot_direct(160)
-      0,	// 307: push_frame
-      5, 101,	// 308: get_slot_direct(101)
-      5, 103,	// 310: get_slot_direct(103)
-      8, 103,	// 312: set_slot_direct(103)
-      0,	// 314: push_frame
-      5, 3,	// 315: get_slot_direct(3)
-      5, 160,	// 317: get_slot_direct(160)
-      0,	// 319: push_frame
-      5, 52,	// 320: get_slot_direct(52)
-      8, 108,	// 322: set_slot_direct(108)
-      0,	// 324: push_frame
-      5, 3,	// 325: get_slot_direct(3)
-      5, 160,	// 327: get_slot_direct(160)
-      0,	// 329: push_frame
-      5, 113,	// 330: get_slot_direct(113)
-      8, 190,	// 332: set_slot_direct(190)
-      0,	// 334: push_frame
-      5, 3,	// 335: get_slot_direct(3)
-      5, 160,	// 337: get_slot_direct(160)
-      11	// 339: return
+      5, 12,	// 284: get_slot_direct(12)
+      0,	// 286: push_frame
+      5, 101,	// 287: get_slot_direct(101)
+      1, 201,	// 289: push_literal(201)
+      10, 2,	// 291: invoke(2)
+      14,	// 293: pop
+      0,	// 294: push_frame
+      5, 3,	// 295: get_slot_direct(3)
+      0,	// 297: push_frame
+      5, 60,	// 298: get_slot_direct(60)
+      15,	// 300: dup
+      7, 65,	// 301: get_slot_direct_check(65)
+      19,	// 303: swap
+      0,	// 304: push_frame
+      5, 58,	// 305: get_slot_direct(58)
+      10, 1,	// 307: invoke(1)
+      8, 160,	// 309: set_slot_direct(160)
+      0,	// 311: push_frame
+      5, 3,	// 312: get_slot_direct(3)
+      5, 160,	// 314: get_slot_direct(160)
+      0,	// 316: push_frame
+      5, 101,	// 317: get_slot_direct(101)
+      5, 9,	// 319: get_slot_direct(9)
+      8, 9,	// 321: set_slot_direct(9)
+      0,	// 323: push_frame
+      5, 3,	// 324: get_slot_direct(3)
+      5, 160,	// 326: get_slot_direct(160)
+      0,	// 328: push_frame
+      5, 101,	// 329: get_slot_direct(101)
+      5, 103,	// 331: get_slot_direct(103)
+      8, 103,	// 333: set_slot_direct(103)
+      0,	// 335: push_frame
+      5, 3,	// 336: get_slot_direct(3)
+      5, 160,	// 338: get_slot_direct(160)
+      0,	// 340: push_frame
+      5, 52,	// 341: get_slot_direct(52)
+      8, 108,	// 343: set_slot_direct(108)
+      0,	// 345: push_frame
+      5, 3,	// 346: get_slot_direct(3)
+      5, 160,	// 348: get_slot_direct(160)
+      0,	// 350: push_frame
+      5, 113,	// 351: get_slot_direct(113)
+      8, 190,	// 353: set_slot_direct(190)
+      0,	// 355: push_frame
+      5, 3,	// 356: get_slot_direct(3)
+      5, 160,	// 358: get_slot_direct(160)
+      11	// 360: return
     ]
   });
   vec::push(functions, @Function { // "expression"
-    name: Some(~"expression"),
+    name: Some(utf16("expression")),
     id: 39,
     nargs: 1,
     max_stack: 4,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -4758,10 +5340,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // "statement"
-    name: Some(~"statement"),
+    name: Some(utf16("statement")),
     id: 40,
     nargs: 0,
     max_stack: 4,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -4899,10 +5482,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // "hoist_var"
-    name: Some(~"hoist_var"),
+    name: Some(utf16("hoist_var")),
     id: 41,
     nargs: 1,
     max_stack: 4,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -4992,10 +5576,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // "statements"
-    name: Some(~"statements"),
+    name: Some(utf16("statements")),
     id: 42,
     nargs: 0,
     max_stack: 4,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -5066,10 +5651,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // "block"
-    name: Some(~"block"),
+    name: Some(utf16("block")),
     id: 43,
     nargs: 0,
     max_stack: 3,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -5100,10 +5686,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // "nud:"
-    name: Some(~"nud:"),
+    name: Some(utf16("nud:")),
     id: 44,
     nargs: 0,
     max_stack: 4,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -5123,10 +5710,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // "led:"
-    name: Some(~"led:"),
+    name: Some(utf16("led:")),
     id: 45,
     nargs: 1,
     max_stack: 4,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -5151,10 +5739,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // "symbol"
-    name: Some(~"symbol"),
+    name: Some(utf16("symbol")),
     id: 46,
     nargs: 2,
     max_stack: 4,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -5256,10 +5845,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // "constant"
-    name: Some(~"constant"),
+    name: Some(utf16("constant")),
     id: 47,
     nargs: 2,
     max_stack: 4,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -5302,10 +5892,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // ".nud"
-    name: Some(~".nud"),
+    name: Some(utf16(".nud")),
     id: 48,
     nargs: 0,
     max_stack: 3,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -5343,10 +5934,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // "infix"
-    name: Some(~"infix"),
+    name: Some(utf16("infix")),
     id: 49,
     nargs: 3,
     max_stack: 5,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -5401,6 +5993,7 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     id: 50,
     nargs: 1,
     max_stack: 4,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -5411,37 +6004,49 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
       8, 203,	// 8: set_slot_direct(203)
       14,	// 10: pop
       0,	// 11: push_frame
-      5, 12,	// 12: get_slot_direct(12)
-      0,	// 14: push_frame
-      5, 203,	// 15: get_slot_direct(203)
-      8, 215,	// 17: set_slot_direct(215)
-      0,	// 19: push_frame
-      5, 12,	// 20: get_slot_direct(12)
+      5, 3,	// 12: get_slot_direct(3)
+      5, 3,	// 14: get_slot_direct(3)
+      5, 158,	// 16: get_slot_direct(158)
+      15,	// 18: dup
+      7, 191,	// 19: get_slot_direct_check(191)
+      19,	// 21: swap
       0,	// 22: push_frame
-      5, 3,	// 23: get_slot_direct(3)
-      5, 3,	// 25: get_slot_direct(3)
-      5, 167,	// 27: get_slot_direct(167)
-      0,	// 29: push_frame
-      5, 12,	// 30: get_slot_direct(12)
-      0,	// 32: push_frame
-      5, 3,	// 33: get_slot_direct(3)
-      5, 214,	// 35: get_slot_direct(214)
-      10, 1,	// 37: invoke(1)
-      8, 216,	// 39: set_slot_direct(216)
-      0,	// 41: push_frame
-      5, 12,	// 42: get_slot_direct(12)
-      1, 217,	// 44: push_literal(217)
-      8, 190,	// 46: set_slot_direct(190)
-      0,	// 48: push_frame
-      5, 12,	// 49: get_slot_direct(12)
-      11	// 51: return
+      5, 12,	// 23: get_slot_direct(12)
+      10, 1,	// 25: invoke(1)
+      14,	// 27: pop
+      0,	// 28: push_frame
+      5, 12,	// 29: get_slot_direct(12)
+      0,	// 31: push_frame
+      5, 203,	// 32: get_slot_direct(203)
+      8, 215,	// 34: set_slot_direct(215)
+      0,	// 36: push_frame
+      5, 12,	// 37: get_slot_direct(12)
+      0,	// 39: push_frame
+      5, 3,	// 40: get_slot_direct(3)
+      5, 3,	// 42: get_slot_direct(3)
+      5, 167,	// 44: get_slot_direct(167)
+      0,	// 46: push_frame
+      5, 12,	// 47: get_slot_direct(12)
+      0,	// 49: push_frame
+      5, 3,	// 50: get_slot_direct(3)
+      5, 214,	// 52: get_slot_direct(214)
+      10, 1,	// 54: invoke(1)
+      8, 216,	// 56: set_slot_direct(216)
+      0,	// 58: push_frame
+      5, 12,	// 59: get_slot_direct(12)
+      1, 217,	// 61: push_literal(217)
+      8, 190,	// 63: set_slot_direct(190)
+      0,	// 65: push_frame
+      5, 12,	// 66: get_slot_direct(12)
+      11	// 68: return
     ]
   });
   vec::push(functions, @Function { // "infixr"
-    name: Some(~"infixr"),
+    name: Some(utf16("infixr")),
     id: 51,
     nargs: 3,
     max_stack: 5,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -5496,6 +6101,7 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     id: 52,
     nargs: 1,
     max_stack: 5,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -5506,39 +6112,51 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
       8, 203,	// 8: set_slot_direct(203)
       14,	// 10: pop
       0,	// 11: push_frame
-      5, 12,	// 12: get_slot_direct(12)
-      0,	// 14: push_frame
-      5, 203,	// 15: get_slot_direct(203)
-      8, 215,	// 17: set_slot_direct(215)
-      0,	// 19: push_frame
-      5, 12,	// 20: get_slot_direct(12)
+      5, 3,	// 12: get_slot_direct(3)
+      5, 3,	// 14: get_slot_direct(3)
+      5, 158,	// 16: get_slot_direct(158)
+      15,	// 18: dup
+      7, 191,	// 19: get_slot_direct_check(191)
+      19,	// 21: swap
       0,	// 22: push_frame
-      5, 3,	// 23: get_slot_direct(3)
-      5, 3,	// 25: get_slot_direct(3)
-      5, 167,	// 27: get_slot_direct(167)
-      0,	// 29: push_frame
-      5, 12,	// 30: get_slot_direct(12)
-      0,	// 32: push_frame
-      5, 3,	// 33: get_slot_direct(3)
-      5, 214,	// 35: get_slot_direct(214)
-      1, 8,	// 37: push_literal(8)
-      27,	// 39: bi_sub
-      10, 1,	// 40: invoke(1)
-      8, 216,	// 42: set_slot_direct(216)
-      0,	// 44: push_frame
-      5, 12,	// 45: get_slot_direct(12)
-      1, 217,	// 47: push_literal(217)
-      8, 190,	// 49: set_slot_direct(190)
-      0,	// 51: push_frame
-      5, 12,	// 52: get_slot_direct(12)
-      11	// 54: return
+      5, 12,	// 23: get_slot_direct(12)
+      10, 1,	// 25: invoke(1)
+      14,	// 27: pop
+      0,	// 28: push_frame
+      5, 12,	// 29: get_slot_direct(12)
+      0,	// 31: push_frame
+      5, 203,	// 32: get_slot_direct(203)
+      8, 215,	// 34: set_slot_direct(215)
+      0,	// 36: push_frame
+      5, 12,	// 37: get_slot_direct(12)
+      0,	// 39: push_frame
+      5, 3,	// 40: get_slot_direct(3)
+      5, 3,	// 42: get_slot_direct(3)
+      5, 167,	// 44: get_slot_direct(167)
+      0,	// 46: push_frame
+      5, 12,	// 47: get_slot_direct(12)
+      0,	// 49: push_frame
+      5, 3,	// 50: get_slot_direct(3)
+      5, 214,	// 52: get_slot_direct(214)
+      1, 8,	// 54: push_literal(8)
+      27,	// 56: bi_sub
+      10, 1,	// 57: invoke(1)
+      8, 216,	// 59: set_slot_direct(216)
+      0,	// 61: push_frame
+      5, 12,	// 62: get_slot_direct(12)
+      1, 217,	// 64: push_literal(217)
+      8, 190,	// 66: set_slot_direct(190)
+      0,	// 68: push_frame
+      5, 12,	// 69: get_slot_direct(12)
+      11	// 71: return
     ]
   });
   vec::push(functions, @Function { // "assignment"
-    name: Some(~"assignment"),
+    name: Some(utf16("assignment")),
     id: 53,
     nargs: 1,
     max_stack: 5,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -5566,6 +6184,7 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     id: 54,
     nargs: 1,
     max_stack: 4,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -5641,10 +6260,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // "prefix"
-    name: Some(~"prefix"),
+    name: Some(utf16("prefix")),
     id: 55,
     nargs: 2,
     max_stack: 4,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -5692,6 +6312,7 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     id: 56,
     nargs: 0,
     max_stack: 4,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -5728,10 +6349,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // "stmt"
-    name: Some(~"stmt"),
+    name: Some(utf16("stmt")),
     id: 57,
     nargs: 2,
     max_stack: 4,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -5769,11 +6391,206 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
       11	// 50: return
     ]
   });
-  vec::push(functions, @Function { // ".nud"
-    name: Some(~".nud"),
+  vec::push(functions, @Function {
+    name: None,
     id: 58,
     nargs: 0,
+    max_stack: 5,
+    source: None,
+    bytecode: ~[
+      0,	// 0: push_frame
+      5, 2,	// 1: get_slot_direct(2)
+      14,	// 3: pop
+      0,	// 4: push_frame
+      1, 0,	// 5: push_literal(0)
+      8, 192,	// 7: set_slot_direct(192)
+      0,	// 9: push_frame
+      1, 0,	// 10: push_literal(0)
+      8, 95,	// 12: set_slot_direct(95)
+      0,	// 14: push_frame
+      1, 0,	// 15: push_literal(0)
+      8, 454,	// 17: set_slot_direct(454)
+      0,	// 19: push_frame
+      1, 0,	// 20: push_literal(0)
+      8, 96,	// 22: set_slot_direct(96)
+      0,	// 24: push_frame
+      0,	// 25: push_frame
+      5, 3,	// 26: get_slot_direct(3)
+      5, 158,	// 28: get_slot_direct(158)
+      8, 192,	// 30: set_slot_direct(192)
+      12, 42,	// 32: jmp(42)
+      0,	// 34: push_frame
+      0,	// 35: push_frame
+      5, 192,	// 36: get_slot_direct(192)
+      5, 187,	// 38: get_slot_direct(187)
+      8, 192,	// 40: set_slot_direct(192)
+      0,	// 42: push_frame
+      5, 192,	// 43: get_slot_direct(192)
+      5, 187,	// 45: get_slot_direct(187)
+      20,	// 47: un_not
+      13, 34,	// 48: jmp_unless(34)
+      0,	// 50: push_frame
+      5, 192,	// 51: get_slot_direct(192)
+      5, 179,	// 53: get_slot_direct(179)
+      15,	// 55: dup
+      7, 36,	// 56: get_slot_direct_check(36)
+      19,	// 58: swap
+      1, 455,	// 59: push_literal(455)
+      10, 1,	// 61: invoke(1)
+      20,	// 63: un_not
+      13, 82,	// 64: jmp_unless(82)
+      0,	// 66: push_frame
+      5, 3,	// 67: get_slot_direct(3)
+      5, 97,	// 69: get_slot_direct(97)
+      0,	// 71: push_frame
+      5, 12,	// 72: get_slot_direct(12)
+      0,	// 74: push_frame
+      5, 12,	// 75: get_slot_direct(12)
+      1, 212,	// 77: push_literal(212)
+      10, 2,	// 79: invoke(2)
+      14,	// 81: pop
+      0,	// 82: push_frame
+      0,	// 83: push_frame
+      5, 60,	// 84: get_slot_direct(60)
+      15,	// 86: dup
+      7, 65,	// 87: get_slot_direct_check(65)
+      19,	// 89: swap
+      0,	// 90: push_frame
+      5, 192,	// 91: get_slot_direct(192)
+      5, 179,	// 93: get_slot_direct(179)
+      5, 455,	// 95: get_slot_direct(455)
+      10, 1,	// 97: invoke(1)
+      8, 95,	// 99: set_slot_direct(95)
+      0,	// 101: push_frame
+      5, 95,	// 102: get_slot_direct(95)
+      0,	// 104: push_frame
+      5, 12,	// 105: get_slot_direct(12)
+      5, 9,	// 107: get_slot_direct(9)
+      8, 9,	// 109: set_slot_direct(9)
+      0,	// 111: push_frame
+      5, 95,	// 112: get_slot_direct(95)
+      0,	// 114: push_frame
+      5, 12,	// 115: get_slot_direct(12)
+      5, 103,	// 117: get_slot_direct(103)
+      8, 103,	// 119: set_slot_direct(103)
+      0,	// 121: push_frame
+      5, 95,	// 122: get_slot_direct(95)
+      1, 455,	// 124: push_literal(455)
+      8, 108,	// 126: set_slot_direct(108)
+      0,	// 128: push_frame
+      5, 95,	// 129: get_slot_direct(95)
+      1, 67,	// 131: push_literal(67)
+      8, 190,	// 133: set_slot_direct(190)
+      0,	// 135: push_frame
+      0,	// 136: push_frame
+      5, 60,	// 137: get_slot_direct(60)
+      15,	// 139: dup
+      7, 65,	// 140: get_slot_direct_check(65)
+      19,	// 142: swap
+      0,	// 143: push_frame
+      5, 3,	// 144: get_slot_direct(3)
+      5, 159,	// 146: get_slot_direct(159)
+      1, 199,	// 148: push_literal(199)
+      6,	// 150: get_slot_indirect
+      10, 1,	// 151: invoke(1)
+      8, 454,	// 153: set_slot_direct(454)
+      0,	// 155: push_frame
+      5, 454,	// 156: get_slot_direct(454)
+      0,	// 158: push_frame
+      5, 12,	// 159: get_slot_direct(12)
+      5, 9,	// 161: get_slot_direct(9)
+      8, 9,	// 163: set_slot_direct(9)
+      0,	// 165: push_frame
+      5, 454,	// 166: get_slot_direct(454)
+      0,	// 168: push_frame
+      5, 12,	// 169: get_slot_direct(12)
+      5, 103,	// 171: get_slot_direct(103)
+      8, 103,	// 173: set_slot_direct(103)
+      0,	// 175: push_frame
+      5, 454,	// 176: get_slot_direct(454)
+      0,	// 178: push_frame
+      5, 12,	// 179: get_slot_direct(12)
+      5, 108,	// 181: get_slot_direct(108)
+      1, 6,	// 183: push_literal(6)
+      6,	// 185: get_slot_indirect
+      8, 108,	// 186: set_slot_direct(108)
+      0,	// 188: push_frame
+      5, 454,	// 189: get_slot_direct(454)
+      1, 200,	// 191: push_literal(200)
+      8, 190,	// 193: set_slot_direct(190)
+      0,	// 195: push_frame
+      0,	// 196: push_frame
+      5, 60,	// 197: get_slot_direct(60)
+      15,	// 199: dup
+      7, 65,	// 200: get_slot_direct_check(65)
+      19,	// 202: swap
+      0,	// 203: push_frame
+      5, 3,	// 204: get_slot_direct(3)
+      5, 159,	// 206: get_slot_direct(159)
+      1, 199,	// 208: push_literal(199)
+      6,	// 210: get_slot_indirect
+      10, 1,	// 211: invoke(1)
+      8, 96,	// 213: set_slot_direct(96)
+      0,	// 215: push_frame
+      5, 96,	// 216: get_slot_direct(96)
+      0,	// 218: push_frame
+      5, 12,	// 219: get_slot_direct(12)
+      5, 9,	// 221: get_slot_direct(9)
+      8, 9,	// 223: set_slot_direct(9)
+      0,	// 225: push_frame
+      5, 96,	// 226: get_slot_direct(96)
+      0,	// 228: push_frame
+      5, 12,	// 229: get_slot_direct(12)
+      5, 103,	// 231: get_slot_direct(103)
+      8, 103,	// 233: set_slot_direct(103)
+      0,	// 235: push_frame
+      5, 96,	// 236: get_slot_direct(96)
+      0,	// 238: push_frame
+      5, 12,	// 239: get_slot_direct(12)
+      5, 108,	// 241: get_slot_direct(108)
+      1, 8,	// 243: push_literal(8)
+      6,	// 245: get_slot_indirect
+      8, 108,	// 246: set_slot_direct(108)
+      0,	// 248: push_frame
+      5, 96,	// 249: get_slot_direct(96)
+      1, 200,	// 251: push_literal(200)
+      8, 190,	// 253: set_slot_direct(190)
+      0,	// 255: push_frame
+      5, 12,	// 256: get_slot_direct(12)
+      0,	// 258: push_frame
+      5, 95,	// 259: get_slot_direct(95)
+      8, 215,	// 261: set_slot_direct(215)
+      0,	// 263: push_frame
+      5, 12,	// 264: get_slot_direct(12)
+      3,	// 266: new_array
+      15,	// 267: dup
+      0,	// 268: push_frame
+      5, 454,	// 269: get_slot_direct(454)
+      8, 6,	// 271: set_slot_direct(6)
+      15,	// 273: dup
+      0,	// 274: push_frame
+      5, 96,	// 275: get_slot_direct(96)
+      8, 8,	// 277: set_slot_direct(8)
+      8, 216,	// 279: set_slot_direct(216)
+      0,	// 281: push_frame
+      5, 12,	// 282: get_slot_direct(12)
+      1, 204,	// 284: push_literal(204)
+      8, 108,	// 286: set_slot_direct(108)
+      0,	// 288: push_frame
+      5, 12,	// 289: get_slot_direct(12)
+      1, 217,	// 291: push_literal(217)
+      8, 190,	// 293: set_slot_direct(190)
+      0,	// 295: push_frame
+      5, 12,	// 296: get_slot_direct(12)
+      11	// 298: return
+    ]
+  });
+  vec::push(functions, @Function { // ".nud"
+    name: Some(utf16(".nud")),
+    id: 59,
+    nargs: 0,
     max_stack: 3,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -5799,9 +6616,10 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
   });
   vec::push(functions, @Function {
     name: None,
-    id: 59,
+    id: 60,
     nargs: 1,
     max_stack: 4,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -5855,9 +6673,10 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
   });
   vec::push(functions, @Function {
     name: None,
-    id: 60,
+    id: 61,
     nargs: 1,
     max_stack: 4,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -5920,9 +6739,10 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
   });
   vec::push(functions, @Function {
     name: None,
-    id: 61,
+    id: 62,
     nargs: 1,
     max_stack: 4,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -5966,9 +6786,10 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
   });
   vec::push(functions, @Function {
     name: None,
-    id: 62,
+    id: 63,
     nargs: 1,
     max_stack: 5,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -6155,9 +6976,10 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
   });
   vec::push(functions, @Function {
     name: None,
-    id: 63,
+    id: 64,
     nargs: 0,
     max_stack: 4,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -6189,9 +7011,10 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
   });
   vec::push(functions, @Function {
     name: None,
-    id: 64,
+    id: 65,
     nargs: 0,
     max_stack: 5,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -6382,35 +7205,43 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
       10, 0,	// 298: invoke(0)
       8, 216,	// 300: set_slot_direct(216)
       0,	// 302: push_frame
-      5, 3,	// 303: get_slot_direct(3)
-      5, 166,	// 305: get_slot_direct(166)
-      0,	// 307: push_frame
-      5, 12,	// 308: get_slot_direct(12)
-      1, 210,	// 310: push_literal(210)
-      10, 1,	// 312: invoke(1)
-      14,	// 314: pop
-      0,	// 315: push_frame
-      5, 12,	// 316: get_slot_direct(12)
-      1, 205,	// 318: push_literal(205)
-      8, 190,	// 320: set_slot_direct(190)
-      0,	// 322: push_frame
-      5, 3,	// 323: get_slot_direct(3)
-      5, 158,	// 325: get_slot_direct(158)
-      15,	// 327: dup
-      7, 25,	// 328: get_slot_direct_check(25)
-      19,	// 330: swap
-      10, 0,	// 331: invoke(0)
-      14,	// 333: pop
+      5, 12,	// 303: get_slot_direct(12)
+      0,	// 305: push_frame
+      5, 3,	// 306: get_slot_direct(3)
+      5, 160,	// 308: get_slot_direct(160)
+      5, 103,	// 310: get_slot_direct(103)
+      8, 457,	// 312: set_slot_direct(457)
+      0,	// 314: push_frame
+      5, 3,	// 315: get_slot_direct(3)
+      5, 166,	// 317: get_slot_direct(166)
+      0,	// 319: push_frame
+      5, 12,	// 320: get_slot_direct(12)
+      1, 210,	// 322: push_literal(210)
+      10, 1,	// 324: invoke(1)
+      14,	// 326: pop
+      0,	// 327: push_frame
+      5, 12,	// 328: get_slot_direct(12)
+      1, 205,	// 330: push_literal(205)
+      8, 190,	// 332: set_slot_direct(190)
       0,	// 334: push_frame
-      5, 12,	// 335: get_slot_direct(12)
-      11	// 337: return
+      5, 3,	// 335: get_slot_direct(3)
+      5, 158,	// 337: get_slot_direct(158)
+      15,	// 339: dup
+      7, 25,	// 340: get_slot_direct_check(25)
+      19,	// 342: swap
+      10, 0,	// 343: invoke(0)
+      14,	// 345: pop
+      0,	// 346: push_frame
+      5, 12,	// 347: get_slot_direct(12)
+      11	// 349: return
     ]
   });
   vec::push(functions, @Function {
     name: None,
-    id: 65,
+    id: 66,
     nargs: 0,
     max_stack: 5,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -6488,9 +7319,10 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
   });
   vec::push(functions, @Function {
     name: None,
-    id: 66,
+    id: 67,
     nargs: 0,
     max_stack: 4,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -6631,9 +7463,10 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
   });
   vec::push(functions, @Function {
     name: None,
-    id: 67,
+    id: 68,
     nargs: 0,
     max_stack: 5,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -6676,9 +7509,10 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
   });
   vec::push(functions, @Function {
     name: None,
-    id: 68,
+    id: 69,
     nargs: 0,
     max_stack: 4,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -6843,9 +7677,10 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
   });
   vec::push(functions, @Function {
     name: None,
-    id: 69,
+    id: 70,
     nargs: 0,
     max_stack: 5,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -6966,9 +7801,10 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
   });
   vec::push(functions, @Function {
     name: None,
-    id: 70,
+    id: 71,
     nargs: 0,
     max_stack: 4,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -7037,9 +7873,10 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
   });
   vec::push(functions, @Function {
     name: None,
-    id: 71,
+    id: 72,
     nargs: 0,
     max_stack: 4,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -7085,9 +7922,10 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
   });
   vec::push(functions, @Function {
     name: None,
-    id: 72,
+    id: 73,
     nargs: 0,
     max_stack: 4,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -7142,10 +7980,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // "parse"
-    name: Some(~"parse"),
-    id: 73,
+    name: Some(utf16("parse")),
+    id: 74,
     nargs: 3,
     max_stack: 6,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -7282,10 +8121,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // "parse_repl"
-    name: Some(~"parse_repl"),
-    id: 74,
+    name: Some(utf16("parse_repl")),
+    id: 75,
     nargs: 4,
     max_stack: 6,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -7438,8 +8278,8 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
       19,	// 230: swap
       0,	// 231: push_frame
       5, 12,	// 232: get_slot_direct(12)
-      4, 75,	// 234: new_function(75)
-      4, 76,	// 236: new_function(76)
+      4, 76,	// 234: new_function(76)
+      4, 77,	// 236: new_function(77)
       10, 3,	// 238: invoke(3)
       14,	// 240: pop
       0,	// 241: push_frame
@@ -7510,9 +8350,10 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
   });
   vec::push(functions, @Function {
     name: None,
-    id: 75,
+    id: 76,
     nargs: 0,
     max_stack: 6,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -7590,9 +8431,10 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
   });
   vec::push(functions, @Function {
     name: None,
-    id: 76,
+    id: 77,
     nargs: 1,
     max_stack: 6,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -7626,10 +8468,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // "make_bytecode_table"
-    name: Some(~"make_bytecode_table"),
-    id: 77,
+    name: Some(utf16("make_bytecode_table")),
+    id: 78,
     nargs: 0,
     max_stack: 7,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -7656,13 +8499,13 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
       2,	// 34: new_object
       8, 288,	// 35: set_slot_direct(288)
       0,	// 37: push_frame
-      4, 78,	// 38: new_function(78)
+      4, 79,	// 38: new_function(79)
       8, 289,	// 40: set_slot_direct(289)
       0,	// 42: push_frame
-      4, 82,	// 43: new_function(82)
+      4, 83,	// 43: new_function(83)
       8, 290,	// 45: set_slot_direct(290)
       0,	// 47: push_frame
-      4, 83,	// 48: new_function(83)
+      4, 84,	// 48: new_function(84)
       8, 291,	// 50: set_slot_direct(291)
       0,	// 52: push_frame
       5, 289,	// 53: get_slot_direct(289)
@@ -7778,7 +8621,7 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
       5, 12,	// 238: get_slot_direct(12)
       1, 315,	// 240: push_literal(315)
       1, 8,	// 242: push_literal(8)
-      4, 84,	// 244: new_function(84)
+      4, 85,	// 244: new_function(85)
       1, 8,	// 246: push_literal(8)
       10, 4,	// 248: invoke(4)
       14,	// 250: pop
@@ -7976,32 +8819,173 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
       1, 8,	// 575: push_literal(8)
       10, 4,	// 577: invoke(4)
       14,	// 579: pop
-      2,	// 580: new_object
-      15,	// 581: dup
-      1, 286,	// 582: push_literal(286)
-      8, 154,	// 584: set_slot_direct(154)
-      15,	// 586: dup
-      0,	// 587: push_frame
-      5, 3,	// 588: get_slot_direct(3)
-      5, 335,	// 590: get_slot_direct(335)
-      8, 156,	// 592: set_slot_direct(156)
-      15,	// 594: dup
-      3,	// 595: new_array
-      8, 284,	// 596: set_slot_direct(284)
-      15,	// 598: dup
-      4, 85,	// 599: new_function(85)
-      8, 336,	// 601: set_slot_direct(336)
-      15,	// 603: dup
-      4, 86,	// 604: new_function(86)
-      8, 337,	// 606: set_slot_direct(337)
-      11	// 608: return
+      0,	// 580: push_frame
+      5, 289,	// 581: get_slot_direct(289)
+      0,	// 583: push_frame
+      5, 12,	// 584: get_slot_direct(12)
+      1, 413,	// 586: push_literal(413)
+      1, 6,	// 588: push_literal(6)
+      1, 8,	// 590: push_literal(8)
+      1, 8,	// 592: push_literal(8)
+      10, 4,	// 594: invoke(4)
+      14,	// 596: pop
+      0,	// 597: push_frame
+      5, 289,	// 598: get_slot_direct(289)
+      0,	// 600: push_frame
+      5, 12,	// 601: get_slot_direct(12)
+      1, 414,	// 603: push_literal(414)
+      1, 6,	// 605: push_literal(6)
+      1, 79,	// 607: push_literal(79)
+      1, 8,	// 609: push_literal(8)
+      10, 4,	// 611: invoke(4)
+      14,	// 613: pop
+      0,	// 614: push_frame
+      5, 289,	// 615: get_slot_direct(289)
+      0,	// 617: push_frame
+      5, 12,	// 618: get_slot_direct(12)
+      1, 415,	// 620: push_literal(415)
+      1, 6,	// 622: push_literal(6)
+      1, 79,	// 624: push_literal(79)
+      1, 8,	// 626: push_literal(8)
+      10, 4,	// 628: invoke(4)
+      14,	// 630: pop
+      0,	// 631: push_frame
+      5, 289,	// 632: get_slot_direct(289)
+      0,	// 634: push_frame
+      5, 12,	// 635: get_slot_direct(12)
+      1, 416,	// 637: push_literal(416)
+      1, 6,	// 639: push_literal(6)
+      1, 79,	// 641: push_literal(79)
+      1, 8,	// 643: push_literal(8)
+      10, 4,	// 645: invoke(4)
+      14,	// 647: pop
+      0,	// 648: push_frame
+      5, 289,	// 649: get_slot_direct(289)
+      0,	// 651: push_frame
+      5, 12,	// 652: get_slot_direct(12)
+      1, 417,	// 654: push_literal(417)
+      1, 6,	// 656: push_literal(6)
+      1, 79,	// 658: push_literal(79)
+      1, 8,	// 660: push_literal(8)
+      10, 4,	// 662: invoke(4)
+      14,	// 664: pop
+      0,	// 665: push_frame
+      5, 289,	// 666: get_slot_direct(289)
+      0,	// 668: push_frame
+      5, 12,	// 669: get_slot_direct(12)
+      1, 418,	// 671: push_literal(418)
+      1, 6,	// 673: push_literal(6)
+      1, 79,	// 675: push_literal(79)
+      1, 8,	// 677: push_literal(8)
+      10, 4,	// 679: invoke(4)
+      14,	// 681: pop
+      0,	// 682: push_frame
+      5, 289,	// 683: get_slot_direct(289)
+      0,	// 685: push_frame
+      5, 12,	// 686: get_slot_direct(12)
+      1, 419,	// 688: push_literal(419)
+      1, 6,	// 690: push_literal(6)
+      1, 79,	// 692: push_literal(79)
+      1, 8,	// 694: push_literal(8)
+      10, 4,	// 696: invoke(4)
+      14,	// 698: pop
+      0,	// 699: push_frame
+      5, 289,	// 700: get_slot_direct(289)
+      0,	// 702: push_frame
+      5, 12,	// 703: get_slot_direct(12)
+      1, 420,	// 705: push_literal(420)
+      1, 6,	// 707: push_literal(6)
+      1, 79,	// 709: push_literal(79)
+      1, 8,	// 711: push_literal(8)
+      10, 4,	// 713: invoke(4)
+      14,	// 715: pop
+      0,	// 716: push_frame
+      5, 289,	// 717: get_slot_direct(289)
+      0,	// 719: push_frame
+      5, 12,	// 720: get_slot_direct(12)
+      1, 440,	// 722: push_literal(440)
+      1, 6,	// 724: push_literal(6)
+      1, 8,	// 726: push_literal(8)
+      1, 8,	// 728: push_literal(8)
+      10, 4,	// 730: invoke(4)
+      14,	// 732: pop
+      0,	// 733: push_frame
+      5, 289,	// 734: get_slot_direct(289)
+      0,	// 736: push_frame
+      5, 12,	// 737: get_slot_direct(12)
+      1, 441,	// 739: push_literal(441)
+      1, 6,	// 741: push_literal(6)
+      1, 79,	// 743: push_literal(79)
+      1, 8,	// 745: push_literal(8)
+      10, 4,	// 747: invoke(4)
+      14,	// 749: pop
+      0,	// 750: push_frame
+      5, 289,	// 751: get_slot_direct(289)
+      0,	// 753: push_frame
+      5, 12,	// 754: get_slot_direct(12)
+      1, 442,	// 756: push_literal(442)
+      1, 6,	// 758: push_literal(6)
+      1, 79,	// 760: push_literal(79)
+      1, 8,	// 762: push_literal(8)
+      10, 4,	// 764: invoke(4)
+      14,	// 766: pop
+      0,	// 767: push_frame
+      5, 289,	// 768: get_slot_direct(289)
+      0,	// 770: push_frame
+      5, 12,	// 771: get_slot_direct(12)
+      1, 443,	// 773: push_literal(443)
+      1, 6,	// 775: push_literal(6)
+      1, 79,	// 777: push_literal(79)
+      1, 8,	// 779: push_literal(8)
+      10, 4,	// 781: invoke(4)
+      14,	// 783: pop
+      0,	// 784: push_frame
+      5, 289,	// 785: get_slot_direct(289)
+      0,	// 787: push_frame
+      5, 12,	// 788: get_slot_direct(12)
+      1, 444,	// 790: push_literal(444)
+      1, 6,	// 792: push_literal(6)
+      1, 79,	// 794: push_literal(79)
+      1, 8,	// 796: push_literal(8)
+      10, 4,	// 798: invoke(4)
+      14,	// 800: pop
+      0,	// 801: push_frame
+      5, 289,	// 802: get_slot_direct(289)
+      0,	// 804: push_frame
+      5, 12,	// 805: get_slot_direct(12)
+      1, 448,	// 807: push_literal(448)
+      1, 6,	// 809: push_literal(6)
+      1, 79,	// 811: push_literal(79)
+      1, 8,	// 813: push_literal(8)
+      10, 4,	// 815: invoke(4)
+      14,	// 817: pop
+      2,	// 818: new_object
+      15,	// 819: dup
+      1, 286,	// 820: push_literal(286)
+      8, 154,	// 822: set_slot_direct(154)
+      15,	// 824: dup
+      0,	// 825: push_frame
+      5, 3,	// 826: get_slot_direct(3)
+      5, 335,	// 828: get_slot_direct(335)
+      8, 156,	// 830: set_slot_direct(156)
+      15,	// 832: dup
+      3,	// 833: new_array
+      8, 284,	// 834: set_slot_direct(284)
+      15,	// 836: dup
+      4, 86,	// 837: new_function(86)
+      8, 336,	// 839: set_slot_direct(336)
+      15,	// 841: dup
+      4, 87,	// 842: new_function(87)
+      8, 337,	// 844: set_slot_direct(337)
+      11	// 846: return
     ]
   });
   vec::push(functions, @Function { // "bc"
-    name: Some(~"bc"),
-    id: 78,
+    name: Some(utf16("bc")),
+    id: 79,
     nargs: 5,
     max_stack: 4,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -8073,7 +9057,7 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
       13, 107,	// 98: jmp_unless(107)
       0,	// 100: push_frame
       5, 296,	// 101: get_slot_direct(296)
-      4, 79,	// 103: new_function(79)
+      4, 80,	// 103: new_function(80)
       8, 293,	// 105: set_slot_direct(293)
       0,	// 107: push_frame
       5, 296,	// 108: get_slot_direct(296)
@@ -8085,7 +9069,7 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
       13, 126,	// 117: jmp_unless(126)
       0,	// 119: push_frame
       5, 296,	// 120: get_slot_direct(296)
-      4, 80,	// 122: new_function(80)
+      4, 81,	// 122: new_function(81)
       8, 294,	// 124: set_slot_direct(294)
       0,	// 126: push_frame
       5, 296,	// 127: get_slot_direct(296)
@@ -8094,7 +9078,7 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
       13, 141,	// 132: jmp_unless(141)
       0,	// 134: push_frame
       5, 296,	// 135: get_slot_direct(296)
-      4, 81,	// 137: new_function(81)
+      4, 82,	// 137: new_function(82)
       8, 295,	// 139: set_slot_direct(295)
       0,	// 141: push_frame
       5, 3,	// 142: get_slot_direct(3)
@@ -8119,10 +9103,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // ".stackpop"
-    name: Some(~".stackpop"),
-    id: 79,
+    name: Some(utf16(".stackpop")),
+    id: 80,
     nargs: 0,
     max_stack: 1,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -8134,10 +9119,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // ".stackpush"
-    name: Some(~".stackpush"),
-    id: 80,
+    name: Some(utf16(".stackpush")),
+    id: 81,
     nargs: 0,
     max_stack: 1,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -8149,10 +9135,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // ".printargs"
-    name: Some(~".printargs"),
-    id: 81,
+    name: Some(utf16(".printargs")),
+    id: 82,
     nargs: 3,
     max_stack: 5,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -8227,10 +9214,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // "print_literal"
-    name: Some(~"print_literal"),
-    id: 82,
+    name: Some(utf16("print_literal")),
+    id: 83,
     nargs: 3,
     max_stack: 4,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -8281,10 +9269,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // "print_label"
-    name: Some(~"print_label"),
-    id: 83,
+    name: Some(utf16("print_label")),
+    id: 84,
     nargs: 3,
     max_stack: 4,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -8337,9 +9326,10 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
   });
   vec::push(functions, @Function {
     name: None,
-    id: 84,
+    id: 85,
     nargs: 2,
     max_stack: 3,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -8362,10 +9352,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // "for_num:"
-    name: Some(~"for_num:"),
-    id: 85,
+    name: Some(utf16("for_num:")),
+    id: 86,
     nargs: 1,
     max_stack: 3,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -8385,10 +9376,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // "for_name:"
-    name: Some(~"for_name:"),
-    id: 86,
+    name: Some(utf16("for_name:")),
+    id: 87,
     nargs: 1,
     max_stack: 3,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -8408,10 +9400,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // "make_bcompile"
-    name: Some(~"make_bcompile"),
-    id: 87,
+    name: Some(utf16("make_bcompile")),
+    id: 88,
     nargs: 1,
     max_stack: 6,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -8449,28 +9442,28 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
       1, 0,	// 52: push_literal(0)
       8, 338,	// 54: set_slot_direct(338)
       0,	// 56: push_frame
-      4, 88,	// 57: new_function(88)
+      4, 89,	// 57: new_function(89)
       8, 340,	// 59: set_slot_direct(340)
       0,	// 61: push_frame
       2,	// 62: new_object
       8, 341,	// 63: set_slot_direct(341)
       0,	// 65: push_frame
-      4, 89,	// 66: new_function(89)
+      4, 90,	// 66: new_function(90)
       8, 342,	// 68: set_slot_direct(342)
       0,	// 70: push_frame
       5, 341,	// 71: get_slot_direct(341)
-      4, 105,	// 73: new_function(105)
+      4, 106,	// 73: new_function(106)
       8, 67,	// 75: set_slot_direct(67)
       0,	// 77: push_frame
       5, 341,	// 78: get_slot_direct(341)
-      4, 106,	// 80: new_function(106)
+      4, 107,	// 80: new_function(107)
       8, 200,	// 82: set_slot_direct(200)
       0,	// 84: push_frame
       5, 341,	// 85: get_slot_direct(341)
-      4, 107,	// 87: new_function(107)
+      4, 108,	// 87: new_function(108)
       8, 222,	// 89: set_slot_direct(222)
       0,	// 91: push_frame
-      4, 108,	// 92: new_function(108)
+      4, 109,	// 92: new_function(109)
       8, 222,	// 94: set_slot_direct(222)
       0,	// 96: push_frame
       5, 222,	// 97: get_slot_direct(222)
@@ -8500,333 +9493,527 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
       5, 222,	// 136: get_slot_direct(222)
       0,	// 138: push_frame
       5, 12,	// 139: get_slot_direct(12)
-      1, 218,	// 141: push_literal(218)
-      4, 110,	// 143: new_function(110)
+      1, 439,	// 141: push_literal(439)
+      1, 413,	// 143: push_literal(413)
       10, 2,	// 145: invoke(2)
       14,	// 147: pop
       0,	// 148: push_frame
       5, 222,	// 149: get_slot_direct(222)
       0,	// 151: push_frame
       5, 12,	// 152: get_slot_direct(12)
-      1, 211,	// 154: push_literal(211)
-      4, 112,	// 156: new_function(112)
+      1, 447,	// 154: push_literal(447)
+      1, 440,	// 156: push_literal(440)
       10, 2,	// 158: invoke(2)
       14,	// 160: pop
       0,	// 161: push_frame
-      5, 341,	// 162: get_slot_direct(341)
-      4, 114,	// 164: new_function(114)
-      8, 217,	// 166: set_slot_direct(217)
-      0,	// 168: push_frame
-      4, 115,	// 169: new_function(115)
-      8, 217,	// 171: set_slot_direct(217)
-      0,	// 173: push_frame
-      4, 117,	// 174: new_function(117)
-      8, 176,	// 176: set_slot_direct(176)
-      0,	// 178: push_frame
-      5, 217,	// 179: get_slot_direct(217)
-      0,	// 181: push_frame
-      5, 12,	// 182: get_slot_direct(12)
-      1, 232,	// 184: push_literal(232)
-      0,	// 186: push_frame
-      5, 176,	// 187: get_slot_direct(176)
-      0,	// 189: push_frame
-      5, 12,	// 190: get_slot_direct(12)
-      1, 32,	// 192: push_literal(32)
-      10, 1,	// 194: invoke(1)
-      10, 2,	// 196: invoke(2)
-      14,	// 198: pop
+      5, 222,	// 162: get_slot_direct(222)
+      0,	// 164: push_frame
+      5, 12,	// 165: get_slot_direct(12)
+      1, 218,	// 167: push_literal(218)
+      4, 111,	// 169: new_function(111)
+      10, 2,	// 171: invoke(2)
+      14,	// 173: pop
+      0,	// 174: push_frame
+      5, 222,	// 175: get_slot_direct(222)
+      0,	// 177: push_frame
+      5, 12,	// 178: get_slot_direct(12)
+      1, 211,	// 180: push_literal(211)
+      4, 113,	// 182: new_function(113)
+      10, 2,	// 184: invoke(2)
+      14,	// 186: pop
+      0,	// 187: push_frame
+      5, 341,	// 188: get_slot_direct(341)
+      4, 115,	// 190: new_function(115)
+      8, 217,	// 192: set_slot_direct(217)
+      0,	// 194: push_frame
+      4, 116,	// 195: new_function(116)
+      8, 217,	// 197: set_slot_direct(217)
       0,	// 199: push_frame
-      5, 217,	// 200: get_slot_direct(217)
-      0,	// 202: push_frame
-      5, 12,	// 203: get_slot_direct(12)
-      1, 233,	// 205: push_literal(233)
+      4, 118,	// 200: new_function(118)
+      8, 176,	// 202: set_slot_direct(176)
+      0,	// 204: push_frame
+      5, 217,	// 205: get_slot_direct(217)
       0,	// 207: push_frame
-      5, 176,	// 208: get_slot_direct(176)
-      0,	// 210: push_frame
-      5, 12,	// 211: get_slot_direct(12)
-      1, 331,	// 213: push_literal(331)
-      10, 1,	// 215: invoke(1)
-      10, 2,	// 217: invoke(2)
-      14,	// 219: pop
-      0,	// 220: push_frame
-      5, 217,	// 221: get_slot_direct(217)
-      0,	// 223: push_frame
-      5, 12,	// 224: get_slot_direct(12)
-      1, 234,	// 226: push_literal(234)
+      5, 12,	// 208: get_slot_direct(12)
+      1, 232,	// 210: push_literal(232)
+      0,	// 212: push_frame
+      5, 176,	// 213: get_slot_direct(176)
+      0,	// 215: push_frame
+      5, 12,	// 216: get_slot_direct(12)
+      1, 32,	// 218: push_literal(32)
+      10, 1,	// 220: invoke(1)
+      10, 2,	// 222: invoke(2)
+      14,	// 224: pop
+      0,	// 225: push_frame
+      5, 217,	// 226: get_slot_direct(217)
       0,	// 228: push_frame
-      5, 176,	// 229: get_slot_direct(176)
-      0,	// 231: push_frame
-      5, 12,	// 232: get_slot_direct(12)
-      1, 332,	// 234: push_literal(332)
-      10, 1,	// 236: invoke(1)
-      10, 2,	// 238: invoke(2)
-      14,	// 240: pop
-      0,	// 241: push_frame
-      5, 217,	// 242: get_slot_direct(217)
-      0,	// 244: push_frame
-      5, 12,	// 245: get_slot_direct(12)
-      1, 235,	// 247: push_literal(235)
+      5, 12,	// 229: get_slot_direct(12)
+      1, 233,	// 231: push_literal(233)
+      0,	// 233: push_frame
+      5, 176,	// 234: get_slot_direct(176)
+      0,	// 236: push_frame
+      5, 12,	// 237: get_slot_direct(12)
+      1, 331,	// 239: push_literal(331)
+      10, 1,	// 241: invoke(1)
+      10, 2,	// 243: invoke(2)
+      14,	// 245: pop
+      0,	// 246: push_frame
+      5, 217,	// 247: get_slot_direct(217)
       0,	// 249: push_frame
-      5, 176,	// 250: get_slot_direct(176)
-      0,	// 252: push_frame
-      5, 12,	// 253: get_slot_direct(12)
-      1, 333,	// 255: push_literal(333)
-      10, 1,	// 257: invoke(1)
-      10, 2,	// 259: invoke(2)
-      14,	// 261: pop
-      0,	// 262: push_frame
-      5, 217,	// 263: get_slot_direct(217)
-      0,	// 265: push_frame
-      5, 12,	// 266: get_slot_direct(12)
-      1, 236,	// 268: push_literal(236)
+      5, 12,	// 250: get_slot_direct(12)
+      1, 234,	// 252: push_literal(234)
+      0,	// 254: push_frame
+      5, 176,	// 255: get_slot_direct(176)
+      0,	// 257: push_frame
+      5, 12,	// 258: get_slot_direct(12)
+      1, 332,	// 260: push_literal(332)
+      10, 1,	// 262: invoke(1)
+      10, 2,	// 264: invoke(2)
+      14,	// 266: pop
+      0,	// 267: push_frame
+      5, 217,	// 268: get_slot_direct(217)
       0,	// 270: push_frame
-      5, 176,	// 271: get_slot_direct(176)
-      0,	// 273: push_frame
-      5, 12,	// 274: get_slot_direct(12)
-      1, 334,	// 276: push_literal(334)
-      10, 1,	// 278: invoke(1)
-      10, 2,	// 280: invoke(2)
-      14,	// 282: pop
-      0,	// 283: push_frame
-      5, 217,	// 284: get_slot_direct(217)
-      0,	// 286: push_frame
-      5, 12,	// 287: get_slot_direct(12)
-      1, 241,	// 289: push_literal(241)
-      4, 119,	// 291: new_function(119)
-      10, 2,	// 293: invoke(2)
-      14,	// 295: pop
+      5, 12,	// 271: get_slot_direct(12)
+      1, 235,	// 273: push_literal(235)
+      0,	// 275: push_frame
+      5, 176,	// 276: get_slot_direct(176)
+      0,	// 278: push_frame
+      5, 12,	// 279: get_slot_direct(12)
+      1, 333,	// 281: push_literal(333)
+      10, 1,	// 283: invoke(1)
+      10, 2,	// 285: invoke(2)
+      14,	// 287: pop
+      0,	// 288: push_frame
+      5, 217,	// 289: get_slot_direct(217)
+      0,	// 291: push_frame
+      5, 12,	// 292: get_slot_direct(12)
+      1, 236,	// 294: push_literal(236)
       0,	// 296: push_frame
-      5, 217,	// 297: get_slot_direct(217)
+      5, 176,	// 297: get_slot_direct(176)
       0,	// 299: push_frame
       5, 12,	// 300: get_slot_direct(12)
-      1, 243,	// 302: push_literal(243)
-      4, 120,	// 304: new_function(120)
+      1, 334,	// 302: push_literal(334)
+      10, 1,	// 304: invoke(1)
       10, 2,	// 306: invoke(2)
       14,	// 308: pop
       0,	// 309: push_frame
       5, 217,	// 310: get_slot_direct(217)
       0,	// 312: push_frame
       5, 12,	// 313: get_slot_direct(12)
-      1, 245,	// 315: push_literal(245)
-      1, 328,	// 317: push_literal(328)
-      10, 2,	// 319: invoke(2)
-      14,	// 321: pop
-      0,	// 322: push_frame
-      5, 217,	// 323: get_slot_direct(217)
-      0,	// 325: push_frame
-      5, 12,	// 326: get_slot_direct(12)
-      1, 247,	// 328: push_literal(247)
-      4, 121,	// 330: new_function(121)
-      10, 2,	// 332: invoke(2)
-      14,	// 334: pop
-      0,	// 335: push_frame
-      5, 217,	// 336: get_slot_direct(217)
+      1, 421,	// 315: push_literal(421)
+      0,	// 317: push_frame
+      5, 176,	// 318: get_slot_direct(176)
+      0,	// 320: push_frame
+      5, 12,	// 321: get_slot_direct(12)
+      1, 414,	// 323: push_literal(414)
+      10, 1,	// 325: invoke(1)
+      10, 2,	// 327: invoke(2)
+      14,	// 329: pop
+      0,	// 330: push_frame
+      5, 217,	// 331: get_slot_direct(217)
+      0,	// 333: push_frame
+      5, 12,	// 334: get_slot_direct(12)
+      1, 422,	// 336: push_literal(422)
       0,	// 338: push_frame
-      5, 12,	// 339: get_slot_direct(12)
-      1, 248,	// 341: push_literal(248)
-      1, 329,	// 343: push_literal(329)
-      1, 8,	// 345: push_literal(8)
-      10, 3,	// 347: invoke(3)
-      14,	// 349: pop
-      0,	// 350: push_frame
-      5, 217,	// 351: get_slot_direct(217)
-      0,	// 353: push_frame
-      5, 12,	// 354: get_slot_direct(12)
-      1, 250,	// 356: push_literal(250)
-      1, 330,	// 358: push_literal(330)
-      1, 8,	// 360: push_literal(8)
-      10, 3,	// 362: invoke(3)
-      14,	// 364: pop
-      0,	// 365: push_frame
-      5, 217,	// 366: get_slot_direct(217)
-      0,	// 368: push_frame
-      5, 12,	// 369: get_slot_direct(12)
-      1, 251,	// 371: push_literal(251)
-      1, 329,	// 373: push_literal(329)
-      10, 2,	// 375: invoke(2)
-      14,	// 377: pop
-      0,	// 378: push_frame
-      5, 217,	// 379: get_slot_direct(217)
-      0,	// 381: push_frame
-      5, 12,	// 382: get_slot_direct(12)
-      1, 252,	// 384: push_literal(252)
-      1, 330,	// 386: push_literal(330)
-      10, 2,	// 388: invoke(2)
-      14,	// 390: pop
-      0,	// 391: push_frame
-      5, 217,	// 392: get_slot_direct(217)
-      0,	// 394: push_frame
-      5, 12,	// 395: get_slot_direct(12)
-      1, 126,	// 397: push_literal(126)
-      1, 331,	// 399: push_literal(331)
-      10, 2,	// 401: invoke(2)
-      14,	// 403: pop
+      5, 176,	// 339: get_slot_direct(176)
+      0,	// 341: push_frame
+      5, 12,	// 342: get_slot_direct(12)
+      1, 415,	// 344: push_literal(415)
+      10, 1,	// 346: invoke(1)
+      10, 2,	// 348: invoke(2)
+      14,	// 350: pop
+      0,	// 351: push_frame
+      5, 217,	// 352: get_slot_direct(217)
+      0,	// 354: push_frame
+      5, 12,	// 355: get_slot_direct(12)
+      1, 423,	// 357: push_literal(423)
+      0,	// 359: push_frame
+      5, 176,	// 360: get_slot_direct(176)
+      0,	// 362: push_frame
+      5, 12,	// 363: get_slot_direct(12)
+      1, 416,	// 365: push_literal(416)
+      10, 1,	// 367: invoke(1)
+      10, 2,	// 369: invoke(2)
+      14,	// 371: pop
+      0,	// 372: push_frame
+      5, 217,	// 373: get_slot_direct(217)
+      0,	// 375: push_frame
+      5, 12,	// 376: get_slot_direct(12)
+      1, 424,	// 378: push_literal(424)
+      0,	// 380: push_frame
+      5, 176,	// 381: get_slot_direct(176)
+      0,	// 383: push_frame
+      5, 12,	// 384: get_slot_direct(12)
+      1, 417,	// 386: push_literal(417)
+      10, 1,	// 388: invoke(1)
+      10, 2,	// 390: invoke(2)
+      14,	// 392: pop
+      0,	// 393: push_frame
+      5, 217,	// 394: get_slot_direct(217)
+      0,	// 396: push_frame
+      5, 12,	// 397: get_slot_direct(12)
+      1, 425,	// 399: push_literal(425)
+      0,	// 401: push_frame
+      5, 176,	// 402: get_slot_direct(176)
       0,	// 404: push_frame
-      5, 217,	// 405: get_slot_direct(217)
-      0,	// 407: push_frame
-      5, 12,	// 408: get_slot_direct(12)
-      1, 105,	// 410: push_literal(105)
-      1, 332,	// 412: push_literal(332)
-      10, 2,	// 414: invoke(2)
-      14,	// 416: pop
+      5, 12,	// 405: get_slot_direct(12)
+      1, 418,	// 407: push_literal(418)
+      10, 1,	// 409: invoke(1)
+      10, 2,	// 411: invoke(2)
+      14,	// 413: pop
+      0,	// 414: push_frame
+      5, 217,	// 415: get_slot_direct(217)
       0,	// 417: push_frame
-      5, 217,	// 418: get_slot_direct(217)
-      0,	// 420: push_frame
-      5, 12,	// 421: get_slot_direct(12)
-      1, 152,	// 423: push_literal(152)
-      1, 333,	// 425: push_literal(333)
-      10, 2,	// 427: invoke(2)
-      14,	// 429: pop
-      0,	// 430: push_frame
-      5, 217,	// 431: get_slot_direct(217)
-      0,	// 433: push_frame
-      5, 12,	// 434: get_slot_direct(12)
-      1, 151,	// 436: push_literal(151)
-      1, 334,	// 438: push_literal(334)
-      10, 2,	// 440: invoke(2)
-      14,	// 442: pop
+      5, 12,	// 418: get_slot_direct(12)
+      1, 426,	// 420: push_literal(426)
+      0,	// 422: push_frame
+      5, 176,	// 423: get_slot_direct(176)
+      0,	// 425: push_frame
+      5, 12,	// 426: get_slot_direct(12)
+      1, 419,	// 428: push_literal(419)
+      10, 1,	// 430: invoke(1)
+      10, 2,	// 432: invoke(2)
+      14,	// 434: pop
+      0,	// 435: push_frame
+      5, 217,	// 436: get_slot_direct(217)
+      0,	// 438: push_frame
+      5, 12,	// 439: get_slot_direct(12)
+      1, 427,	// 441: push_literal(427)
       0,	// 443: push_frame
-      5, 217,	// 444: get_slot_direct(217)
+      5, 176,	// 444: get_slot_direct(176)
       0,	// 446: push_frame
       5, 12,	// 447: get_slot_direct(12)
-      1, 121,	// 449: push_literal(121)
-      4, 122,	// 451: new_function(122)
+      1, 420,	// 449: push_literal(420)
+      10, 1,	// 451: invoke(1)
       10, 2,	// 453: invoke(2)
       14,	// 455: pop
       0,	// 456: push_frame
       5, 217,	// 457: get_slot_direct(217)
       0,	// 459: push_frame
       5, 12,	// 460: get_slot_direct(12)
-      1, 218,	// 462: push_literal(218)
-      4, 123,	// 464: new_function(123)
+      1, 241,	// 462: push_literal(241)
+      4, 120,	// 464: new_function(120)
       10, 2,	// 466: invoke(2)
       14,	// 468: pop
       0,	// 469: push_frame
       5, 217,	// 470: get_slot_direct(217)
       0,	// 472: push_frame
       5, 12,	// 473: get_slot_direct(12)
-      1, 204,	// 475: push_literal(204)
-      4, 124,	// 477: new_function(124)
+      1, 243,	// 475: push_literal(243)
+      4, 121,	// 477: new_function(121)
       10, 2,	// 479: invoke(2)
       14,	// 481: pop
       0,	// 482: push_frame
-      5, 341,	// 483: get_slot_direct(341)
-      4, 126,	// 485: new_function(126)
-      8, 240,	// 487: set_slot_direct(240)
-      0,	// 489: push_frame
-      4, 127,	// 490: new_function(127)
-      8, 240,	// 492: set_slot_direct(240)
-      0,	// 494: push_frame
-      5, 240,	// 495: get_slot_direct(240)
-      0,	// 497: push_frame
-      5, 12,	// 498: get_slot_direct(12)
-      1, 237,	// 500: push_literal(237)
-      4, 128,	// 502: new_function(128)
-      10, 2,	// 504: invoke(2)
-      14,	// 506: pop
-      0,	// 507: push_frame
-      5, 240,	// 508: get_slot_direct(240)
-      0,	// 510: push_frame
-      5, 12,	// 511: get_slot_direct(12)
-      1, 204,	// 513: push_literal(204)
-      4, 129,	// 515: new_function(129)
-      10, 2,	// 517: invoke(2)
-      14,	// 519: pop
-      0,	// 520: push_frame
-      5, 341,	// 521: get_slot_direct(341)
-      4, 131,	// 523: new_function(131)
-      8, 168,	// 525: set_slot_direct(168)
-      0,	// 527: push_frame
-      4, 132,	// 528: new_function(132)
-      8, 177,	// 530: set_slot_direct(177)
-      0,	// 532: push_frame
-      5, 177,	// 533: get_slot_direct(177)
-      0,	// 535: push_frame
-      5, 12,	// 536: get_slot_direct(12)
-      1, 38,	// 538: push_literal(38)
-      4, 133,	// 540: new_function(133)
-      10, 2,	// 542: invoke(2)
-      14,	// 544: pop
-      0,	// 545: push_frame
-      5, 177,	// 546: get_slot_direct(177)
-      0,	// 548: push_frame
-      5, 12,	// 549: get_slot_direct(12)
-      1, 209,	// 551: push_literal(209)
-      4, 135,	// 553: new_function(135)
-      10, 2,	// 555: invoke(2)
-      14,	// 557: pop
-      0,	// 558: push_frame
-      5, 177,	// 559: get_slot_direct(177)
-      0,	// 561: push_frame
-      5, 12,	// 562: get_slot_direct(12)
-      1, 265,	// 564: push_literal(265)
-      4, 136,	// 566: new_function(136)
-      10, 2,	// 568: invoke(2)
-      14,	// 570: pop
-      0,	// 571: push_frame
-      5, 177,	// 572: get_slot_direct(177)
-      0,	// 574: push_frame
-      5, 12,	// 575: get_slot_direct(12)
-      1, 266,	// 577: push_literal(266)
-      4, 137,	// 579: new_function(137)
-      10, 2,	// 581: invoke(2)
-      14,	// 583: pop
-      0,	// 584: push_frame
-      5, 177,	// 585: get_slot_direct(177)
-      0,	// 587: push_frame
-      5, 12,	// 588: get_slot_direct(12)
-      1, 268,	// 590: push_literal(268)
-      4, 138,	// 592: new_function(138)
-      10, 2,	// 594: invoke(2)
-      14,	// 596: pop
-      0,	// 597: push_frame
-      5, 177,	// 598: get_slot_direct(177)
-      0,	// 600: push_frame
-      5, 12,	// 601: get_slot_direct(12)
-      1, 76,	// 603: push_literal(76)
-      4, 139,	// 605: new_function(139)
-      10, 2,	// 607: invoke(2)
-      14,	// 609: pop
-      0,	// 610: push_frame
-      5, 341,	// 611: get_slot_direct(341)
-      1, 12,	// 613: push_literal(12)
-      4, 140,	// 615: new_function(140)
-      9,	// 617: set_slot_indirect
-      0,	// 618: push_frame
-      5, 341,	// 619: get_slot_direct(341)
-      1, 205,	// 621: push_literal(205)
-      4, 141,	// 623: new_function(141)
-      9,	// 625: set_slot_indirect
-      0,	// 626: push_frame
-      4, 143,	// 627: new_function(143)
-      8, 338,	// 629: set_slot_direct(338)
-      0,	// 631: push_frame
-      5, 338,	// 632: get_slot_direct(338)
-      1, 338,	// 634: push_literal(338)
-      8, 154,	// 636: set_slot_direct(154)
+      5, 217,	// 483: get_slot_direct(217)
+      0,	// 485: push_frame
+      5, 12,	// 486: get_slot_direct(12)
+      1, 245,	// 488: push_literal(245)
+      1, 328,	// 490: push_literal(328)
+      10, 2,	// 492: invoke(2)
+      14,	// 494: pop
+      0,	// 495: push_frame
+      5, 217,	// 496: get_slot_direct(217)
+      0,	// 498: push_frame
+      5, 12,	// 499: get_slot_direct(12)
+      1, 247,	// 501: push_literal(247)
+      4, 122,	// 503: new_function(122)
+      10, 2,	// 505: invoke(2)
+      14,	// 507: pop
+      0,	// 508: push_frame
+      5, 217,	// 509: get_slot_direct(217)
+      0,	// 511: push_frame
+      5, 12,	// 512: get_slot_direct(12)
+      1, 449,	// 514: push_literal(449)
+      1, 448,	// 516: push_literal(448)
+      10, 2,	// 518: invoke(2)
+      14,	// 520: pop
+      0,	// 521: push_frame
+      5, 217,	// 522: get_slot_direct(217)
+      0,	// 524: push_frame
+      5, 12,	// 525: get_slot_direct(12)
+      1, 450,	// 527: push_literal(450)
+      4, 123,	// 529: new_function(123)
+      10, 2,	// 531: invoke(2)
+      14,	// 533: pop
+      0,	// 534: push_frame
+      5, 217,	// 535: get_slot_direct(217)
+      0,	// 537: push_frame
+      5, 12,	// 538: get_slot_direct(12)
+      1, 248,	// 540: push_literal(248)
+      1, 443,	// 542: push_literal(443)
+      10, 2,	// 544: invoke(2)
+      14,	// 546: pop
+      0,	// 547: push_frame
+      5, 217,	// 548: get_slot_direct(217)
+      0,	// 550: push_frame
+      5, 12,	// 551: get_slot_direct(12)
+      1, 250,	// 553: push_literal(250)
+      1, 444,	// 555: push_literal(444)
+      10, 2,	// 557: invoke(2)
+      14,	// 559: pop
+      0,	// 560: push_frame
+      5, 217,	// 561: get_slot_direct(217)
+      0,	// 563: push_frame
+      5, 12,	// 564: get_slot_direct(12)
+      1, 251,	// 566: push_literal(251)
+      1, 329,	// 568: push_literal(329)
+      10, 2,	// 570: invoke(2)
+      14,	// 572: pop
+      0,	// 573: push_frame
+      5, 217,	// 574: get_slot_direct(217)
+      0,	// 576: push_frame
+      5, 12,	// 577: get_slot_direct(12)
+      1, 252,	// 579: push_literal(252)
+      1, 330,	// 581: push_literal(330)
+      10, 2,	// 583: invoke(2)
+      14,	// 585: pop
+      0,	// 586: push_frame
+      5, 217,	// 587: get_slot_direct(217)
+      0,	// 589: push_frame
+      5, 12,	// 590: get_slot_direct(12)
+      1, 445,	// 592: push_literal(445)
+      1, 441,	// 594: push_literal(441)
+      10, 2,	// 596: invoke(2)
+      14,	// 598: pop
+      0,	// 599: push_frame
+      5, 217,	// 600: get_slot_direct(217)
+      0,	// 602: push_frame
+      5, 12,	// 603: get_slot_direct(12)
+      1, 446,	// 605: push_literal(446)
+      1, 442,	// 607: push_literal(442)
+      10, 2,	// 609: invoke(2)
+      14,	// 611: pop
+      0,	// 612: push_frame
+      5, 217,	// 613: get_slot_direct(217)
+      0,	// 615: push_frame
+      5, 12,	// 616: get_slot_direct(12)
+      1, 126,	// 618: push_literal(126)
+      1, 331,	// 620: push_literal(331)
+      10, 2,	// 622: invoke(2)
+      14,	// 624: pop
+      0,	// 625: push_frame
+      5, 217,	// 626: get_slot_direct(217)
+      0,	// 628: push_frame
+      5, 12,	// 629: get_slot_direct(12)
+      1, 105,	// 631: push_literal(105)
+      1, 332,	// 633: push_literal(332)
+      10, 2,	// 635: invoke(2)
+      14,	// 637: pop
       0,	// 638: push_frame
-      5, 338,	// 639: get_slot_direct(338)
+      5, 217,	// 639: get_slot_direct(217)
       0,	// 641: push_frame
-      5, 3,	// 642: get_slot_direct(3)
-      5, 404,	// 644: get_slot_direct(404)
-      8, 156,	// 646: set_slot_direct(156)
-      0,	// 648: push_frame
-      5, 338,	// 649: get_slot_direct(338)
-      3,	// 651: new_array
-      15,	// 652: dup
-      1, 286,	// 653: push_literal(286)
-      8, 6,	// 655: set_slot_direct(6)
-      8, 284,	// 657: set_slot_direct(284)
-      0,	// 659: push_frame
-      5, 338,	// 660: get_slot_direct(338)
-      11	// 662: return
+      5, 12,	// 642: get_slot_direct(12)
+      1, 152,	// 644: push_literal(152)
+      1, 333,	// 646: push_literal(333)
+      10, 2,	// 648: invoke(2)
+      14,	// 650: pop
+      0,	// 651: push_frame
+      5, 217,	// 652: get_slot_direct(217)
+      0,	// 654: push_frame
+      5, 12,	// 655: get_slot_direct(12)
+      1, 151,	// 657: push_literal(151)
+      1, 334,	// 659: push_literal(334)
+      10, 2,	// 661: invoke(2)
+      14,	// 663: pop
+      0,	// 664: push_frame
+      5, 217,	// 665: get_slot_direct(217)
+      0,	// 667: push_frame
+      5, 12,	// 668: get_slot_direct(12)
+      1, 438,	// 670: push_literal(438)
+      1, 414,	// 672: push_literal(414)
+      10, 2,	// 674: invoke(2)
+      14,	// 676: pop
+      0,	// 677: push_frame
+      5, 217,	// 678: get_slot_direct(217)
+      0,	// 680: push_frame
+      5, 12,	// 681: get_slot_direct(12)
+      1, 432,	// 683: push_literal(432)
+      1, 415,	// 685: push_literal(415)
+      10, 2,	// 687: invoke(2)
+      14,	// 689: pop
+      0,	// 690: push_frame
+      5, 217,	// 691: get_slot_direct(217)
+      0,	// 693: push_frame
+      5, 12,	// 694: get_slot_direct(12)
+      1, 428,	// 696: push_literal(428)
+      1, 416,	// 698: push_literal(416)
+      10, 2,	// 700: invoke(2)
+      14,	// 702: pop
+      0,	// 703: push_frame
+      5, 217,	// 704: get_slot_direct(217)
+      0,	// 706: push_frame
+      5, 12,	// 707: get_slot_direct(12)
+      1, 430,	// 709: push_literal(430)
+      1, 417,	// 711: push_literal(417)
+      10, 2,	// 713: invoke(2)
+      14,	// 715: pop
+      0,	// 716: push_frame
+      5, 217,	// 717: get_slot_direct(217)
+      0,	// 719: push_frame
+      5, 12,	// 720: get_slot_direct(12)
+      1, 434,	// 722: push_literal(434)
+      1, 418,	// 724: push_literal(418)
+      10, 2,	// 726: invoke(2)
+      14,	// 728: pop
+      0,	// 729: push_frame
+      5, 217,	// 730: get_slot_direct(217)
+      0,	// 732: push_frame
+      5, 12,	// 733: get_slot_direct(12)
+      1, 436,	// 735: push_literal(436)
+      1, 419,	// 737: push_literal(419)
+      10, 2,	// 739: invoke(2)
+      14,	// 741: pop
+      0,	// 742: push_frame
+      5, 217,	// 743: get_slot_direct(217)
+      0,	// 745: push_frame
+      5, 12,	// 746: get_slot_direct(12)
+      1, 437,	// 748: push_literal(437)
+      1, 420,	// 750: push_literal(420)
+      10, 2,	// 752: invoke(2)
+      14,	// 754: pop
+      0,	// 755: push_frame
+      5, 217,	// 756: get_slot_direct(217)
+      0,	// 758: push_frame
+      5, 12,	// 759: get_slot_direct(12)
+      1, 121,	// 761: push_literal(121)
+      4, 124,	// 763: new_function(124)
+      10, 2,	// 765: invoke(2)
+      14,	// 767: pop
+      0,	// 768: push_frame
+      5, 217,	// 769: get_slot_direct(217)
+      0,	// 771: push_frame
+      5, 12,	// 772: get_slot_direct(12)
+      1, 218,	// 774: push_literal(218)
+      4, 125,	// 776: new_function(125)
+      10, 2,	// 778: invoke(2)
+      14,	// 780: pop
+      0,	// 781: push_frame
+      5, 217,	// 782: get_slot_direct(217)
+      0,	// 784: push_frame
+      5, 12,	// 785: get_slot_direct(12)
+      1, 204,	// 787: push_literal(204)
+      4, 126,	// 789: new_function(126)
+      10, 2,	// 791: invoke(2)
+      14,	// 793: pop
+      0,	// 794: push_frame
+      5, 341,	// 795: get_slot_direct(341)
+      4, 128,	// 797: new_function(128)
+      8, 240,	// 799: set_slot_direct(240)
+      0,	// 801: push_frame
+      4, 129,	// 802: new_function(129)
+      8, 240,	// 804: set_slot_direct(240)
+      0,	// 806: push_frame
+      5, 240,	// 807: get_slot_direct(240)
+      0,	// 809: push_frame
+      5, 12,	// 810: get_slot_direct(12)
+      1, 237,	// 812: push_literal(237)
+      4, 130,	// 814: new_function(130)
+      10, 2,	// 816: invoke(2)
+      14,	// 818: pop
+      0,	// 819: push_frame
+      5, 240,	// 820: get_slot_direct(240)
+      0,	// 822: push_frame
+      5, 12,	// 823: get_slot_direct(12)
+      1, 204,	// 825: push_literal(204)
+      4, 131,	// 827: new_function(131)
+      10, 2,	// 829: invoke(2)
+      14,	// 831: pop
+      0,	// 832: push_frame
+      5, 341,	// 833: get_slot_direct(341)
+      4, 133,	// 835: new_function(133)
+      8, 168,	// 837: set_slot_direct(168)
+      0,	// 839: push_frame
+      4, 134,	// 840: new_function(134)
+      8, 177,	// 842: set_slot_direct(177)
+      0,	// 844: push_frame
+      5, 177,	// 845: get_slot_direct(177)
+      0,	// 847: push_frame
+      5, 12,	// 848: get_slot_direct(12)
+      1, 38,	// 850: push_literal(38)
+      4, 135,	// 852: new_function(135)
+      10, 2,	// 854: invoke(2)
+      14,	// 856: pop
+      0,	// 857: push_frame
+      5, 177,	// 858: get_slot_direct(177)
+      0,	// 860: push_frame
+      5, 12,	// 861: get_slot_direct(12)
+      1, 209,	// 863: push_literal(209)
+      4, 137,	// 865: new_function(137)
+      10, 2,	// 867: invoke(2)
+      14,	// 869: pop
+      0,	// 870: push_frame
+      5, 177,	// 871: get_slot_direct(177)
+      0,	// 873: push_frame
+      5, 12,	// 874: get_slot_direct(12)
+      1, 265,	// 876: push_literal(265)
+      4, 138,	// 878: new_function(138)
+      10, 2,	// 880: invoke(2)
+      14,	// 882: pop
+      0,	// 883: push_frame
+      5, 177,	// 884: get_slot_direct(177)
+      0,	// 886: push_frame
+      5, 12,	// 887: get_slot_direct(12)
+      1, 266,	// 889: push_literal(266)
+      4, 139,	// 891: new_function(139)
+      10, 2,	// 893: invoke(2)
+      14,	// 895: pop
+      0,	// 896: push_frame
+      5, 177,	// 897: get_slot_direct(177)
+      0,	// 899: push_frame
+      5, 12,	// 900: get_slot_direct(12)
+      1, 268,	// 902: push_literal(268)
+      4, 140,	// 904: new_function(140)
+      10, 2,	// 906: invoke(2)
+      14,	// 908: pop
+      0,	// 909: push_frame
+      5, 177,	// 910: get_slot_direct(177)
+      0,	// 912: push_frame
+      5, 12,	// 913: get_slot_direct(12)
+      1, 76,	// 915: push_literal(76)
+      4, 141,	// 917: new_function(141)
+      10, 2,	// 919: invoke(2)
+      14,	// 921: pop
+      0,	// 922: push_frame
+      5, 341,	// 923: get_slot_direct(341)
+      1, 12,	// 925: push_literal(12)
+      4, 142,	// 927: new_function(142)
+      9,	// 929: set_slot_indirect
+      0,	// 930: push_frame
+      5, 341,	// 931: get_slot_direct(341)
+      1, 205,	// 933: push_literal(205)
+      4, 143,	// 935: new_function(143)
+      9,	// 937: set_slot_indirect
+      0,	// 938: push_frame
+      4, 145,	// 939: new_function(145)
+      8, 338,	// 941: set_slot_direct(338)
+      0,	// 943: push_frame
+      5, 338,	// 944: get_slot_direct(338)
+      1, 338,	// 946: push_literal(338)
+      8, 154,	// 948: set_slot_direct(154)
+      0,	// 950: push_frame
+      5, 338,	// 951: get_slot_direct(338)
+      0,	// 953: push_frame
+      5, 3,	// 954: get_slot_direct(3)
+      5, 404,	// 956: get_slot_direct(404)
+      8, 156,	// 958: set_slot_direct(156)
+      0,	// 960: push_frame
+      5, 338,	// 961: get_slot_direct(338)
+      3,	// 963: new_array
+      15,	// 964: dup
+      1, 286,	// 965: push_literal(286)
+      8, 6,	// 967: set_slot_direct(6)
+      8, 284,	// 969: set_slot_direct(284)
+      0,	// 971: push_frame
+      5, 338,	// 972: get_slot_direct(338)
+      11	// 974: return
     ]
   });
   vec::push(functions, @Function { // "assert"
-    name: Some(~"assert"),
-    id: 88,
+    name: Some(utf16("assert")),
+    id: 89,
     nargs: 2,
     max_stack: 4,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -8872,10 +10059,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // "mkstate"
-    name: Some(~"mkstate"),
-    id: 89,
+    name: Some(utf16("mkstate")),
+    id: 90,
     nargs: 0,
     max_stack: 4,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -8903,61 +10091,61 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
       8, 274,	// 34: set_slot_direct(274)
       0,	// 36: push_frame
       5, 274,	// 37: get_slot_direct(274)
-      4, 90,	// 39: new_function(90)
+      4, 91,	// 39: new_function(91)
       8, 200,	// 41: set_slot_direct(200)
       0,	// 43: push_frame
       5, 274,	// 44: get_slot_direct(274)
-      4, 91,	// 46: new_function(91)
+      4, 92,	// 46: new_function(92)
       8, 309,	// 48: set_slot_direct(309)
       0,	// 50: push_frame
       5, 274,	// 51: get_slot_direct(274)
-      4, 92,	// 53: new_function(92)
+      4, 93,	// 53: new_function(93)
       8, 361,	// 55: set_slot_direct(361)
       0,	// 57: push_frame
       5, 274,	// 58: get_slot_direct(274)
-      4, 93,	// 60: new_function(93)
+      4, 94,	// 60: new_function(94)
       8, 364,	// 62: set_slot_direct(364)
       0,	// 64: push_frame
-      4, 94,	// 65: new_function(94)
+      4, 95,	// 65: new_function(95)
       8, 346,	// 67: set_slot_direct(346)
       0,	// 69: push_frame
-      4, 95,	// 70: new_function(95)
+      4, 96,	// 70: new_function(96)
       8, 347,	// 72: set_slot_direct(347)
       0,	// 74: push_frame
       5, 274,	// 75: get_slot_direct(274)
-      4, 96,	// 77: new_function(96)
+      4, 97,	// 77: new_function(97)
       8, 377,	// 79: set_slot_direct(377)
       0,	// 81: push_frame
       5, 274,	// 82: get_slot_direct(274)
-      4, 97,	// 84: new_function(97)
+      4, 98,	// 84: new_function(98)
       8, 379,	// 86: set_slot_direct(379)
       0,	// 88: push_frame
       5, 274,	// 89: get_slot_direct(274)
-      4, 98,	// 91: new_function(98)
+      4, 99,	// 91: new_function(99)
       8, 380,	// 93: set_slot_direct(380)
       0,	// 95: push_frame
       5, 274,	// 96: get_slot_direct(274)
-      4, 99,	// 98: new_function(99)
+      4, 100,	// 98: new_function(100)
       8, 382,	// 100: set_slot_direct(382)
       0,	// 102: push_frame
       5, 274,	// 103: get_slot_direct(274)
-      4, 100,	// 105: new_function(100)
+      4, 101,	// 105: new_function(101)
       8, 383,	// 107: set_slot_direct(383)
       0,	// 109: push_frame
       5, 274,	// 110: get_slot_direct(274)
-      4, 101,	// 112: new_function(101)
+      4, 102,	// 112: new_function(102)
       8, 384,	// 114: set_slot_direct(384)
       0,	// 116: push_frame
       5, 274,	// 117: get_slot_direct(274)
-      4, 102,	// 119: new_function(102)
+      4, 103,	// 119: new_function(103)
       8, 387,	// 121: set_slot_direct(387)
       0,	// 123: push_frame
       5, 274,	// 124: get_slot_direct(274)
-      4, 103,	// 126: new_function(103)
+      4, 104,	// 126: new_function(104)
       8, 386,	// 128: set_slot_direct(386)
       0,	// 130: push_frame
       5, 274,	// 131: get_slot_direct(274)
-      4, 104,	// 133: new_function(104)
+      4, 105,	// 133: new_function(105)
       8, 388,	// 135: set_slot_direct(388)
       0,	// 137: push_frame
       5, 274,	// 138: get_slot_direct(274)
@@ -8965,10 +10153,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // ".literal"
-    name: Some(~".literal"),
-    id: 90,
+    name: Some(utf16(".literal")),
+    id: 91,
     nargs: 1,
     max_stack: 3,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -9056,10 +10245,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // ".new_function"
-    name: Some(~".new_function"),
-    id: 91,
+    name: Some(utf16(".new_function")),
+    id: 92,
     nargs: 1,
     max_stack: 4,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -9112,10 +10302,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // ".emit"
-    name: Some(~".emit"),
-    id: 92,
+    name: Some(utf16(".emit")),
+    id: 93,
     nargs: 1,
     max_stack: 7,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -9286,10 +10477,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // ".decompile"
-    name: Some(~".decompile"),
-    id: 93,
+    name: Some(utf16(".decompile")),
+    id: 94,
     nargs: 1,
     max_stack: 7,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -9415,10 +10607,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // "encode_uint"
-    name: Some(~"encode_uint"),
-    id: 94,
+    name: Some(utf16("encode_uint")),
+    id: 95,
     nargs: 2,
     max_stack: 5,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -9557,10 +10750,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // "encode_str"
-    name: Some(~"encode_str"),
-    id: 95,
+    name: Some(utf16("encode_str")),
+    id: 96,
     nargs: 2,
     max_stack: 6,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -9631,10 +10825,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // ".encode"
-    name: Some(~".encode"),
-    id: 96,
+    name: Some(utf16(".encode")),
+    id: 97,
     nargs: 0,
     max_stack: 5,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -9988,14 +11183,69 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
       13, 292,	// 550: jmp_unless(292)
       0,	// 552: push_frame
       5, 365,	// 553: get_slot_direct(365)
-      11	// 555: return
+      3,	// 555: new_array
+      8, 456,	// 556: set_slot_direct(456)
+      0,	// 558: push_frame
+      1, 8,	// 559: push_literal(8)
+      8, 10,	// 561: set_slot_direct(10)
+      12, 620,	// 563: jmp(620)
+      0,	// 565: push_frame
+      5, 365,	// 566: get_slot_direct(365)
+      5, 456,	// 568: get_slot_direct(456)
+      15,	// 570: dup
+      7, 23,	// 571: get_slot_direct_check(23)
+      19,	// 573: swap
+      0,	// 574: push_frame
+      5, 12,	// 575: get_slot_direct(12)
+      5, 348,	// 577: get_slot_direct(348)
+      0,	// 579: push_frame
+      5, 10,	// 580: get_slot_direct(10)
+      6,	// 582: get_slot_indirect
+      5, 9,	// 583: get_slot_direct(9)
+      10, 1,	// 585: invoke(1)
+      14,	// 587: pop
+      0,	// 588: push_frame
+      5, 365,	// 589: get_slot_direct(365)
+      5, 456,	// 591: get_slot_direct(456)
+      15,	// 593: dup
+      7, 23,	// 594: get_slot_direct_check(23)
+      19,	// 596: swap
+      0,	// 597: push_frame
+      5, 12,	// 598: get_slot_direct(12)
+      5, 348,	// 600: get_slot_direct(348)
+      0,	// 602: push_frame
+      5, 10,	// 603: get_slot_direct(10)
+      6,	// 605: get_slot_indirect
+      5, 457,	// 606: get_slot_direct(457)
+      10, 1,	// 608: invoke(1)
+      14,	// 610: pop
+      0,	// 611: push_frame
+      15,	// 612: dup
+      5, 10,	// 613: get_slot_direct(10)
+      1, 8,	// 615: push_literal(8)
+      26,	// 617: bi_add
+      8, 10,	// 618: set_slot_direct(10)
+      0,	// 620: push_frame
+      5, 10,	// 621: get_slot_direct(10)
+      0,	// 623: push_frame
+      5, 12,	// 624: get_slot_direct(12)
+      5, 348,	// 626: get_slot_direct(348)
+      5, 13,	// 628: get_slot_direct(13)
+      19,	// 630: swap
+      24,	// 631: bi_gt
+      20,	// 632: un_not
+      13, 565,	// 633: jmp_unless(565)
+      0,	// 635: push_frame
+      5, 365,	// 636: get_slot_direct(365)
+      11	// 638: return
     ]
   });
   vec::push(functions, @Function { // ".new_label"
-    name: Some(~".new_label"),
-    id: 97,
+    name: Some(utf16(".new_label")),
+    id: 98,
     nargs: 0,
     max_stack: 3,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -10008,10 +11258,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // ".set_label"
-    name: Some(~".set_label"),
-    id: 98,
+    name: Some(utf16(".set_label")),
+    id: 99,
     nargs: 1,
     max_stack: 3,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -10034,10 +11285,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // ".peek_loop_label"
-    name: Some(~".peek_loop_label"),
-    id: 99,
+    name: Some(utf16(".peek_loop_label")),
+    id: 100,
     nargs: 0,
     max_stack: 3,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -10063,10 +11315,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // ".pop_loop_label"
-    name: Some(~".pop_loop_label"),
-    id: 100,
+    name: Some(utf16(".pop_loop_label")),
+    id: 101,
     nargs: 0,
     max_stack: 2,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -10083,10 +11336,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // ".push_loop_label"
-    name: Some(~".push_loop_label"),
-    id: 101,
+    name: Some(utf16(".push_loop_label")),
+    id: 102,
     nargs: 1,
     max_stack: 3,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -10110,10 +11364,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // ".bcompile_stmts"
-    name: Some(~".bcompile_stmts"),
-    id: 102,
+    name: Some(utf16(".bcompile_stmts")),
+    id: 103,
     nargs: 1,
     max_stack: 5,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -10146,10 +11401,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // ".bcompile_stmt"
-    name: Some(~".bcompile_stmt"),
-    id: 103,
+    name: Some(utf16(".bcompile_stmt")),
+    id: 104,
     nargs: 1,
     max_stack: 5,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -10182,7 +11438,7 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
       1, 217,	// 44: push_literal(217)
       23,	// 46: bi_eq
       15,	// 47: dup
-      13, 111,	// 48: jmp_unless(111)
+      13, 202,	// 48: jmp_unless(202)
       14,	// 50: pop
       0,	// 51: push_frame
       5, 280,	// 52: get_slot_direct(280)
@@ -10191,7 +11447,7 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
       23,	// 58: bi_eq
       15,	// 59: dup
       20,	// 60: un_not
-      13, 111,	// 61: jmp_unless(111)
+      13, 202,	// 61: jmp_unless(202)
       14,	// 63: pop
       0,	// 64: push_frame
       5, 280,	// 65: get_slot_direct(280)
@@ -10200,7 +11456,7 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
       23,	// 71: bi_eq
       15,	// 72: dup
       20,	// 73: un_not
-      13, 111,	// 74: jmp_unless(111)
+      13, 202,	// 74: jmp_unless(202)
       14,	// 76: pop
       0,	// 77: push_frame
       5, 280,	// 78: get_slot_direct(280)
@@ -10209,7 +11465,7 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
       23,	// 84: bi_eq
       15,	// 85: dup
       20,	// 86: un_not
-      13, 111,	// 87: jmp_unless(111)
+      13, 202,	// 87: jmp_unless(202)
       14,	// 89: pop
       0,	// 90: push_frame
       5, 280,	// 91: get_slot_direct(280)
@@ -10218,118 +11474,182 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
       23,	// 97: bi_eq
       15,	// 98: dup
       20,	// 99: un_not
-      13, 111,	// 100: jmp_unless(111)
+      13, 202,	// 100: jmp_unless(202)
       14,	// 102: pop
       0,	// 103: push_frame
       5, 280,	// 104: get_slot_direct(280)
       5, 108,	// 106: get_slot_direct(108)
       1, 236,	// 108: push_literal(236)
       23,	// 110: bi_eq
-      13, 172,	// 111: jmp_unless(172)
-      0,	// 113: push_frame
-      5, 3,	// 114: get_slot_direct(3)
-      5, 3,	// 116: get_slot_direct(3)
-      5, 341,	// 118: get_slot_direct(341)
-      0,	// 120: push_frame
-      5, 280,	// 121: get_slot_direct(280)
-      5, 190,	// 123: get_slot_direct(190)
-      6,	// 125: get_slot_indirect
-      15,	// 126: dup
-      7, 40,	// 127: get_slot_direct_check(40)
-      19,	// 129: swap
-      0,	// 130: push_frame
-      5, 280,	// 131: get_slot_direct(280)
-      0,	// 133: push_frame
-      5, 12,	// 134: get_slot_direct(12)
-      1, 8,	// 136: push_literal(8)
-      10, 3,	// 138: invoke(3)
-      14,	// 140: pop
-      0,	// 141: push_frame
-      5, 3,	// 142: get_slot_direct(3)
-      5, 3,	// 144: get_slot_direct(3)
-      5, 340,	// 146: get_slot_direct(340)
-      0,	// 148: push_frame
-      5, 12,	// 149: get_slot_direct(12)
-      0,	// 151: push_frame
-      5, 3,	// 152: get_slot_direct(3)
-      5, 274,	// 154: get_slot_direct(274)
-      5, 359,	// 156: get_slot_direct(359)
-      5, 354,	// 158: get_slot_direct(354)
-      1, 6,	// 160: push_literal(6)
+      15,	// 111: dup
+      20,	// 112: un_not
+      13, 202,	// 113: jmp_unless(202)
+      14,	// 115: pop
+      0,	// 116: push_frame
+      5, 280,	// 117: get_slot_direct(280)
+      5, 108,	// 119: get_slot_direct(108)
+      1, 421,	// 121: push_literal(421)
+      23,	// 123: bi_eq
+      15,	// 124: dup
+      20,	// 125: un_not
+      13, 202,	// 126: jmp_unless(202)
+      14,	// 128: pop
+      0,	// 129: push_frame
+      5, 280,	// 130: get_slot_direct(280)
+      5, 108,	// 132: get_slot_direct(108)
+      1, 422,	// 134: push_literal(422)
+      23,	// 136: bi_eq
+      15,	// 137: dup
+      20,	// 138: un_not
+      13, 202,	// 139: jmp_unless(202)
+      14,	// 141: pop
+      0,	// 142: push_frame
+      5, 280,	// 143: get_slot_direct(280)
+      5, 108,	// 145: get_slot_direct(108)
+      1, 423,	// 147: push_literal(423)
+      23,	// 149: bi_eq
+      15,	// 150: dup
+      20,	// 151: un_not
+      13, 202,	// 152: jmp_unless(202)
+      14,	// 154: pop
+      0,	// 155: push_frame
+      5, 280,	// 156: get_slot_direct(280)
+      5, 108,	// 158: get_slot_direct(108)
+      1, 424,	// 160: push_literal(424)
       23,	// 162: bi_eq
-      0,	// 163: push_frame
-      5, 280,	// 164: get_slot_direct(280)
-      10, 2,	// 166: invoke(2)
-      14,	// 168: pop
-      1, 0,	// 169: push_literal(0)
-      11,	// 171: return
-      0,	// 172: push_frame
-      5, 12,	// 173: get_slot_direct(12)
-      15,	// 175: dup
-      7, 388,	// 176: get_slot_direct_check(388)
-      19,	// 178: swap
-      0,	// 179: push_frame
-      5, 280,	// 180: get_slot_direct(280)
-      10, 1,	// 182: invoke(1)
-      14,	// 184: pop
-      0,	// 185: push_frame
-      5, 280,	// 186: get_slot_direct(280)
-      5, 190,	// 188: get_slot_direct(190)
-      1, 168,	// 190: push_literal(168)
-      23,	// 192: bi_eq
-      20,	// 193: un_not
-      13, 236,	// 194: jmp_unless(236)
-      0,	// 196: push_frame
-      5, 3,	// 197: get_slot_direct(3)
-      5, 3,	// 199: get_slot_direct(3)
-      5, 340,	// 201: get_slot_direct(340)
-      0,	// 203: push_frame
-      5, 12,	// 204: get_slot_direct(12)
-      0,	// 206: push_frame
+      15,	// 163: dup
+      20,	// 164: un_not
+      13, 202,	// 165: jmp_unless(202)
+      14,	// 167: pop
+      0,	// 168: push_frame
+      5, 280,	// 169: get_slot_direct(280)
+      5, 108,	// 171: get_slot_direct(108)
+      1, 425,	// 173: push_literal(425)
+      23,	// 175: bi_eq
+      15,	// 176: dup
+      20,	// 177: un_not
+      13, 202,	// 178: jmp_unless(202)
+      14,	// 180: pop
+      0,	// 181: push_frame
+      5, 280,	// 182: get_slot_direct(280)
+      5, 108,	// 184: get_slot_direct(108)
+      1, 426,	// 186: push_literal(426)
+      23,	// 188: bi_eq
+      15,	// 189: dup
+      20,	// 190: un_not
+      13, 202,	// 191: jmp_unless(202)
+      14,	// 193: pop
+      0,	// 194: push_frame
+      5, 280,	// 195: get_slot_direct(280)
+      5, 108,	// 197: get_slot_direct(108)
+      1, 427,	// 199: push_literal(427)
+      23,	// 201: bi_eq
+      13, 263,	// 202: jmp_unless(263)
+      0,	// 204: push_frame
+      5, 3,	// 205: get_slot_direct(3)
       5, 3,	// 207: get_slot_direct(3)
-      5, 274,	// 209: get_slot_direct(274)
-      5, 359,	// 211: get_slot_direct(359)
-      5, 354,	// 213: get_slot_direct(354)
-      1, 8,	// 215: push_literal(8)
-      23,	// 217: bi_eq
-      0,	// 218: push_frame
-      5, 280,	// 219: get_slot_direct(280)
-      10, 2,	// 221: invoke(2)
-      14,	// 223: pop
+      5, 341,	// 209: get_slot_direct(341)
+      0,	// 211: push_frame
+      5, 280,	// 212: get_slot_direct(280)
+      5, 190,	// 214: get_slot_direct(190)
+      6,	// 216: get_slot_indirect
+      15,	// 217: dup
+      7, 40,	// 218: get_slot_direct_check(40)
+      19,	// 220: swap
+      0,	// 221: push_frame
+      5, 280,	// 222: get_slot_direct(280)
       0,	// 224: push_frame
       5, 12,	// 225: get_slot_direct(12)
-      15,	// 227: dup
-      7, 361,	// 228: get_slot_direct_check(361)
-      19,	// 230: swap
-      1, 25,	// 231: push_literal(25)
-      10, 1,	// 233: invoke(1)
-      14,	// 235: pop
-      0,	// 236: push_frame
-      5, 3,	// 237: get_slot_direct(3)
-      5, 3,	// 239: get_slot_direct(3)
-      5, 340,	// 241: get_slot_direct(340)
-      0,	// 243: push_frame
-      5, 12,	// 244: get_slot_direct(12)
-      0,	// 246: push_frame
-      5, 3,	// 247: get_slot_direct(3)
-      5, 274,	// 249: get_slot_direct(274)
-      5, 359,	// 251: get_slot_direct(359)
-      5, 354,	// 253: get_slot_direct(354)
-      1, 6,	// 255: push_literal(6)
-      23,	// 257: bi_eq
-      0,	// 258: push_frame
-      5, 280,	// 259: get_slot_direct(280)
-      10, 2,	// 261: invoke(2)
-      14,	// 263: pop
-      1, 0,	// 264: push_literal(0)
-      11	// 266: return
+      1, 8,	// 227: push_literal(8)
+      10, 3,	// 229: invoke(3)
+      14,	// 231: pop
+      0,	// 232: push_frame
+      5, 3,	// 233: get_slot_direct(3)
+      5, 3,	// 235: get_slot_direct(3)
+      5, 340,	// 237: get_slot_direct(340)
+      0,	// 239: push_frame
+      5, 12,	// 240: get_slot_direct(12)
+      0,	// 242: push_frame
+      5, 3,	// 243: get_slot_direct(3)
+      5, 274,	// 245: get_slot_direct(274)
+      5, 359,	// 247: get_slot_direct(359)
+      5, 354,	// 249: get_slot_direct(354)
+      1, 6,	// 251: push_literal(6)
+      23,	// 253: bi_eq
+      0,	// 254: push_frame
+      5, 280,	// 255: get_slot_direct(280)
+      10, 2,	// 257: invoke(2)
+      14,	// 259: pop
+      1, 0,	// 260: push_literal(0)
+      11,	// 262: return
+      0,	// 263: push_frame
+      5, 12,	// 264: get_slot_direct(12)
+      15,	// 266: dup
+      7, 388,	// 267: get_slot_direct_check(388)
+      19,	// 269: swap
+      0,	// 270: push_frame
+      5, 280,	// 271: get_slot_direct(280)
+      10, 1,	// 273: invoke(1)
+      14,	// 275: pop
+      0,	// 276: push_frame
+      5, 280,	// 277: get_slot_direct(280)
+      5, 190,	// 279: get_slot_direct(190)
+      1, 168,	// 281: push_literal(168)
+      23,	// 283: bi_eq
+      20,	// 284: un_not
+      13, 327,	// 285: jmp_unless(327)
+      0,	// 287: push_frame
+      5, 3,	// 288: get_slot_direct(3)
+      5, 3,	// 290: get_slot_direct(3)
+      5, 340,	// 292: get_slot_direct(340)
+      0,	// 294: push_frame
+      5, 12,	// 295: get_slot_direct(12)
+      0,	// 297: push_frame
+      5, 3,	// 298: get_slot_direct(3)
+      5, 274,	// 300: get_slot_direct(274)
+      5, 359,	// 302: get_slot_direct(359)
+      5, 354,	// 304: get_slot_direct(354)
+      1, 8,	// 306: push_literal(8)
+      23,	// 308: bi_eq
+      0,	// 309: push_frame
+      5, 280,	// 310: get_slot_direct(280)
+      10, 2,	// 312: invoke(2)
+      14,	// 314: pop
+      0,	// 315: push_frame
+      5, 12,	// 316: get_slot_direct(12)
+      15,	// 318: dup
+      7, 361,	// 319: get_slot_direct_check(361)
+      19,	// 321: swap
+      1, 25,	// 322: push_literal(25)
+      10, 1,	// 324: invoke(1)
+      14,	// 326: pop
+      0,	// 327: push_frame
+      5, 3,	// 328: get_slot_direct(3)
+      5, 3,	// 330: get_slot_direct(3)
+      5, 340,	// 332: get_slot_direct(340)
+      0,	// 334: push_frame
+      5, 12,	// 335: get_slot_direct(12)
+      0,	// 337: push_frame
+      5, 3,	// 338: get_slot_direct(3)
+      5, 274,	// 340: get_slot_direct(274)
+      5, 359,	// 342: get_slot_direct(359)
+      5, 354,	// 344: get_slot_direct(354)
+      1, 6,	// 346: push_literal(6)
+      23,	// 348: bi_eq
+      0,	// 349: push_frame
+      5, 280,	// 350: get_slot_direct(280)
+      10, 2,	// 352: invoke(2)
+      14,	// 354: pop
+      1, 0,	// 355: push_literal(0)
+      11	// 357: return
     ]
   });
   vec::push(functions, @Function { // ".bcompile_expr"
-    name: Some(~".bcompile_expr"),
-    id: 104,
+    name: Some(utf16(".bcompile_expr")),
+    id: 105,
     nargs: 1,
     max_stack: 4,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -10377,10 +11697,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // ".name"
-    name: Some(~".name"),
-    id: 105,
+    name: Some(utf16(".name")),
+    id: 106,
     nargs: 1,
     max_stack: 6,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -10469,10 +11790,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // ".literal"
-    name: Some(~".literal"),
-    id: 106,
+    name: Some(utf16(".literal")),
+    id: 107,
     nargs: 1,
     max_stack: 6,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -10664,10 +11986,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // ".unary"
-    name: Some(~".unary"),
-    id: 107,
+    name: Some(utf16(".unary")),
+    id: 108,
     nargs: 1,
     max_stack: 4,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -10716,10 +12039,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // "unary"
-    name: Some(~"unary"),
-    id: 108,
+    name: Some(utf16("unary")),
+    id: 109,
     nargs: 2,
     max_stack: 3,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -10746,7 +12070,7 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
       5, 222,	// 32: get_slot_direct(222)
       0,	// 34: push_frame
       5, 357,	// 35: get_slot_direct(357)
-      4, 109,	// 37: new_function(109)
+      4, 110,	// 37: new_function(110)
       9,	// 39: set_slot_indirect
       12, 56,	// 40: jmp(56)
       0,	// 42: push_frame
@@ -10764,9 +12088,10 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
   });
   vec::push(functions, @Function {
     name: None,
-    id: 109,
+    id: 110,
     nargs: 1,
     max_stack: 3,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -10802,9 +12127,10 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
   });
   vec::push(functions, @Function {
     name: None,
-    id: 110,
+    id: 111,
     nargs: 1,
     max_stack: 3,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -10834,7 +12160,7 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
       15,	// 38: dup
       7, 41,	// 39: get_slot_direct_check(41)
       19,	// 41: swap
-      4, 111,	// 42: new_function(111)
+      4, 112,	// 42: new_function(112)
       10, 1,	// 44: invoke(1)
       14,	// 46: pop
       1, 0,	// 47: push_literal(0)
@@ -10843,9 +12169,10 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
   });
   vec::push(functions, @Function {
     name: None,
-    id: 111,
+    id: 112,
     nargs: 2,
     max_stack: 6,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -10903,9 +12230,10 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
   });
   vec::push(functions, @Function {
     name: None,
-    id: 112,
+    id: 113,
     nargs: 1,
     max_stack: 3,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -10935,7 +12263,7 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
       15,	// 38: dup
       7, 41,	// 39: get_slot_direct_check(41)
       19,	// 41: swap
-      4, 113,	// 42: new_function(113)
+      4, 114,	// 42: new_function(114)
       10, 1,	// 44: invoke(1)
       14,	// 46: pop
       1, 0,	// 47: push_literal(0)
@@ -10944,9 +12272,10 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
   });
   vec::push(functions, @Function {
     name: None,
-    id: 113,
+    id: 114,
     nargs: 2,
     max_stack: 6,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -11018,10 +12347,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // ".binary"
-    name: Some(~".binary"),
-    id: 114,
+    name: Some(utf16(".binary")),
+    id: 115,
     nargs: 2,
     max_stack: 5,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -11077,10 +12407,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // "binary"
-    name: Some(~"binary"),
-    id: 115,
+    name: Some(utf16("binary")),
+    id: 116,
     nargs: 3,
     max_stack: 3,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -11112,7 +12443,7 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
       5, 217,	// 39: get_slot_direct(217)
       0,	// 41: push_frame
       5, 357,	// 42: get_slot_direct(357)
-      4, 116,	// 44: new_function(116)
+      4, 117,	// 44: new_function(117)
       9,	// 46: set_slot_indirect
       12, 63,	// 47: jmp(63)
       0,	// 49: push_frame
@@ -11130,9 +12461,10 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
   });
   vec::push(functions, @Function {
     name: None,
-    id: 116,
+    id: 117,
     nargs: 1,
     max_stack: 3,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -11189,10 +12521,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // "assignment"
-    name: Some(~"assignment"),
-    id: 117,
+    name: Some(utf16("assignment")),
+    id: 118,
     nargs: 1,
     max_stack: 3,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -11202,15 +12535,16 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
       19,	// 7: swap
       8, 393,	// 8: set_slot_direct(393)
       14,	// 10: pop
-      4, 118,	// 11: new_function(118)
+      4, 119,	// 11: new_function(119)
       11	// 13: return
     ]
   });
   vec::push(functions, @Function {
     name: None,
-    id: 118,
+    id: 119,
     nargs: 2,
     max_stack: 6,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -11663,9 +12997,10 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
   });
   vec::push(functions, @Function {
     name: None,
-    id: 119,
+    id: 120,
     nargs: 1,
     max_stack: 4,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -11787,9 +13122,10 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
   });
   vec::push(functions, @Function {
     name: None,
-    id: 120,
+    id: 121,
     nargs: 1,
     max_stack: 4,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -11903,9 +13239,10 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
   });
   vec::push(functions, @Function {
     name: None,
-    id: 121,
+    id: 122,
     nargs: 1,
     max_stack: 7,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -11954,9 +13291,62 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
   });
   vec::push(functions, @Function {
     name: None,
-    id: 122,
+    id: 123,
+    nargs: 1,
+    max_stack: 7,
+    source: None,
+    bytecode: ~[
+      0,	// 0: push_frame
+      5, 2,	// 1: get_slot_direct(2)
+      15,	// 3: dup
+      5, 6,	// 4: get_slot_direct(6)
+      0,	// 6: push_frame
+      19,	// 7: swap
+      8, 274,	// 8: set_slot_direct(274)
+      14,	// 10: pop
+      0,	// 11: push_frame
+      5, 274,	// 12: get_slot_direct(274)
+      15,	// 14: dup
+      7, 388,	// 15: get_slot_direct_check(388)
+      19,	// 17: swap
+      2,	// 18: new_object
+      15,	// 19: dup
+      1, 259,	// 20: push_literal(259)
+      8, 108,	// 22: set_slot_direct(108)
+      15,	// 24: dup
+      1, 222,	// 25: push_literal(222)
+      8, 190,	// 27: set_slot_direct(190)
+      15,	// 29: dup
+      2,	// 30: new_object
+      15,	// 31: dup
+      1, 449,	// 32: push_literal(449)
+      8, 108,	// 34: set_slot_direct(108)
+      15,	// 36: dup
+      1, 217,	// 37: push_literal(217)
+      8, 190,	// 39: set_slot_direct(190)
+      15,	// 41: dup
+      0,	// 42: push_frame
+      5, 12,	// 43: get_slot_direct(12)
+      5, 215,	// 45: get_slot_direct(215)
+      8, 215,	// 47: set_slot_direct(215)
+      15,	// 49: dup
+      0,	// 50: push_frame
+      5, 12,	// 51: get_slot_direct(12)
+      5, 216,	// 53: get_slot_direct(216)
+      8, 216,	// 55: set_slot_direct(216)
+      8, 215,	// 57: set_slot_direct(215)
+      10, 1,	// 59: invoke(1)
+      14,	// 61: pop
+      1, 0,	// 62: push_literal(0)
+      11	// 64: return
+    ]
+  });
+  vec::push(functions, @Function {
+    name: None,
+    id: 124,
     nargs: 1,
     max_stack: 6,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -12016,9 +13406,10 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
   });
   vec::push(functions, @Function {
     name: None,
-    id: 123,
+    id: 125,
     nargs: 1,
     max_stack: 3,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -12062,9 +13453,10 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
   });
   vec::push(functions, @Function {
     name: None,
-    id: 124,
+    id: 126,
     nargs: 1,
     max_stack: 5,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -12104,7 +13496,7 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
       15,	// 52: dup
       7, 41,	// 53: get_slot_direct_check(41)
       19,	// 55: swap
-      4, 125,	// 56: new_function(125)
+      4, 127,	// 56: new_function(127)
       10, 1,	// 58: invoke(1)
       14,	// 60: pop
       0,	// 61: push_frame
@@ -12125,9 +13517,10 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
   });
   vec::push(functions, @Function {
     name: None,
-    id: 125,
+    id: 127,
     nargs: 2,
     max_stack: 3,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -12157,10 +13550,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // ".ternary"
-    name: Some(~".ternary"),
-    id: 126,
+    name: Some(utf16(".ternary")),
+    id: 128,
     nargs: 1,
     max_stack: 4,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -12209,10 +13603,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // "ternary"
-    name: Some(~"ternary"),
-    id: 127,
+    name: Some(utf16("ternary")),
+    id: 129,
     nargs: 2,
     max_stack: 3,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -12242,9 +13637,10 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
   });
   vec::push(functions, @Function {
     name: None,
-    id: 128,
+    id: 130,
     nargs: 1,
     max_stack: 4,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -12390,9 +13786,10 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
   });
   vec::push(functions, @Function {
     name: None,
-    id: 129,
+    id: 131,
     nargs: 1,
     max_stack: 6,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -12488,7 +13885,7 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
       15,	// 139: dup
       7, 41,	// 140: get_slot_direct_check(41)
       19,	// 142: swap
-      4, 130,	// 143: new_function(130)
+      4, 132,	// 143: new_function(132)
       10, 1,	// 145: invoke(1)
       14,	// 147: pop
       0,	// 148: push_frame
@@ -12509,9 +13906,10 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
   });
   vec::push(functions, @Function {
     name: None,
-    id: 130,
+    id: 132,
     nargs: 2,
     max_stack: 3,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -12541,10 +13939,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // ".statement"
-    name: Some(~".statement"),
-    id: 131,
+    name: Some(utf16(".statement")),
+    id: 133,
     nargs: 1,
     max_stack: 4,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -12593,10 +13992,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // "stmt"
-    name: Some(~"stmt"),
-    id: 132,
+    name: Some(utf16("stmt")),
+    id: 134,
     nargs: 2,
     max_stack: 3,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -12626,9 +14026,10 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
   });
   vec::push(functions, @Function {
     name: None,
-    id: 133,
+    id: 135,
     nargs: 1,
     max_stack: 3,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -12644,7 +14045,7 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
       15,	// 16: dup
       7, 41,	// 17: get_slot_direct_check(41)
       19,	// 19: swap
-      4, 134,	// 20: new_function(134)
+      4, 136,	// 20: new_function(136)
       10, 1,	// 22: invoke(1)
       14,	// 24: pop
       1, 0,	// 25: push_literal(0)
@@ -12653,9 +14054,10 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
   });
   vec::push(functions, @Function {
     name: None,
-    id: 134,
+    id: 136,
     nargs: 2,
     max_stack: 3,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -12686,9 +14088,10 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
   });
   vec::push(functions, @Function {
     name: None,
-    id: 135,
+    id: 137,
     nargs: 1,
     max_stack: 7,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -12732,9 +14135,10 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
   });
   vec::push(functions, @Function {
     name: None,
-    id: 136,
+    id: 138,
     nargs: 1,
     max_stack: 4,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -12854,9 +14258,10 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
   });
   vec::push(functions, @Function {
     name: None,
-    id: 137,
+    id: 139,
     nargs: 1,
     max_stack: 6,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -12915,9 +14320,10 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
   });
   vec::push(functions, @Function {
     name: None,
-    id: 138,
+    id: 140,
     nargs: 1,
     max_stack: 5,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -12947,9 +14353,10 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
   });
   vec::push(functions, @Function {
     name: None,
-    id: 139,
+    id: 141,
     nargs: 1,
     max_stack: 4,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -13089,9 +14496,10 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
   });
   vec::push(functions, @Function {
     name: None,
-    id: 140,
+    id: 142,
     nargs: 1,
     max_stack: 6,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -13130,9 +14538,10 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
   });
   vec::push(functions, @Function {
     name: None,
-    id: 141,
+    id: 143,
     nargs: 1,
     max_stack: 7,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -13151,7 +14560,7 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
       0,	// 21: push_frame
       5, 12,	// 22: get_slot_direct(12)
       5, 67,	// 24: get_slot_direct(67)
-      13, 115,	// 26: jmp_unless(115)
+      13, 131,	// 26: jmp_unless(131)
       0,	// 28: push_frame
       5, 274,	// 29: get_slot_direct(274)
       15,	// 31: dup
@@ -13203,151 +14612,174 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
       5, 12,	// 101: get_slot_direct(12)
       5, 67,	// 103: get_slot_direct(67)
       8, 391,	// 105: set_slot_direct(391)
-      8, 216,	// 107: set_slot_direct(216)
-      10, 1,	// 109: invoke(1)
-      14,	// 111: pop
-      1, 0,	// 112: push_literal(0)
-      11,	// 114: return
-      0,	// 115: push_frame
+      15,	// 107: dup
+      0,	// 108: push_frame
+      5, 12,	// 109: get_slot_direct(12)
+      5, 9,	// 111: get_slot_direct(9)
+      8, 9,	// 113: set_slot_direct(9)
+      15,	// 115: dup
       0,	// 116: push_frame
-      5, 274,	// 117: get_slot_direct(274)
-      5, 359,	// 119: get_slot_direct(359)
-      8, 401,	// 121: set_slot_direct(401)
-      0,	// 123: push_frame
-      0,	// 124: push_frame
-      5, 274,	// 125: get_slot_direct(274)
-      15,	// 127: dup
-      7, 309,	// 128: get_slot_direct_check(309)
-      19,	// 130: swap
+      5, 12,	// 117: get_slot_direct(12)
+      5, 457,	// 119: get_slot_direct(457)
+      8, 457,	// 121: set_slot_direct(457)
+      8, 216,	// 123: set_slot_direct(216)
+      10, 1,	// 125: invoke(1)
+      14,	// 127: pop
+      1, 0,	// 128: push_literal(0)
+      11,	// 130: return
       0,	// 131: push_frame
-      5, 12,	// 132: get_slot_direct(12)
-      5, 215,	// 134: get_slot_direct(215)
-      5, 13,	// 136: get_slot_direct(13)
-      10, 1,	// 138: invoke(1)
-      8, 402,	// 140: set_slot_direct(402)
-      0,	// 142: push_frame
-      5, 12,	// 143: get_slot_direct(12)
-      5, 391,	// 145: get_slot_direct(391)
-      13, 159,	// 147: jmp_unless(159)
-      0,	// 149: push_frame
-      5, 402,	// 150: get_slot_direct(402)
-      0,	// 152: push_frame
-      5, 12,	// 153: get_slot_direct(12)
-      5, 391,	// 155: get_slot_direct(391)
-      8, 67,	// 157: set_slot_direct(67)
-      0,	// 159: push_frame
-      5, 274,	// 160: get_slot_direct(274)
-      0,	// 162: push_frame
-      5, 402,	// 163: get_slot_direct(402)
-      8, 359,	// 165: set_slot_direct(359)
-      0,	// 167: push_frame
-      5, 274,	// 168: get_slot_direct(274)
-      15,	// 170: dup
-      5, 158,	// 171: get_slot_direct(158)
-      1, 8,	// 173: push_literal(8)
-      26,	// 175: bi_add
-      8, 158,	// 176: set_slot_direct(158)
+      0,	// 132: push_frame
+      5, 274,	// 133: get_slot_direct(274)
+      5, 359,	// 135: get_slot_direct(359)
+      8, 401,	// 137: set_slot_direct(401)
+      0,	// 139: push_frame
+      0,	// 140: push_frame
+      5, 274,	// 141: get_slot_direct(274)
+      15,	// 143: dup
+      7, 309,	// 144: get_slot_direct_check(309)
+      19,	// 146: swap
+      0,	// 147: push_frame
+      5, 12,	// 148: get_slot_direct(12)
+      5, 215,	// 150: get_slot_direct(215)
+      5, 13,	// 152: get_slot_direct(13)
+      10, 1,	// 154: invoke(1)
+      8, 402,	// 156: set_slot_direct(402)
+      0,	// 158: push_frame
+      5, 12,	// 159: get_slot_direct(12)
+      5, 391,	// 161: get_slot_direct(391)
+      13, 175,	// 163: jmp_unless(175)
+      0,	// 165: push_frame
+      5, 402,	// 166: get_slot_direct(402)
+      0,	// 168: push_frame
+      5, 12,	// 169: get_slot_direct(12)
+      5, 391,	// 171: get_slot_direct(391)
+      8, 67,	// 173: set_slot_direct(67)
+      0,	// 175: push_frame
+      5, 402,	// 176: get_slot_direct(402)
       0,	// 178: push_frame
-      5, 274,	// 179: get_slot_direct(274)
-      15,	// 181: dup
-      7, 361,	// 182: get_slot_direct_check(361)
-      19,	// 184: swap
-      1, 305,	// 185: push_literal(305)
-      10, 1,	// 187: invoke(1)
-      14,	// 189: pop
-      0,	// 190: push_frame
-      5, 274,	// 191: get_slot_direct(274)
-      15,	// 193: dup
-      7, 361,	// 194: get_slot_direct_check(361)
-      19,	// 196: swap
-      1, 310,	// 197: push_literal(310)
-      0,	// 199: push_frame
-      5, 274,	// 200: get_slot_direct(274)
-      15,	// 202: dup
-      7, 200,	// 203: get_slot_direct_check(200)
-      19,	// 205: swap
-      1, 2,	// 206: push_literal(2)
-      10, 1,	// 208: invoke(1)
-      10, 2,	// 210: invoke(2)
-      14,	// 212: pop
-      0,	// 213: push_frame
-      5, 12,	// 214: get_slot_direct(12)
-      5, 215,	// 216: get_slot_direct(215)
-      15,	// 218: dup
-      7, 41,	// 219: get_slot_direct_check(41)
-      19,	// 221: swap
-      4, 142,	// 222: new_function(142)
-      10, 1,	// 224: invoke(1)
-      14,	// 226: pop
-      0,	// 227: push_frame
-      5, 274,	// 228: get_slot_direct(274)
-      15,	// 230: dup
-      7, 361,	// 231: get_slot_direct_check(361)
-      19,	// 233: swap
-      1, 25,	// 234: push_literal(25)
-      10, 1,	// 236: invoke(1)
-      14,	// 238: pop
-      0,	// 239: push_frame
-      5, 274,	// 240: get_slot_direct(274)
-      15,	// 242: dup
-      7, 387,	// 243: get_slot_direct_check(387)
-      19,	// 245: swap
-      0,	// 246: push_frame
-      5, 12,	// 247: get_slot_direct(12)
-      5, 216,	// 249: get_slot_direct(216)
-      10, 1,	// 251: invoke(1)
-      14,	// 253: pop
-      0,	// 254: push_frame
-      5, 274,	// 255: get_slot_direct(274)
-      5, 359,	// 257: get_slot_direct(359)
-      5, 360,	// 259: get_slot_direct(360)
-      13, 284,	// 261: jmp_unless(284)
+      5, 12,	// 179: get_slot_direct(12)
+      5, 9,	// 181: get_slot_direct(9)
+      8, 9,	// 183: set_slot_direct(9)
+      0,	// 185: push_frame
+      5, 402,	// 186: get_slot_direct(402)
+      0,	// 188: push_frame
+      5, 12,	// 189: get_slot_direct(12)
+      5, 457,	// 191: get_slot_direct(457)
+      8, 457,	// 193: set_slot_direct(457)
+      0,	// 195: push_frame
+      5, 274,	// 196: get_slot_direct(274)
+      0,	// 198: push_frame
+      5, 402,	// 199: get_slot_direct(402)
+      8, 359,	// 201: set_slot_direct(359)
+      0,	// 203: push_frame
+      5, 274,	// 204: get_slot_direct(274)
+      15,	// 206: dup
+      5, 158,	// 207: get_slot_direct(158)
+      1, 8,	// 209: push_literal(8)
+      26,	// 211: bi_add
+      8, 158,	// 212: set_slot_direct(158)
+      0,	// 214: push_frame
+      5, 274,	// 215: get_slot_direct(274)
+      15,	// 217: dup
+      7, 361,	// 218: get_slot_direct_check(361)
+      19,	// 220: swap
+      1, 305,	// 221: push_literal(305)
+      10, 1,	// 223: invoke(1)
+      14,	// 225: pop
+      0,	// 226: push_frame
+      5, 274,	// 227: get_slot_direct(274)
+      15,	// 229: dup
+      7, 361,	// 230: get_slot_direct_check(361)
+      19,	// 232: swap
+      1, 310,	// 233: push_literal(310)
+      0,	// 235: push_frame
+      5, 274,	// 236: get_slot_direct(274)
+      15,	// 238: dup
+      7, 200,	// 239: get_slot_direct_check(200)
+      19,	// 241: swap
+      1, 2,	// 242: push_literal(2)
+      10, 1,	// 244: invoke(1)
+      10, 2,	// 246: invoke(2)
+      14,	// 248: pop
+      0,	// 249: push_frame
+      5, 12,	// 250: get_slot_direct(12)
+      5, 215,	// 252: get_slot_direct(215)
+      15,	// 254: dup
+      7, 41,	// 255: get_slot_direct_check(41)
+      19,	// 257: swap
+      4, 144,	// 258: new_function(144)
+      10, 1,	// 260: invoke(1)
+      14,	// 262: pop
       0,	// 263: push_frame
       5, 274,	// 264: get_slot_direct(274)
       15,	// 266: dup
-      7, 386,	// 267: get_slot_direct_check(386)
+      7, 361,	// 267: get_slot_direct_check(361)
       19,	// 269: swap
-      2,	// 270: new_object
-      15,	// 271: dup
-      1, 266,	// 272: push_literal(266)
-      8, 108,	// 274: set_slot_direct(108)
-      15,	// 276: dup
-      1, 168,	// 277: push_literal(168)
-      8, 190,	// 279: set_slot_direct(190)
-      10, 1,	// 281: invoke(1)
-      14,	// 283: pop
-      0,	// 284: push_frame
-      5, 274,	// 285: get_slot_direct(274)
-      0,	// 287: push_frame
-      5, 401,	// 288: get_slot_direct(401)
-      8, 359,	// 290: set_slot_direct(359)
-      0,	// 292: push_frame
-      5, 274,	// 293: get_slot_direct(274)
-      15,	// 295: dup
-      5, 158,	// 296: get_slot_direct(158)
-      1, 8,	// 298: push_literal(8)
-      27,	// 300: bi_sub
-      8, 158,	// 301: set_slot_direct(158)
-      0,	// 303: push_frame
-      5, 274,	// 304: get_slot_direct(274)
-      15,	// 306: dup
-      7, 361,	// 307: get_slot_direct_check(361)
-      19,	// 309: swap
-      1, 309,	// 310: push_literal(309)
-      0,	// 312: push_frame
-      5, 402,	// 313: get_slot_direct(402)
-      5, 194,	// 315: get_slot_direct(194)
-      10, 2,	// 317: invoke(2)
+      1, 25,	// 270: push_literal(25)
+      10, 1,	// 272: invoke(1)
+      14,	// 274: pop
+      0,	// 275: push_frame
+      5, 274,	// 276: get_slot_direct(274)
+      15,	// 278: dup
+      7, 387,	// 279: get_slot_direct_check(387)
+      19,	// 281: swap
+      0,	// 282: push_frame
+      5, 12,	// 283: get_slot_direct(12)
+      5, 216,	// 285: get_slot_direct(216)
+      10, 1,	// 287: invoke(1)
+      14,	// 289: pop
+      0,	// 290: push_frame
+      5, 274,	// 291: get_slot_direct(274)
+      5, 359,	// 293: get_slot_direct(359)
+      5, 360,	// 295: get_slot_direct(360)
+      13, 320,	// 297: jmp_unless(320)
+      0,	// 299: push_frame
+      5, 274,	// 300: get_slot_direct(274)
+      15,	// 302: dup
+      7, 386,	// 303: get_slot_direct_check(386)
+      19,	// 305: swap
+      2,	// 306: new_object
+      15,	// 307: dup
+      1, 266,	// 308: push_literal(266)
+      8, 108,	// 310: set_slot_direct(108)
+      15,	// 312: dup
+      1, 168,	// 313: push_literal(168)
+      8, 190,	// 315: set_slot_direct(190)
+      10, 1,	// 317: invoke(1)
       14,	// 319: pop
-      1, 0,	// 320: push_literal(0)
-      11	// 322: return
+      0,	// 320: push_frame
+      5, 274,	// 321: get_slot_direct(274)
+      0,	// 323: push_frame
+      5, 401,	// 324: get_slot_direct(401)
+      8, 359,	// 326: set_slot_direct(359)
+      0,	// 328: push_frame
+      5, 274,	// 329: get_slot_direct(274)
+      15,	// 331: dup
+      5, 158,	// 332: get_slot_direct(158)
+      1, 8,	// 334: push_literal(8)
+      27,	// 336: bi_sub
+      8, 158,	// 337: set_slot_direct(158)
+      0,	// 339: push_frame
+      5, 274,	// 340: get_slot_direct(274)
+      15,	// 342: dup
+      7, 361,	// 343: get_slot_direct_check(361)
+      19,	// 345: swap
+      1, 309,	// 346: push_literal(309)
+      0,	// 348: push_frame
+      5, 402,	// 349: get_slot_direct(402)
+      5, 194,	// 351: get_slot_direct(194)
+      10, 2,	// 353: invoke(2)
+      14,	// 355: pop
+      1, 0,	// 356: push_literal(0)
+      11	// 358: return
     ]
   });
   vec::push(functions, @Function {
     name: None,
-    id: 142,
+    id: 144,
     nargs: 2,
     max_stack: 6,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -13431,10 +14863,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // "bcompile"
-    name: Some(~"bcompile"),
-    id: 143,
+    name: Some(utf16("bcompile")),
+    id: 145,
     nargs: 1,
     max_stack: 5,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -13500,9 +14933,10 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
   });
   vec::push(functions, @Function {
     name: None,
-    id: 144,
+    id: 146,
     nargs: 0,
     max_stack: 1,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -13513,9 +14947,10 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
   });
   vec::push(functions, @Function {
     name: None,
-    id: 145,
+    id: 147,
     nargs: 3,
     max_stack: 3,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -13539,11 +14974,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
       1, 0,	// 26: push_literal(0)
       8, 407,	// 28: set_slot_direct(407)
       0,	// 30: push_frame
-      4, 146,	// 31: new_function(146)
+      4, 148,	// 31: new_function(148)
       8, 407,	// 33: set_slot_direct(407)
       0,	// 35: push_frame
       5, 407,	// 36: get_slot_direct(407)
-      4, 147,	// 38: new_function(147)
+      4, 149,	// 38: new_function(149)
       8, 412,	// 40: set_slot_direct(412)
       0,	// 42: push_frame
       5, 407,	// 43: get_slot_direct(407)
@@ -13551,10 +14986,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // "compile_from_source"
-    name: Some(~"compile_from_source"),
-    id: 146,
+    name: Some(utf16("compile_from_source")),
+    id: 148,
     nargs: 2,
     max_stack: 5,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -13630,10 +15066,11 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
     ]
   });
   vec::push(functions, @Function { // ".make_repl"
-    name: Some(~".make_repl"),
-    id: 147,
+    name: Some(utf16(".make_repl")),
+    id: 149,
     nargs: 0,
     max_stack: 2,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -13644,15 +15081,16 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
       0,	// 9: push_frame
       1, 32,	// 10: push_literal(32)
       8, 274,	// 12: set_slot_direct(274)
-      4, 148,	// 14: new_function(148)
+      4, 150,	// 14: new_function(150)
       11	// 16: return
     ]
   });
   vec::push(functions, @Function {
     name: None,
-    id: 148,
+    id: 150,
     nargs: 1,
     max_stack: 6,
+    source: None,
     bytecode: ~[
       0,	// 0: push_frame
       5, 2,	// 1: get_slot_direct(2)
@@ -14115,11 +15553,60 @@ pub fn init(functions: &mut ~[@Function], literals: &mut ~[JsVal]) {
   vec::push(literals, JsVal::from_str("parse_tree"));	// 403
   vec::push(literals, JsVal::from_str("make_bcompile"));	// 404
   vec::push(literals, JsVal::from_str("top-level"));	// 405
-  vec::push(literals, JsVal::from_str("isFinite parseInt parseFloat isNaN Boolean String Function Math Number JSON RegExp SyntaxError console arguments now define document"));	// 406
+  // edited by hand: Date, Map, Set, require, module and exports are not
+  // in the TOP_LEVEL of bcompile.js, so regenerating this file from the
+  // TurtleScript sources drops them.  Add them there before
+  // regenerating.
+  vec::push(literals, JsVal::from_str("isFinite parseInt parseFloat isNaN Boolean String Function Math Number JSON RegExp Date Map Set SyntaxError console arguments now define document require module exports"));	// 406
   vec::push(literals, JsVal::from_str("compile_from_source"));	// 407
   vec::push(literals, JsVal::from_str("TOP_LEVEL"));	// 408
   vec::push(literals, JsVal::from_str("as_object"));	// 409
   vec::push(literals, JsVal::from_str("{ return 1+2; }"));	// 410
   vec::push(literals, JsVal::from_str("rv"));	// 411
   vec::push(literals, JsVal::from_str("make_repl"));	// 412
+  vec::push(literals, JsVal::from_str("un_bitnot"));	// 413
+  vec::push(literals, JsVal::from_str("bi_mod"));	// 414
+  vec::push(literals, JsVal::from_str("bi_bitand"));	// 415
+  vec::push(literals, JsVal::from_str("bi_bitor"));	// 416
+  vec::push(literals, JsVal::from_str("bi_bitxor"));	// 417
+  vec::push(literals, JsVal::from_str("bi_lsh"));	// 418
+  vec::push(literals, JsVal::from_str("bi_rsh"));	// 419
+  vec::push(literals, JsVal::from_str("bi_ursh"));	// 420
+  vec::push(literals, JsVal::from_str("%="));	// 421
+  vec::push(literals, JsVal::from_str("&="));	// 422
+  vec::push(literals, JsVal::from_str("|="));	// 423
+  vec::push(literals, JsVal::from_str("^="));	// 424
+  vec::push(literals, JsVal::from_str("<<="));	// 425
+  vec::push(literals, JsVal::from_str(">>="));	// 426
+  vec::push(literals, JsVal::from_str(">>>="));	// 427
+  vec::push(literals, JsVal::from_str("|"));	// 428
+  vec::push(literals, JsNumber(36f64));	// 429
+  vec::push(literals, JsVal::from_str("^"));	// 430
+  vec::push(literals, JsNumber(37f64));	// 431
+  vec::push(literals, JsVal::from_str("&"));	// 432
+  vec::push(literals, JsNumber(38f64));	// 433
+  vec::push(literals, JsVal::from_str("<<"));	// 434
+  vec::push(literals, JsNumber(47f64));	// 435
+  vec::push(literals, JsVal::from_str(">>"));	// 436
+  vec::push(literals, JsVal::from_str(">>>"));	// 437
+  vec::push(literals, JsVal::from_str("%"));	// 438
+  vec::push(literals, JsVal::from_str("~"));	// 439
+  vec::push(literals, JsVal::from_str("un_void"));	// 440
+  vec::push(literals, JsVal::from_str("bi_in"));	// 441
+  vec::push(literals, JsVal::from_str("bi_instanceof"));	// 442
+  vec::push(literals, JsVal::from_str("bi_lt"));	// 443
+  vec::push(literals, JsVal::from_str("bi_lte"));	// 444
+  vec::push(literals, JsVal::from_str("in"));	// 445
+  vec::push(literals, JsVal::from_str("instanceof"));	// 446
+  vec::push(literals, JsVal::from_str("void"));	// 447
+  vec::push(literals, JsVal::from_str("bi_loose_eq"));	// 448
+  vec::push(literals, JsVal::from_str("=="));	// 449
+  vec::push(literals, JsVal::from_str("!="));	// 450
+  vec::push(literals, JsVal::from_str("regexp"));	// 451
+  vec::push(literals, JsVal::from_str("Unterminated regular expression."));	// 452
+  vec::push(literals, JsVal::from_str("(regexp)"));	// 453
+  vec::push(literals, JsVal::from_str("p"));	// 454
+  vec::push(literals, JsVal::from_str("RegExp"));	// 455
+  vec::push(literals, JsVal::from_str("spans"));	// 456
+  vec::push(literals, JsVal::from_str("end"));	// 457
 }
@@ -0,0 +1,341 @@
+// the TurtleScript tokenizer, ported from tokenize.js
+//
+// This has to agree with the self-hosted tokenizer in startup.rs down to
+// its quirks (which characters end a number, the messages and ranges of
+// its errors) since the native compiler must produce the same modules.
+// Regexp literals are the one addition.
+use numconv;
+
+pub enum TokenValue {
+    TName(~[u16]),
+    TOperator(~[u16]),
+    TString(~[u16]),
+    TNumber(f64),
+    // a regexp literal: its pattern, as written, and its flags
+    TRegExp(~[u16], ~[u16])
+}
+
+pub struct Token {
+    value: TokenValue,
+    from: uint,
+    to: uint
+}
+
+// a compile error; the range is the offending token, if there is one.
+pub struct SyntaxError {
+    message: ~str,
+    range: Option<(uint, uint)>
+}
+
+impl SyntaxError {
+    pub fn new(message: &str, from: uint, to: uint) -> SyntaxError {
+        SyntaxError {
+            message: message.to_owned(),
+            // tokenize.js only reports a range if it isn't [0-0]
+            range: if from != 0 || to != 0 { Some((from, to)) } else { None }
+        }
+    }
+
+    // the message as the self-hosted compiler formats it
+    pub fn to_str(&self) -> ~str {
+        match self.range {
+            Some((from, to)) => fmt!("%s [%u-%u]", self.message, from, to),
+            None => copy self.message
+        }
+    }
+}
+
+// the operators the parser wants: a prefix character starts an
+// operator, which then continues with any suffix characters.
+pub static PREFIX: &'static str = "=<>!+-*&|/%^";
+pub static SUFFIX: &'static str = "=<>&|";
+
+// tokenize.js compares one-character strings, and the character past
+// the end is the empty string, which sorts before everything.  Code
+// units as ints with -1 for the end give the same answers.
+static END: int = -1;
+
+// a '/' starts a regexp literal where an expression can start: at the
+// start of the input, or after an operator other than a closing
+// bracket.  Anywhere else it's division.
+priv fn regexp_allowed(prev: Option<&Token>) -> bool {
+    match prev {
+        None => true,
+        Some(t) => match t.value {
+            TOperator(ref s) => *s != ~[')' as u16] && *s != ~[']' as u16],
+            TName(ref s) => *s == str::to_utf16("return") ||
+                *s == str::to_utf16("typeof"),
+            _ => false
+        }
+    }
+}
+
+priv fn is(c: int, ch: char) -> bool { c == ch as int }
+priv fn between(c: int, lo: char, hi: char) -> bool {
+    c >= lo as int && c <= hi as int
+}
+priv fn is_digit(c: int) -> bool { between(c, '0', '9') }
+priv fn is_one_of(c: int, chars: &str) -> bool {
+    c != END && str::any(chars, |ch| is(c, ch))
+}
+
+pub fn tokenize(src: &[u16], prefix: &str,
+                suffix: &str) -> Result<~[Token], SyntaxError> {
+    let length = src.len();
+    let at = |i: uint| -> int { if i < length { src[i] as int } else { END } };
+    let mut result : ~[Token] = ~[];
+    let mut i = 0u;
+    let mut from;
+    let mut c = at(i);
+    while c != END {
+        from = i;
+        if c <= ' ' as int {
+            // whitespace
+            i += 1;
+            c = at(i);
+        } else if between(c, 'a', 'z') || between(c, 'A', 'Z') ||
+            is(c, '$') || is(c, '_') {
+            // name
+            let mut s : ~[u16] = ~[c as u16];
+            i += 1;
+            loop {
+                c = at(i);
+                if between(c, 'a', 'z') || between(c, 'A', 'Z') ||
+                    is_digit(c) || is(c, '_') || is(c, '$') {
+                    s.push(c as u16);
+                    i += 1;
+                } else {
+                    break;
+                }
+            }
+            result.push(Token { value: TName(s), from: from, to: i });
+        } else if is_digit(c) || (is(c, '.') && is_digit(at(i + 1))) {
+            // number
+            let mut s : ~[u16] = ~[];
+            // tokenize.js doesn't reset this for a number which starts
+            // with '.', and loops forever on one after a hex literal.
+            let mut is_hex = false;
+            if !is(c, '.') {
+                s.push(c as u16);
+                i += 1;
+                c = at(i);
+                is_hex = s[0] == ('0' as u16) && is(c, 'x');
+                if is_hex {
+                    s.push(c as u16);
+                    i += 1;
+                }
+                loop {
+                    c = at(i);
+                    if !is_digit(c) && !(is_hex && (between(c, 'a', 'f') ||
+                                                    between(c, 'A', 'F'))) {
+                        break;
+                    }
+                    i += 1;
+                    s.push(c as u16);
+                }
+            }
+            if is(c, '.') && !is_hex {
+                i += 1;
+                s.push(c as u16);
+                loop {
+                    c = at(i);
+                    if !is_digit(c) { break; }
+                    i += 1;
+                    s.push(c as u16);
+                }
+            }
+            if (is(c, 'e') || is(c, 'E')) && !is_hex {
+                i += 1;
+                s.push(c as u16);
+                c = at(i);
+                if is(c, '-') || is(c, '+') {
+                    i += 1;
+                    s.push(c as u16);
+                    c = at(i);
+                }
+                if !is_digit(c) {
+                    return Err(SyntaxError::new("Bad exponent", from, i));
+                }
+                loop {
+                    i += 1;
+                    s.push(c as u16);
+                    c = at(i);
+                    if !is_digit(c) { break; }
+                }
+            }
+            if between(c, 'a', 'z') {
+                i += 1;
+                return Err(SyntaxError::new("Bad number", from, i));
+            }
+            let n = numconv::string_to_number(s);
+            if n.is_NaN() || n.is_infinite() {
+                return Err(SyntaxError::new("Bad number", from, i));
+            }
+            result.push(Token { value: TNumber(n), from: from, to: i });
+        } else if is(c, '\'') || is(c, '"') {
+            // string
+            let mut s : ~[u16] = ~[];
+            let q = c;
+            i += 1;
+            loop {
+                c = at(i);
+                if c < ' ' as int {
+                    return Err(SyntaxError::new(
+                        if is(c, '\n') || is(c, '\r') || c == END {
+                            "Unterminated string."
+                        } else {
+                            "Control character in string."
+                        }, from, i));
+                }
+                if c == q { break; }
+                if is(c, '\\') {
+                    i += 1;
+                    if i >= length {
+                        return Err(SyntaxError::new("Unterminated string",
+                                                    from, i));
+                    }
+                    c = at(i);
+                    if is(c, 'b') { c = 8; }
+                    else if is(c, 'f') { c = 12; }
+                    else if is(c, 'n') { c = 10; }
+                    else if is(c, 'r') { c = 13; }
+                    else if is(c, 't') { c = 9; }
+                    else if is(c, 'u') {
+                        let end = uint::min(i + 5, length);
+                        let v = numconv::parse_int(
+                            src.slice(uint::min(i + 1, end), end), 16);
+                        if v.is_NaN() || v.is_infinite() || v < 0f64 {
+                            return Err(SyntaxError::new("Unterminated string",
+                                                        from, i));
+                        }
+                        c = (numconv::to_uint32(v) & 0xFFFF) as int;
+                        i += 4;
+                    }
+                }
+                s.push(c as u16);
+                i += 1;
+            }
+            i += 1;
+            result.push(Token { value: TString(s), from: from, to: i });
+            c = at(i);
+        } else if is(c, '/') && is(at(i + 1), '/') {
+            // comment to the end of the line
+            i += 1;
+            loop {
+                c = at(i);
+                if is(c, '\n') || is(c, '\r') || c == END { break; }
+                i += 1;
+            }
+        } else if is(c, '/') && is(at(i + 1), '*') {
+            // block comment; "/*/" doesn't close it
+            i += 3;
+            loop {
+                c = at(i);
+                if c == END || (is(c, '/') && is(at(i - 1), '*')) {
+                    i += 1;
+                    c = at(i);
+                    break;
+                }
+                i += 1;
+            }
+        } else if is(c, '/') && regexp_allowed(result.last_opt()) {
+            // a regexp literal; a '/' in a character class doesn't end it
+            let mut pattern : ~[u16] = ~[];
+            let mut in_class = false;
+            i += 1;
+            loop {
+                c = at(i);
+                if c == END || is(c, '\n') || is(c, '\r') {
+                    return Err(SyntaxError::new(
+                        "Unterminated regular expression.", from, i));
+                }
+                if is(c, '/') && !in_class { break; }
+                if is(c, '[') { in_class = true; }
+                else if is(c, ']') { in_class = false; }
+                else if is(c, '\\') {
+                    pattern.push(c as u16);
+                    i += 1;
+                    c = at(i);
+                    if c == END || is(c, '\n') || is(c, '\r') { loop; }
+                }
+                pattern.push(c as u16);
+                i += 1;
+            }
+            i += 1;
+            let mut flags : ~[u16] = ~[];
+            loop {
+                c = at(i);
+                if !(between(c, 'a', 'z') || between(c, 'A', 'Z')) { break; }
+                flags.push(c as u16);
+                i += 1;
+            }
+            result.push(Token { value: TRegExp(pattern, flags), from: from, to: i });
+        } else if is_one_of(c, prefix) {
+            // a combining operator
+            let mut s : ~[u16] = ~[c as u16];
+            i += 1;
+            loop {
+                // tokenize.js forgets to read the end of the input here,
+                // and loops forever on a source ending in an operator.
+                if i >= length { c = END; break; }
+                c = at(i);
+                if !is_one_of(c, suffix) { break; }
+                s.push(c as u16);
+                i += 1;
+            }
+            result.push(Token { value: TOperator(s), from: from, to: i });
+        } else {
+            // a single-character operator
+            i += 1;
+            result.push(Token { value: TOperator(~[c as u16]), from: from, to: i });
+            c = at(i);
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use numconv;
+
+    fn toks(src: &str) -> ~[~str] {
+        match tokenize(str::to_utf16(src), PREFIX, SUFFIX) {
+            Ok(ts) => ts.map(|t| match t.value {
+                TName(ref s) => ~"name " + str::from_utf16(*s),
+                TOperator(ref s) => ~"operator " + str::from_utf16(*s),
+                TString(ref s) => ~"string " + str::from_utf16(*s),
+                TNumber(n) => ~"number " + numconv::number_to_str(n),
+                TRegExp(ref p, ref f) => fmt!("regexp /%s/%s", str::from_utf16(*p),
+                                              str::from_utf16(*f))
+            }),
+            Err(e) => ~[e.to_str()]
+        }
+    }
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(toks("var x = a.b >= 'c\\u0041';"),
+                   ~[~"name var", ~"name x", ~"operator =", ~"name a",
+                     ~"operator .", ~"name b", ~"operator >=",
+                     ~"string cA", ~"operator ;"]);
+        assert_eq!(toks("0x1f /* c */ .5 // d"),
+                   ~[~"number 31", ~"number 0.5"]);
+        assert_eq!(toks("1e"), ~[~"Bad exponent [0-2]"]);
+        assert_eq!(toks("12ab"), ~[~"Bad number [0-3]"]);
+        assert_eq!(toks("'abc"), ~[~"Unterminated string. [0-4]"]);
+        // an operator at the very end
+        assert_eq!(toks("a +"), ~[~"name a", ~"operator +"]);
+        // a '/' after an operand divides; elsewhere it starts a regexp
+        assert_eq!(toks("a / b / (c) / 2"),
+                   ~[~"name a", ~"operator /", ~"name b", ~"operator /",
+                     ~"operator (", ~"name c", ~"operator )", ~"operator /",
+                     ~"number 2"]);
+        assert_eq!(toks("x = /[/]\\/+/gi.test(s)"),
+                   ~[~"name x", ~"operator =", ~"regexp /[/]\\/+/gi",
+                     ~"operator .", ~"name test", ~"operator (", ~"name s",
+                     ~"operator )"]);
+        assert_eq!(toks("return /a/"), ~[~"name return", ~"regexp /a/"]);
+        assert_eq!(toks("(/a\\/)"), ~[~"Unterminated regular expression. [1-6]"]);
+    }
+}
@@ -0,0 +1,173 @@
+// a bytecode verifier, for modules we didn't compile ourselves.
+//
+// interpret_one trusts its input: a bad literal index or function id,
+// a jump into the middle of an instruction or a pop from an empty
+// stack either fails or quietly corrupts the interpreter.  verify
+// checks a whole module before it runs, following every path through
+// each function with the same stackpush/stackpop the compiler used to
+// compute max_stack.
+use function::Function;
+use module::Module;
+use op::*;
+
+pub struct VerifyError {
+    function: uint,
+    pc: uint,
+    message: ~str
+}
+
+impl VerifyError {
+    pub fn to_str(&self) -> ~str {
+        fmt!("function %u, pc %u: %s", self.function, self.pc, self.message)
+    }
+}
+
+pub fn verify(module: &Module) -> Result<(), VerifyError> {
+    if module.functions.is_empty() {
+        return Err(VerifyError { function: 0, pc: 0,
+                                 message: ~"module has no functions" });
+    }
+    for module.functions.eachi |id, f| {
+        match verify_function(module, id, *f) {
+            Ok(()) => {},
+            Err(e) => { return Err(e); }
+        }
+    }
+    Ok(())
+}
+
+priv fn verify_function(module: &Module, id: uint,
+                        f: &Function) -> Result<(), VerifyError> {
+    let err = |pc: uint, message: ~str| -> Result<(), VerifyError> {
+        Err(VerifyError { function: id, pc: pc, message: message })
+    };
+    let bytecode : &[uint] = f.bytecode;
+    let len = bytecode.len();
+    // find the instruction boundaries
+    let mut starts = vec::from_elem(len, false);
+    let mut pc = 0;
+    while pc < len {
+        if !is_op(bytecode[pc]) {
+            return err(pc, fmt!("unknown opcode %u", bytecode[pc]));
+        }
+        starts[pc] = true;
+        let next = pc + 1 + Op::new_from_uint(bytecode[pc]).args();
+        if next > len { return err(pc, ~"missing operand"); }
+        pc = next;
+    }
+    // check the operands, reachable or not
+    pc = 0;
+    while pc < len {
+        let op = Op::new_from_uint(bytecode[pc]);
+        let arg = if op.args() > 0 { bytecode[pc + 1] } else { 0 };
+        match op {
+            Op_push_literal | Op_get_slot_direct |
+            Op_get_slot_direct_check | Op_set_slot_direct
+                if arg >= module.literals.len() => {
+                return err(pc, fmt!("literal %u out of range", arg));
+            },
+            Op_new_function if arg >= module.functions.len() => {
+                return err(pc, fmt!("function %u out of range", arg));
+            },
+            Op_jmp | Op_jmp_unless if arg >= len || !starts[arg] => {
+                return err(pc, fmt!("jump to %u, which isn't an instruction", arg));
+            },
+            _ => {}
+        }
+        pc += 1 + op.args();
+    }
+    // follow every path, keeping track of the stack depth, which has
+    // to be the same whichever way we reach an instruction.
+    let mut depths : ~[Option<uint>] = vec::from_elem(len, None);
+    let mut work : ~[(uint, uint)] = ~[(0, 0)];
+    while !work.is_empty() {
+        let (pc, depth) = work.pop();
+        if pc >= len {
+            return err(pc, ~"runs off the end without returning");
+        }
+        match depths[pc] {
+            Some(d) if d == depth => loop,
+            Some(d) => {
+                return err(pc, fmt!("stack depth is %u on one path and %u on another",
+                                    d, depth));
+            },
+            None => { depths[pc] = Some(depth); }
+        }
+        let op = Op::new_from_uint(bytecode[pc]);
+        let arg = if op.args() > 0 { bytecode[pc + 1] } else { 0 };
+        // check invoke's argument count first, so it can't overflow
+        let underflow = match op {
+            Op_invoke => arg > depth || arg + 2 > depth,
+            _ => op.stackpop([]) > depth
+        };
+        if underflow {
+            return err(pc, fmt!("pops more than the %u values on the stack",
+                                depth));
+        }
+        let depth = depth - op.stackpop([arg as int]) + op.stackpush();
+        if depth > f.max_stack {
+            return err(pc, fmt!("stack depth %u exceeds max_stack %u",
+                                depth, f.max_stack));
+        }
+        let next = pc + 1 + op.args();
+        match op {
+            Op_return => {},
+            Op_jmp => work.push((arg, depth)),
+            Op_jmp_unless => {
+                work.push((arg, depth));
+                work.push((next, depth));
+            },
+            _ => work.push((next, depth))
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use function::Function;
+    use module::Module;
+    use object::JsNumber;
+
+    fn check(max_stack: uint, bytecode: ~[uint]) -> ~str {
+        let module = Module {
+            functions: ~[@Function {
+                name: None, id: 0, nargs: 0, max_stack: max_stack,
+                source: None, bytecode: bytecode
+            }],
+            literals: ~[JsNumber(1f64)],
+            source: None
+        };
+        match verify(&module) {
+            Ok(()) => ~"ok",
+            Err(e) => e.to_str()
+        }
+    }
+
+    #[test]
+    fn test_verify() {
+        assert!(verify(&Module::new_startup_module()).is_ok());
+        assert_eq!(check(1, ~[1, 0, 11]), ~"ok");
+        assert_eq!(check(2, ~[1, 0, 13, 6, 1, 0, 1, 0, 11]),
+                   ~"function 0, pc 6: stack depth is 1 on one path and 0 on another");
+        assert_eq!(check(1, ~[11]),
+                   ~"function 0, pc 0: pops more than the 0 values on the stack");
+        assert_eq!(check(1, ~[1, 0, 1, 0, 11]),
+                   ~"function 0, pc 2: stack depth 2 exceeds max_stack 1");
+        assert_eq!(check(1, ~[1, 0, 14]),
+                   ~"function 0, pc 3: runs off the end without returning");
+        assert_eq!(check(1, ~[1, 5, 11]),
+                   ~"function 0, pc 0: literal 5 out of range");
+        assert_eq!(check(1, ~[4, 3, 11]),
+                   ~"function 0, pc 0: function 3 out of range");
+        assert_eq!(check(1, ~[12, 1, 1, 0, 11]),
+                   ~"function 0, pc 0: jump to 1, which isn't an instruction");
+        assert_eq!(check(1, ~[99]), ~"function 0, pc 0: unknown opcode 99");
+        assert_eq!(check(1, ~[1]), ~"function 0, pc 0: missing operand");
+        assert_eq!(check(2, ~[1, 0, 1, 0, 10, 5, 11]),
+                   ~"function 0, pc 4: pops more than the 2 values on the stack");
+        assert_eq!(check(2, ~[1, 0, 1, 0, 10, uint::max_value, 11]),
+                   ~"function 0, pc 4: pops more than the 2 values on the stack");
+    }
+}